@@ -0,0 +1,95 @@
+//! Benchmark of the string cache of the url change callback, on a page
+//! calling `pushState` with the same long url many times.
+//!
+//! Run with `cargo bench --bench url_change_cache`, it prints the time and
+//! the number of Rust allocations done while the page runs, with the cache
+//! enabled (the default) and disabled.
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use ul_next::{renderer::Renderer, view::View};
+
+/// Counts the allocations, to compare the runs with and without the cache.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const PUSH_STATES: usize = 2000;
+
+/// Each `pushState` alternates between two long urls, so the 2 entries
+/// cache always hits.
+fn storm_page() -> String {
+    format!(
+        r#"<html><body><script>
+var path = "/{}";
+function storm() {{
+    for (var i = 0; i < {PUSH_STATES}; i++) {{
+        history.pushState(null, "", path + (i % 2));
+    }}
+}}
+</script></body></html>"#,
+        "segment/".repeat(64)
+    )
+}
+
+/// Run the storm in `view` with the cache `capacity`.
+fn run(renderer: &Renderer, view: &View, capacity: usize) {
+    view.set_string_cache_capacity(capacity);
+
+    let calls = Rc::new(Cell::new(0));
+    {
+        let calls = calls.clone();
+        view.set_change_url_callback(move |_view, url| {
+            std::hint::black_box(url);
+            calls.set(calls.get() + 1);
+        });
+    }
+
+    let page = storm_page();
+    common::load_and_wait(renderer, view, |view| view.load_html(&page).unwrap());
+    calls.set(0);
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    view.evaluate_script("storm()").unwrap().unwrap();
+    common::update_until(renderer, "the url callbacks", || calls.get() >= PUSH_STATES);
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "capacity {capacity}: {} callbacks in {elapsed:?}, {allocations} allocations",
+        calls.get()
+    );
+}
+
+fn main() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+
+    for capacity in [0, 2] {
+        let view = common::create_view(&lib, &renderer, 200, 100);
+        run(&renderer, &view, capacity);
+    }
+}
@@ -0,0 +1,79 @@
+//! Show a page in an `App` window using a private (in-memory) session, so
+//! cookies and local storage are not shared with the default session and
+//! are not written to disk.
+use std::rc::Rc;
+
+use ul_next::{app::App, platform, view::ViewConfig, window::WindowFlags, Library};
+
+fn main() {
+    let lib = Library::linked();
+
+    platform::enable_platform_filesystem(lib.clone(), "./examples").unwrap();
+
+    let app = Rc::new(App::new(lib.clone(), None, None).unwrap());
+
+    let window = app
+        .try_create_window(
+            600,
+            400,
+            false,
+            WindowFlags {
+                borderless: false,
+                titled: true,
+                resizable: false,
+                maximizable: false,
+                hidden: false,
+            },
+        )
+        .unwrap();
+    window.set_title("Private Session");
+
+    // The session and the view are created from the renderer owned by the
+    // app, they are dropped at the end of `main` before the app.
+    let session = app.renderer().create_session(false, "private").unwrap();
+    println!(
+        "session `{}` (id {}), persistent: {}",
+        session.name(),
+        session.id(),
+        session.is_persistent()
+    );
+
+    // views shown in windows are rendered on the GPU by the app
+    let view_config = ViewConfig::start()
+        .is_accelerated(true)
+        .try_build(lib.clone())
+        .unwrap();
+    let view = app
+        .renderer()
+        .create_view(
+            window.width(),
+            window.height(),
+            &view_config,
+            Some(&session),
+        )
+        .unwrap();
+    let overlay = window.try_create_overlay_with_view(view, 0, 0).unwrap();
+    overlay.view().load_html(HTML_STRING).unwrap();
+
+    let app_clone = app.clone();
+    window.set_close_callback(move |_window| {
+        app_clone.quit();
+    });
+
+    app.run();
+}
+
+const HTML_STRING: &str = r#"
+<html>
+  <body style="font-family: sans-serif; background: #f7f9fc; color: #22283d">
+    <h2>Private session</h2>
+    <p id="visits"></p>
+    <script>
+      var visits = Number(localStorage.getItem('visits') || 0) + 1;
+      localStorage.setItem('visits', visits);
+      document.getElementById('visits').innerText =
+        'Visits in this session: ' + visits + ' (restarts from 1 on every run)';
+    </script>
+  </body>
+</html>
+"#;
@@ -34,7 +34,7 @@ fn main() {
     // The window's size (900 by 600) is in virtual device coordinates, the actual size in pixels
     // is automatically determined by the monitor's DPI.
     let window = app
-        .create_window(
+        .try_create_window(
             900,
             600,
             false,
@@ -58,7 +58,7 @@ fn main() {
     // AppCore automatically manages focus, keyboard/mouse input, and GPU painting for each active
     // overlay. Dropping the overlay will remove it from the window.
     let overlay = window
-        .create_overlay(window.width(), window.height(), 0, 0)
+        .try_create_overlay(window.width(), window.height(), 0, 0)
         .unwrap();
 
     // Load a string of HTML into our overlay's View
@@ -0,0 +1,248 @@
+use std::borrow::Cow;
+
+use glium::backend::Facade;
+use glium::implement_vertex;
+use glium::texture::{ClientFormat, MipmapsOption, RawImage2d, UncompressedFloatFormat};
+use glium::{index::PrimitiveType, program::ProgramCreationInput, Program, Texture2d};
+use glium::{uniform, Surface};
+use ul_next::Library;
+use ul_next::{config::Config, platform, renderer::Renderer, view::ViewConfig};
+
+/// This example renders a view using the CPU renderer, and uploads only
+/// the changed (dirty) region of the surface to a `glium` texture each frame.
+///
+/// With the `glium` feature, `ul_next::gpu_driver::glium::upload_surface_damage`
+/// does the same upload.
+fn main() {
+    let lib = Library::linked();
+
+    let event_loop = winit::event_loop::EventLoop::builder().build().unwrap();
+
+    let (_window, display) = glium::backend::glutin::SimpleWindowBuilder::new()
+        .with_title("CPU partial upload")
+        .with_inner_size(900, 600)
+        .build(&event_loop);
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+
+    // basic setup (check `render_to_png` for full explanation)
+    platform::enable_platform_fontloader(lib.clone());
+    platform::enable_platform_filesystem(lib.clone(), "./examples").unwrap();
+    platform::enable_default_logger(lib.clone(), "./log.log").unwrap();
+
+    let renderer = Renderer::create(config).unwrap();
+
+    // not accelerated, so we can get the `Surface`
+    let view_config = ViewConfig::start()
+        .initial_device_scale(1.0)
+        .is_accelerated(false)
+        .try_build(lib.clone())
+        .unwrap();
+
+    let view = renderer.create_view(900, 600, &view_config, None).unwrap();
+
+    view.load_html(HTML_STRING).unwrap();
+
+    let mut texture = create_texture(&display, 900, 600);
+
+    // create vertex/index buffers and program which will be used
+    // to blit the texture to the window.
+    let vertex_buffer = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: [f32; 2],
+            tex_coords: [f32; 2],
+        }
+
+        implement_vertex!(Vertex, position, tex_coords);
+
+        glium::VertexBuffer::new(
+            &display,
+            &[
+                Vertex {
+                    position: [-1.0, -1.0],
+                    tex_coords: [0.0, 1.0],
+                },
+                Vertex {
+                    position: [-1.0, 1.0],
+                    tex_coords: [0.0, 0.0],
+                },
+                Vertex {
+                    position: [1.0, 1.0],
+                    tex_coords: [1.0, 0.0],
+                },
+                Vertex {
+                    position: [1.0, -1.0],
+                    tex_coords: [1.0, 1.0],
+                },
+            ],
+        )
+        .unwrap()
+    };
+
+    let index_buffer =
+        glium::IndexBuffer::new(&display, PrimitiveType::TriangleStrip, &[1u16, 2, 0, 3]).unwrap();
+
+    let program = Program::new(
+        &display,
+        ProgramCreationInput::SourceCode {
+            vertex_shader: "
+                #version 140
+                in vec2 position;
+                in vec2 tex_coords;
+                out vec2 v_tex_coords;
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    v_tex_coords = tex_coords;
+                }
+            ",
+            tessellation_control_shader: None,
+            tessellation_evaluation_shader: None,
+            geometry_shader: None,
+            // the surface pixels are in BGRA order, and we upload them as is
+            fragment_shader: "
+                #version 140
+                uniform sampler2D tex;
+                in vec2 v_tex_coords;
+                out vec4 f_color;
+                void main() {
+                    f_color = texture(tex, v_tex_coords).bgra;
+                }
+            ",
+            transform_feedback_varyings: None,
+            // the pixels are already in sRGB
+            outputs_srgb: true,
+            uses_point_size: false,
+        },
+    )
+    .unwrap();
+
+    let mut update_and_draw = move |size: Option<(u32, u32)>| {
+        if let Some((width, height)) = size {
+            view.resize(width, height).unwrap();
+            // the whole surface will be dirty after resize
+            texture = create_texture(&display, width, height);
+        }
+
+        renderer.update().unwrap();
+        renderer.render().unwrap();
+
+        let mut surface = view.surface().unwrap();
+        let Some(damage) = surface.damage_bounds() else {
+            return;
+        };
+
+        {
+            let pixels = surface.lock_pixels().unwrap();
+            let row_bytes = pixels.row_bytes() as usize;
+
+            let width = damage.right - damage.left;
+            let height = damage.bottom - damage.top;
+
+            // copy only the dirty rows/columns
+            let mut data = Vec::with_capacity((width * height * 4) as usize);
+            for y in damage.top..damage.bottom {
+                let start = y as usize * row_bytes + damage.left as usize * 4;
+                data.extend_from_slice(&pixels[start..start + width as usize * 4]);
+            }
+
+            println!(
+                "uploading {}x{} at ({}, {})",
+                width, height, damage.left, damage.top
+            );
+            texture.write(
+                glium::Rect {
+                    left: damage.left,
+                    bottom: damage.top,
+                    width,
+                    height,
+                },
+                RawImage2d {
+                    data: Cow::Owned(data),
+                    width,
+                    height,
+                    format: ClientFormat::U8U8U8U8,
+                },
+            );
+        }
+        surface.clear_dirty_bounds();
+
+        let uniforms = uniform! {
+            tex: texture.sampled()
+        };
+
+        let mut target = display.draw();
+        target.clear_color_srgb(0.0, 0.0, 0.0, 0.0);
+        target
+            .draw(
+                &vertex_buffer,
+                &index_buffer,
+                &program,
+                &uniforms,
+                &Default::default(),
+            )
+            .unwrap();
+        target.finish().unwrap();
+    };
+
+    update_and_draw(None);
+    #[allow(deprecated)]
+    event_loop
+        .run(move |event, target| {
+            match event {
+                winit::event::Event::WindowEvent { event, .. } => match event {
+                    winit::event::WindowEvent::CloseRequested => target.exit(),
+                    winit::event::WindowEvent::Resized(size) => {
+                        update_and_draw(Some((size.width, size.height)));
+                    }
+                    _ => {}
+                },
+                _ => {
+                    update_and_draw(None);
+                }
+            };
+            target.set_control_flow(winit::event_loop::ControlFlow::Poll);
+        })
+        .unwrap();
+}
+
+fn create_texture<F: Facade>(facade: &F, width: u32, height: u32) -> Texture2d {
+    Texture2d::empty_with_format(
+        facade,
+        UncompressedFloatFormat::U8U8U8U8,
+        MipmapsOption::NoMipmap,
+        width,
+        height,
+    )
+    .unwrap()
+}
+
+const HTML_STRING: &str = r#"
+<html>
+  <head>
+    <style type="text/css">
+      body {
+        margin: 0;
+        padding: 2em;
+        font-family: Arial;
+        background: #f5f5f5;
+      }
+      .spinner {
+        width: 40px;
+        height: 40px;
+        border-radius: 50%;
+        border: 6px solid #acb4ff;
+        border-top-color: #8f8ae1;
+        animation: spin 1s linear infinite;
+      }
+      @keyframes spin {
+        to { transform: rotate(360deg); }
+      }
+    </style>
+  </head>
+  <body>
+    <h1>Partial upload</h1>
+    <p>Only the spinner region is uploaded to the texture every frame.</p>
+    <div class="spinner"></div>
+  </body>
+</html>"#;
@@ -0,0 +1,112 @@
+//! Two views on two simulated displays, refreshing at 60Hz and 144Hz,
+//! driven by a [`DisplayPump`].
+//!
+//! Each page counts its `requestAnimationFrame` callbacks, after two seconds
+//! the counts are printed, and should be close to 120 and 288.
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use ul_next::{
+    config::Config,
+    platform,
+    renderer::{DisplayPump, Renderer},
+    view::ViewConfig,
+    Library,
+};
+
+const HTML_STRING: &str = r#"
+<html>
+  <body>
+    <script>
+      window.frames_count = 0;
+      function frame() {
+        window.frames_count++;
+        requestAnimationFrame(frame);
+      }
+      requestAnimationFrame(frame);
+    </script>
+  </body>
+</html>
+"#;
+
+/// A simulated display, with the time of its next vsync.
+struct Display {
+    id: u32,
+    interval: Duration,
+    next_vsync: Instant,
+}
+
+fn main() {
+    let lib = Library::linked();
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+
+    // basic setup (check `render_to_png` for full explanation)
+    platform::enable_platform_fontloader(lib.clone());
+    platform::enable_platform_filesystem(lib.clone(), "./examples").unwrap();
+    platform::set_logger(lib.clone(), platform::loggers::StderrLogger);
+
+    let renderer = Renderer::create(config).unwrap();
+
+    // the display can be set in the config, or later with `DisplayPump::register`
+    let view_config = ViewConfig::start()
+        .display_id(1)
+        .try_build(lib.clone())
+        .unwrap();
+    let view_60 = renderer.create_view(200, 200, &view_config, None).unwrap();
+    let view_144 = renderer.create_view(200, 200, &view_config, None).unwrap();
+
+    let mut pump = DisplayPump::new(&renderer);
+    pump.register(&view_60, 1);
+    pump.register(&view_144, 2);
+
+    view_60.load_html(HTML_STRING).unwrap();
+    view_144.load_html(HTML_STRING).unwrap();
+
+    let start = Instant::now();
+    let mut displays = [
+        Display {
+            id: 1,
+            interval: Duration::from_secs(1) / 60,
+            next_vsync: start,
+        },
+        Display {
+            id: 2,
+            interval: Duration::from_secs(1) / 144,
+            next_vsync: start,
+        },
+    ];
+    let mut last_update = start;
+
+    while start.elapsed() < Duration::from_secs(2) {
+        let now = Instant::now();
+        for display in &mut displays {
+            if now >= display.next_vsync {
+                pump.on_vsync(display.id);
+                display.next_vsync += display.interval;
+            }
+        }
+
+        if pump.should_update(last_update) {
+            renderer.update().unwrap();
+            renderer.render().unwrap();
+            last_update = now;
+        }
+
+        let next_vsync = displays.iter().map(|d| d.next_vsync).min().unwrap();
+        thread::sleep(next_vsync.saturating_duration_since(Instant::now()));
+    }
+
+    for (name, view, display_id) in [("60Hz", &view_60, 1), ("144Hz", &view_144, 2)] {
+        let frames = view
+            .evaluate_script("window.frames_count")
+            .unwrap()
+            .unwrap();
+        println!(
+            "{name}: {frames} frames, measured refresh interval: {:?}",
+            pump.refresh_interval(display_id)
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Show the sticks and buttons of connected gamepads using the JavaScript
+//! Gamepad API, with the events forwarded from `gilrs` by
+//! [`GilrsBridge`].
+//!
+//! Run with `cargo run --example gilrs_gamepad --features gilrs`.
+use gilrs::Gilrs;
+use ul_next::{app::App, platform, renderer::gamepad::GilrsBridge, window::WindowFlags, Library};
+
+fn main() {
+    let lib = Library::linked();
+
+    platform::enable_platform_filesystem(lib.clone(), "./examples").unwrap();
+
+    // The bridge borrows the renderer of the app, and is used in the update
+    // callback which must be `'static`, the app lives until the end of the
+    // program anyway.
+    let app: &'static App = Box::leak(Box::new(App::new(lib.clone(), None, None).unwrap()));
+
+    let window = app
+        .try_create_window(
+            600,
+            400,
+            false,
+            WindowFlags {
+                borderless: false,
+                titled: true,
+                resizable: false,
+                maximizable: false,
+                hidden: false,
+            },
+        )
+        .unwrap();
+    window.set_title("Gamepad");
+
+    let overlay = window
+        .try_create_overlay(window.width(), window.height(), 0, 0)
+        .unwrap();
+    overlay.view().load_html(HTML_STRING).unwrap();
+
+    let mut bridge = GilrsBridge::new(Gilrs::new().unwrap(), app.renderer()).unwrap();
+    app.set_update_callback(move || {
+        bridge.pump().unwrap();
+    });
+
+    window.set_close_callback(move |_window| {
+        app.quit();
+    });
+
+    // keep the overlay alive while running
+    let _overlay = overlay;
+    app.run();
+}
+
+const HTML_STRING: &str = r#"
+<html>
+  <head>
+    <style>
+      body {
+        font-family: -apple-system, 'Segoe UI', Ubuntu, Arial, sans-serif;
+        background: #22283d;
+        color: white;
+        margin: 20px;
+      }
+      .pad { margin-bottom: 20px; }
+      .sticks { display: flex; gap: 20px; }
+      .stick {
+        position: relative;
+        width: 100px;
+        height: 100px;
+        border-radius: 50%;
+        background: #3a4160;
+      }
+      .dot {
+        position: absolute;
+        width: 16px;
+        height: 16px;
+        margin: -8px 0 0 -8px;
+        border-radius: 50%;
+        background: #34d7d6;
+      }
+      .buttons span {
+        display: inline-block;
+        width: 24px;
+        margin: 2px;
+        text-align: center;
+        border-radius: 4px;
+        background: #3a4160;
+      }
+    </style>
+  </head>
+  <body>
+    <div id="pads">Connect a gamepad and press a button...</div>
+    <script>
+      function stick(x, y) {
+        return '<div class="stick"><div class="dot" style="left: ' + (50 + x * 50) +
+          'px; top: ' + (50 + y * 50) + 'px"></div></div>';
+      }
+
+      function frame() {
+        var html = '';
+        var pads = navigator.getGamepads();
+        for (var i = 0; i < pads.length; i++) {
+          var pad = pads[i];
+          if (!pad || !pad.connected) continue;
+
+          html += '<div class="pad"><h3>#' + pad.index + ': ' + pad.id + '</h3>';
+          html += '<div class="sticks">' + stick(pad.axes[0], pad.axes[1]) +
+            stick(pad.axes[2], pad.axes[3]) + '</div><div class="buttons">';
+          for (var b = 0; b < pad.buttons.length; b++) {
+            var value = pad.buttons[b].value;
+            html += '<span style="background: rgba(52, 215, 214, ' + (0.2 + value * 0.8) +
+              ')">' + b + '</span>';
+          }
+          html += '</div></div>';
+        }
+        if (html) document.getElementById('pads').innerHTML = html;
+        requestAnimationFrame(frame);
+      }
+      requestAnimationFrame(frame);
+    </script>
+  </body>
+</html>
+"#;
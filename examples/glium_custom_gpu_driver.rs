@@ -1,8 +1,17 @@
+use std::cell::Cell;
+
 use glium::implement_vertex;
 use glium::{index::PrimitiveType, program::ProgramCreationInput, Program};
 use glium::{uniform, Surface};
+use ul_next::event::{KeyEvent, MouseEvent, ScrollEvent};
+use ul_next::renderer::inspector::LocalInspector;
 use ul_next::Library;
 use ul_next::{config::Config, platform, renderer::Renderer, view::ViewConfig};
+use winit::{
+    dpi::PhysicalPosition,
+    event::ElementState,
+    keyboard::{Key, ModifiersState, NamedKey},
+};
 
 fn main() {
     let lib = Library::linked();
@@ -14,7 +23,7 @@ fn main() {
         .with_inner_size(900, 600)
         .build(&event_loop);
 
-    let config = Config::start().build(lib.clone()).unwrap();
+    let config = Config::start().try_build(lib.clone()).unwrap();
 
     // basic setup (check `render_to_png` for full explanation)
     platform::enable_platform_fontloader(lib.clone());
@@ -31,12 +40,41 @@ fn main() {
     let view_config = ViewConfig::start()
         .initial_device_scale(1.0)
         .is_accelerated(true)
-        .build(lib.clone())
+        .try_build(lib.clone())
         .unwrap();
 
     let view = renderer.create_view(900, 600, &view_config, None).unwrap();
 
     view.load_html(HTML_STRING).unwrap();
+    view.focus();
+
+    // the view is used by both the drawing and the event handling closures
+    let view = &view;
+
+    // The inspector is toggled with F12 and shown in the bottom half of the
+    // window, it needs the inspector assets from the Ultralight SDK in
+    // `./examples/inspector`.
+    let inspector = match LocalInspector::new(&renderer, view, "./examples", 900, 300) {
+        Ok(inspector) => Some(inspector),
+        Err(err) => {
+            println!("Inspector disabled: {err}");
+            None
+        }
+    };
+    let inspector = &inspector;
+
+    let window_size = Cell::new((900, 600));
+    let window_size = &window_size;
+
+    // returns the height of the page view, the inspector takes the bottom
+    // half of the window when open
+    let layout = move || {
+        let (_, height) = window_size.get();
+        match inspector {
+            Some(inspector) if inspector.is_open() => height - height / 2,
+            _ => height,
+        }
+    };
 
     // create vertex/index buffers and program which will be used
     // to blit the `rendered` texture from the GPU driver to the window.
@@ -113,47 +151,93 @@ fn main() {
     )
     .unwrap();
 
-    let mut update_and_draw = move |size: Option<(u32, u32)>| {
-        if let Some(size) = size {
-            view.resize(size.0, size.1);
+    let mut update_and_draw = move |resized: bool| {
+        let page_height = layout();
+        if resized {
+            let (width, height) = window_size.get();
+            view.resize(width, page_height).unwrap();
+            if let Some(inspector) = inspector {
+                inspector.resize(width, (height / 2).max(1)).unwrap();
+            }
         }
 
-        renderer.update();
+        renderer.update().unwrap();
+
+        let inspector_view = inspector.as_ref().and_then(|inspector| inspector.view());
 
         // in case of resize of the view needs repaint, render and blit
         // otherwise, exit.
-        if !(view.needs_paint() || size.is_some()) {
+        let inspector_needs_paint = inspector_view
+            .as_ref()
+            .is_some_and(|inspector_view| inspector_view.needs_paint());
+        if !(view.needs_paint() || inspector_needs_paint || resized) {
             return;
         }
 
         // painting
-        renderer.render();
+        renderer.render().unwrap();
         // flush the drawing commands
         receiver.render().unwrap();
 
-        let render_target = view.render_target().unwrap();
+        let (width, height) = window_size.get();
+        let mut target = display.draw();
+        target.clear_color_srgb(0.0, 0.0, 0.0, 0.0);
+
+        // the page is at the top of the window, and the inspector below it
+        let mut blit = |view: &ul_next::view::View, bottom: u32, height: u32| {
+            let render_target = view.render_target().unwrap();
+            let texture = receiver.get_texture(&render_target.texture_id).unwrap();
 
-        let texture = receiver.get_texture(&render_target.texture_id).unwrap();
+            let uniforms = uniform! {
+                tex: texture.sampled()
+            };
+            let draw_parameters = glium::DrawParameters {
+                viewport: Some(glium::Rect {
+                    left: 0,
+                    bottom,
+                    width,
+                    height,
+                }),
+                ..Default::default()
+            };
 
-        let uniforms = uniform! {
-            tex: texture.sampled()
+            target
+                .draw(
+                    &vertex_buffer,
+                    &index_buffer,
+                    &program,
+                    &uniforms,
+                    &draw_parameters,
+                )
+                .unwrap();
         };
 
-        let mut target = display.draw();
-        target.clear_color_srgb(0.0, 0.0, 0.0, 0.0);
-        target
-            .draw(
-                &vertex_buffer,
-                &index_buffer,
-                &program,
-                &uniforms,
-                &Default::default(),
-            )
-            .unwrap();
+        blit(view, height - page_height, page_height);
+        if let Some(inspector_view) = &inspector_view {
+            blit(inspector_view, 0, height - page_height);
+        }
         target.finish().unwrap();
     };
 
-    update_and_draw(None);
+    // input state needed to convert `winit` events
+    let mut modifiers = ModifiersState::empty();
+    let mut cursor_position = PhysicalPosition::new(0.0, 0.0);
+    // whether keyboard events go to the inspector, changed by clicking
+    let mut inspector_focused = false;
+
+    // the view under `position`, and the position relative to it
+    let view_at = move |position: PhysicalPosition<f64>| {
+        let page_height = layout() as f64;
+        match inspector {
+            Some(inspector) if inspector.is_open() && position.y >= page_height => (
+                inspector.view(),
+                PhysicalPosition::new(position.x, position.y - page_height),
+            ),
+            _ => (None, position),
+        }
+    };
+
+    update_and_draw(true);
     #[allow(deprecated)]
     event_loop
         .run(move |event, target| {
@@ -163,12 +247,82 @@ fn main() {
                     winit::event::WindowEvent::CloseRequested => target.exit(),
                     // Redraw the triangle when the window is resized.
                     winit::event::WindowEvent::Resized(size) => {
-                        update_and_draw(Some((size.width, size.height)));
+                        window_size.set((size.width, size.height));
+                        update_and_draw(true);
+                    }
+                    winit::event::WindowEvent::ModifiersChanged(new_modifiers) => {
+                        modifiers = new_modifiers.state();
+                    }
+                    winit::event::WindowEvent::KeyboardInput { ref event, .. }
+                        if event.logical_key == Key::Named(NamedKey::F12) =>
+                    {
+                        if let (Some(inspector), ElementState::Pressed) = (inspector, event.state) {
+                            inspector.toggle();
+                            inspector_focused = inspector.is_open();
+                            update_and_draw(true);
+                        }
+                    }
+                    winit::event::WindowEvent::KeyboardInput { ref event, .. } => {
+                        let inspector_view = inspector
+                            .as_ref()
+                            .filter(|_| inspector_focused)
+                            .and_then(|inspector| inspector.view());
+                        let view = inspector_view.as_deref().unwrap_or(view);
+
+                        view.fire_key_event(
+                            KeyEvent::from_winit(lib.clone(), event, modifiers).unwrap(),
+                        );
+                        if let Some(char_event) =
+                            KeyEvent::char_from_winit(lib.clone(), event, modifiers).unwrap()
+                        {
+                            view.fire_key_event(char_event);
+                        }
+                    }
+                    winit::event::WindowEvent::CursorMoved { position, .. } => {
+                        cursor_position = position;
+                        let (inspector_view, position) = view_at(cursor_position);
+                        let view = inspector_view.as_deref().unwrap_or(view);
+
+                        if let Some(mouse_event) = MouseEvent::from_winit(
+                            lib.clone(),
+                            &event,
+                            position,
+                            view.device_scale(),
+                        )
+                        .unwrap()
+                        {
+                            view.fire_mouse_event(mouse_event);
+                        }
+                    }
+                    winit::event::WindowEvent::MouseInput { .. } => {
+                        let (inspector_view, position) = view_at(cursor_position);
+                        inspector_focused = inspector_view.is_some();
+                        let view = inspector_view.as_deref().unwrap_or(view);
+
+                        if let Some(mouse_event) = MouseEvent::from_winit(
+                            lib.clone(),
+                            &event,
+                            position,
+                            view.device_scale(),
+                        )
+                        .unwrap()
+                        {
+                            view.fire_mouse_event(mouse_event);
+                        }
+                    }
+                    winit::event::WindowEvent::MouseWheel { delta, .. } => {
+                        let (inspector_view, _) = view_at(cursor_position);
+                        let view = inspector_view.as_deref().unwrap_or(view);
+
+                        view.fire_scroll_event(
+                            ScrollEvent::from_winit(lib.clone(), delta, view.device_scale())
+                                .unwrap(),
+                        );
                     }
                     _ => {}
                 },
                 _ => {
-                    update_and_draw(None);
+                    update_and_draw(false);
                 }
             };
             target.set_control_flow(winit::event_loop::ControlFlow::Poll);
@@ -208,12 +362,26 @@ const HTML_STRING: &str = r#"
         margin: 40px;
         border-radius: 25px;
       }
+      .spinner {
+        display: block;
+        width: 32px;
+        height: 32px;
+        margin: 0 auto;
+        border-radius: 50%;
+        border: 4px solid #e5eaf9;
+        border-top-color: #8f8ae1;
+        animation: spin 1s linear infinite;
+      }
+      @keyframes spin {
+        to { transform: rotate(360deg); }
+      }
     </style>
   </head>
   <body>
     <div>
       <h1>Hello World!</h1>
       <p>Welcome to Ultralight!</p>
+      <span class="spinner"></span>
     </div>
   </body>
 </html>"#;
@@ -0,0 +1,163 @@
+//! Display a texture rendered by the application in a page, without copying
+//! it to the CPU.
+//!
+//! The texture is registered with the `glium` GPU driver as an external
+//! texture, and referenced by the page through `video.imgsrc`, which maps
+//! to the `video` image source.
+use std::time::Instant;
+
+use glium::{framebuffer::SimpleFrameBuffer, Surface, Texture2d};
+use ul_next::{
+    config::Config,
+    gpu_driver::glium::{create_gpu_driver, EitherTexture},
+    image_source::{image_source_provider, ImageSource},
+    platform,
+    renderer::Renderer,
+    view::ViewConfig,
+    Library, Rect,
+};
+
+const VIDEO_WIDTH: u32 = 320;
+const VIDEO_HEIGHT: u32 = 180;
+
+fn main() {
+    let lib = Library::linked();
+
+    let event_loop = winit::event_loop::EventLoop::builder().build().unwrap();
+
+    let (_window, display) = glium::backend::glutin::SimpleWindowBuilder::new()
+        .with_title("External texture")
+        .with_inner_size(900, 600)
+        .build(&event_loop);
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+
+    // basic setup (check `render_to_png` for full explanation)
+    platform::enable_platform_fontloader(lib.clone());
+    platform::enable_platform_filesystem(lib.clone(), "./examples").unwrap();
+    platform::enable_default_logger(lib.clone(), "./log.log").unwrap();
+
+    let (sender, mut receiver) = create_gpu_driver(&display).unwrap();
+
+    // the "video" texture, owned by the application and registered with the
+    // driver under an id that Ultralight never uses for its own textures
+    let video_id = sender.reserve_external_texture_id();
+    platform::set_gpu_driver(lib.clone(), sender);
+    let video = Texture2d::empty(&display, VIDEO_WIDTH, VIDEO_HEIGHT).unwrap();
+    receiver
+        .register_external_texture(video_id, EitherTexture::Regular2d(video))
+        .unwrap();
+
+    let renderer = Renderer::create(config).unwrap();
+
+    // the page references the texture with `video.imgsrc`
+    let image_source = ImageSource::create_from_texture(
+        lib.clone(),
+        VIDEO_WIDTH,
+        VIDEO_HEIGHT,
+        video_id,
+        Rect {
+            left: 0.0,
+            top: 0.0,
+            right: 1.0,
+            bottom: 1.0,
+        },
+        None,
+    )
+    .unwrap();
+    image_source_provider::add_image_source("video", &image_source).unwrap();
+
+    let view_config = ViewConfig::start()
+        .initial_device_scale(1.0)
+        .is_accelerated(true)
+        .try_build(lib.clone())
+        .unwrap();
+    let view = renderer.create_view(900, 600, &view_config, None).unwrap();
+    view.load_html_with_base_url(HTML_STRING, "file:///")
+        .unwrap();
+
+    let start = Instant::now();
+
+    #[allow(deprecated)]
+    event_loop
+        .run(move |event, target| {
+            match event {
+                winit::event::Event::WindowEvent { event, .. } => match event {
+                    winit::event::WindowEvent::CloseRequested => target.exit(),
+                    winit::event::WindowEvent::Resized(size) => {
+                        view.resize(size.width, size.height).unwrap();
+                    }
+                    _ => {}
+                },
+                _ => {
+                    // draw the next "frame" of the video directly on the GPU
+                    let time = start.elapsed().as_secs_f32();
+                    if let Some(EitherTexture::Regular2d(video)) = receiver.get_texture(&video_id) {
+                        let mut frame = SimpleFrameBuffer::new(&display, video).unwrap();
+                        let shade = 0.5 + 0.5 * time.sin();
+                        frame.clear_color(0.2, 0.2 * shade, 0.6 * shade, 1.0);
+
+                        let bar_width = VIDEO_WIDTH / 8;
+                        let travel = (time * 0.25).fract() * (VIDEO_WIDTH - bar_width) as f32;
+                        frame.clear(
+                            Some(&glium::Rect {
+                                left: travel as u32,
+                                bottom: 0,
+                                width: bar_width,
+                                height: VIDEO_HEIGHT,
+                            }),
+                            Some((1.0, 0.8, 0.2, 1.0)),
+                            false,
+                            None,
+                            None,
+                        );
+                    }
+                    // the texture changed, the page needs to be repainted
+                    image_source.invalidate();
+
+                    renderer.update().unwrap();
+                    renderer.render().unwrap();
+                    receiver.render().unwrap();
+
+                    let render_target = view.render_target().unwrap();
+                    let mut target = display.draw();
+                    target.clear_color(0.0, 0.0, 0.0, 0.0);
+                    receiver
+                        .draw_texture(&render_target.texture_id, &mut target)
+                        .unwrap();
+                    target.finish().unwrap();
+                }
+            };
+            target.set_control_flow(winit::event_loop::ControlFlow::Poll);
+        })
+        .unwrap();
+}
+
+const HTML_STRING: &str = r#"
+<html>
+  <head>
+    <style type="text/css">
+      body {
+        margin: 0;
+        font-family: Arial;
+        background: linear-gradient(-45deg, #acb4ff, #f5d4e2);
+        display: flex;
+        flex-direction: column;
+        justify-content: center;
+        align-items: center;
+        height: 100vh;
+      }
+      img {
+        width: 640px;
+        height: 360px;
+        border-radius: 12px;
+        box-shadow: 0 7px 18px -6px #8f8ae1;
+        transform: rotate(-3deg);
+      }
+    </style>
+  </head>
+  <body>
+    <h1>Live external texture</h1>
+    <img src="video.imgsrc" />
+  </body>
+</html>"#;
@@ -0,0 +1,178 @@
+//! Compare the frame times of [`GliumGpuDriverReceiver::render`] and
+//! [`GliumGpuDriverReceiver::render_with_budget`] when loading a heavy page.
+//!
+//! The page is loaded once with each method, and the time spent running the
+//! GPU commands is measured for each frame. Loading the page creates many
+//! textures and geometries at once, `render` runs them in a single frame (a
+//! hitch), while `render_with_budget` spreads them over several frames.
+//!
+//! Run with `cargo run --example glium_render_budget --features glium,winit`.
+//!
+//! [`GliumGpuDriverReceiver::render`]: ul_next::gpu_driver::glium::GliumGpuDriverReceiver::render
+//! [`GliumGpuDriverReceiver::render_with_budget`]: ul_next::gpu_driver::glium::GliumGpuDriverReceiver::render_with_budget
+use std::time::{Duration, Instant};
+
+use glium::Surface;
+use ul_next::{
+    config::Config, gpu_driver::glium::create_gpu_driver, platform, renderer::Renderer,
+    view::ViewConfig, Library,
+};
+
+/// The budget given to `render_with_budget`, in milliseconds.
+const BUDGET_MILLIS: f32 = 4.0;
+/// The number of frames measured for each method.
+const FRAMES: usize = 240;
+/// The frame time of a 60Hz display.
+const FRAME_TIME: Duration = Duration::from_micros(16_667);
+
+#[derive(Default)]
+struct Stats {
+    frames: Vec<Duration>,
+    /// The frame the page was fully rendered at.
+    completed_at: Option<usize>,
+}
+
+impl Stats {
+    fn print(&self, name: &str) {
+        let max = self.frames.iter().max().copied().unwrap_or_default();
+        let total = self.frames.iter().sum::<Duration>();
+        let hitches = self.frames.iter().filter(|t| **t > FRAME_TIME).count();
+        println!(
+            "{name:>18}: max {:>8.2?}, total {:>8.2?}, {hitches} frame(s) over {:.2?}, completed at frame {:?}",
+            max, total, FRAME_TIME, self.completed_at
+        );
+    }
+}
+
+fn main() {
+    let lib = Library::linked();
+
+    let event_loop = winit::event_loop::EventLoop::builder().build().unwrap();
+
+    let (_window, display) = glium::backend::glutin::SimpleWindowBuilder::new()
+        .with_title("Render budget")
+        .with_inner_size(900, 600)
+        .build(&event_loop);
+
+    // basic setup (check `render_to_png` for full explanation)
+    platform::enable_platform_fontloader(lib.clone());
+    platform::enable_platform_filesystem(lib.clone(), "./examples").unwrap();
+    platform::enable_default_logger(lib.clone(), "./log.log").unwrap();
+
+    let (sender, mut receiver) = create_gpu_driver(&display).unwrap();
+    platform::set_gpu_driver(lib.clone(), sender);
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+    let renderer = Renderer::create(config).unwrap();
+
+    let view_config = ViewConfig::start()
+        .initial_device_scale(1.0)
+        .is_accelerated(true)
+        .try_build(lib.clone())
+        .unwrap();
+    let view = renderer.create_view(900, 600, &view_config, None).unwrap();
+
+    // [full render, budgeted render]
+    let mut stats = [Stats::default(), Stats::default()];
+    let mut pass = 0;
+    let mut frame = 0;
+    view.load_html(HTML_STRING).unwrap();
+
+    #[allow(deprecated)]
+    event_loop
+        .run(move |event, target| {
+            match event {
+                winit::event::Event::WindowEvent {
+                    event: winit::event::WindowEvent::CloseRequested,
+                    ..
+                } => target.exit(),
+                winit::event::Event::AboutToWait => {
+                    renderer.update().unwrap();
+                    if view.needs_paint() {
+                        renderer.render().unwrap();
+                    }
+
+                    let start = Instant::now();
+                    let completed = if pass == 0 {
+                        receiver.render().unwrap();
+                        true
+                    } else {
+                        receiver
+                            .render_with_budget(BUDGET_MILLIS)
+                            .unwrap()
+                            .completed
+                    };
+                    stats[pass].frames.push(start.elapsed());
+                    if completed && !view.is_loading() && stats[pass].completed_at.is_none() {
+                        stats[pass].completed_at = Some(frame);
+                    }
+
+                    // present the last complete frame of the view, its
+                    // texture may not exist yet while the budget is
+                    // exhausted
+                    let mut target_frame = display.draw();
+                    target_frame.clear_color(0.0, 0.0, 0.0, 0.0);
+                    if let Some(render_target) = view.render_target() {
+                        if receiver.get_texture(&render_target.texture_id).is_some() {
+                            receiver
+                                .draw_texture(&render_target.texture_id, &mut target_frame)
+                                .unwrap();
+                        }
+                    }
+                    target_frame.finish().unwrap();
+
+                    frame += 1;
+                    if frame == FRAMES {
+                        frame = 0;
+                        pass += 1;
+                        if pass == stats.len() {
+                            stats[0].print("render");
+                            stats[1].print(&format!("budget {BUDGET_MILLIS}ms"));
+                            target.exit();
+                            return;
+                        }
+                        view.load_html(HTML_STRING).unwrap();
+                    }
+                }
+                _ => {}
+            }
+            target.set_control_flow(winit::event_loop::ControlFlow::Poll);
+        })
+        .unwrap();
+}
+
+// thousands of elements with gradients, shadows and rounded corners, to
+// create many textures and geometries at once
+const HTML_STRING: &str = r#"
+<html>
+  <head>
+    <style type="text/css">
+      body {
+        margin: 0;
+        font-family: Arial;
+        background: linear-gradient(-45deg, #acb4ff, #f5d4e2);
+      }
+      .card {
+        display: inline-block;
+        width: 60px;
+        height: 40px;
+        margin: 4px;
+        font-size: 10px;
+        border-radius: 8px;
+        box-shadow: 0 3px 8px -2px #8f8ae1;
+      }
+    </style>
+  </head>
+  <body>
+    <script>
+      var html = '';
+      for (var i = 0; i < 3000; i++) {
+        var hue = (i * 37) % 360;
+        html += '<div class="card" style="background: linear-gradient(' + (i % 360) +
+          'deg, hsl(' + hue + ', 80%, 70%), hsl(' + ((hue + 90) % 360) + ', 80%, 40%))">' +
+          'card ' + i + '</div>';
+      }
+      document.body.innerHTML = html;
+    </script>
+  </body>
+</html>"#;
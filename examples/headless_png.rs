@@ -0,0 +1,37 @@
+use ul_next::{
+    headless::{self, HeadlessOptions},
+    Library,
+};
+
+/// Renders a string of HTML to a PNG using the [`headless`] helpers, which
+/// do all the setup of `examples/render_to_png.rs` for us.
+fn main() {
+    let lib = Library::linked();
+
+    // the platform file system is created relative to the current directory,
+    // and Ultralight needs to find the `resources` folder there.
+    let output = std::env::current_dir().unwrap().join("result.png");
+    std::env::set_current_dir("./examples").unwrap();
+
+    let options = HeadlessOptions {
+        device_scale: 2.0,
+        ..Default::default()
+    };
+    let bitmap =
+        headless::render_html_to_bitmap(lib.clone(), HTML_STRING, 400, 200, &options).unwrap();
+
+    bitmap
+        .to_bitmap(lib)
+        .unwrap()
+        .write_to_png(&output)
+        .unwrap();
+
+    println!("Saved {}", output.display());
+}
+
+const HTML_STRING: &str = r#"
+<html>
+  <body style="margin: 0; background: linear-gradient(-45deg, #acb4ff, #f5d4e2);">
+    <h1 style="text-align: center; font-family: Arial;">Hello from headless!</h1>
+  </body>
+</html>"#;
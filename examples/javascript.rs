@@ -18,7 +18,7 @@ fn main() {
 
     // create window
     let window = app
-        .create_window(
+        .try_create_window(
             900,
             600,
             false,
@@ -42,45 +42,47 @@ fn main() {
     // AppCore automatically manages focus, keyboard/mouse input, and GPU painting for each active
     // overlay. Dropping the overlay will remove it from the window.
     let overlay = window
-        .create_overlay(window.width(), window.height(), 0, 0)
+        .try_create_overlay(window.width(), window.height(), 0, 0)
         .unwrap();
 
-    overlay.view().set_dom_ready_callback(move |view, _, _, _| {
-        let ctx = view.lock_js_context();
-        let global = ctx.global_object();
-
-        let func = JSObject::new_function_with_callback(&ctx, |ctx, _this, _args| {
-            // call the javascript function `JavascriptCallback`
-            // can be done by running a script
-            println!(
-                "Javascript returned {:?}",
-                ctx.evaluate_script("JavascriptCallback();")
-                    .unwrap()
-                    .as_string()
-                    .unwrap()
-            );
-            // or by getting the object and calling it
-            println!(
-                "Javascript returned {:?}",
-                ctx.global_object()
-                    .get_property("JavascriptCallback")
-                    .unwrap()
-                    .as_object()
-                    .unwrap()
-                    .call_as_function(None, &[])
-                    .unwrap()
-                    .as_string()
-                    .unwrap()
-            );
-
-            Ok(JSValue::new_string(ctx, "And Hello from Rust!<br>"))
+    // the JavaScript context is already locked in the callback
+    overlay
+        .view()
+        .set_dom_ready_callback(move |_view, _, _, _, ctx| {
+            let global = ctx.global_object();
+
+            let func = JSObject::new_function_with_callback(ctx, |ctx, _this, _args| {
+                // call the javascript function `JavascriptCallback`
+                // can be done by running a script
+                println!(
+                    "Javascript returned {:?}",
+                    ctx.evaluate_script("JavascriptCallback();", None, None, 1)
+                        .unwrap()
+                        .as_string()
+                        .unwrap()
+                );
+                // or by getting the object and calling it
+                println!(
+                    "Javascript returned {:?}",
+                    ctx.global_object()
+                        .get_property("JavascriptCallback")
+                        .unwrap()
+                        .as_object()
+                        .unwrap()
+                        .call_as_function(None, &[])
+                        .unwrap()
+                        .as_string()
+                        .unwrap()
+                );
+
+                Ok(JSValue::new_string(ctx, "And Hello from Rust!<br>"))
+            });
+
+            global
+                .set_property("GetRustMessage", &func, JSPropertyAttributes::default())
+                .unwrap();
         });
 
-        global
-            .set_property("GetRustMessage", &func, JSPropertyAttributes::default())
-            .unwrap();
-    });
-
     // Load a string of HTML into our overlay's View
     overlay.view().load_html(HTML_STRING).unwrap();
 
@@ -0,0 +1,176 @@
+//! Draw a page on top of a `macroquad` scene, the page is rendered by
+//! the `miniquad` GPU driver into a texture which is drawn by `macroquad`
+//! like any other texture.
+//!
+//! Run with `cargo run --example macroquad_overlay --features miniquad`.
+use macroquad::prelude::*;
+use ul_next::{
+    config::Config,
+    event::{self, MouseEvent, MouseEventType, ScrollEvent, ScrollEventType},
+    gpu_driver::miniquad::create_gpu_driver,
+    platform,
+    renderer::Renderer,
+    view::ViewConfig,
+    Library,
+};
+
+#[macroquad::main("Macroquad overlay")]
+async fn main() {
+    let lib = Library::linked();
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+
+    // basic setup (check `render_to_png` for full explanation)
+    platform::enable_platform_fontloader(lib.clone());
+    platform::enable_platform_filesystem(lib.clone(), "./examples").unwrap();
+
+    // flush what `macroquad` has queued before using the context directly
+    let mut gl = unsafe { get_internal_gl() };
+    gl.flush();
+    let (sender, mut receiver) = create_gpu_driver(gl.quad_context).unwrap();
+    platform::set_gpu_driver(lib.clone(), sender);
+
+    let renderer = Renderer::create(config).unwrap();
+
+    let view_config = ViewConfig::start()
+        .initial_device_scale(1.0)
+        .is_accelerated(true)
+        .is_transparent(true)
+        .try_build(lib.clone())
+        .unwrap();
+
+    let (mut width, mut height) = (screen_width() as u32, screen_height() as u32);
+    let view = renderer
+        .create_view(width, height, &view_config, None)
+        .unwrap();
+    view.load_html(HTML_STRING).unwrap();
+    view.focus();
+
+    let mut last_mouse = (-1.0, -1.0);
+    let mut rotation = 0.0;
+
+    loop {
+        if (screen_width() as u32, screen_height() as u32) != (width, height) {
+            (width, height) = (screen_width() as u32, screen_height() as u32);
+            view.resize(width, height).unwrap();
+        }
+
+        // forward the mouse input to the view
+        let (x, y) = mouse_position();
+        if (x, y) != last_mouse {
+            last_mouse = (x, y);
+            fire_mouse(
+                &lib,
+                &view,
+                MouseEventType::MouseMoved,
+                event::MouseButton::None,
+            );
+        }
+        for (button, ul_button) in [
+            (MouseButton::Left, event::MouseButton::Left),
+            (MouseButton::Middle, event::MouseButton::Middle),
+            (MouseButton::Right, event::MouseButton::Right),
+        ] {
+            if is_mouse_button_pressed(button) {
+                fire_mouse(&lib, &view, MouseEventType::MouseDown, ul_button);
+            }
+            if is_mouse_button_released(button) {
+                fire_mouse(&lib, &view, MouseEventType::MouseUp, ul_button);
+            }
+        }
+        let (wheel_x, wheel_y) = mouse_wheel();
+        if wheel_x != 0.0 || wheel_y != 0.0 {
+            view.fire_scroll_event(
+                ScrollEvent::new(
+                    lib.clone(),
+                    ScrollEventType::ScrollByPixel,
+                    wheel_x as i32,
+                    wheel_y as i32,
+                )
+                .unwrap(),
+            );
+        }
+
+        // the scene behind the page
+        clear_background(DARKBLUE);
+        rotation += get_frame_time() * 90.0;
+        draw_poly(
+            screen_width() / 2.0,
+            screen_height() / 2.0,
+            4,
+            150.0,
+            rotation,
+            ORANGE,
+        );
+
+        renderer.update().unwrap();
+        renderer.render().unwrap();
+
+        // execute the commands of the GPU driver
+        let mut gl = unsafe { get_internal_gl() };
+        gl.flush();
+        receiver.render(gl.quad_context).unwrap();
+
+        if let Some(texture) = view
+            .render_target()
+            .and_then(|render_target| receiver.get_texture(&render_target.texture_id))
+        {
+            draw_texture_ex(
+                &Texture2D::from_miniquad_texture(texture),
+                0.0,
+                0.0,
+                Color::new(1.0, 1.0, 1.0, 0.9),
+                DrawTextureParams {
+                    dest_size: Some(vec2(width as f32, height as f32)),
+                    ..Default::default()
+                },
+            );
+        }
+
+        next_frame().await
+    }
+}
+
+fn fire_mouse(
+    lib: &std::sync::Arc<Library>,
+    view: &ul_next::view::View,
+    ty: MouseEventType,
+    button: event::MouseButton,
+) {
+    let (x, y) = mouse_position();
+    view.fire_mouse_event(MouseEvent::new(lib.clone(), ty, x as i32, y as i32, button).unwrap());
+}
+
+const HTML_STRING: &str = r#"
+<html>
+  <head>
+    <style>
+      body {
+        font-family: -apple-system, 'Segoe UI', Ubuntu, Arial, sans-serif;
+        color: white;
+        background: transparent;
+        margin: 40px;
+      }
+      .panel {
+        width: 320px;
+        padding: 20px;
+        border-radius: 12px;
+        background: rgba(20, 24, 40, 0.8);
+      }
+      button {
+        padding: 8px 16px;
+        font-size: 16px;
+      }
+    </style>
+  </head>
+  <body>
+    <div class="panel">
+      <h2>Hello from Ultralight</h2>
+      <p>This page is rendered with the <code>miniquad</code> GPU driver
+      on top of a <code>macroquad</code> scene.</p>
+      <button onclick="this.innerText = 'Clicked ' + (++window.clicks) + ' times'">Click me</button>
+      <script>window.clicks = 0;</script>
+    </div>
+  </body>
+</html>
+"#;
@@ -39,7 +39,7 @@ fn main() {
 
     // create our config
     // we are using the defaults, but we can change that if we need
-    let config = Config::start().build(lib.clone()).unwrap();
+    let config = Config::start().try_build(lib.clone()).unwrap();
 
     // Since we're not using App::Create(), we must provide our own Platform API handlers.
     //
@@ -87,7 +87,7 @@ fn main() {
         .initial_device_scale(2.0)
         .font_family_standard("Arial")
         .is_accelerated(false)
-        .build(lib.clone())
+        .try_build(lib.clone())
         .unwrap();
 
     // We use the default session by passing `None`.
@@ -123,7 +123,7 @@ fn main() {
         // @note:
         //   Calling Renderer::Update handles any pending network requests, resource loads, and
         //   JavaScript timers.
-        renderer.update();
+        renderer.update().unwrap();
         thread::sleep(Duration::from_millis(10));
     }
     println!("finished main loop");
@@ -132,7 +132,7 @@ fn main() {
     //
     // @note:
     //   Calling Renderer::Render will render any dirty Views to their respective Surfaces.
-    renderer.render();
+    renderer.render().unwrap();
 
     // Get the Surface for our View.
     // This will be `None` if the View is accelerated.
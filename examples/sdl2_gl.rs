@@ -0,0 +1,125 @@
+//! Render a page in an SDL2 window with the `glium` GPU driver, using the
+//! glue of [`ul_next::integration::sdl2`] for the OpenGL context and the
+//! input events.
+//!
+//! Run with `cargo run --example sdl2_gl --features sdl2,glium [URL]`, a
+//! small form is shown when no URL is given.
+use sdl2::{event::Event, video::GLProfile};
+use ul_next::{
+    config::Config,
+    integration::sdl2::{handle_event, Sdl2GlDriverHost},
+    platform,
+    renderer::Renderer,
+    view::ViewConfig,
+    Library,
+};
+
+fn main() {
+    let lib = Library::linked();
+
+    let sdl = sdl2::init().unwrap();
+    let video = sdl.video().unwrap();
+
+    // `glium` needs an OpenGL 3.3 core context, the attributes must be set
+    // before creating the window
+    let gl_attr = video.gl_attr();
+    gl_attr.set_context_profile(GLProfile::Core);
+    gl_attr.set_context_version(3, 3);
+
+    let window = video
+        .window("Ultralight + SDL2", 900, 600)
+        .opengl()
+        .resizable()
+        .position_centered()
+        .build()
+        .unwrap();
+
+    let mut host = Sdl2GlDriverHost::new(&video, window).unwrap();
+
+    // basic setup (check `render_to_png` for full explanation)
+    platform::enable_platform_fontloader(lib.clone());
+    platform::enable_platform_filesystem(lib.clone(), "./examples").unwrap();
+    platform::enable_default_logger(lib.clone(), "./log.log").unwrap();
+    platform::set_gpu_driver(lib.clone(), host.take_gpu_driver().unwrap());
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+    let renderer = Renderer::create(config).unwrap();
+
+    let view_config = ViewConfig::start()
+        .initial_device_scale(1.0)
+        .is_accelerated(true)
+        .try_build(lib.clone())
+        .unwrap();
+
+    let (width, height) = host.drawable_size();
+    let view = renderer
+        .create_view(width, height, &view_config, None)
+        .unwrap();
+
+    match std::env::args().nth(1) {
+        Some(url) => view.load_url(&url).unwrap(),
+        None => view.load_html(HTML_STRING).unwrap(),
+    }
+    view.focus();
+
+    // SDL only sends `TextInput` events (the typed characters) while text
+    // input is active
+    video.text_input().start();
+
+    let mut event_pump = sdl.event_pump().unwrap();
+    'main: loop {
+        for event in event_pump.poll_iter() {
+            if let Event::Quit { .. } = event {
+                break 'main;
+            }
+            handle_event(&event, host.window(), &view).unwrap();
+        }
+
+        renderer.update().unwrap();
+        if view.needs_paint() {
+            renderer.render().unwrap();
+            host.draw_view(&view).unwrap();
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(4));
+    }
+}
+
+const HTML_STRING: &str = r#"
+<html>
+  <head>
+    <style type="text/css">
+      body {
+        margin: 0;
+        font-family: Arial;
+        background: linear-gradient(-45deg, #acb4ff, #f5d4e2);
+        display: flex;
+        justify-content: center;
+        align-items: center;
+        height: 100vh;
+      }
+      form {
+        width: 350px;
+        padding: 2em;
+        border-radius: 25px;
+        background: white;
+        box-shadow: 0 7px 18px -6px #8f8ae1;
+      }
+      input, textarea {
+        display: block;
+        width: 100%;
+        margin-bottom: 1em;
+        font-size: 16px;
+      }
+    </style>
+  </head>
+  <body>
+    <form onsubmit="event.preventDefault(); output.textContent = 'Hello, ' + name.value + '!';">
+      <h2>SDL2 + Ultralight</h2>
+      <input id="name" placeholder="Your name" autofocus>
+      <textarea rows="4" placeholder="Scroll, click and type here"></textarea>
+      <button type="submit">Greet</button>
+      <p id="output"></p>
+    </form>
+  </body>
+</html>"#;
@@ -7,12 +7,17 @@
 //! have access to the textures to integrate into your game/application, check
 //! [`Renderer`] where you can implement your own
 //! [`GpuDriver`](crate::gpu_driver::GpuDriver) and integrate it with your project.
-use std::sync::Arc;
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use crate::{
     config::Config,
-    error::CreationError,
+    error::{CreationError, Error},
     renderer::Renderer,
+    string::path_to_str,
     window::{Window, WindowFlags},
     Library,
 };
@@ -50,7 +55,7 @@ impl Drop for Settings {
 pub struct SettingsBuilder {
     developer_name: Option<String>,
     app_name: Option<String>,
-    filesystem_path: Option<String>,
+    filesystem_path: Option<PathBuf>,
     load_shaders_from_filesystem: Option<bool>,
     force_cpu_renderer: Option<bool>,
 }
@@ -83,8 +88,8 @@ impl SettingsBuilder {
     ///     - Windows: relative to the executable path
     ///     - Linux:   relative to the executable path
     ///     - macOS:   relative to `YourApp.app/Contents/Resources/`
-    pub fn filesystem_path(mut self, filesystem_path: &str) -> Self {
-        self.filesystem_path = Some(filesystem_path.to_string());
+    pub fn filesystem_path<P: AsRef<Path>>(mut self, filesystem_path: P) -> Self {
+        self.filesystem_path = Some(filesystem_path.as_ref().to_path_buf());
         self
     }
 
@@ -108,14 +113,36 @@ impl SettingsBuilder {
 
     /// Builds the [`Settings`] struct using the settings configured in this builder.
     ///
-    /// Return [`None`] if failed to create [`Settings`].
+    /// Returns [`None`] on failure, use [`SettingsBuilder::try_build`] to get the
+    /// error.
+    #[deprecated(note = "use `try_build` instead, it returns the error")]
     pub fn build(self, lib: Arc<Library>) -> Option<Settings> {
+        self.try_build(lib).ok()
+    }
+
+    /// Builds the [`Settings`] struct using the settings configured in this builder.
+    ///
+    /// Returns [`CreationError::InvalidPath`] if the
+    /// [`filesystem_path`](Self::filesystem_path) is not valid UTF-8.
+    pub fn try_build(self, lib: Arc<Library>) -> Result<Settings, CreationError> {
+        let filesystem_path = self
+            .filesystem_path
+            .as_deref()
+            .map(|path| path_to_str(path).map(str::to_string))
+            .transpose()?;
+
         let internal = unsafe { lib.appcore().ulCreateSettings() };
 
         if internal.is_null() {
-            return None;
+            return Err(CreationError::NullReference);
         }
 
+        // destroys `internal` if setting one of the options fails
+        let settings = Settings {
+            lib: lib.clone(),
+            internal,
+        };
+
         set_config_str!(
             internal,
             self.developer_name,
@@ -126,7 +153,7 @@ impl SettingsBuilder {
 
         set_config_str!(
             internal,
-            self.filesystem_path,
+            filesystem_path,
             lib.appcore().ulSettingsSetFileSystemPath
         );
 
@@ -142,18 +169,30 @@ impl SettingsBuilder {
             lib.appcore().ulSettingsSetForceCPURenderer
         );
 
-        Some(Settings { lib, internal })
+        Ok(settings)
     }
 }
 
 /// Monitor struct, represents a platform monitor.
-pub struct Monitor {
+///
+/// Monitors are owned by the [`App`], so they can't outlive it.
+/// (See [`App::main_monitor`] and [`App::all_monitors`])
+#[derive(Clone)]
+pub struct Monitor<'a> {
     lib: Arc<Library>,
     // This is managed by the `App`, so we don't need to free it.
     internal: ul_sys::ULMonitor,
+    index: usize,
+    _app: PhantomData<&'a App>,
 }
 
-impl Monitor {
+impl Monitor<'_> {
+    /// Get the index of the monitor in [`App::all_monitors`], the main
+    /// monitor is always `0`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
     /// Get the DPI scale (1.0 = 100%)
     pub fn get_scale(&self) -> f64 {
         unsafe { self.lib.appcore().ulMonitorGetScale(self.internal) }
@@ -175,7 +214,8 @@ pub struct App {
     lib: Arc<Library>,
     settings: Settings,
 
-    monitor: Monitor,
+    /// The main monitor, null on headless systems.
+    monitor: ul_sys::ULMonitor,
     renderer: Renderer,
 
     internal: ul_sys::ULApp,
@@ -201,16 +241,12 @@ impl App {
     ) -> Result<Self, CreationError> {
         let config = match config {
             Some(config) => config,
-            None => Config::start()
-                .build(lib.clone())
-                .ok_or(CreationError::NullReference)?,
+            None => Config::start().try_build(lib.clone())?,
         };
 
         let settings = match settings {
             Some(settings) => settings,
-            None => Settings::start()
-                .build(lib.clone())
-                .ok_or(CreationError::NullReference)?,
+            None => Settings::start().try_build(lib.clone())?,
         };
 
         unsafe {
@@ -219,14 +255,7 @@ impl App {
                 return Err(CreationError::NullReference);
             }
 
-            let monitor = Monitor {
-                lib: lib.clone(),
-                internal: lib.appcore().ulAppGetMainMonitor(app_internal),
-            };
-            if monitor.internal.is_null() {
-                lib.appcore().ulDestroyApp(app_internal);
-                return Err(CreationError::NullReference);
-            }
+            let monitor = lib.appcore().ulAppGetMainMonitor(app_internal);
             let renderer_raw = lib.appcore().ulAppGetRenderer(app_internal);
             if let Ok(renderer) = Renderer::from_raw(lib.clone(), renderer_raw) {
                 Ok(Self {
@@ -251,8 +280,29 @@ impl App {
     }
 
     /// Get the main monitor of the app.
-    pub fn main_monitor(&self) -> &Monitor {
-        &self.monitor
+    ///
+    /// Returns [`None`] if there is no monitor, for example on headless
+    /// systems.
+    pub fn main_monitor(&self) -> Option<Monitor<'_>> {
+        if self.monitor.is_null() {
+            return None;
+        }
+
+        Some(Monitor {
+            lib: self.lib.clone(),
+            internal: self.monitor,
+            index: 0,
+            _app: PhantomData,
+        })
+    }
+
+    /// Get all the monitors connected to the system.
+    ///
+    /// AppCore only exposes the main monitor, so this is currently the same
+    /// as [`App::main_monitor`] as a list, and is empty when there is no
+    /// monitor.
+    pub fn all_monitors(&self) -> Vec<Monitor<'_>> {
+        self.main_monitor().into_iter().collect()
     }
 
     /// Whether or not the app is running.
@@ -261,6 +311,37 @@ impl App {
     }
 
     /// Get the underlying [`Renderer`] instance.
+    ///
+    /// The renderer is owned by the `App` (it is destroyed with it), so only
+    /// a reference is returned, and anything created from it (eg. sessions
+    /// with [`Renderer::create_session`] or views with
+    /// [`Renderer::create_view`]) should be dropped before the `App`.
+    ///
+    /// The `App` calls [`Renderer::update`] and [`Renderer::render`] itself
+    /// in its run loop, so these shouldn't be called manually, but the
+    /// renderer can be used for everything else, for example to create a
+    /// view with a private (in-memory) session and show it in a window:
+    /// ```no_run
+    /// # use ul_next::{app::App, view::ViewConfig, window::Window};
+    /// # fn example(
+    /// #     lib: std::sync::Arc<ul_next::Library>,
+    /// #     app: &App,
+    /// #     window: &Window,
+    /// # ) -> Result<(), ul_next::error::CreationError> {
+    /// let session = app.renderer().create_session(false, "private")?;
+    /// let view_config = ViewConfig::start()
+    ///     .is_accelerated(true)
+    ///     .try_build(lib.clone())
+    ///     .unwrap();
+    /// let view = app
+    ///     .renderer()
+    ///     .create_view(window.width(), window.height(), &view_config, Some(&session))?;
+    /// let overlay = window.try_create_overlay_with_view(view, 0, 0).unwrap();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// See the `app_private_session` example.
     pub fn renderer(&self) -> &Renderer {
         &self.renderer
     }
@@ -273,7 +354,9 @@ impl App {
         /// [`Renderer::update`](crate::renderer::Renderer::update) and
         /// [`Renderer::render`](crate::renderer::Renderer::render).
         pub fn set_update_callback(&self, callback: FnMut()) :
-            [App::lib.appcore()] ulAppSetUpdateCallback() {}
+            [App::lib.appcore()] ulAppSetUpdateCallback() {
+               let _appcore = crate::callback_panic::enter_appcore_callback();
+        }
     }
 
     /// Start the main loop.
@@ -298,7 +381,9 @@ impl App {
     ///
     /// The window will be closed automatically if the object is dropped.
     ///
-    /// Returns [`None`] if the window could not be created.
+    /// Returns [`None`] on failure, use [`App::try_create_window`] to get
+    /// the error.
+    #[deprecated(note = "use `try_create_window` instead, it returns the error")]
     pub fn create_window(
         &self,
         width: u32,
@@ -306,16 +391,48 @@ impl App {
         fullscreen: bool,
         window_flags: WindowFlags,
     ) -> Option<Window> {
+        self.try_create_window(width, height, fullscreen, window_flags)
+            .ok()
+    }
+
+    /// Create a new window.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the window.
+    /// * `height` - The height of the window.
+    /// * `fullscreen` - Whether or not the window should be fullscreen.
+    /// * `window_flags` - Various [`WindowFlags`].
+    ///
+    /// The window will be shown by default unless [`WindowFlags::hidden`] was set.
+    ///
+    /// The window will be closed automatically if the object is dropped.
+    ///
+    /// Returns [`Error::Unsupported`] if there is no monitor (see
+    /// [`App::main_monitor`]).
+    pub fn try_create_window(
+        &self,
+        width: u32,
+        height: u32,
+        fullscreen: bool,
+        window_flags: WindowFlags,
+    ) -> Result<Window, Error> {
+        if self.monitor.is_null() {
+            return Err(Error::Unsupported(
+                "creating a window without a monitor".to_string(),
+            ));
+        }
+
         unsafe {
             Window::create(
                 self.lib.clone(),
-                self.monitor.internal,
+                self.monitor,
                 width,
                 height,
                 fullscreen,
                 window_flags,
             )
         }
+        .ok_or_else(|| CreationError::NullReference.into())
     }
 }
 
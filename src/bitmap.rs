@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use crate::Library;
+use crate::{error::UnknownEnumValue, Library};
 
 /// Errors can occure when creating [`Bitmap`]s
 #[derive(Debug, thiserror::Error)]
@@ -30,11 +30,18 @@ pub enum BitmapError {
     /// Could not create bitmap because its empty
     #[error("Could not create bitmap because its empty")]
     EmptyBitmap,
+    /// Could not write bitmap to JPEG successfully.
+    #[error("Could not write bitmap to JPEG successfully")]
+    FailedJpegWrite,
+    /// Could not write bitmap to WebP successfully.
+    #[error("Could not write bitmap to WebP successfully")]
+    FailedWebpWrite,
 }
 
 type BitmapResult<T> = std::result::Result<T, BitmapError>;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The supported bitmap formats.
 pub enum BitmapFormat {
     /// Alpha channel only, 8-bits per pixel.
@@ -53,7 +60,7 @@ pub enum BitmapFormat {
 }
 
 impl TryFrom<ul_sys::ULBitmapFormat> for BitmapFormat {
-    type Error = ();
+    type Error = UnknownEnumValue;
 
     fn try_from(format: ul_sys::ULBitmapFormat) -> Result<Self, Self::Error> {
         match format {
@@ -61,7 +68,7 @@ impl TryFrom<ul_sys::ULBitmapFormat> for BitmapFormat {
             ul_sys::ULBitmapFormat_kBitmapFormat_BGRA8_UNORM_SRGB => {
                 Ok(BitmapFormat::Bgra8UnormSrgb)
             }
-            _ => Err(()),
+            _ => Err(UnknownEnumValue::new("BitmapFormat", format)),
         }
     }
 }
@@ -232,6 +239,34 @@ impl Bitmap {
         }
     }
 
+    /// Create a Bitmap from an [`image::DynamicImage`].
+    ///
+    /// [`image::DynamicImage::ImageLuma8`] images are converted to
+    /// [`BitmapFormat::A8Unorm`] bitmaps, and all other images to
+    /// [`BitmapFormat::Bgra8UnormSrgb`] (with premultiplied alpha).
+    ///
+    /// This is the reverse of [`Bitmap::to_image_buffer`].
+    #[cfg(feature = "image-compat")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "image-compat")))]
+    pub fn from_image_buffer(lib: Arc<Library>, img: &image::DynamicImage) -> BitmapResult<Self> {
+        match img {
+            image::DynamicImage::ImageLuma8(gray) => Self::create_from_pixels(
+                lib,
+                gray.width(),
+                gray.height(),
+                BitmapFormat::A8Unorm,
+                gray.as_raw(),
+            ),
+            img => {
+                let rgba = img.to_rgba8();
+                let (width, height) = rgba.dimensions();
+                let mut pixels = rgba.into_raw();
+                premultiply_rgba_to_bgra(&mut pixels);
+                Self::create_from_pixels(lib, width, height, BitmapFormat::Bgra8UnormSrgb, &pixels)
+            }
+        }
+    }
+
     /// Create a bitmap from a deep copy of another Bitmap.
     pub fn copy(&self) -> BitmapResult<Self> {
         let internal = unsafe { self.lib.ultralight().ulCreateBitmapFromCopy(self.internal) };
@@ -324,8 +359,15 @@ impl Bitmap {
     }
 
     /// Write bitmap to a PNG on disk.
+    ///
+    /// Returns [`BitmapError::FailedPngWrite`] if the path is not valid
+    /// UTF-8 or contains NUL characters, as it can't be passed to `Ultralight`.
     pub fn write_to_png<P: AsRef<Path>>(&self, path: P) -> BitmapResult<()> {
-        let c_path = CString::new(path.as_ref().to_str().unwrap()).unwrap();
+        let c_path = path
+            .as_ref()
+            .to_str()
+            .and_then(|path| CString::new(path).ok())
+            .ok_or(BitmapError::FailedPngWrite)?;
         let result = unsafe {
             self.lib
                 .ultralight()
@@ -338,6 +380,131 @@ impl Bitmap {
         }
     }
 
+    /// Encode the bitmap as a PNG image in memory, without going through
+    /// the file system like [`Bitmap::write_to_png`].
+    ///
+    /// [`BitmapFormat::A8Unorm`] bitmaps are encoded as grayscale images,
+    /// and [`BitmapFormat::Bgra8UnormSrgb`] bitmaps as `RGBA` with straight
+    /// (not premultiplied) alpha.
+    #[cfg(feature = "png")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "png")))]
+    pub fn encode_png(&self) -> BitmapResult<Vec<u8>> {
+        encode_png(
+            self.packed_pixels()?,
+            self.width(),
+            self.height(),
+            self.format(),
+        )
+    }
+
+    /// Write bitmap to a JPEG on disk, `quality` is clamped to `0..=100`.
+    ///
+    /// See [`Bitmap::encode_jpeg`] for how the pixels are encoded.
+    #[cfg(feature = "jpeg")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jpeg")))]
+    pub fn write_to_jpeg<P: AsRef<Path>>(&self, path: P, quality: u8) -> BitmapResult<()> {
+        let encoded = self.encode_jpeg(quality)?;
+        std::fs::write(path, encoded).map_err(|_| BitmapError::FailedJpegWrite)
+    }
+
+    /// Encode the bitmap as a JPEG image in memory, `quality` is clamped to
+    /// `0..=100`.
+    ///
+    /// [`BitmapFormat::A8Unorm`] bitmaps are encoded as grayscale images.
+    /// JPEG doesn't support transparency, so the alpha channel of
+    /// [`BitmapFormat::Bgra8UnormSrgb`] bitmaps is dropped, which (since the
+    /// alpha is premultiplied) is the same as drawing them on black.
+    #[cfg(feature = "jpeg")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jpeg")))]
+    pub fn encode_jpeg(&self, quality: u8) -> BitmapResult<Vec<u8>> {
+        encode_jpeg(
+            &self.packed_pixels()?,
+            self.width(),
+            self.height(),
+            self.format(),
+            quality,
+        )
+    }
+
+    /// Convert the bitmap to an [`image::DynamicImage`] to be processed with
+    /// the `image` crate.
+    ///
+    /// [`BitmapFormat::A8Unorm`] bitmaps are converted to
+    /// [`image::GrayImage`], and [`BitmapFormat::Bgra8UnormSrgb`] bitmaps to
+    /// [`image::RgbaImage`] with straight (not premultiplied) alpha.
+    #[cfg(feature = "image-compat")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "image-compat")))]
+    pub fn to_image_buffer(&self) -> BitmapResult<image::DynamicImage> {
+        to_image_buffer(
+            self.packed_pixels()?,
+            self.width(),
+            self.height(),
+            self.format(),
+        )
+    }
+
+    /// Write bitmap to a lossy WebP on disk, `quality` is clamped to `0..=100`.
+    ///
+    /// [`BitmapFormat::A8Unorm`] bitmaps are written as grayscale images.
+    #[cfg(feature = "webp")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "webp")))]
+    pub fn write_to_webp<P: AsRef<Path>>(&self, path: P, quality: u8) -> BitmapResult<()> {
+        let width = self.width();
+        let height = self.height();
+        let pixels = self.packed_pixels()?;
+
+        let encoded = match self.format() {
+            BitmapFormat::A8Unorm => {
+                let rgb = pixels.iter().flat_map(|&v| [v, v, v]).collect::<Vec<_>>();
+                webp::Encoder::from_rgb(&rgb, width, height).encode(quality.min(100) as f32)
+            }
+            BitmapFormat::Bgra8UnormSrgb => {
+                // WebP expects straight (not premultiplied) RGBA
+                let mut rgba = pixels;
+                unpremultiply_bgra_to_rgba(&mut rgba);
+                webp::Encoder::from_rgba(&rgba, width, height).encode(quality.min(100) as f32)
+            }
+        };
+
+        std::fs::write(path, &*encoded).map_err(|_| BitmapError::FailedWebpWrite)
+    }
+
+    /// Copy the pixels without the row padding.
+    #[cfg(any(
+        feature = "png",
+        feature = "jpeg",
+        feature = "webp",
+        feature = "image-compat"
+    ))]
+    fn packed_pixels(&self) -> BitmapResult<Vec<u8>> {
+        if self.is_empty() {
+            return Err(BitmapError::EmptyBitmap);
+        }
+
+        let row_len = self.width() as usize * self.bpp() as usize;
+        let row_bytes = self.row_bytes() as usize;
+        let height = self.height() as usize;
+
+        let mut result = Vec::with_capacity(row_len * height);
+        unsafe {
+            self.lib.ultralight().ulBitmapLockPixels(self.internal);
+            let raw_pixels = self.lib.ultralight().ulBitmapRawPixels(self.internal) as *const u8;
+            if !raw_pixels.is_null() {
+                let data = slice::from_raw_parts(raw_pixels, self.bytes_size());
+                for row in data.chunks(row_bytes).take(height) {
+                    result.extend_from_slice(&row[..row_len]);
+                }
+            }
+            self.lib.ultralight().ulBitmapUnlockPixels(self.internal);
+        }
+
+        if result.is_empty() {
+            Err(BitmapError::EmptyBitmap)
+        } else {
+            Ok(result)
+        }
+    }
+
     /// This converts a BGRA bitmap to RGBA bitmap and vice-versa by swapping the red and blue channels.
     ///
     /// Only valid if the format is BitmapFormat::BGRA8_UNORM_SRGB
@@ -353,6 +520,79 @@ impl Bitmap {
             Err(BitmapError::UnsupportedOperationForPixelFormat)
         }
     }
+
+    /// Flip the bitmap upside down in place, by reversing the order of its
+    /// rows.
+    ///
+    /// This is useful to upload the pixels to APIs with a bottom-left
+    /// origin, such as OpenGL textures.
+    pub fn flip_vertical(&mut self) -> BitmapResult<()> {
+        let (width, height, bpp, row_bytes) =
+            (self.width(), self.height(), self.bpp(), self.row_bytes());
+        let mut pixels = self.lock_pixels().ok_or(BitmapError::EmptyBitmap)?;
+        flip_rows(&mut pixels, width, height, bpp, row_bytes);
+        Ok(())
+    }
+
+    /// Mirror the bitmap in place, by reversing the order of the pixels of
+    /// each row.
+    pub fn flip_horizontal(&mut self) -> BitmapResult<()> {
+        let (width, bpp, row_bytes) = (self.width(), self.bpp(), self.row_bytes());
+        let mut pixels = self.lock_pixels().ok_or(BitmapError::EmptyBitmap)?;
+        mirror_rows(&mut pixels, width, bpp, row_bytes);
+        Ok(())
+    }
+
+    /// Call `f(x, y, pixel)` for each pixel of the bitmap, in row-major
+    /// order, with the [`bpp`](Bitmap::bpp) bytes of the pixel, which can be
+    /// modified.
+    ///
+    /// The pixels are locked during the iteration, and the padding at the
+    /// end of the rows (see [`Bitmap::row_bytes`]) is skipped.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(bitmap: &mut ul_next::bitmap::Bitmap) -> Result<(), ul_next::bitmap::BitmapError> {
+    /// // make the left half of a BGRA bitmap transparent
+    /// let half = bitmap.width() / 2;
+    /// bitmap.iterate_pixels(|x, _y, pixel| {
+    ///     if x < half {
+    ///         pixel.fill(0);
+    ///     }
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iterate_pixels<F: FnMut(u32, u32, &mut [u8])>(&mut self, f: F) -> BitmapResult<()> {
+        let (width, height, bpp, row_bytes) =
+            (self.width(), self.height(), self.bpp(), self.row_bytes());
+        let mut pixels = self.lock_pixels().ok_or(BitmapError::EmptyBitmap)?;
+        visit_pixels_mut(&mut pixels, width, height, bpp, row_bytes, f);
+        Ok(())
+    }
+
+    /// Call `f(x, y, pixel)` for each pixel of the bitmap, in row-major
+    /// order, with the [`bpp`](Bitmap::bpp) bytes of the pixel.
+    ///
+    /// This is the read-only version of [`Bitmap::iterate_pixels`].
+    pub fn for_each_pixel<F: FnMut(u32, u32, &[u8])>(&self, f: F) -> BitmapResult<()> {
+        let (width, height, bpp, row_bytes) =
+            (self.width(), self.height(), self.bpp(), self.row_bytes());
+        unsafe {
+            self.lib.ultralight().ulBitmapLockPixels(self.internal);
+            let _unlock = scopeguard::guard((), |_| {
+                self.lib.ultralight().ulBitmapUnlockPixels(self.internal)
+            });
+
+            let raw_pixels = self.lib.ultralight().ulBitmapRawPixels(self.internal);
+            if raw_pixels.is_null() {
+                return Err(BitmapError::EmptyBitmap);
+            }
+            let pixels = slice::from_raw_parts(raw_pixels as *const u8, self.bytes_size());
+            visit_pixels(pixels, width, height, bpp, row_bytes, f);
+        }
+        Ok(())
+    }
 }
 
 impl Drop for Bitmap {
@@ -369,6 +609,8 @@ impl Drop for Bitmap {
 /// binding to the underlying C library.
 ///
 /// To create an `Ultralight` bitmap, use [`OwnedBitmap::to_bitmap`].
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OwnedBitmap {
     width: u32,
     height: u32,
@@ -407,6 +649,56 @@ impl OwnedBitmap {
         })
     }
 
+    /// Create an [`OwnedBitmap`] from tightly packed `pixels` (no row padding)
+    /// without calling into `Ultralight`.
+    ///
+    /// Returns [`BitmapError::PixelBufferSizeMismatch`] if the size of
+    /// `pixels` is not `width * height * format.bytes_per_pixel()`.
+    pub fn from_pixels(
+        width: u32,
+        height: u32,
+        format: BitmapFormat,
+        pixels: Vec<u8>,
+    ) -> BitmapResult<Self> {
+        let bpp = format.bytes_per_pixel();
+        let row_bytes = width * bpp;
+        let bytes_size = row_bytes as usize * height as usize;
+        if pixels.len() != bytes_size {
+            return Err(BitmapError::PixelBufferSizeMismatch {
+                got: pixels.len(),
+                required: bytes_size,
+            });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            format,
+            bpp,
+            row_bytes,
+            bytes_size,
+            pixels: Some(pixels),
+            is_empty: false,
+        })
+    }
+
+    /// Create an empty [`OwnedBitmap`] (no pixels allocated), this is what
+    /// [`GpuDriver::create_texture`](crate::gpu_driver::GpuDriver::create_texture)
+    /// receives for the backing textures of render buffers.
+    pub fn empty(width: u32, height: u32, format: BitmapFormat) -> Self {
+        let bpp = format.bytes_per_pixel();
+        Self {
+            width,
+            height,
+            format,
+            bpp,
+            row_bytes: width * bpp,
+            bytes_size: 0,
+            pixels: None,
+            is_empty: true,
+        }
+    }
+
     /// Create a [`Bitmap`] from an [`OwnedBitmap`].
     ///
     /// This is useful when we need to call `Ultralight` logic that require [`Bitmap`].
@@ -464,4 +756,344 @@ impl OwnedBitmap {
     pub fn is_empty(&self) -> bool {
         self.is_empty
     }
+
+    /// This converts a BGRA bitmap to RGBA bitmap and vice-versa by swapping the red and blue channels.
+    ///
+    /// Only valid if the format is BitmapFormat::BGRA8_UNORM_SRGB
+    ///
+    /// Unlike [`Bitmap::swap_red_blue_channels`], this is done in place
+    /// on the owned pixels without calling into `Ultralight`.
+    pub fn swap_red_blue_channels(&mut self) -> BitmapResult<()> {
+        if let BitmapFormat::A8Unorm = self.format {
+            return Err(BitmapError::UnsupportedOperationForPixelFormat);
+        }
+        let row_len = self.width as usize * self.bpp as usize;
+        let row_bytes = self.row_bytes as usize;
+        let pixels = self.pixels.as_mut().ok_or(BitmapError::EmptyBitmap)?;
+
+        for row in pixels.chunks_mut(row_bytes) {
+            // the last row may not have padding
+            let row_len = row_len.min(row.len());
+            for pixel in row[..row_len].chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flip the bitmap upside down in place, by reversing the order of its
+    /// rows.
+    ///
+    /// Like [`OwnedBitmap::swap_red_blue_channels`], this works on the owned
+    /// pixels without calling into `Ultralight`.
+    pub fn flip_vertical(&mut self) -> BitmapResult<()> {
+        let (width, height, bpp, row_bytes) = (self.width, self.height, self.bpp, self.row_bytes);
+        let pixels = self.pixels.as_mut().ok_or(BitmapError::EmptyBitmap)?;
+        flip_rows(pixels, width, height, bpp, row_bytes);
+        Ok(())
+    }
+
+    /// Mirror the bitmap in place, by reversing the order of the pixels of
+    /// each row.
+    pub fn flip_horizontal(&mut self) -> BitmapResult<()> {
+        let (width, bpp, row_bytes) = (self.width, self.bpp, self.row_bytes);
+        let pixels = self.pixels.as_mut().ok_or(BitmapError::EmptyBitmap)?;
+        mirror_rows(pixels, width, bpp, row_bytes);
+        Ok(())
+    }
+
+    /// Convert the pixels into tightly packed `RGBA` with 16-bits per channel.
+    ///
+    /// This is useful for feeding higher bit-depth pipelines (10-bit or 16-bit
+    /// swapchains) on the CPU side. Each 8-bit value `v` is expanded to
+    /// `v * 257`, so `255` maps to `u16::MAX` exactly, and row padding is
+    /// dropped. The color-space is left as is, i.e. sRGB gamma with
+    /// premultiplied alpha.
+    ///
+    /// Returns [`BitmapError::UnsupportedOperationForPixelFormat`] for
+    /// [`BitmapFormat::A8Unorm`] bitmaps.
+    pub fn to_rgba16(&self) -> BitmapResult<Vec<u16>> {
+        if let BitmapFormat::A8Unorm = self.format {
+            return Err(BitmapError::UnsupportedOperationForPixelFormat);
+        }
+        let pixels = self.pixels.as_ref().ok_or(BitmapError::EmptyBitmap)?;
+
+        let row_len = self.width as usize * self.bpp as usize;
+        let mut result = Vec::with_capacity(self.width as usize * self.height as usize * 4);
+        for row in pixels
+            .chunks(self.row_bytes as usize)
+            .take(self.height as usize)
+        {
+            // the last row may not have padding
+            let row_len = row_len.min(row.len());
+            for pixel in row[..row_len].chunks_exact(4) {
+                // BGRA -> RGBA
+                result.push(pixel[2] as u16 * 257);
+                result.push(pixel[1] as u16 * 257);
+                result.push(pixel[0] as u16 * 257);
+                result.push(pixel[3] as u16 * 257);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Encode the bitmap as a PNG image in memory.
+    ///
+    /// See [`Bitmap::encode_png`] for how the pixels are encoded.
+    #[cfg(feature = "png")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "png")))]
+    pub fn encode_png(&self) -> BitmapResult<Vec<u8>> {
+        encode_png(self.packed_pixels()?, self.width, self.height, self.format)
+    }
+
+    /// Encode the bitmap as a JPEG image in memory, `quality` is clamped to
+    /// `0..=100`.
+    ///
+    /// See [`Bitmap::encode_jpeg`] for how the pixels are encoded.
+    #[cfg(feature = "jpeg")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jpeg")))]
+    pub fn encode_jpeg(&self, quality: u8) -> BitmapResult<Vec<u8>> {
+        encode_jpeg(
+            &self.packed_pixels()?,
+            self.width,
+            self.height,
+            self.format,
+            quality,
+        )
+    }
+
+    /// Convert the bitmap to an [`image::DynamicImage`].
+    ///
+    /// See [`Bitmap::to_image_buffer`] for how the pixels are converted.
+    #[cfg(feature = "image-compat")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "image-compat")))]
+    pub fn to_image_buffer(&self) -> BitmapResult<image::DynamicImage> {
+        to_image_buffer(self.packed_pixels()?, self.width, self.height, self.format)
+    }
+
+    /// Copy the pixels without the row padding.
+    #[cfg(any(feature = "png", feature = "jpeg", feature = "image-compat"))]
+    fn packed_pixels(&self) -> BitmapResult<Vec<u8>> {
+        let pixels = self.pixels.as_ref().ok_or(BitmapError::EmptyBitmap)?;
+
+        let row_len = self.width as usize * self.bpp as usize;
+        let mut result = Vec::with_capacity(row_len * self.height as usize);
+        for row in pixels
+            .chunks(self.row_bytes as usize)
+            .take(self.height as usize)
+        {
+            result.extend_from_slice(&row[..row_len]);
+        }
+
+        if result.is_empty() {
+            Err(BitmapError::EmptyBitmap)
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+/// Convert premultiplied `BGRA` pixels into straight `RGBA` in place.
+#[cfg(any(feature = "png", feature = "webp", feature = "image-compat"))]
+fn unpremultiply_bgra_to_rgba(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+        let alpha = pixel[3] as u32;
+        if alpha != 0 && alpha != 255 {
+            for c in &mut pixel[..3] {
+                *c = ((*c as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+            }
+        }
+    }
+}
+
+/// Convert straight `RGBA` pixels into premultiplied `BGRA` in place.
+#[cfg(feature = "image-compat")]
+fn premultiply_rgba_to_bgra(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+        let alpha = pixel[3] as u32;
+        if alpha != 255 {
+            for c in &mut pixel[..3] {
+                *c = ((*c as u32 * alpha + 127) / 255) as u8;
+            }
+        }
+    }
+}
+
+/// Convert tightly packed `pixels` to an [`image::DynamicImage`].
+#[cfg(feature = "image-compat")]
+fn to_image_buffer(
+    mut pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    format: BitmapFormat,
+) -> BitmapResult<image::DynamicImage> {
+    let required = (width * height * format.bytes_per_pixel()) as usize;
+    let got = pixels.len();
+    let size_mismatch = || BitmapError::PixelBufferSizeMismatch { got, required };
+
+    match format {
+        BitmapFormat::A8Unorm => image::GrayImage::from_raw(width, height, pixels)
+            .map(image::DynamicImage::ImageLuma8)
+            .ok_or_else(size_mismatch),
+        BitmapFormat::Bgra8UnormSrgb => {
+            unpremultiply_bgra_to_rgba(&mut pixels);
+            image::RgbaImage::from_raw(width, height, pixels)
+                .map(image::DynamicImage::ImageRgba8)
+                .ok_or_else(size_mismatch)
+        }
+    }
+}
+
+/// Encode tightly packed `pixels` as PNG.
+#[cfg(feature = "png")]
+fn encode_png(
+    mut pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    format: BitmapFormat,
+) -> BitmapResult<Vec<u8>> {
+    let color_type = match format {
+        BitmapFormat::A8Unorm => png::ColorType::Grayscale,
+        BitmapFormat::Bgra8UnormSrgb => {
+            unpremultiply_bgra_to_rgba(&mut pixels);
+            png::ColorType::Rgba
+        }
+    };
+
+    let mut result = Vec::new();
+    let mut encoder = png::Encoder::new(&mut result, width, height);
+    encoder.set_color(color_type);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|_| BitmapError::FailedPngWrite)?;
+    writer
+        .write_image_data(&pixels)
+        .map_err(|_| BitmapError::FailedPngWrite)?;
+    writer.finish().map_err(|_| BitmapError::FailedPngWrite)?;
+
+    Ok(result)
+}
+
+/// Encode tightly packed `pixels` as JPEG.
+#[cfg(feature = "jpeg")]
+fn encode_jpeg(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    format: BitmapFormat,
+    quality: u8,
+) -> BitmapResult<Vec<u8>> {
+    use jpeg_encoder::{ColorType, Encoder};
+
+    let width = u16::try_from(width).map_err(|_| BitmapError::FailedJpegWrite)?;
+    let height = u16::try_from(height).map_err(|_| BitmapError::FailedJpegWrite)?;
+    let color_type = match format {
+        BitmapFormat::A8Unorm => ColorType::Luma,
+        // the alpha channel is ignored by the encoder
+        BitmapFormat::Bgra8UnormSrgb => ColorType::Bgra,
+    };
+
+    let mut result = Vec::new();
+    Encoder::new(&mut result, quality.min(100))
+        .encode(pixels, width, height, color_type)
+        .map_err(|_| BitmapError::FailedJpegWrite)?;
+
+    Ok(result)
+}
+
+/// Reverse the order of the rows of `pixels`, only the first
+/// `width * bpp` bytes of each row are moved, the padding is left as is.
+fn flip_rows(pixels: &mut [u8], width: u32, height: u32, bpp: u32, row_bytes: u32) {
+    let row_len = width as usize * bpp as usize;
+    let row_bytes = row_bytes as usize;
+    if row_bytes < row_len || row_bytes == 0 {
+        return;
+    }
+    // the last row may not have padding
+    let height = (height as usize).min((pixels.len() + row_bytes - row_len) / row_bytes);
+
+    for top in 0..height / 2 {
+        let bottom = height - 1 - top;
+        let (start, end) = pixels.split_at_mut(bottom * row_bytes);
+        start[top * row_bytes..][..row_len].swap_with_slice(&mut end[..row_len]);
+    }
+}
+
+/// Call `f(x, y, pixel)` for each pixel of `pixels`, skipping the padding
+/// at the end of the rows.
+fn visit_pixels<F: FnMut(u32, u32, &[u8])>(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    bpp: u32,
+    row_bytes: u32,
+    mut f: F,
+) {
+    let bpp = bpp as usize;
+    if row_bytes == 0 || bpp == 0 {
+        return;
+    }
+
+    for (y, row) in pixels
+        .chunks(row_bytes as usize)
+        .take(height as usize)
+        .enumerate()
+    {
+        let row_len = (width as usize * bpp).min(row.len());
+        for (x, pixel) in row[..row_len].chunks_exact(bpp).enumerate() {
+            f(x as u32, y as u32, pixel);
+        }
+    }
+}
+
+/// The mutable version of [`visit_pixels`].
+fn visit_pixels_mut<F: FnMut(u32, u32, &mut [u8])>(
+    pixels: &mut [u8],
+    width: u32,
+    height: u32,
+    bpp: u32,
+    row_bytes: u32,
+    mut f: F,
+) {
+    let bpp = bpp as usize;
+    if row_bytes == 0 || bpp == 0 {
+        return;
+    }
+
+    for (y, row) in pixels
+        .chunks_mut(row_bytes as usize)
+        .take(height as usize)
+        .enumerate()
+    {
+        let row_len = (width as usize * bpp).min(row.len());
+        for (x, pixel) in row[..row_len].chunks_exact_mut(bpp).enumerate() {
+            f(x as u32, y as u32, pixel);
+        }
+    }
+}
+
+/// Reverse the order of the pixels in each row of `pixels`.
+fn mirror_rows(pixels: &mut [u8], width: u32, bpp: u32, row_bytes: u32) {
+    let bpp = bpp as usize;
+    let row_len = width as usize * bpp;
+    let row_bytes = row_bytes as usize;
+    if row_bytes == 0 || bpp == 0 {
+        return;
+    }
+
+    for row in pixels.chunks_mut(row_bytes) {
+        let row_len = row_len.min(row.len());
+        let row = &mut row[..row_len - row_len % bpp];
+        // reversing the bytes reverses the bytes of each pixel too, which is
+        // undone by reversing each pixel again
+        row.reverse();
+        for pixel in row.chunks_exact_mut(bpp) {
+            pixel.reverse();
+        }
+    }
 }
@@ -40,27 +40,30 @@ macro_rules! c_callback {
             where
                 Env: ::std::ops::FnMut($($($arg_ty),*)?) $(-> $ret_ty)? + 'static,
         {
-            // Prevent unwinding accross the FFI
+            // Prevent unwinding accross the FFI, panics of the callback are
+            // caught below, so this is only a fallback.
             ::scopeguard::defer_on_unwind!({
                 ::std::process::abort();
             });
 
-            let at_env_raw_ptr: *mut ::std::boxed::Box<Holder<Env>> = callback_data as *mut ::std::boxed::Box<Holder<Env>>;
-            let holder: &mut ::std::boxed::Box<Holder<Env>> = ffi_unwrap!(at_env_raw_ptr.as_mut(), "null ptr",);
-            let callback = &mut holder.callback;
-            $(let $myself = &holder.myself;)?
+            $crate::callback_panic::catch_callback_panic(|| {
+                let at_env_raw_ptr: *mut ::std::boxed::Box<Holder<Env>> = callback_data as *mut ::std::boxed::Box<Holder<Env>>;
+                let holder: &mut ::std::boxed::Box<Holder<Env>> = ffi_unwrap!(at_env_raw_ptr.as_mut(), "null ptr",);
+                let callback = &mut holder.callback;
+                $(let $myself = &holder.myself;)?
 
-            $($body)*
+                $($body)*
 
-            // For each given Env type parameter,
-            // Rust knows how to call this since it is using the static address
-            // <Env as FnMut<_>>::call_mut(at_env, result, data)
-            // (this is the only part of the code that depends on the Env type)
-            let _ret = callback($($($arg),*)?);
+                // For each given Env type parameter,
+                // Rust knows how to call this since it is using the static address
+                // <Env as FnMut<_>>::call_mut(at_env, result, data)
+                // (this is the only part of the code that depends on the Env type)
+                let _ret = callback($($($arg),*)?);
 
-            $(let $ret = _ret;)?
-            $(let _ret = $ret_body;)?
-            _ret
+                $(let $ret = _ret;)?
+                $(let _ret = $ret_body;)?
+                _ret
+            })
         }
     };
 }
@@ -145,23 +148,29 @@ macro_rules! platform_set_interface_macro {
         $(#[$attr])*
         $vis fn $setter_name<T: $rust_ty + Send + 'static>(lib: ::std::sync::Arc<$crate::Library>, $setter_arg_name: T) {
             let $setter_arg_name = Box::new($setter_arg_name);
-            $static_name.lib.lock().unwrap().replace(lib.clone());
-            $static_name.obj.lock().unwrap().replace($setter_arg_name);
+            $static_name.lib.lock().unwrap_or_else(::std::sync::PoisonError::into_inner).replace(lib.clone());
+            $static_name.obj.lock().unwrap_or_else(::std::sync::PoisonError::into_inner).replace($setter_arg_name);
 
             $(
                 unsafe extern "C" fn $fn_name($($($ul_arg: $ul_arg_ty),*)?) $($(-> $ul_ret_ty)?)? {
-                    #[allow(unused_variables)]
-                    let $lib = $static_name.lib.lock().unwrap().as_ref().unwrap().clone();
-                    $($from_ul_to_rs_body)*
-
-                    let mut $setter_arg_name = $static_name.obj.lock().unwrap();
-                    // the $setter_arg_name must always be `Some` at this point.
-                    let _r $($(: $ret_ty)?)? = $setter_arg_name.as_mut().unwrap().$fn_name($($($arg),*)?);
-                    $($(let $ret = _r;)?)?
-                    $(
-                    let _r = $from_rs_to_ul_body;
-                    )?
-                    _r
+                    ::scopeguard::defer_on_unwind!({
+                        ::std::process::abort();
+                    });
+
+                    $crate::callback_panic::catch_callback_panic(|| {
+                        #[allow(unused_variables)]
+                        let $lib = $static_name.lib.lock().unwrap_or_else(::std::sync::PoisonError::into_inner).as_ref().unwrap().clone();
+                        $($from_ul_to_rs_body)*
+
+                        let mut $setter_arg_name = $static_name.obj.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+                        // the $setter_arg_name must always be `Some` at this point.
+                        let _r $($(: $ret_ty)?)? = $setter_arg_name.as_mut().unwrap().$fn_name($($($arg),*)?);
+                        $($(let $ret = _r;)?)?
+                        $(
+                        let _r = $from_rs_to_ul_body;
+                        )?
+                        _r
+                    })
                 }
             )+
 
@@ -0,0 +1,111 @@
+//! Catching panics of user callbacks called from Ultralight, so they don't
+//! unwind across the FFI boundary, and tracking whether such a callback is
+//! running, to prevent re-entering Ultralight from it.
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::error::Error;
+
+thread_local! {
+    /// The payload of the last panic of a callback that ran on this thread,
+    /// callbacks run on the thread that called into Ultralight (eg. the one
+    /// calling [`Renderer::update`](crate::renderer::Renderer::update)), so
+    /// renderers on different threads don't see each other's panics.
+    static LAST_CALLBACK_PANIC: RefCell<Option<Box<dyn Any + Send>>> = const { RefCell::new(None) };
+
+    /// The number of user callbacks running on this thread, callbacks can be
+    /// nested (eg. a JavaScript function evaluating a script).
+    static CALLBACK_DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+/// The value returned to Ultralight when a callback panics.
+pub(crate) trait FfiDefault {
+    fn ffi_default() -> Self;
+}
+
+impl FfiDefault for () {
+    fn ffi_default() -> Self {}
+}
+
+impl FfiDefault for bool {
+    fn ffi_default() -> Self {
+        false
+    }
+}
+
+macro_rules! impl_ffi_default_zero {
+    ($($ty:ty),*) => {
+        $(
+            impl FfiDefault for $ty {
+                fn ffi_default() -> Self {
+                    0 as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_ffi_default_zero!(i32, u32, i64, u64, f32, f64);
+
+impl<T> FfiDefault for *mut T {
+    fn ffi_default() -> Self {
+        std::ptr::null_mut()
+    }
+}
+
+impl<T> FfiDefault for *const T {
+    fn ffi_default() -> Self {
+        std::ptr::null()
+    }
+}
+
+/// Run `f`, and if it panics, store the panic payload to be retrieved by
+/// [`Library::take_last_callback_panic`](crate::Library::take_last_callback_panic)
+/// and return a default value instead.
+pub(crate) fn catch_callback_panic<R: FfiDefault>(f: impl FnOnce() -> R) -> R {
+    CALLBACK_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    let _guard = scopeguard::guard((), |_| {
+        CALLBACK_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    });
+
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(r) => r,
+        Err(payload) => {
+            LAST_CALLBACK_PANIC.with(|slot| *slot.borrow_mut() = Some(payload));
+            R::ffi_default()
+        }
+    }
+}
+
+pub(crate) fn take_last_callback_panic() -> Option<Box<dyn Any + Send>> {
+    LAST_CALLBACK_PANIC.with(|slot| slot.borrow_mut().take())
+}
+
+/// Whether a user callback called from Ultralight is running on this thread.
+pub(crate) fn is_in_callback() -> bool {
+    CALLBACK_DEPTH.with(|depth| depth.get() > 0)
+}
+
+/// Mark the running callback as called by the run loop of `AppCore`, outside
+/// of Ultralight, where calling into Ultralight is allowed, until the guard
+/// is dropped.
+pub(crate) fn enter_appcore_callback() -> impl Drop {
+    CALLBACK_DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+    scopeguard::guard((), |_| {
+        CALLBACK_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    })
+}
+
+/// Check that `function` is not called from a user callback, where it would
+/// re-enter Ultralight.
+///
+/// Returns [`Error::ReentrantCall`] if it is.
+pub(crate) fn check_not_reentrant(function: &'static str) -> Result<(), Error> {
+    if is_in_callback() {
+        return Err(Error::ReentrantCall(function));
+    }
+    Ok(())
+}
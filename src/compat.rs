@@ -0,0 +1,359 @@
+//! Compatibility layer for projects migrating from the older `ul` and
+//! `ultralight` crates.
+//!
+//! This module provides thin adapters with the names used by those crates,
+//! implemented on top of the `ul-next` API. All of them are deprecated, and
+//! the deprecation notes point to the idiomatic replacement, so a project can
+//! switch its imports first and then migrate piece by piece following the
+//! warnings.
+//!
+//! # Old to new mapping
+//!
+//! | Old API                                   | `ul-next`                                                                       |
+//! |-------------------------------------------|---------------------------------------------------------------------------------|
+//! | `UltralightConfig::new()`                 | [`Config::start`]                                                               |
+//! | `config.resource_path(path)`              | [`ConfigBuilder::resource_path_prefix`]                                         |
+//! | `config.cache_path(path)`                 | [`ConfigBuilder::cache_path`]                                                   |
+//! | `config.device_scale_hint(scale)`         | [`ViewConfigBuilder::initial_device_scale`]                                     |
+//! | `config.font_family_standard(name)`       | [`ViewConfigBuilder::font_family_standard`]                                     |
+//! | `config.user_agent(agent)`                | [`ViewConfigBuilder::user_agent`]                                               |
+//! | `Ultralight::new(config, None)`           | [`Renderer::create`]                                                            |
+//! | `ul.view(width, height, transparent)`     | [`Renderer::create_view`]                                                       |
+//! | `ul.load_url(url)`                        | [`View::load_url`]                                                              |
+//! | `ul.load_html(html)`                      | [`View::load_html`]                                                             |
+//! | `ul.update()`                             | [`Renderer::update`]                                                            |
+//! | `ul.render()`                             | [`Renderer::render`]                                                            |
+//! | `ul.update_until_loaded()`                | [`Renderer::update`] until [`View::is_loading`] is `false`                      |
+//! | `ul.is_loading()`                         | [`View::is_loading`]                                                            |
+//! | `ul.evaluate_script(script)`              | [`View::evaluate_script`]                                                       |
+//! | `ul.scroll(dx, dy)`                       | [`View::fire_scroll_event`]                                                     |
+//! | `ul.resize(width, height)`                | [`View::resize`]                                                                |
+//! | `ul.set_finish_loading(callback)`         | [`View::set_finish_loading_callback`]                                           |
+//! | `ul.set_dom_ready(callback)`              | [`View::set_dom_ready_callback`]                                                |
+//! | `ul.set_change_title(callback)`           | [`View::set_change_title_callback`]                                             |
+//! | `ul.set_change_url(callback)`             | [`View::set_change_url_callback`]                                               |
+//! | `ul.set_add_console_message(callback)`    | [`View::set_add_console_message_callback`]                                      |
+//! | `ul.write_png_to_file(path)`              | [`Surface::bitmap`] and [`Bitmap::write_to_png`]                                |
+//! | `UltralightApp`                           | [`App`](crate::app::App) (requires AppCore)                                     |
+//! | `UltralightWindow`                        | [`Window`](crate::window::Window) (requires AppCore)                            |
+//! | `UltralightOverlay`                       | [`Overlay`](crate::overlay::Overlay) (requires AppCore)                         |
+//!
+//! The main difference with the old crates is that `ul-next` separates the
+//! [`Renderer`] and the [`View`]s, and all objects need a [`Library`] handle,
+//! so [`Ultralight::new`] takes it as an extra argument.
+//!
+//! [`Config::start`]: crate::config::Config::start
+//! [`ConfigBuilder::resource_path_prefix`]: crate::config::ConfigBuilder::resource_path_prefix
+//! [`ConfigBuilder::cache_path`]: crate::config::ConfigBuilder::cache_path
+//! [`ViewConfigBuilder::initial_device_scale`]: crate::view::ViewConfigBuilder::initial_device_scale
+//! [`ViewConfigBuilder::font_family_standard`]: crate::view::ViewConfigBuilder::font_family_standard
+//! [`ViewConfigBuilder::user_agent`]: crate::view::ViewConfigBuilder::user_agent
+//! [`Surface::bitmap`]: crate::surface::Surface::bitmap
+//! [`Bitmap::write_to_png`]: crate::bitmap::Bitmap::write_to_png
+#![allow(deprecated)]
+
+use std::{path::Path, sync::Arc};
+
+use crate::{
+    bitmap::BitmapError,
+    config::ConfigBuilder,
+    error::{CreationError, Error},
+    event::{ScrollEvent, ScrollEventType},
+    renderer::Renderer,
+    view::{View, ViewConfig, ViewConfigBuilder},
+    Library,
+};
+
+/// Old name of [`App`](crate::app::App).
+#[cfg(any(feature = "appcore_linked", feature = "loaded"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "appcore_linked", feature = "loaded"))))]
+#[deprecated(note = "use `ul_next::app::App` instead")]
+pub type UltralightApp = crate::app::App;
+
+/// Old name of [`Window`](crate::window::Window).
+#[cfg(any(feature = "appcore_linked", feature = "loaded"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "appcore_linked", feature = "loaded"))))]
+#[deprecated(note = "use `ul_next::window::Window` instead")]
+pub type UltralightWindow = crate::window::Window;
+
+/// Old name of [`Overlay`](crate::overlay::Overlay).
+#[cfg(any(feature = "appcore_linked", feature = "loaded"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "appcore_linked", feature = "loaded"))))]
+#[deprecated(note = "use `ul_next::overlay::Overlay` instead")]
+pub type UltralightOverlay = crate::overlay::Overlay;
+
+/// Configuration builder with the old combined renderer/view options.
+///
+/// The renderer options go to [`ConfigBuilder`], and the view options go to
+/// [`ViewConfigBuilder`].
+#[deprecated(note = "use `Config::start()` and `ViewConfig::start()` instead")]
+#[derive(Default)]
+pub struct UltralightConfig {
+    config: ConfigBuilder,
+    view_config: ViewConfigBuilder,
+}
+
+impl UltralightConfig {
+    /// Create a new configuration with default values.
+    #[deprecated(note = "use `Config::start()` and `ViewConfig::start()` instead")]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the file path prefix for the resources.
+    #[deprecated(note = "use `ConfigBuilder::resource_path_prefix` instead")]
+    pub fn resource_path(&mut self, path: &str) -> &mut Self {
+        self.config = std::mem::take(&mut self.config).resource_path_prefix(path);
+        self
+    }
+
+    /// Set the file path to the directory used for cache.
+    #[deprecated(note = "use `ConfigBuilder::cache_path` instead")]
+    pub fn cache_path(&mut self, path: &str) -> &mut Self {
+        self.config = std::mem::take(&mut self.config).cache_path(path);
+        self
+    }
+
+    /// Set the initial device scale of the views.
+    #[deprecated(note = "use `ViewConfigBuilder::initial_device_scale` instead")]
+    pub fn device_scale_hint(&mut self, scale: f64) -> &mut Self {
+        self.view_config = std::mem::take(&mut self.view_config).initial_device_scale(scale);
+        self
+    }
+
+    /// Set the default font-family of the views.
+    #[deprecated(note = "use `ViewConfigBuilder::font_family_standard` instead")]
+    pub fn font_family_standard(&mut self, name: &str) -> &mut Self {
+        self.view_config = std::mem::take(&mut self.view_config).font_family_standard(name);
+        self
+    }
+
+    /// Set the user-agent string of the views.
+    #[deprecated(note = "use `ViewConfigBuilder::user_agent` instead")]
+    pub fn user_agent(&mut self, agent: &str) -> &mut Self {
+        self.view_config = std::mem::take(&mut self.view_config).user_agent(agent);
+        self
+    }
+}
+
+/// The old all-in-one renderer and view container.
+///
+/// This owns a [`Renderer`] and at most one CPU-rendered [`View`], which
+/// is the usage pattern of the old crates.
+#[deprecated(note = "use `Renderer` and `View` directly")]
+pub struct Ultralight {
+    lib: Arc<Library>,
+    // declared before `renderer`, so that the view is dropped first
+    view: Option<View>,
+    renderer: Renderer,
+    view_config: ViewConfigBuilder,
+}
+
+impl Ultralight {
+    /// Create the renderer using `config`.
+    ///
+    /// The platform handlers (font loader, file system, logger) must be set
+    /// before calling this, see [`platform`](crate::platform).
+    #[deprecated(note = "use `Renderer::create` instead")]
+    pub fn new(lib: Arc<Library>, config: Option<UltralightConfig>) -> Result<Self, CreationError> {
+        let config = config.unwrap_or_default();
+        let renderer = Renderer::create(config.config.try_build(lib.clone())?)?;
+
+        Ok(Self {
+            lib,
+            view: None,
+            renderer,
+            view_config: config.view_config,
+        })
+    }
+
+    /// Create the view, replacing the previous one if any.
+    #[deprecated(note = "use `Renderer::create_view` instead")]
+    pub fn view(
+        &mut self,
+        width: u32,
+        height: u32,
+        transparent: bool,
+    ) -> Result<&View, CreationError> {
+        let view_config: ViewConfig = self
+            .view_config
+            .clone()
+            .is_accelerated(false)
+            .is_transparent(transparent)
+            .try_build(self.lib.clone())?;
+
+        let view = self
+            .renderer
+            .create_view(width, height, &view_config, None)?;
+
+        Ok(self.view.insert(view))
+    }
+
+    /// Get the underlying renderer.
+    pub fn renderer(&self) -> &Renderer {
+        &self.renderer
+    }
+
+    /// Get the view created by [`Ultralight::view`].
+    pub fn get_view(&self) -> Option<&View> {
+        self.view.as_ref()
+    }
+
+    fn view_or_err(&self) -> Result<&View, CreationError> {
+        self.view.as_ref().ok_or(CreationError::NullReference)
+    }
+
+    /// Load a URL into the view.
+    #[deprecated(note = "use `View::load_url` instead")]
+    pub fn load_url(&mut self, url: &str) -> Result<(), CreationError> {
+        self.view_or_err()?.load_url(url)
+    }
+
+    /// Load a raw string of HTML into the view.
+    #[deprecated(note = "use `View::load_html` instead")]
+    pub fn load_html(&mut self, html: &str) -> Result<(), CreationError> {
+        self.view_or_err()?.load_html(html)
+    }
+
+    /// Update timers and dispatch internal callbacks.
+    #[deprecated(note = "use `Renderer::update` instead")]
+    pub fn update(&mut self) -> Result<(), Error> {
+        self.renderer.update()
+    }
+
+    /// Render all the views.
+    #[deprecated(note = "use `Renderer::render` instead")]
+    pub fn render(&mut self) -> Result<(), Error> {
+        self.renderer.render()
+    }
+
+    /// Call [`Renderer::update`] until the view finishes loading.
+    #[deprecated(note = "use `Renderer::update` with `View::set_finish_loading_callback` instead")]
+    pub fn update_until_loaded(&mut self) -> Result<(), Error> {
+        let view = self.view_or_err()?;
+        while view.is_loading() {
+            self.renderer.update()?;
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        Ok(())
+    }
+
+    /// Whether or not the view is loading.
+    #[deprecated(note = "use `View::is_loading` instead")]
+    pub fn is_loading(&self) -> bool {
+        self.view.as_ref().is_some_and(|v| v.is_loading())
+    }
+
+    /// Evaluate a string of JavaScript in the view.
+    #[deprecated(note = "use `View::evaluate_script` instead")]
+    pub fn evaluate_script(
+        &mut self,
+        script: &str,
+    ) -> Result<Result<String, String>, CreationError> {
+        self.view_or_err()?.evaluate_script(script)
+    }
+
+    /// Scroll the view by pixels.
+    #[deprecated(note = "use `View::fire_scroll_event` instead")]
+    pub fn scroll(&mut self, delta_x: i32, delta_y: i32) -> Result<(), CreationError> {
+        let event = ScrollEvent::new(
+            self.lib.clone(),
+            ScrollEventType::ScrollByPixel,
+            delta_x,
+            delta_y,
+        )?;
+        self.view_or_err()?.fire_scroll_event(event);
+        Ok(())
+    }
+
+    /// Resize the view.
+    #[deprecated(note = "use `View::resize` instead")]
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<(), Error> {
+        self.view_or_err()?.resize(width, height)
+    }
+
+    /// Set the callback for when the view finishes loading.
+    ///
+    /// The callback is called only for the main frame.
+    #[deprecated(note = "use `View::set_finish_loading_callback` instead")]
+    pub fn set_finish_loading<F: FnMut() + 'static>(
+        &mut self,
+        mut callback: F,
+    ) -> Result<(), CreationError> {
+        self.view_or_err()?
+            .set_finish_loading_callback(move |_, _, is_main_frame, _| {
+                if is_main_frame {
+                    callback()
+                }
+            });
+        Ok(())
+    }
+
+    /// Set the callback for when the DOM of the view is ready.
+    ///
+    /// The callback is called only for the main frame.
+    #[deprecated(note = "use `View::set_dom_ready_callback` instead")]
+    pub fn set_dom_ready<F: FnMut() + 'static>(
+        &mut self,
+        mut callback: F,
+    ) -> Result<(), CreationError> {
+        self.view_or_err()?
+            .set_dom_ready_callback(move |_, _, is_main_frame, _, _| {
+                if is_main_frame {
+                    callback()
+                }
+            });
+        Ok(())
+    }
+
+    /// Set the callback for when the page title changes.
+    #[deprecated(note = "use `View::set_change_title_callback` instead")]
+    pub fn set_change_title<F: FnMut(String) + 'static>(
+        &mut self,
+        mut callback: F,
+    ) -> Result<(), CreationError> {
+        self.view_or_err()?
+            .set_change_title_callback(move |_, title| callback(title.to_string()));
+        Ok(())
+    }
+
+    /// Set the callback for when the page URL changes.
+    #[deprecated(note = "use `View::set_change_url_callback` instead")]
+    pub fn set_change_url<F: FnMut(String) + 'static>(
+        &mut self,
+        mut callback: F,
+    ) -> Result<(), CreationError> {
+        self.view_or_err()?
+            .set_change_url_callback(move |_, url| callback(url.to_string()));
+        Ok(())
+    }
+
+    /// Set the callback for console messages, the callback gets the message,
+    /// line number and source id.
+    #[deprecated(note = "use `View::set_add_console_message_callback` instead")]
+    pub fn set_add_console_message<F: FnMut(String, u32, String) + 'static>(
+        &mut self,
+        mut callback: F,
+    ) -> Result<(), CreationError> {
+        self.view_or_err()?.set_add_console_message_callback(
+            move |_, _, _, message, line, _, source_id| callback(message, line, source_id),
+        );
+        Ok(())
+    }
+
+    /// Render the view and write it to a PNG file.
+    #[deprecated(
+        note = "use `View::surface`, `Surface::bitmap` and `Bitmap::write_to_png` instead"
+    )]
+    pub fn write_png_to_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        self.renderer.render()?;
+
+        let bitmap = self
+            .view
+            .as_ref()
+            .and_then(|v| v.surface())
+            .and_then(|s| s.bitmap())
+            .ok_or(BitmapError::NullReference)?;
+        Ok(bitmap.write_to_png(path)?)
+    }
+}
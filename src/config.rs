@@ -1,8 +1,20 @@
 //! The configuration of the [`Renderer`](crate::renderer::Renderer) struct.
 
-use std::sync::Arc;
-
-use crate::Library;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    error::CreationError,
+    platform::{self, filesystems::ResourceFileSystem},
+    string::path_to_str,
+    Library,
+};
+
+/// The default value of [`ConfigBuilder::resource_path_prefix`].
+const DEFAULT_RESOURCE_PATH_PREFIX: &str = "resources/";
 
 /// The winding order for front-facing triangles. (Only used when the GPU renderer is used)
 pub enum FaceWinding {
@@ -33,6 +45,8 @@ pub enum FontHinting {
 pub struct Config {
     lib: Arc<Library>,
     internal: ul_sys::ULConfig,
+    ephemeral_cache_path: Option<PathBuf>,
+    is_accelerated: bool,
 }
 
 impl Config {
@@ -52,6 +66,18 @@ impl Config {
     pub(crate) fn lib(&self) -> &Arc<Library> {
         &self.lib
     }
+
+    /// Returns the cache path that should be removed when the renderer is
+    /// destroyed, if [`ConfigBuilder::is_ephemeral`] was set.
+    pub(crate) fn ephemeral_cache_path(&self) -> Option<&Path> {
+        self.ephemeral_cache_path.as_deref()
+    }
+
+    /// Whether the renderer will render accelerated views, see
+    /// [`ConfigBuilder::is_accelerated`].
+    pub(crate) fn is_accelerated(&self) -> bool {
+        self.is_accelerated
+    }
 }
 
 impl Drop for Config {
@@ -65,8 +91,12 @@ impl Drop for Config {
 /// Builder for the [`Config`] struct.
 #[derive(Default)]
 pub struct ConfigBuilder {
-    cache_path: Option<String>,
+    cache_path: Option<PathBuf>,
+    create_cache_dir: bool,
+    is_ephemeral: bool,
+    is_accelerated: bool,
     resource_path_prefix: Option<String>,
+    embedded_resources: Option<HashMap<String, Vec<u8>>>,
     face_winding: Option<FaceWinding>,
     font_hinting: Option<FontHinting>,
     font_gamma: Option<f64>,
@@ -92,8 +122,38 @@ impl ConfigBuilder {
     ///
     /// Files are only written to disk when using a persistent Session (see
     /// [`Renderer::create_session`](crate::renderer::Renderer::create_session)).
-    pub fn cache_path(mut self, path: &str) -> Self {
-        self.cache_path = Some(path.to_string());
+    ///
+    /// If this is not called, the cache path is not set at all, and the
+    /// library will keep all session data in memory only.
+    ///
+    /// A relative path is resolved against the current working directory
+    /// when the config is built. The path must be valid UTF-8, otherwise
+    /// [`ConfigBuilder::try_build`] returns [`CreationError::InvalidPath`].
+    pub fn cache_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.cache_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Whether or not to create the [`cache_path`](Self::cache_path)
+    /// directory (and its parents) when building the config, if it doesn't
+    /// exist.
+    ///
+    /// (Default = false)
+    pub fn create_cache_dir(mut self, create: bool) -> Self {
+        self.create_cache_dir = create;
+        self
+    }
+
+    /// Whether or not to remove the [`cache_path`](Self::cache_path) directory
+    /// and its contents when the [`Renderer`](crate::renderer::Renderer)
+    /// created with this config is destroyed.
+    ///
+    /// This has no effect if no cache path is set, or if the renderer is
+    /// created by [`App`](crate::app::App) (which manages its own renderer).
+    ///
+    /// (Default = false)
+    pub fn is_ephemeral(mut self, ephemeral: bool) -> Self {
+        self.is_ephemeral = ephemeral;
         self
     }
 
@@ -109,6 +169,23 @@ impl ConfigBuilder {
         self
     }
 
+    /// Serve the resources of the library from memory, so that no resources
+    /// folder is needed on disk.
+    ///
+    /// The keys of `resources` are the file names relative to the
+    /// [`resource_path_prefix`](Self::resource_path_prefix), eg.
+    /// `icudt67l.dat` and `cacert.pem`.
+    ///
+    /// When building the [`Config`], a
+    /// [`ResourceFileSystem`] serving only these resources is set with
+    /// [`platform::set_filesystem`], which replaces the file system set
+    /// before. To also load other files, set a [`ResourceFileSystem`] with a
+    /// fallback yourself instead.
+    pub fn embedded_resources(mut self, resources: HashMap<String, Vec<u8>>) -> Self {
+        self.embedded_resources = Some(resources);
+        self
+    }
+
     /// The winding order for front-facing triangles. (See [`FaceWinding`])
     ///
     /// Note: This is only used when the GPU renderer is enabled.
@@ -138,6 +215,21 @@ impl ConfigBuilder {
         self
     }
 
+    /// Whether the renderer is used to render accelerated views (see
+    /// [`ViewConfigBuilder::is_accelerated`](crate::view::ViewConfigBuilder::is_accelerated)),
+    /// with the GPU driver set with
+    /// [`platform::set_gpu_driver`](crate::platform::set_gpu_driver).
+    ///
+    /// This is not passed to Ultralight, [`Renderer::create`](crate::renderer::Renderer::create)
+    /// only uses it to return [`CreationError::GpuDriverNotSet`] early if no
+    /// GPU driver was set.
+    ///
+    /// (Default = false)
+    pub fn is_accelerated(mut self, accelerated: bool) -> Self {
+        self.is_accelerated = accelerated;
+        self
+    }
+
     /// Whether or not we should continuously repaint any Views or compositor
     /// layers, regardless if they are dirty or not. This is mainly used to
     /// diagnose painting/shader issues.
@@ -186,6 +278,16 @@ impl ConfigBuilder {
         self
     }
 
+    /// Disable WebCore's memory cache, same as setting
+    /// [`memory_cache_size`](Self::memory_cache_size) to `0`.
+    ///
+    /// Resources are then loaded again every time they are used, which
+    /// lowers the memory usage at the cost of more loading (eg. in CI or
+    /// when rendering pages only once).
+    pub fn disable_memory_cache(self) -> Self {
+        self.memory_cache_size(0)
+    }
+
     /// Number of pages to keep in the cache. Defaults to 0 (none).
     ///
     /// Safari typically caches about 5 pages and maintains an on-disk cache
@@ -280,17 +382,53 @@ impl ConfigBuilder {
 
     /// Builds the [`Config`] struct using the settings configured in this builder.
     ///
-    /// Returns [`None`] if failed to create [`Config`].
+    /// Returns [`None`] on failure, use [`ConfigBuilder::try_build`] to get the
+    /// error.
+    #[deprecated(note = "use `try_build` instead, it returns the error")]
     pub fn build(self, lib: Arc<Library>) -> Option<Config> {
+        self.try_build(lib).ok()
+    }
+
+    /// Builds the [`Config`] struct using the settings configured in this builder.
+    ///
+    /// Returns [`CreationError::InvalidPath`] if the
+    /// [`cache_path`](Self::cache_path) is not valid UTF-8, and
+    /// [`CreationError::IoError`] if it couldn't be created
+    /// (see [`create_cache_dir`](Self::create_cache_dir)).
+    pub fn try_build(self, lib: Arc<Library>) -> Result<Config, CreationError> {
+        let cache_path = match self.cache_path {
+            Some(path) if path.is_relative() => Some(std::env::current_dir()?.join(path)),
+            path => path,
+        };
+        if let Some(path) = &cache_path {
+            path_to_str(path)?;
+            if self.create_cache_dir {
+                std::fs::create_dir_all(path)?;
+            }
+        }
+
+        let resource_prefix = self
+            .resource_path_prefix
+            .clone()
+            .unwrap_or_else(|| DEFAULT_RESOURCE_PATH_PREFIX.to_string());
+
         let internal = unsafe { lib.ultralight().ulCreateConfig() };
 
         if internal.is_null() {
-            return None;
+            return Err(CreationError::NullReference);
         }
 
+        // destroys `internal` if setting one of the options fails
+        let mut config = Config {
+            lib: lib.clone(),
+            internal,
+            ephemeral_cache_path: None,
+            is_accelerated: self.is_accelerated,
+        };
+
         set_config_str!(
             internal,
-            self.cache_path,
+            cache_path.as_deref().and_then(Path::to_str),
             lib.ultralight().ulConfigSetCachePath
         );
         set_config_str!(
@@ -379,6 +517,14 @@ impl ConfigBuilder {
             lib.ultralight().ulConfigSetBitmapAlignment
         );
 
-        Some(Config { lib, internal })
+        if self.is_ephemeral {
+            config.ephemeral_cache_path = cache_path;
+        }
+
+        if let Some(resources) = self.embedded_resources {
+            platform::set_filesystem(lib, ResourceFileSystem::new(&resource_prefix, resources));
+        }
+
+        Ok(config)
     }
 }
@@ -12,14 +12,11 @@ macro_rules! set_config_str (
     ($config: expr, $config_item: expr, $lib: ident . $($ffiName:tt)+) => (
         if let Some(config_item) = $config_item {
             unsafe {
-                let cstr = ::std::ffi::CString::new(
-                    config_item
-                ).unwrap();
-                let str = $lib.ultralight().ulCreateString(
-                    cstr.as_ptr()
-                );
+                // the string is passed with its length, so it doesn't need
+                // to be NUL-terminated, and is copied by the setter
+                let str = $crate::string::UlString::from_str($lib.clone(), &config_item)?;
 
-                $lib.$($ffiName)+($config, str);
+                $lib.$($ffiName)+($config, str.to_ul());
             }
         }
     )
@@ -1,4 +1,6 @@
-use std::string::FromUtf8Error;
+use std::{path::PathBuf, string::FromUtf8Error};
+
+use crate::bitmap::BitmapError;
 
 /// Errors can occure when creating some of the structs.
 #[derive(Debug, thiserror::Error)]
@@ -18,4 +20,120 @@ pub enum CreationError {
     /// `&str` contained a null byte, and couldn't convert it to a valid C string without losing data.
     #[error("Failed to convert a rust `&str` to a C string")]
     CStringCreationError(#[from] std::ffi::NulError),
+    /// A GPU accelerated view or renderer was requested, but no GPU driver
+    /// was set for its library with
+    /// [`platform::set_gpu_driver`](crate::platform::set_gpu_driver).
+    #[error("GPU rendering requested without setting a GPU driver")]
+    GpuDriverNotSet,
+    /// The content type is not supported by the operation.
+    #[error("Unsupported content type `{0}`")]
+    UnsupportedContentType(String),
+    /// The path is not valid UTF-8, which is required to pass it to
+    /// Ultralight.
+    #[error("The path `{}` is not valid UTF-8", .0.display())]
+    InvalidPath(PathBuf),
+    /// An IO error occurred, for example when creating the cache directory
+    /// (see [`ConfigBuilder::create_cache_dir`](crate::config::ConfigBuilder::create_cache_dir)).
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// A value received from Ultralight doesn't match any variant of the
+/// corresponding Rust enum, returned by the `TryFrom` implementations of
+/// the enums wrapping Ultralight values (eg. [`BitmapFormat`](crate::bitmap::BitmapFormat)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown value {value} for `{enum_name}`")]
+pub struct UnknownEnumValue {
+    /// The name of the Rust enum.
+    pub enum_name: &'static str,
+    /// The value received from Ultralight.
+    pub value: i64,
+}
+
+impl UnknownEnumValue {
+    pub(crate) fn new<T: Into<i64>>(enum_name: &'static str, value: T) -> Self {
+        Self {
+            enum_name,
+            value: value.into(),
+        }
+    }
+}
+
+/// The error type of the crate, that the errors of the different modules
+/// convert into, so that `?` can be used across them.
+///
+/// The [`CreationError`] variants that have a matching variant here
+/// ([`CreationError::GpuDriverNotSet`], [`CreationError::InvalidPath`] and
+/// [`CreationError::UnsupportedContentType`]) are converted to it, so they
+/// only need to be matched once.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Failed to create an object, see [`CreationError`].
+    #[error(transparent)]
+    Creation(CreationError),
+    /// Failed to create or use a [`Bitmap`](crate::bitmap::Bitmap).
+    #[error(transparent)]
+    Bitmap(#[from] BitmapError),
+    /// A value received from Ultralight is not known by this crate.
+    #[error(transparent)]
+    UnknownEnumValue(#[from] UnknownEnumValue),
+    /// A GPU accelerated view or renderer was requested, but no GPU driver
+    /// was set for its library with
+    /// [`platform::set_gpu_driver`](crate::platform::set_gpu_driver).
+    #[error("GPU rendering requested without setting a GPU driver")]
+    GpuDriverNotSet,
+    /// The operation is not supported, the message says what is missing.
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+    /// The content type is not supported by the operation.
+    #[error("Unsupported content type `{0}`")]
+    UnsupportedContentType(String),
+    /// The function was called from a callback called by Ultralight, where
+    /// it would re-enter it, see [`DeferQueue`](crate::renderer::DeferQueue).
+    #[error("`{0}` was called from an Ultralight callback, use `Renderer::defer` instead")]
+    ReentrantCall(&'static str),
+    /// The path is not valid UTF-8, which is required to pass it to
+    /// Ultralight.
+    #[error("The path `{}` is not valid UTF-8", .0.display())]
+    InvalidPath(PathBuf),
+}
+
+impl From<CreationError> for Error {
+    fn from(err: CreationError) -> Self {
+        match err {
+            CreationError::GpuDriverNotSet => Error::GpuDriverNotSet,
+            CreationError::InvalidPath(path) => Error::InvalidPath(path),
+            CreationError::UnsupportedContentType(content_type) => {
+                Error::UnsupportedContentType(content_type)
+            }
+            err => Error::Creation(err),
+        }
+    }
+}
+
+/// Errors that can occur when loading the Ultralight libraries at runtime
+/// with [`Library::load`](crate::Library::load) and the other `load*`
+/// functions.
+#[cfg(feature = "loaded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "loaded")))]
+#[derive(Debug, thiserror::Error)]
+pub enum LoadingError {
+    /// Failed to load a library or one of its functions.
+    #[error("Failed to load the Ultralight library: {0}")]
+    Library(#[from] ul_sys::library::LoadingError),
+    /// The loaded library has a different major or minor version than the
+    /// SDK the bindings were generated from, so the layout of the structs
+    /// shared with it may differ.
+    ///
+    /// The `load*_unchecked` functions (eg.
+    /// [`Library::load_unchecked`](crate::Library::load_unchecked)) can be
+    /// used to load it anyway.
+    #[error("Ultralight version mismatch, expected {expected} but found {found}")]
+    VersionMismatch {
+        /// The version the bindings were generated from, see
+        /// [`Library::expected_version`](crate::Library::expected_version).
+        expected: crate::Version,
+        /// The version of the loaded library.
+        found: crate::Version,
+    },
 }
@@ -1,9 +1,21 @@
 //! Events that can be fired in [`View`](crate::view::View)s.
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{error::CreationError, key_code::VirtualKeyCode, string::UlString, Library};
 
+#[cfg(feature = "sdl2")]
+mod sdl2;
+#[cfg(feature = "winit")]
+mod winit;
+
+/// The number of pixels to scroll for each line (or wheel step) of the
+/// scroll events of windowing libraries.
+///
+/// This is the same value used by WebKit for a single scroll step.
+#[cfg(any(feature = "winit", feature = "sdl2"))]
+const PIXELS_PER_LINE: f32 = 40.0;
+
 #[derive(Clone, Copy)]
 /// The type of the [`KeyEvent`].
 pub enum KeyEventType {
@@ -157,7 +169,7 @@ impl Drop for KeyEvent {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// The type of the [`MouseEvent`].
 pub enum MouseEventType {
     /// Mouse moved event type
@@ -168,7 +180,7 @@ pub enum MouseEventType {
     MouseUp = ul_sys::ULMouseEventType_kMouseEventType_MouseUp as isize,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// The type of button that was pressed or released.
 pub enum MouseButton {
     None = ul_sys::ULMouseButton_kMouseButton_None as isize,
@@ -227,7 +239,7 @@ impl Drop for MouseEvent {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// The type of the [`ScrollEvent`].
 pub enum ScrollEventType {
     /// The delta value is interpreted as number of pixels
@@ -284,6 +296,80 @@ impl Drop for ScrollEvent {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The type of the [`DragEvent`].
+pub enum DragEventType {
+    /// The user started dragging an element (`dragstart`).
+    DragStart,
+    /// The drag entered an element (`dragenter`).
+    DragEnter,
+    /// The drag moved over an element (`dragover`).
+    DragOver,
+    /// The drag left an element (`dragleave`).
+    DragLeave,
+    /// The dragged data was dropped on an element (`drop`).
+    Drop,
+    /// The drag operation ended (`dragend`).
+    DragEnd,
+}
+
+impl DragEventType {
+    /// The name of the DOM event.
+    pub(crate) fn dom_name(self) -> &'static str {
+        match self {
+            DragEventType::DragStart => "dragstart",
+            DragEventType::DragEnter => "dragenter",
+            DragEventType::DragOver => "dragover",
+            DragEventType::DragLeave => "dragleave",
+            DragEventType::Drop => "drop",
+            DragEventType::DragEnd => "dragend",
+        }
+    }
+}
+
+/// An HTML5 drag and drop event, that can be fired in a `view` by
+/// [`View::fire_drag_event`](crate::view::View::fire_drag_event).
+///
+/// Ultralight doesn't support drag and drop natively, so unlike the other
+/// events, this is dispatched to the page with JavaScript.
+#[derive(Clone, Debug)]
+pub struct DragEvent {
+    /// The type of the event.
+    pub ty: DragEventType,
+    /// The x-position of the pointer, relative to the view.
+    pub x: i32,
+    /// The y-position of the pointer, relative to the view.
+    pub y: i32,
+    /// The dragged data, as `format -> data` (eg. `text/plain -> Hello`),
+    /// available in the `dataTransfer` of the event.
+    pub data_transfer: Option<HashMap<String, String>>,
+}
+
+impl DragEvent {
+    /// Create a new `DragEvent` without data.
+    ///
+    /// # Arguments
+    /// * `ty` - The type of the event.
+    /// * `x` - The x-position of the pointer. relative to the view.
+    /// * `y` - The y-position of the pointer. relative to the view.
+    pub fn new(ty: DragEventType, x: i32, y: i32) -> Self {
+        Self {
+            ty,
+            x,
+            y,
+            data_transfer: None,
+        }
+    }
+
+    /// Add `data` of type `format` to the `dataTransfer` of the event.
+    pub fn with_data(mut self, format: &str, data: &str) -> Self {
+        self.data_transfer
+            .get_or_insert_with(HashMap::new)
+            .insert(format.to_string(), data.to_string());
+        self
+    }
+}
+
 #[derive(Clone, Copy)]
 /// The type of the [`GamepadEvent`].
 pub enum GamepadEventType {
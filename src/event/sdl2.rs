@@ -0,0 +1,568 @@
+//! Conversion of [`sdl2`] input events into Ultralight events.
+use std::sync::Arc;
+
+use sdl2::{
+    event::Event,
+    keyboard::{Keycode, Mod, Scancode},
+    mouse::MouseWheelDirection,
+};
+
+use crate::{error::CreationError, key_code::VirtualKeyCode, Library};
+
+use super::{
+    KeyEvent, KeyEventCreationInfo, KeyEventModifiers, KeyEventType, MouseButton, MouseEvent,
+    MouseEventType, ScrollEvent, ScrollEventType, PIXELS_PER_LINE,
+};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+impl From<Mod> for KeyEventModifiers {
+    fn from(modifiers: Mod) -> Self {
+        Self {
+            alt: modifiers.intersects(Mod::LALTMOD | Mod::RALTMOD),
+            ctrl: modifiers.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD),
+            meta: modifiers.intersects(Mod::LGUIMOD | Mod::RGUIMOD),
+            shift: modifiers.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD),
+        }
+    }
+}
+
+impl KeyEvent {
+    /// Create a key event from an [`sdl2`] event.
+    ///
+    /// [`Event::KeyDown`] and [`Event::KeyUp`] are converted to
+    /// [`KeyEventType::RawKeyDown`] and [`KeyEventType::KeyUp`], and
+    /// [`Event::TextInput`] to [`KeyEventType::Char`], as SDL reports the
+    /// text generated by a key in a separate event (when text input is
+    /// enabled). Returns [`None`] for all other events.
+    ///
+    /// Keypad keys are mapped to the navigation keys they stand for
+    /// (eg. `End` for `1`) when num lock is off.
+    ///
+    /// # Arguments
+    /// * `lib` - The ultralight library.
+    /// * `event` - The SDL event.
+    #[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+    pub fn from_sdl2(lib: Arc<Library>, event: &Event) -> Result<Option<KeyEvent>, CreationError> {
+        let (ty, keycode, scancode, keymod, repeat) = match event {
+            Event::KeyDown {
+                keycode,
+                scancode,
+                keymod,
+                repeat,
+                ..
+            } => (KeyEventType::RawKeyDown, keycode, scancode, keymod, *repeat),
+            Event::KeyUp {
+                keycode,
+                scancode,
+                keymod,
+                repeat,
+                ..
+            } => (KeyEventType::KeyUp, keycode, scancode, keymod, *repeat),
+            Event::TextInput { text, .. } => {
+                if text.is_empty() {
+                    return Ok(None);
+                }
+
+                return KeyEvent::new(
+                    lib,
+                    KeyEventCreationInfo {
+                        ty: KeyEventType::Char,
+                        // not reported with the text, and not needed for
+                        // `Char` events, accelerators use the key down event
+                        modifiers: Mod::NOMOD.into(),
+                        virtual_key_code: VirtualKeyCode::Unknown,
+                        native_key_code: 0,
+                        text,
+                        unmodified_text: text,
+                        is_keypad: false,
+                        is_auto_repeat: false,
+                        is_system_key: false,
+                    },
+                )
+                .map(Some);
+            }
+            _ => return Ok(None),
+        };
+
+        let navigation_key = scancode
+            .filter(|_| !keymod.contains(Mod::NUMMOD))
+            .and_then(keypad_navigation_key);
+        let virtual_key_code = navigation_key
+            .or_else(|| keycode.and_then(VirtualKeyCode::from_sdl2_keycode))
+            .or_else(|| scancode.and_then(VirtualKeyCode::from_sdl2_scancode))
+            .unwrap_or(VirtualKeyCode::Unknown);
+
+        KeyEvent::new(
+            lib,
+            KeyEventCreationInfo {
+                ty,
+                modifiers: (*keymod).into(),
+                virtual_key_code,
+                native_key_code: scancode.map_or(0, |scancode| scancode as i32),
+                text: "",
+                unmodified_text: "",
+                is_keypad: scancode.is_some_and(is_keypad_scancode),
+                is_auto_repeat: repeat,
+                is_system_key: false,
+            },
+        )
+        .map(Some)
+    }
+}
+
+impl MouseEvent {
+    /// Create a mouse event from an [`sdl2`] event.
+    ///
+    /// Handles [`Event::MouseMotion`], [`Event::MouseButtonDown`] and
+    /// [`Event::MouseButtonUp`] (for the left, middle and right buttons),
+    /// returns [`None`] for all other events.
+    ///
+    /// # Arguments
+    /// * `lib` - The ultralight library.
+    /// * `event` - The SDL event.
+    /// * `window_scale` - The ratio between the drawable size and the size of
+    ///   the window (see [`integration::sdl2::window_scale`]), positions are
+    ///   multiplied by it to convert from window coordinates to view pixels,
+    ///   like [`View::window_to_view_coords`].
+    ///
+    /// [`integration::sdl2::window_scale`]: crate::integration::sdl2::window_scale
+    /// [`View::window_to_view_coords`]: crate::view::View::window_to_view_coords
+    #[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+    pub fn from_sdl2(
+        lib: Arc<Library>,
+        event: &Event,
+        window_scale: f64,
+    ) -> Result<Option<MouseEvent>, CreationError> {
+        let (ty, x, y, button) = match event {
+            Event::MouseMotion { x, y, .. } => {
+                (MouseEventType::MouseMoved, x, y, MouseButton::None)
+            }
+            Event::MouseButtonDown {
+                x, y, mouse_btn, ..
+            }
+            | Event::MouseButtonUp {
+                x, y, mouse_btn, ..
+            } => {
+                let ty = match event {
+                    Event::MouseButtonDown { .. } => MouseEventType::MouseDown,
+                    _ => MouseEventType::MouseUp,
+                };
+                let button = match mouse_btn {
+                    sdl2::mouse::MouseButton::Left => MouseButton::Left,
+                    sdl2::mouse::MouseButton::Middle => MouseButton::Middle,
+                    sdl2::mouse::MouseButton::Right => MouseButton::Right,
+                    _ => return Ok(None),
+                };
+                (ty, x, y, button)
+            }
+            _ => return Ok(None),
+        };
+
+        MouseEvent::new(
+            lib,
+            ty,
+            (*x as f64 * window_scale).floor() as i32,
+            (*y as f64 * window_scale).floor() as i32,
+            button,
+        )
+        .map(Some)
+    }
+}
+
+impl ScrollEvent {
+    /// Create a [`ScrollEventType::ScrollByPixel`] event from an [`sdl2`]
+    /// [`Event::MouseWheel`], returns [`None`] for all other events.
+    ///
+    /// Wheel steps are converted to pixels using the same step size as
+    /// WebKit, and flipped wheels (natural scrolling) are taken into account.
+    ///
+    /// # Arguments
+    /// * `lib` - The ultralight library.
+    /// * `event` - The SDL event.
+    #[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+    pub fn from_sdl2(
+        lib: Arc<Library>,
+        event: &Event,
+    ) -> Result<Option<ScrollEvent>, CreationError> {
+        let Event::MouseWheel {
+            precise_x,
+            precise_y,
+            direction,
+            ..
+        } = event
+        else {
+            return Ok(None);
+        };
+
+        let sign = match direction {
+            MouseWheelDirection::Flipped => -1.0,
+            _ => 1.0,
+        };
+        ScrollEvent::new(
+            lib,
+            ScrollEventType::ScrollByPixel,
+            (precise_x * sign * PIXELS_PER_LINE) as i32,
+            (precise_y * sign * PIXELS_PER_LINE) as i32,
+        )
+        .map(Some)
+    }
+}
+
+impl VirtualKeyCode {
+    /// Map an [`sdl2`] key code (the key in the current keyboard layout) to
+    /// a virtual key code.
+    ///
+    /// Keypad keys are always mapped to the keypad digits/operators, use
+    /// [`KeyEvent::from_sdl2`] to take the num lock state into account.
+    ///
+    /// Returns [`None`] if the key has no virtual key code.
+    #[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+    pub fn from_sdl2_keycode(keycode: Keycode) -> Option<VirtualKeyCode> {
+        Some(match keycode {
+            Keycode::Backspace => VirtualKeyCode::Back,
+            Keycode::Tab => VirtualKeyCode::Tab,
+            Keycode::Clear => VirtualKeyCode::Clear,
+            Keycode::Return | Keycode::Return2 | Keycode::KpEnter => VirtualKeyCode::Return,
+            Keycode::Pause => VirtualKeyCode::Pause,
+            Keycode::CapsLock => VirtualKeyCode::Capital,
+            Keycode::Escape => VirtualKeyCode::Escape,
+            Keycode::Space => VirtualKeyCode::Space,
+            Keycode::PageUp => VirtualKeyCode::Prior,
+            Keycode::PageDown => VirtualKeyCode::Next,
+            Keycode::End => VirtualKeyCode::End,
+            Keycode::Home => VirtualKeyCode::Home,
+            Keycode::Left => VirtualKeyCode::Left,
+            Keycode::Up => VirtualKeyCode::Up,
+            Keycode::Right => VirtualKeyCode::Right,
+            Keycode::Down => VirtualKeyCode::Down,
+            Keycode::Select => VirtualKeyCode::Select,
+            Keycode::Execute => VirtualKeyCode::Execute,
+            Keycode::PrintScreen => VirtualKeyCode::Snapshot,
+            Keycode::Insert => VirtualKeyCode::Insert,
+            Keycode::Delete => VirtualKeyCode::Delete,
+            Keycode::Help => VirtualKeyCode::Help,
+            Keycode::Num0 => VirtualKeyCode::Key0,
+            Keycode::Num1 => VirtualKeyCode::Key1,
+            Keycode::Num2 => VirtualKeyCode::Key2,
+            Keycode::Num3 => VirtualKeyCode::Key3,
+            Keycode::Num4 => VirtualKeyCode::Key4,
+            Keycode::Num5 => VirtualKeyCode::Key5,
+            Keycode::Num6 => VirtualKeyCode::Key6,
+            Keycode::Num7 => VirtualKeyCode::Key7,
+            Keycode::Num8 => VirtualKeyCode::Key8,
+            Keycode::Num9 => VirtualKeyCode::Key9,
+            Keycode::A => VirtualKeyCode::A,
+            Keycode::B => VirtualKeyCode::B,
+            Keycode::C => VirtualKeyCode::C,
+            Keycode::D => VirtualKeyCode::D,
+            Keycode::E => VirtualKeyCode::E,
+            Keycode::F => VirtualKeyCode::F,
+            Keycode::G => VirtualKeyCode::G,
+            Keycode::H => VirtualKeyCode::H,
+            Keycode::I => VirtualKeyCode::I,
+            Keycode::J => VirtualKeyCode::J,
+            Keycode::K => VirtualKeyCode::K,
+            Keycode::L => VirtualKeyCode::L,
+            Keycode::M => VirtualKeyCode::M,
+            Keycode::N => VirtualKeyCode::N,
+            Keycode::O => VirtualKeyCode::O,
+            Keycode::P => VirtualKeyCode::P,
+            Keycode::Q => VirtualKeyCode::Q,
+            Keycode::R => VirtualKeyCode::R,
+            Keycode::S => VirtualKeyCode::S,
+            Keycode::T => VirtualKeyCode::T,
+            Keycode::U => VirtualKeyCode::U,
+            Keycode::V => VirtualKeyCode::V,
+            Keycode::W => VirtualKeyCode::W,
+            Keycode::X => VirtualKeyCode::X,
+            Keycode::Y => VirtualKeyCode::Y,
+            Keycode::Z => VirtualKeyCode::Z,
+            Keycode::LGui => VirtualKeyCode::Lwin,
+            Keycode::RGui => VirtualKeyCode::Rwin,
+            Keycode::Application | Keycode::Menu => VirtualKeyCode::Apps,
+            Keycode::Sleep => VirtualKeyCode::Sleep,
+            Keycode::Kp0 => VirtualKeyCode::Numpad0,
+            Keycode::Kp1 => VirtualKeyCode::Numpad1,
+            Keycode::Kp2 => VirtualKeyCode::Numpad2,
+            Keycode::Kp3 => VirtualKeyCode::Numpad3,
+            Keycode::Kp4 => VirtualKeyCode::Numpad4,
+            Keycode::Kp5 => VirtualKeyCode::Numpad5,
+            Keycode::Kp6 => VirtualKeyCode::Numpad6,
+            Keycode::Kp7 => VirtualKeyCode::Numpad7,
+            Keycode::Kp8 => VirtualKeyCode::Numpad8,
+            Keycode::Kp9 => VirtualKeyCode::Numpad9,
+            Keycode::KpMultiply => VirtualKeyCode::Multiply,
+            Keycode::KpPlus => VirtualKeyCode::Add,
+            Keycode::KpComma => VirtualKeyCode::Separator,
+            Keycode::KpMinus => VirtualKeyCode::Subtract,
+            Keycode::KpPeriod | Keycode::KpDecimal => VirtualKeyCode::Decimal,
+            Keycode::KpDivide => VirtualKeyCode::Divide,
+            Keycode::KpClear => VirtualKeyCode::Clear,
+            Keycode::F1 => VirtualKeyCode::F1,
+            Keycode::F2 => VirtualKeyCode::F2,
+            Keycode::F3 => VirtualKeyCode::F3,
+            Keycode::F4 => VirtualKeyCode::F4,
+            Keycode::F5 => VirtualKeyCode::F5,
+            Keycode::F6 => VirtualKeyCode::F6,
+            Keycode::F7 => VirtualKeyCode::F7,
+            Keycode::F8 => VirtualKeyCode::F8,
+            Keycode::F9 => VirtualKeyCode::F9,
+            Keycode::F10 => VirtualKeyCode::F10,
+            Keycode::F11 => VirtualKeyCode::F11,
+            Keycode::F12 => VirtualKeyCode::F12,
+            Keycode::F13 => VirtualKeyCode::F13,
+            Keycode::F14 => VirtualKeyCode::F14,
+            Keycode::F15 => VirtualKeyCode::F15,
+            Keycode::F16 => VirtualKeyCode::F16,
+            Keycode::F17 => VirtualKeyCode::F17,
+            Keycode::F18 => VirtualKeyCode::F18,
+            Keycode::F19 => VirtualKeyCode::F19,
+            Keycode::F20 => VirtualKeyCode::F20,
+            Keycode::F21 => VirtualKeyCode::F21,
+            Keycode::F22 => VirtualKeyCode::F22,
+            Keycode::F23 => VirtualKeyCode::F23,
+            Keycode::F24 => VirtualKeyCode::F24,
+            Keycode::NumLockClear => VirtualKeyCode::Numlock,
+            Keycode::ScrollLock => VirtualKeyCode::Scroll,
+            Keycode::LShift | Keycode::RShift => VirtualKeyCode::Shift,
+            Keycode::LCtrl | Keycode::RCtrl => VirtualKeyCode::Control,
+            Keycode::LAlt | Keycode::RAlt => VirtualKeyCode::Menu,
+            Keycode::AcBack => VirtualKeyCode::BrowserBack,
+            Keycode::AcForward => VirtualKeyCode::BrowserForward,
+            Keycode::AcRefresh => VirtualKeyCode::BrowserRefresh,
+            Keycode::AcStop => VirtualKeyCode::BrowserStop,
+            Keycode::AcSearch => VirtualKeyCode::BrowserSearch,
+            Keycode::AcBookmarks => VirtualKeyCode::BrowserFavorites,
+            Keycode::AcHome => VirtualKeyCode::BrowserHome,
+            Keycode::Mute | Keycode::AudioMute => VirtualKeyCode::VolumeMute,
+            Keycode::VolumeDown => VirtualKeyCode::VolumeDown,
+            Keycode::VolumeUp => VirtualKeyCode::VolumeUp,
+            Keycode::AudioNext => VirtualKeyCode::MediaNextTrack,
+            Keycode::AudioPrev => VirtualKeyCode::MediaPrevTrack,
+            Keycode::AudioStop => VirtualKeyCode::MediaStop,
+            Keycode::AudioPlay => VirtualKeyCode::MediaPlayPause,
+            Keycode::Mail => VirtualKeyCode::MediaLaunchMail,
+            Keycode::MediaSelect => VirtualKeyCode::MediaLaunchMediaSelect,
+            Keycode::Computer => VirtualKeyCode::MediaLaunchApp1,
+            Keycode::Calculator => VirtualKeyCode::MediaLaunchApp2,
+            Keycode::Semicolon => VirtualKeyCode::Oem1,
+            Keycode::Equals | Keycode::Plus => VirtualKeyCode::OemPlus,
+            Keycode::Comma => VirtualKeyCode::OemComma,
+            Keycode::Minus => VirtualKeyCode::OemMinus,
+            Keycode::Period => VirtualKeyCode::OemPeriod,
+            Keycode::Slash => VirtualKeyCode::Oem2,
+            Keycode::Backquote => VirtualKeyCode::Oem3,
+            Keycode::LeftBracket => VirtualKeyCode::Oem4,
+            Keycode::Backslash => VirtualKeyCode::Oem5,
+            Keycode::RightBracket => VirtualKeyCode::Oem6,
+            Keycode::Quote => VirtualKeyCode::Oem7,
+            Keycode::CrSel => VirtualKeyCode::Crsel,
+            Keycode::ExSel => VirtualKeyCode::Exsel,
+            _ => return None,
+        })
+    }
+
+    /// Map an [`sdl2`] scan code (the physical key, independent of the
+    /// keyboard layout) to a virtual key code, using the key in the US
+    /// layout.
+    ///
+    /// Keypad keys are always mapped to the keypad digits/operators, use
+    /// [`KeyEvent::from_sdl2`] to take the num lock state into account.
+    ///
+    /// Returns [`None`] if the key has no virtual key code.
+    #[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+    pub fn from_sdl2_scancode(scancode: Scancode) -> Option<VirtualKeyCode> {
+        Some(match scancode {
+            Scancode::A => VirtualKeyCode::A,
+            Scancode::B => VirtualKeyCode::B,
+            Scancode::C => VirtualKeyCode::C,
+            Scancode::D => VirtualKeyCode::D,
+            Scancode::E => VirtualKeyCode::E,
+            Scancode::F => VirtualKeyCode::F,
+            Scancode::G => VirtualKeyCode::G,
+            Scancode::H => VirtualKeyCode::H,
+            Scancode::I => VirtualKeyCode::I,
+            Scancode::J => VirtualKeyCode::J,
+            Scancode::K => VirtualKeyCode::K,
+            Scancode::L => VirtualKeyCode::L,
+            Scancode::M => VirtualKeyCode::M,
+            Scancode::N => VirtualKeyCode::N,
+            Scancode::O => VirtualKeyCode::O,
+            Scancode::P => VirtualKeyCode::P,
+            Scancode::Q => VirtualKeyCode::Q,
+            Scancode::R => VirtualKeyCode::R,
+            Scancode::S => VirtualKeyCode::S,
+            Scancode::T => VirtualKeyCode::T,
+            Scancode::U => VirtualKeyCode::U,
+            Scancode::V => VirtualKeyCode::V,
+            Scancode::W => VirtualKeyCode::W,
+            Scancode::X => VirtualKeyCode::X,
+            Scancode::Y => VirtualKeyCode::Y,
+            Scancode::Z => VirtualKeyCode::Z,
+            Scancode::Num1 => VirtualKeyCode::Key1,
+            Scancode::Num2 => VirtualKeyCode::Key2,
+            Scancode::Num3 => VirtualKeyCode::Key3,
+            Scancode::Num4 => VirtualKeyCode::Key4,
+            Scancode::Num5 => VirtualKeyCode::Key5,
+            Scancode::Num6 => VirtualKeyCode::Key6,
+            Scancode::Num7 => VirtualKeyCode::Key7,
+            Scancode::Num8 => VirtualKeyCode::Key8,
+            Scancode::Num9 => VirtualKeyCode::Key9,
+            Scancode::Num0 => VirtualKeyCode::Key0,
+            Scancode::Return | Scancode::Return2 | Scancode::KpEnter => VirtualKeyCode::Return,
+            Scancode::Escape => VirtualKeyCode::Escape,
+            Scancode::Backspace => VirtualKeyCode::Back,
+            Scancode::Tab => VirtualKeyCode::Tab,
+            Scancode::Space => VirtualKeyCode::Space,
+            Scancode::Minus => VirtualKeyCode::OemMinus,
+            Scancode::Equals => VirtualKeyCode::OemPlus,
+            Scancode::LeftBracket => VirtualKeyCode::Oem4,
+            Scancode::RightBracket => VirtualKeyCode::Oem6,
+            Scancode::Backslash | Scancode::NonUsHash => VirtualKeyCode::Oem5,
+            Scancode::Semicolon => VirtualKeyCode::Oem1,
+            Scancode::Apostrophe => VirtualKeyCode::Oem7,
+            Scancode::Grave => VirtualKeyCode::Oem3,
+            Scancode::Comma => VirtualKeyCode::OemComma,
+            Scancode::Period => VirtualKeyCode::OemPeriod,
+            Scancode::Slash => VirtualKeyCode::Oem2,
+            Scancode::NonUsBackslash => VirtualKeyCode::Oem102,
+            Scancode::CapsLock => VirtualKeyCode::Capital,
+            Scancode::F1 => VirtualKeyCode::F1,
+            Scancode::F2 => VirtualKeyCode::F2,
+            Scancode::F3 => VirtualKeyCode::F3,
+            Scancode::F4 => VirtualKeyCode::F4,
+            Scancode::F5 => VirtualKeyCode::F5,
+            Scancode::F6 => VirtualKeyCode::F6,
+            Scancode::F7 => VirtualKeyCode::F7,
+            Scancode::F8 => VirtualKeyCode::F8,
+            Scancode::F9 => VirtualKeyCode::F9,
+            Scancode::F10 => VirtualKeyCode::F10,
+            Scancode::F11 => VirtualKeyCode::F11,
+            Scancode::F12 => VirtualKeyCode::F12,
+            Scancode::F13 => VirtualKeyCode::F13,
+            Scancode::F14 => VirtualKeyCode::F14,
+            Scancode::F15 => VirtualKeyCode::F15,
+            Scancode::F16 => VirtualKeyCode::F16,
+            Scancode::F17 => VirtualKeyCode::F17,
+            Scancode::F18 => VirtualKeyCode::F18,
+            Scancode::F19 => VirtualKeyCode::F19,
+            Scancode::F20 => VirtualKeyCode::F20,
+            Scancode::F21 => VirtualKeyCode::F21,
+            Scancode::F22 => VirtualKeyCode::F22,
+            Scancode::F23 => VirtualKeyCode::F23,
+            Scancode::F24 => VirtualKeyCode::F24,
+            Scancode::PrintScreen => VirtualKeyCode::Snapshot,
+            Scancode::ScrollLock => VirtualKeyCode::Scroll,
+            Scancode::Pause => VirtualKeyCode::Pause,
+            Scancode::Insert => VirtualKeyCode::Insert,
+            Scancode::Home => VirtualKeyCode::Home,
+            Scancode::PageUp => VirtualKeyCode::Prior,
+            Scancode::Delete => VirtualKeyCode::Delete,
+            Scancode::End => VirtualKeyCode::End,
+            Scancode::PageDown => VirtualKeyCode::Next,
+            Scancode::Right => VirtualKeyCode::Right,
+            Scancode::Left => VirtualKeyCode::Left,
+            Scancode::Down => VirtualKeyCode::Down,
+            Scancode::Up => VirtualKeyCode::Up,
+            Scancode::NumLockClear => VirtualKeyCode::Numlock,
+            Scancode::KpDivide => VirtualKeyCode::Divide,
+            Scancode::KpMultiply => VirtualKeyCode::Multiply,
+            Scancode::KpMinus => VirtualKeyCode::Subtract,
+            Scancode::KpPlus => VirtualKeyCode::Add,
+            Scancode::Kp1 => VirtualKeyCode::Numpad1,
+            Scancode::Kp2 => VirtualKeyCode::Numpad2,
+            Scancode::Kp3 => VirtualKeyCode::Numpad3,
+            Scancode::Kp4 => VirtualKeyCode::Numpad4,
+            Scancode::Kp5 => VirtualKeyCode::Numpad5,
+            Scancode::Kp6 => VirtualKeyCode::Numpad6,
+            Scancode::Kp7 => VirtualKeyCode::Numpad7,
+            Scancode::Kp8 => VirtualKeyCode::Numpad8,
+            Scancode::Kp9 => VirtualKeyCode::Numpad9,
+            Scancode::Kp0 => VirtualKeyCode::Numpad0,
+            Scancode::KpPeriod | Scancode::KpDecimal => VirtualKeyCode::Decimal,
+            Scancode::KpComma => VirtualKeyCode::Separator,
+            Scancode::KpClear => VirtualKeyCode::Clear,
+            Scancode::Application | Scancode::Menu => VirtualKeyCode::Apps,
+            Scancode::Help => VirtualKeyCode::Help,
+            Scancode::Select => VirtualKeyCode::Select,
+            Scancode::Execute => VirtualKeyCode::Execute,
+            Scancode::Clear => VirtualKeyCode::Clear,
+            Scancode::Mute | Scancode::AudioMute => VirtualKeyCode::VolumeMute,
+            Scancode::VolumeUp => VirtualKeyCode::VolumeUp,
+            Scancode::VolumeDown => VirtualKeyCode::VolumeDown,
+            Scancode::LCtrl | Scancode::RCtrl => VirtualKeyCode::Control,
+            Scancode::LShift | Scancode::RShift => VirtualKeyCode::Shift,
+            Scancode::LAlt | Scancode::RAlt => VirtualKeyCode::Menu,
+            Scancode::LGui => VirtualKeyCode::Lwin,
+            Scancode::RGui => VirtualKeyCode::Rwin,
+            Scancode::Lang1 => VirtualKeyCode::Hangul,
+            Scancode::Lang2 => VirtualKeyCode::Hanja,
+            Scancode::International4 => VirtualKeyCode::Convert,
+            Scancode::International5 => VirtualKeyCode::Nonconvert,
+            Scancode::AudioNext => VirtualKeyCode::MediaNextTrack,
+            Scancode::AudioPrev => VirtualKeyCode::MediaPrevTrack,
+            Scancode::AudioStop => VirtualKeyCode::MediaStop,
+            Scancode::AudioPlay => VirtualKeyCode::MediaPlayPause,
+            Scancode::Mail => VirtualKeyCode::MediaLaunchMail,
+            Scancode::MediaSelect => VirtualKeyCode::MediaLaunchMediaSelect,
+            Scancode::Computer => VirtualKeyCode::MediaLaunchApp1,
+            Scancode::Calculator => VirtualKeyCode::MediaLaunchApp2,
+            Scancode::AcSearch => VirtualKeyCode::BrowserSearch,
+            Scancode::AcHome => VirtualKeyCode::BrowserHome,
+            Scancode::AcBack => VirtualKeyCode::BrowserBack,
+            Scancode::AcForward => VirtualKeyCode::BrowserForward,
+            Scancode::AcStop => VirtualKeyCode::BrowserStop,
+            Scancode::AcRefresh => VirtualKeyCode::BrowserRefresh,
+            Scancode::AcBookmarks => VirtualKeyCode::BrowserFavorites,
+            Scancode::Sleep => VirtualKeyCode::Sleep,
+            Scancode::CrSel => VirtualKeyCode::Crsel,
+            Scancode::ExSel => VirtualKeyCode::Exsel,
+            _ => return None,
+        })
+    }
+}
+
+/// Whether the scan code is a key of the keypad.
+fn is_keypad_scancode(scancode: Scancode) -> bool {
+    matches!(
+        scancode,
+        Scancode::Kp0
+            | Scancode::Kp1
+            | Scancode::Kp2
+            | Scancode::Kp3
+            | Scancode::Kp4
+            | Scancode::Kp5
+            | Scancode::Kp6
+            | Scancode::Kp7
+            | Scancode::Kp8
+            | Scancode::Kp9
+            | Scancode::KpPeriod
+            | Scancode::KpDecimal
+            | Scancode::KpComma
+            | Scancode::KpDivide
+            | Scancode::KpMultiply
+            | Scancode::KpMinus
+            | Scancode::KpPlus
+            | Scancode::KpEnter
+            | Scancode::KpEquals
+            | Scancode::KpClear
+    )
+}
+
+/// The navigation key of a keypad key when num lock is off.
+fn keypad_navigation_key(scancode: Scancode) -> Option<VirtualKeyCode> {
+    Some(match scancode {
+        Scancode::Kp0 => VirtualKeyCode::Insert,
+        Scancode::Kp1 => VirtualKeyCode::End,
+        Scancode::Kp2 => VirtualKeyCode::Down,
+        Scancode::Kp3 => VirtualKeyCode::Next,
+        Scancode::Kp4 => VirtualKeyCode::Left,
+        Scancode::Kp5 => VirtualKeyCode::Clear,
+        Scancode::Kp6 => VirtualKeyCode::Right,
+        Scancode::Kp7 => VirtualKeyCode::Home,
+        Scancode::Kp8 => VirtualKeyCode::Up,
+        Scancode::Kp9 => VirtualKeyCode::Prior,
+        Scancode::KpPeriod => VirtualKeyCode::Delete,
+        _ => return None,
+    })
+}
@@ -0,0 +1,351 @@
+//! Conversion of [`winit`] input events into Ultralight events.
+use std::sync::Arc;
+
+use winit::{
+    dpi::PhysicalPosition,
+    event::{ElementState, MouseScrollDelta, WindowEvent},
+    keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey, PhysicalKey},
+};
+
+use crate::{error::CreationError, key_code::VirtualKeyCode, Library};
+
+use super::{
+    KeyEvent, KeyEventCreationInfo, KeyEventModifiers, KeyEventType, MouseButton, MouseEvent,
+    MouseEventType, ScrollEvent, ScrollEventType, PIXELS_PER_LINE,
+};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "winit")))]
+impl From<ModifiersState> for KeyEventModifiers {
+    fn from(modifiers: ModifiersState) -> Self {
+        Self {
+            alt: modifiers.alt_key(),
+            ctrl: modifiers.control_key(),
+            meta: modifiers.super_key(),
+            shift: modifiers.shift_key(),
+        }
+    }
+}
+
+impl KeyEvent {
+    /// Create a [`KeyEventType::RawKeyDown`] or [`KeyEventType::KeyUp`] event
+    /// from a [`winit`] keyboard event.
+    ///
+    /// Text input is not part of this event, use [`KeyEvent::char_from_winit`]
+    /// to get the [`KeyEventType::Char`] event that should be fired after
+    /// the key down event.
+    ///
+    /// # Arguments
+    /// * `lib` - The ultralight library.
+    /// * `event` - The keyboard event from [`WindowEvent::KeyboardInput`].
+    /// * `modifiers` - The current modifiers, tracked from
+    ///   [`WindowEvent::ModifiersChanged`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "winit")))]
+    pub fn from_winit(
+        lib: Arc<Library>,
+        event: &winit::event::KeyEvent,
+        modifiers: ModifiersState,
+    ) -> Result<KeyEvent, CreationError> {
+        let ty = match event.state {
+            ElementState::Pressed => KeyEventType::RawKeyDown,
+            ElementState::Released => KeyEventType::KeyUp,
+        };
+        let virtual_key_code = match event.physical_key {
+            PhysicalKey::Code(code) => virtual_key_code_from_winit(code),
+            PhysicalKey::Unidentified(_) => VirtualKeyCode::Unknown,
+        };
+
+        KeyEvent::new(
+            lib,
+            KeyEventCreationInfo {
+                ty,
+                modifiers: modifiers.into(),
+                virtual_key_code,
+                native_key_code: 0,
+                text: "",
+                unmodified_text: "",
+                is_keypad: event.location == KeyLocation::Numpad,
+                is_auto_repeat: event.repeat,
+                is_system_key: false,
+            },
+        )
+    }
+
+    /// Create a [`KeyEventType::Char`] event from a [`winit`] keyboard event,
+    /// using the text generated by the key.
+    ///
+    /// Returns [`None`] if the event is a key release, or if it didn't
+    /// generate any text.
+    ///
+    /// # Arguments
+    /// * `lib` - The ultralight library.
+    /// * `event` - The keyboard event from [`WindowEvent::KeyboardInput`].
+    /// * `modifiers` - The current modifiers, tracked from
+    ///   [`WindowEvent::ModifiersChanged`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "winit")))]
+    pub fn char_from_winit(
+        lib: Arc<Library>,
+        event: &winit::event::KeyEvent,
+        modifiers: ModifiersState,
+    ) -> Result<Option<KeyEvent>, CreationError> {
+        if event.state != ElementState::Pressed {
+            return Ok(None);
+        }
+
+        let text = match (&event.text, &event.logical_key) {
+            (Some(text), _) => text.as_str(),
+            (None, Key::Character(text)) => text.as_str(),
+            (None, Key::Named(NamedKey::Enter)) => "\r",
+            (None, Key::Named(NamedKey::Tab)) => "\t",
+            (None, Key::Named(NamedKey::Space)) => " ",
+            _ => return Ok(None),
+        };
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        KeyEvent::new(
+            lib,
+            KeyEventCreationInfo {
+                ty: KeyEventType::Char,
+                modifiers: modifiers.into(),
+                virtual_key_code: VirtualKeyCode::Unknown,
+                native_key_code: 0,
+                text,
+                unmodified_text: text,
+                is_keypad: event.location == KeyLocation::Numpad,
+                is_auto_repeat: event.repeat,
+                is_system_key: false,
+            },
+        )
+        .map(Some)
+    }
+}
+
+impl MouseEvent {
+    /// Create a mouse event from a [`winit`] window event.
+    ///
+    /// Handles [`WindowEvent::CursorMoved`] and [`WindowEvent::MouseInput`]
+    /// (for the left, middle and right buttons), returns [`None`] for
+    /// all other events.
+    ///
+    /// # Arguments
+    /// * `lib` - The ultralight library.
+    /// * `event` - The window event.
+    /// * `cursor_position` - The last known cursor position, used for button
+    ///   events since they don't include the position.
+    /// * `device_scale` - The device scale of the view, positions are divided
+    ///   by it to convert from physical pixels to view coordinates.
+    #[cfg_attr(docsrs, doc(cfg(feature = "winit")))]
+    pub fn from_winit(
+        lib: Arc<Library>,
+        event: &WindowEvent,
+        cursor_position: PhysicalPosition<f64>,
+        device_scale: f64,
+    ) -> Result<Option<MouseEvent>, CreationError> {
+        let (ty, position, button) = match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                (MouseEventType::MouseMoved, *position, MouseButton::None)
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                let ty = match state {
+                    ElementState::Pressed => MouseEventType::MouseDown,
+                    ElementState::Released => MouseEventType::MouseUp,
+                };
+                let button = match button {
+                    winit::event::MouseButton::Left => MouseButton::Left,
+                    winit::event::MouseButton::Middle => MouseButton::Middle,
+                    winit::event::MouseButton::Right => MouseButton::Right,
+                    _ => return Ok(None),
+                };
+                (ty, cursor_position, button)
+            }
+            _ => return Ok(None),
+        };
+
+        MouseEvent::new(
+            lib,
+            ty,
+            (position.x / device_scale) as i32,
+            (position.y / device_scale) as i32,
+            button,
+        )
+        .map(Some)
+    }
+}
+
+impl ScrollEvent {
+    /// Create a [`ScrollEventType::ScrollByPixel`] event from a [`winit`]
+    /// scroll delta (from [`WindowEvent::MouseWheel`]).
+    ///
+    /// Line deltas are converted to pixels using the same step size as WebKit.
+    ///
+    /// # Arguments
+    /// * `lib` - The ultralight library.
+    /// * `delta` - The scroll delta.
+    /// * `device_scale` - The device scale of the view, pixel deltas are
+    ///   divided by it to convert from physical pixels to view coordinates.
+    #[cfg_attr(docsrs, doc(cfg(feature = "winit")))]
+    pub fn from_winit(
+        lib: Arc<Library>,
+        delta: MouseScrollDelta,
+        device_scale: f64,
+    ) -> Result<ScrollEvent, CreationError> {
+        let (delta_x, delta_y) = match delta {
+            MouseScrollDelta::LineDelta(x, y) => {
+                ((x * PIXELS_PER_LINE) as i32, (y * PIXELS_PER_LINE) as i32)
+            }
+            MouseScrollDelta::PixelDelta(position) => (
+                (position.x / device_scale) as i32,
+                (position.y / device_scale) as i32,
+            ),
+        };
+
+        ScrollEvent::new(lib, ScrollEventType::ScrollByPixel, delta_x, delta_y)
+    }
+}
+
+fn virtual_key_code_from_winit(code: KeyCode) -> VirtualKeyCode {
+    match code {
+        KeyCode::Backquote => VirtualKeyCode::Oem3,
+        KeyCode::Backslash => VirtualKeyCode::Oem5,
+        KeyCode::BracketLeft => VirtualKeyCode::Oem4,
+        KeyCode::BracketRight => VirtualKeyCode::Oem6,
+        KeyCode::Comma => VirtualKeyCode::OemComma,
+        KeyCode::Digit0 => VirtualKeyCode::Key0,
+        KeyCode::Digit1 => VirtualKeyCode::Key1,
+        KeyCode::Digit2 => VirtualKeyCode::Key2,
+        KeyCode::Digit3 => VirtualKeyCode::Key3,
+        KeyCode::Digit4 => VirtualKeyCode::Key4,
+        KeyCode::Digit5 => VirtualKeyCode::Key5,
+        KeyCode::Digit6 => VirtualKeyCode::Key6,
+        KeyCode::Digit7 => VirtualKeyCode::Key7,
+        KeyCode::Digit8 => VirtualKeyCode::Key8,
+        KeyCode::Digit9 => VirtualKeyCode::Key9,
+        KeyCode::Equal => VirtualKeyCode::OemPlus,
+        KeyCode::IntlBackslash => VirtualKeyCode::Oem102,
+        KeyCode::KeyA => VirtualKeyCode::A,
+        KeyCode::KeyB => VirtualKeyCode::B,
+        KeyCode::KeyC => VirtualKeyCode::C,
+        KeyCode::KeyD => VirtualKeyCode::D,
+        KeyCode::KeyE => VirtualKeyCode::E,
+        KeyCode::KeyF => VirtualKeyCode::F,
+        KeyCode::KeyG => VirtualKeyCode::G,
+        KeyCode::KeyH => VirtualKeyCode::H,
+        KeyCode::KeyI => VirtualKeyCode::I,
+        KeyCode::KeyJ => VirtualKeyCode::J,
+        KeyCode::KeyK => VirtualKeyCode::K,
+        KeyCode::KeyL => VirtualKeyCode::L,
+        KeyCode::KeyM => VirtualKeyCode::M,
+        KeyCode::KeyN => VirtualKeyCode::N,
+        KeyCode::KeyO => VirtualKeyCode::O,
+        KeyCode::KeyP => VirtualKeyCode::P,
+        KeyCode::KeyQ => VirtualKeyCode::Q,
+        KeyCode::KeyR => VirtualKeyCode::R,
+        KeyCode::KeyS => VirtualKeyCode::S,
+        KeyCode::KeyT => VirtualKeyCode::T,
+        KeyCode::KeyU => VirtualKeyCode::U,
+        KeyCode::KeyV => VirtualKeyCode::V,
+        KeyCode::KeyW => VirtualKeyCode::W,
+        KeyCode::KeyX => VirtualKeyCode::X,
+        KeyCode::KeyY => VirtualKeyCode::Y,
+        KeyCode::KeyZ => VirtualKeyCode::Z,
+        KeyCode::Minus => VirtualKeyCode::OemMinus,
+        KeyCode::Period => VirtualKeyCode::OemPeriod,
+        KeyCode::Quote => VirtualKeyCode::Oem7,
+        KeyCode::Semicolon => VirtualKeyCode::Oem1,
+        KeyCode::Slash => VirtualKeyCode::Oem2,
+        KeyCode::AltLeft | KeyCode::AltRight => VirtualKeyCode::Menu,
+        KeyCode::Backspace => VirtualKeyCode::Back,
+        KeyCode::CapsLock => VirtualKeyCode::Capital,
+        KeyCode::ContextMenu => VirtualKeyCode::Apps,
+        KeyCode::ControlLeft | KeyCode::ControlRight => VirtualKeyCode::Control,
+        KeyCode::Enter | KeyCode::NumpadEnter => VirtualKeyCode::Return,
+        KeyCode::SuperLeft => VirtualKeyCode::Lwin,
+        KeyCode::SuperRight => VirtualKeyCode::Rwin,
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => VirtualKeyCode::Shift,
+        KeyCode::Space => VirtualKeyCode::Space,
+        KeyCode::Tab => VirtualKeyCode::Tab,
+        KeyCode::Convert => VirtualKeyCode::Convert,
+        KeyCode::KanaMode => VirtualKeyCode::Kana,
+        KeyCode::NonConvert => VirtualKeyCode::Nonconvert,
+        KeyCode::Delete => VirtualKeyCode::Delete,
+        KeyCode::End => VirtualKeyCode::End,
+        KeyCode::Help => VirtualKeyCode::Help,
+        KeyCode::Home => VirtualKeyCode::Home,
+        KeyCode::Insert => VirtualKeyCode::Insert,
+        KeyCode::PageDown => VirtualKeyCode::Next,
+        KeyCode::PageUp => VirtualKeyCode::Prior,
+        KeyCode::ArrowDown => VirtualKeyCode::Down,
+        KeyCode::ArrowLeft => VirtualKeyCode::Left,
+        KeyCode::ArrowRight => VirtualKeyCode::Right,
+        KeyCode::ArrowUp => VirtualKeyCode::Up,
+        KeyCode::NumLock => VirtualKeyCode::Numlock,
+        KeyCode::Numpad0 => VirtualKeyCode::Numpad0,
+        KeyCode::Numpad1 => VirtualKeyCode::Numpad1,
+        KeyCode::Numpad2 => VirtualKeyCode::Numpad2,
+        KeyCode::Numpad3 => VirtualKeyCode::Numpad3,
+        KeyCode::Numpad4 => VirtualKeyCode::Numpad4,
+        KeyCode::Numpad5 => VirtualKeyCode::Numpad5,
+        KeyCode::Numpad6 => VirtualKeyCode::Numpad6,
+        KeyCode::Numpad7 => VirtualKeyCode::Numpad7,
+        KeyCode::Numpad8 => VirtualKeyCode::Numpad8,
+        KeyCode::Numpad9 => VirtualKeyCode::Numpad9,
+        KeyCode::NumpadAdd => VirtualKeyCode::Add,
+        KeyCode::NumpadBackspace => VirtualKeyCode::Back,
+        KeyCode::NumpadClear => VirtualKeyCode::Clear,
+        KeyCode::NumpadComma => VirtualKeyCode::Separator,
+        KeyCode::NumpadDecimal => VirtualKeyCode::Decimal,
+        KeyCode::NumpadDivide => VirtualKeyCode::Divide,
+        KeyCode::NumpadMultiply | KeyCode::NumpadStar => VirtualKeyCode::Multiply,
+        KeyCode::NumpadSubtract => VirtualKeyCode::Subtract,
+        KeyCode::Escape => VirtualKeyCode::Escape,
+        KeyCode::PrintScreen => VirtualKeyCode::Snapshot,
+        KeyCode::ScrollLock => VirtualKeyCode::Scroll,
+        KeyCode::Pause => VirtualKeyCode::Pause,
+        KeyCode::BrowserBack => VirtualKeyCode::BrowserBack,
+        KeyCode::BrowserFavorites => VirtualKeyCode::BrowserFavorites,
+        KeyCode::BrowserForward => VirtualKeyCode::BrowserForward,
+        KeyCode::BrowserHome => VirtualKeyCode::BrowserHome,
+        KeyCode::BrowserRefresh => VirtualKeyCode::BrowserRefresh,
+        KeyCode::BrowserSearch => VirtualKeyCode::BrowserSearch,
+        KeyCode::BrowserStop => VirtualKeyCode::BrowserStop,
+        KeyCode::LaunchApp1 => VirtualKeyCode::MediaLaunchApp1,
+        KeyCode::LaunchApp2 => VirtualKeyCode::MediaLaunchApp2,
+        KeyCode::LaunchMail => VirtualKeyCode::MediaLaunchMail,
+        KeyCode::MediaPlayPause => VirtualKeyCode::MediaPlayPause,
+        KeyCode::MediaSelect => VirtualKeyCode::MediaLaunchMediaSelect,
+        KeyCode::MediaStop => VirtualKeyCode::MediaStop,
+        KeyCode::MediaTrackNext => VirtualKeyCode::MediaNextTrack,
+        KeyCode::MediaTrackPrevious => VirtualKeyCode::MediaPrevTrack,
+        KeyCode::Sleep => VirtualKeyCode::Sleep,
+        KeyCode::AudioVolumeDown => VirtualKeyCode::VolumeDown,
+        KeyCode::AudioVolumeMute => VirtualKeyCode::VolumeMute,
+        KeyCode::AudioVolumeUp => VirtualKeyCode::VolumeUp,
+        KeyCode::Select => VirtualKeyCode::Select,
+        KeyCode::F1 => VirtualKeyCode::F1,
+        KeyCode::F2 => VirtualKeyCode::F2,
+        KeyCode::F3 => VirtualKeyCode::F3,
+        KeyCode::F4 => VirtualKeyCode::F4,
+        KeyCode::F5 => VirtualKeyCode::F5,
+        KeyCode::F6 => VirtualKeyCode::F6,
+        KeyCode::F7 => VirtualKeyCode::F7,
+        KeyCode::F8 => VirtualKeyCode::F8,
+        KeyCode::F9 => VirtualKeyCode::F9,
+        KeyCode::F10 => VirtualKeyCode::F10,
+        KeyCode::F11 => VirtualKeyCode::F11,
+        KeyCode::F12 => VirtualKeyCode::F12,
+        KeyCode::F13 => VirtualKeyCode::F13,
+        KeyCode::F14 => VirtualKeyCode::F14,
+        KeyCode::F15 => VirtualKeyCode::F15,
+        KeyCode::F16 => VirtualKeyCode::F16,
+        KeyCode::F17 => VirtualKeyCode::F17,
+        KeyCode::F18 => VirtualKeyCode::F18,
+        KeyCode::F19 => VirtualKeyCode::F19,
+        KeyCode::F20 => VirtualKeyCode::F20,
+        KeyCode::F21 => VirtualKeyCode::F21,
+        KeyCode::F22 => VirtualKeyCode::F22,
+        KeyCode::F23 => VirtualKeyCode::F23,
+        KeyCode::F24 => VirtualKeyCode::F24,
+        _ => VirtualKeyCode::Unknown,
+    }
+}
@@ -7,21 +7,34 @@
 //! There is an example `C++` implementation for `OpenGL`, `DirectX11`, `DirectX12`
 //! and `Metal` in the [`AppCore`](https://github.com/ultralight-ux/AppCore) repository.
 //!
-//! This library also have a custom GPU driver for [`glium`].
+//! This library also have a custom GPU driver for [`glium`], and one for
+//! `miniquad` (and `macroquad`) in [`miniquad`](self::miniquad).
+//!
+//! To debug a driver, the calls can be captured and replayed with
+//! [`recording::RecordingDriver`].
 
 #[cfg(feature = "glium")]
 #[cfg_attr(docsrs, doc(cfg(feature = "glium")))]
 pub mod glium;
+#[cfg(feature = "miniquad")]
+#[cfg_attr(docsrs, doc(cfg(feature = "miniquad")))]
+pub mod miniquad;
+pub mod recording;
 
-use std::slice;
+use std::{
+    slice,
+    sync::{Mutex, PoisonError},
+};
 
 use crate::{
     bitmap::{Bitmap, OwnedBitmap},
+    error::{CreationError, Error, UnknownEnumValue},
     platform::GPUDRIVER,
     rect::Rect,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// RenderBuffer description. (See [`GpuDriver::create_render_buffer`]).
 pub struct RenderBuffer {
     /// The backing texture id for this render buffer.
@@ -48,7 +61,8 @@ impl From<ul_sys::ULRenderBuffer> for RenderBuffer {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 /// Vertex buffer format types
 pub enum VertexBufferFormat {
@@ -60,7 +74,7 @@ pub enum VertexBufferFormat {
 }
 
 impl TryFrom<ul_sys::ULVertexBufferFormat> for VertexBufferFormat {
-    type Error = ();
+    type Error = UnknownEnumValue;
 
     fn try_from(vbf: ul_sys::ULVertexBufferFormat) -> Result<Self, Self::Error> {
         match vbf {
@@ -70,7 +84,7 @@ impl TryFrom<ul_sys::ULVertexBufferFormat> for VertexBufferFormat {
             ul_sys::ULVertexBufferFormat_kVertexBufferFormat_2f_4ub_2f_2f_28f => {
                 Ok(VertexBufferFormat::Format_2f_4ub_2f_2f_28f)
             }
-            _ => Err(()),
+            _ => Err(UnknownEnumValue::new("VertexBufferFormat", vbf)),
         }
     }
 }
@@ -79,6 +93,8 @@ impl TryFrom<ul_sys::ULVertexBufferFormat> for VertexBufferFormat {
 //       a specific format? like what we did in `glium` gpu_driver.
 /// Vertex buffer, the buffer is used for `quad` or `path` rendering based on
 /// the `format`. (See [`GpuDriver::create_geometry`]).
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexBuffer {
     /// The format of the raw data. Either path or quad vertices.
     pub format: VertexBufferFormat,
@@ -87,11 +103,11 @@ pub struct VertexBuffer {
 }
 
 impl TryFrom<ul_sys::ULVertexBuffer> for VertexBuffer {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(vb: ul_sys::ULVertexBuffer) -> Result<Self, Self::Error> {
         if vb.data.is_null() {
-            return Err(());
+            return Err(CreationError::NullReference.into());
         }
         let format = VertexBufferFormat::try_from(vb.format)?;
         let buffer = unsafe { slice::from_raw_parts(vb.data, vb.size as usize) };
@@ -103,6 +119,8 @@ impl TryFrom<ul_sys::ULVertexBuffer> for VertexBuffer {
 }
 
 /// Index buffer. (See [`GpuDriver::create_geometry`]).
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IndexBuffer {
     pub buffer: Vec<u32>,
 }
@@ -155,6 +173,7 @@ macro_rules! from_ul_arr {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Shader types, used by [`GpuState::shader_type`]
 ///
 /// Each of these correspond to a vertex/pixel shader pair to be used.
@@ -169,18 +188,19 @@ pub enum ShaderType {
 }
 
 impl TryFrom<ul_sys::ULShaderType> for ShaderType {
-    type Error = ();
+    type Error = UnknownEnumValue;
 
     fn try_from(st: ul_sys::ULShaderType) -> Result<Self, Self::Error> {
         match st {
             ul_sys::ULShaderType_kShaderType_Fill => Ok(ShaderType::Fill),
             ul_sys::ULShaderType_kShaderType_FillPath => Ok(ShaderType::FillPath),
-            _ => Err(()),
+            _ => Err(UnknownEnumValue::new("ShaderType", st)),
         }
     }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The GPU state description to be used when handling draw command.
 /// (See [`GpuCommand::DrawGeometry`]).
 pub struct GpuState {
@@ -225,7 +245,7 @@ pub struct GpuState {
 }
 
 impl TryFrom<ul_sys::ULGPUState> for GpuState {
-    type Error = ();
+    type Error = UnknownEnumValue;
 
     fn try_from(gs: ul_sys::ULGPUState) -> Result<Self, Self::Error> {
         Ok(GpuState {
@@ -262,6 +282,7 @@ impl TryFrom<ul_sys::ULGPUState> for GpuState {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The GPU command to be executed.
 ///
 /// This describes a command to be executed on the GPU.
@@ -291,7 +312,7 @@ pub enum GpuCommand {
 }
 
 impl TryFrom<ul_sys::ULCommand> for GpuCommand {
-    type Error = ();
+    type Error = UnknownEnumValue;
 
     fn try_from(gc: ul_sys::ULCommand) -> Result<Self, Self::Error> {
         match gc.command_type as u32 {
@@ -306,7 +327,7 @@ impl TryFrom<ul_sys::ULCommand> for GpuCommand {
                     render_buffer_id: gc.gpu_state.render_buffer_id,
                 })
             }
-            _ => Err(()),
+            _ => Err(UnknownEnumValue::new("GpuCommand", gc.command_type)),
         }
     }
 }
@@ -333,6 +354,22 @@ pub trait GpuDriver {
     /// Called after all state has been updated during a call to
     /// [`Renderer::render`](crate::renderer::Renderer::render).
     fn end_synchronize(&mut self);
+    /// Called for each display refreshed with
+    /// [`Renderer::refresh_display`](crate::renderer::Renderer::refresh_display)
+    /// since the previous frame, right after [`GpuDriver::begin_synchronize`].
+    ///
+    /// This is not called if no display was refreshed, the default
+    /// implementation does nothing.
+    fn begin_frame(&mut self, display_id: u32) {
+        let _ = display_id;
+    }
+    /// Called for each display of the frame (see [`GpuDriver::begin_frame`]),
+    /// in reverse order, right before [`GpuDriver::end_synchronize`].
+    ///
+    /// The default implementation does nothing.
+    fn end_frame(&mut self, display_id: u32) {
+        let _ = display_id;
+    }
     /// Get the next available texture ID. **DO NOT return `0` (reserved)**.
     ///
     /// This is used to generate a unique texture ID for each texture created by the library.
@@ -379,11 +416,52 @@ pub trait GpuDriver {
     fn update_command_list(&mut self, command_list: Vec<GpuCommand>);
 }
 
+/// The displays of the frames, the C API doesn't give them to the driver.
+struct FrameDisplays {
+    /// The displays refreshed since the last frame.
+    refreshed: Vec<u32>,
+    /// The displays of the frame being synchronized.
+    current: Vec<u32>,
+}
+
+static FRAME_DISPLAYS: Mutex<FrameDisplays> = Mutex::new(FrameDisplays {
+    refreshed: Vec::new(),
+    current: Vec::new(),
+});
+
+/// Mark `display_id` as part of the next frame, for [`GpuDriver::begin_frame`].
+pub(crate) fn display_refreshed(display_id: u32) {
+    let mut displays = FRAME_DISPLAYS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if !displays.refreshed.contains(&display_id) {
+        displays.refreshed.push(display_id);
+    }
+}
+
 platform_set_interface_macro! {
     #[inline]
     pub(crate) set_gpu_driver<GpuDriver>(lib, gpu_driver -> GPUDRIVER) -> ulPlatformSetGPUDriver(ULGPUDriver) {
-        begin_synchronize() -> () {}
-        end_synchronize() -> () {}
+        begin_synchronize() -> () {} {
+            let display_ids = {
+                let mut displays = FRAME_DISPLAYS.lock().unwrap_or_else(PoisonError::into_inner);
+                let displays = &mut *displays;
+                displays.current = std::mem::take(&mut displays.refreshed);
+                displays.current.clone()
+            };
+            let driver = gpu_driver.as_mut().unwrap();
+            for display_id in display_ids {
+                driver.begin_frame(display_id);
+            }
+        }
+        end_synchronize() -> () {
+            let display_ids = std::mem::take(&mut FRAME_DISPLAYS.lock().unwrap_or_else(PoisonError::into_inner).current);
+            if let Some(driver) = GPUDRIVER.obj.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+                for &display_id in display_ids.iter().rev() {
+                    driver.end_frame(display_id);
+                }
+            }
+        }
         next_texture_id(() -> u32) -> () {}
         create_texture((texture_id: u32, ul_bitmap: ul_sys::ULBitmap)) -> ((texture_id: u32, bitmap: OwnedBitmap)) {
             let mut bitmap = Bitmap::from_raw(lib.clone(), ul_bitmap).unwrap();
@@ -1,24 +1,38 @@
 //! A custom [`GpuDriver`] implementation for the `glium` backend.
 
-use std::{borrow::Cow, collections::HashMap, rc::Rc, sync::mpsc};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc, Arc,
+    },
+    time::{Duration, Instant},
+};
 
 use glium::{
     backend::{Context, Facade},
     framebuffer::SimpleFrameBuffer,
+    index::{NoIndices, PrimitiveType},
     program,
+    program::{ProgramChooserCreationError, ProgramCreationInput},
     texture::{ClientFormat, MipmapsOption, RawImage2d, SrgbTexture2d, UncompressedFloatFormat},
     uniform,
     uniforms::UniformBuffer,
-    vertex::{AttributeType, VertexBufferAny},
+    vertex::{AttributeType, EmptyVertexAttributes, VertexBufferAny},
     Blend, DrawParameters, Program, Surface, Texture2d,
 };
 
 use crate::{
     bitmap::{BitmapFormat, OwnedBitmap},
     gpu_driver::ShaderType,
+    rect::Rect,
 };
 
-use super::{GpuCommand, GpuDriver, IndexBuffer, RenderBuffer, VertexBuffer, VertexBufferFormat};
+use super::{
+    GpuCommand, GpuDriver, GpuState, IndexBuffer, RenderBuffer, VertexBuffer, VertexBufferFormat,
+};
 pub use either_texture::{EitherSampler, EitherTexture};
 
 mod either_texture;
@@ -150,6 +164,10 @@ pub enum GliumGpuDriverError {
     FrameBufferCreationError(#[from] glium::framebuffer::ValidationError),
     #[error("Failed to draw")]
     DrawError(#[from] glium::DrawError),
+    #[error("Texture with id {0} does not exist")]
+    TextureNotFound(u32),
+    #[error("Texture id {0} is not an external texture id")]
+    NotExternalTextureId(u32),
     #[error(
         "The index offset ({draw_index_offset}) and size ({draw_index_size}) used in draw is out of range from the selected index buffer (size = {index_buffer_size})"
     )]
@@ -160,6 +178,89 @@ pub enum GliumGpuDriverError {
     },
 }
 
+/// The transfer function (gamma curve) expected by the output target.
+///
+/// See [`OutputTransform`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferFn {
+    /// sRGB encoded values, this is what `Ultralight` renders to, so no
+    /// conversion will be performed.
+    Srgb,
+    /// Linear values, with no gamma encoding.
+    Linear,
+    /// Values encoded with a pure power curve of the given gamma (e.g. `2.2`).
+    Gamma(f32),
+}
+
+/// Transformation applied when compositing textures to the final target
+/// with [`GliumGpuDriverReceiver::draw_texture`].
+///
+/// `Ultralight` renders 8-bit sRGB textures, blitting these into a higher
+/// bit-depth target (e.g. 10-bit swapchains) will show banding in smooth
+/// gradients. Enabling `dithering` breaks these bands up with an ordered
+/// dither pattern before the conversion to `target_gamma`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputTransform {
+    /// Apply ordered dithering to hide the 8-bit quantization steps.
+    pub dithering: bool,
+    /// The transfer function to encode the output with.
+    pub target_gamma: TransferFn,
+}
+
+impl Default for OutputTransform {
+    fn default() -> Self {
+        Self {
+            dithering: false,
+            target_gamma: TransferFn::Srgb,
+        }
+    }
+}
+
+/// The work done by [`GliumGpuDriverReceiver::render_with_budget`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderProgress {
+    /// Whether all the queued commands were executed, the remaining ones
+    /// are executed by the next call otherwise.
+    pub completed: bool,
+    /// The number of commands executed, a command list counts as one.
+    pub commands_executed: usize,
+    /// The number of textures created.
+    pub textures_created: usize,
+    /// The number of geometries drawn by the command lists.
+    pub draws: usize,
+}
+
+/// The first texture id reserved for external textures, see
+/// [`GliumGpuDriverReceiver::register_external_texture`].
+///
+/// The ids allocated for Ultralight by [`GliumGpuDriverSender`] are always
+/// below this value.
+pub const EXTERNAL_TEXTURE_ID_START: u32 = 0x8000_0000;
+
+/// Whether `id` is in the range of external texture ids.
+fn is_external_texture_id(id: u32) -> bool {
+    id >= EXTERNAL_TEXTURE_ID_START
+}
+
+/// Allocator of external texture ids, shared by the sender and the
+/// receiver, so that ids can still be reserved after the sender is given to
+/// [`platform::set_gpu_driver`](crate::platform::set_gpu_driver).
+#[derive(Clone, Default)]
+struct ExternalTextureIds {
+    next: Arc<AtomicU32>,
+}
+
+impl ExternalTextureIds {
+    fn reserve(&self) -> u32 {
+        let offset = self.next.fetch_add(1, Ordering::Relaxed);
+        assert!(
+            offset < u32::MAX - EXTERNAL_TEXTURE_ID_START,
+            "External texture ids exhausted"
+        );
+        EXTERNAL_TEXTURE_ID_START + offset
+    }
+}
+
 /// Creates a GPU driver for `glium`.
 ///
 /// `glium` context must run in one thread, but the `gpu_driver` require `Send`,
@@ -188,14 +289,16 @@ where
     F: Facade + ?Sized,
 {
     let (sender, receiver) = mpsc::channel();
+    let external_texture_ids = ExternalTextureIds::default();
     Ok((
         GliumGpuDriverSender {
             next_texture_id: 0,
             next_render_buffer_id: 0,
             next_geometry_id: 0,
+            external_texture_ids: external_texture_ids.clone(),
             sender,
         },
-        GliumGpuDriverReceiver::new(receiver, facade.get_context())?,
+        GliumGpuDriverReceiver::new(receiver, facade.get_context(), external_texture_ids)?,
     ))
 }
 
@@ -261,9 +364,35 @@ pub struct GliumGpuDriverSender {
     next_texture_id: u32,
     next_render_buffer_id: u32,
     next_geometry_id: u32,
+    external_texture_ids: ExternalTextureIds,
     sender: mpsc::Sender<GliumGpuCommand>,
 }
 
+impl GliumGpuDriverSender {
+    /// Reserve a texture id for an external texture, see
+    /// [`GliumGpuDriverReceiver::register_external_texture`].
+    ///
+    /// The id is never used by Ultralight's own textures. The same
+    /// allocator is available with
+    /// [`GliumGpuDriverReceiver::reserve_external_texture_id`], once the
+    /// sender is given to the platform.
+    ///
+    /// # Panics
+    /// If all the external texture ids (`2^31`) were reserved.
+    pub fn reserve_external_texture_id(&self) -> u32 {
+        self.external_texture_ids.reserve()
+    }
+
+    /// Send `command` to the receiver.
+    ///
+    /// If the receiver was dropped (eg. the window was closed while a
+    /// [`Renderer`](crate::renderer::Renderer) is still alive), there is
+    /// nothing to render to, so the command is dropped.
+    fn send(&self, command: GliumGpuCommand) {
+        let _ = self.sender.send(command);
+    }
+}
+
 impl GpuDriver for GliumGpuDriverSender {
     fn begin_synchronize(&mut self) {
         // unhandled
@@ -275,25 +404,23 @@ impl GpuDriver for GliumGpuDriverSender {
 
     fn next_texture_id(&mut self) -> u32 {
         self.next_texture_id += 1;
+        assert!(
+            !is_external_texture_id(self.next_texture_id),
+            "Texture ids exhausted"
+        );
         self.next_texture_id
     }
 
     fn create_texture(&mut self, texture_id: u32, bitmap: OwnedBitmap) {
-        self.sender
-            .send(GliumGpuCommand::CreateTexture(texture_id, bitmap))
-            .unwrap();
+        self.send(GliumGpuCommand::CreateTexture(texture_id, bitmap));
     }
 
     fn update_texture(&mut self, texture_id: u32, bitmap: OwnedBitmap) {
-        self.sender
-            .send(GliumGpuCommand::UpdateTexture(texture_id, bitmap))
-            .unwrap();
+        self.send(GliumGpuCommand::UpdateTexture(texture_id, bitmap));
     }
 
     fn destroy_texture(&mut self, texture_id: u32) {
-        self.sender
-            .send(GliumGpuCommand::DestroyTexture(texture_id))
-            .unwrap();
+        self.send(GliumGpuCommand::DestroyTexture(texture_id));
     }
 
     fn next_render_buffer_id(&mut self) -> u32 {
@@ -302,18 +429,14 @@ impl GpuDriver for GliumGpuDriverSender {
     }
 
     fn create_render_buffer(&mut self, render_buffer_id: u32, render_buffer: RenderBuffer) {
-        self.sender
-            .send(GliumGpuCommand::CreateRenderBuffer(
-                render_buffer_id,
-                render_buffer,
-            ))
-            .unwrap();
+        self.send(GliumGpuCommand::CreateRenderBuffer(
+            render_buffer_id,
+            render_buffer,
+        ));
     }
 
     fn destroy_render_buffer(&mut self, render_buffer_id: u32) {
-        self.sender
-            .send(GliumGpuCommand::DestroyRenderBuffer(render_buffer_id))
-            .unwrap();
+        self.send(GliumGpuCommand::DestroyRenderBuffer(render_buffer_id));
     }
 
     fn next_geometry_id(&mut self) -> u32 {
@@ -360,13 +483,11 @@ impl GpuDriver for GliumGpuDriverSender {
             }
         };
 
-        self.sender
-            .send(GliumGpuCommand::CreateGeometry(
-                geometry_id,
-                glium_vertex_buffer,
-                index_buffer,
-            ))
-            .unwrap();
+        self.send(GliumGpuCommand::CreateGeometry(
+            geometry_id,
+            glium_vertex_buffer,
+            index_buffer,
+        ));
     }
 
     fn update_geometry(
@@ -408,25 +529,19 @@ impl GpuDriver for GliumGpuDriverSender {
             }
         };
 
-        self.sender
-            .send(GliumGpuCommand::UpdateGeometry(
-                geometry_id,
-                glium_vertex_buffer,
-                index_buffer,
-            ))
-            .unwrap();
+        self.send(GliumGpuCommand::UpdateGeometry(
+            geometry_id,
+            glium_vertex_buffer,
+            index_buffer,
+        ));
     }
 
     fn destroy_geometry(&mut self, geometry_id: u32) {
-        self.sender
-            .send(GliumGpuCommand::DestroyGeometry(geometry_id))
-            .unwrap();
+        self.send(GliumGpuCommand::DestroyGeometry(geometry_id));
     }
 
     fn update_command_list(&mut self, command_list: Vec<GpuCommand>) {
-        self.sender
-            .send(GliumGpuCommand::UpdateCommandList(command_list))
-            .unwrap();
+        self.send(GliumGpuCommand::UpdateCommandList(command_list));
     }
 }
 
@@ -458,7 +573,11 @@ pub struct GliumGpuDriverReceiver {
     /// the gpu driver doesn't set a texture for a draw call
     empty_texture: EitherTexture,
     /// map for (id -> texture), and storing the `render_buffer` id if applicable.
+    /// External textures are stored here as well, with ids starting at
+    /// [`EXTERNAL_TEXTURE_ID_START`].
     texture_map: HashMap<u32, (EitherTexture, Option<u32>)>,
+    /// allocator of the external texture ids, shared with the sender.
+    external_texture_ids: ExternalTextureIds,
     /// map for (id -> render_buffer metadata), the render_buffer itself is a texture
     /// stored in the `texture_map`, we only create a framebuffer when drawing.
     render_buffer_map: HashMap<u32, RenderBuffer>,
@@ -469,12 +588,28 @@ pub struct GliumGpuDriverReceiver {
     path_program: Program,
     /// Shader program for fill rendering commands.
     fill_program: Program,
+    /// Shader program for compositing textures into the final target.
+    output_program: Program,
+    /// Transformation used in `output_program`.
+    output_transform: OutputTransform,
+    /// The time [`GliumGpuDriverReceiver::render`] measures the shader time from.
+    start_time: Instant,
+    /// The command left by [`GliumGpuDriverReceiver::render_with_budget`]
+    /// when the budget was exhausted.
+    pending_command: Option<GliumGpuCommand>,
+    /// Render buffer textures destroyed since the commands were last fully
+    /// processed, by (width, height), see [`GliumGpuDriverReceiver::resize_render_buffer`].
+    texture_pool: HashMap<(u32, u32), Vec<Texture2d>>,
+    /// ids of the textures created for render buffers (from empty bitmaps),
+    /// the only ones that can be put in `texture_pool`.
+    render_buffer_textures: HashSet<u32>,
 }
 
 impl GliumGpuDriverReceiver {
     fn new(
         receiver: mpsc::Receiver<GliumGpuCommand>,
         context: &Rc<Context>,
+        external_texture_ids: ExternalTextureIds,
     ) -> Result<Self, GliumGpuDriverError> {
         let context = GluimContextWrapper {
             context: context.clone(),
@@ -495,88 +630,256 @@ impl GliumGpuDriverReceiver {
             vertex: include_str!("./shaders/v2f_c4f_t2f_t2f_d28f_vert.glsl"),
             fragment: include_str!("./shaders/fill_frag.glsl")
         })?;
+        let output_program = Program::new(
+            &context,
+            ProgramCreationInput::SourceCode {
+                vertex_shader: include_str!("./shaders/output_vert.glsl"),
+                tessellation_control_shader: None,
+                tessellation_evaluation_shader: None,
+                geometry_shader: None,
+                fragment_shader: include_str!("./shaders/output_frag.glsl"),
+                transform_feedback_varyings: None,
+                // we handle the output encoding ourselves
+                outputs_srgb: true,
+                uses_point_size: false,
+            },
+        )
+        .map_err(ProgramChooserCreationError::ProgramCreationError)?;
 
         Ok(GliumGpuDriverReceiver {
             receiver,
             context,
             empty_texture,
             texture_map,
+            external_texture_ids,
             render_buffer_map,
             geometry_map,
 
             path_program,
             fill_program,
+            output_program,
+            output_transform: OutputTransform::default(),
+            start_time: Instant::now(),
+            pending_command: None,
+            texture_pool: HashMap::new(),
+            render_buffer_textures: HashSet::new(),
         })
     }
 
     /// helper function to create a texture based on bitmap
     fn create_texture(&self, bitmap: &OwnedBitmap) -> Result<EitherTexture, GliumGpuDriverError> {
         if bitmap.is_empty() {
-            Texture2d::empty(&self.context, bitmap.width(), bitmap.height())
+            return Texture2d::empty(&self.context, bitmap.width(), bitmap.height())
                 .map_err(|e| e.into())
-                .map(EitherTexture::Regular2d)
-        } else {
-            // since its not empty, it should have a valid pixels.
-            let bitmap_pixels = bitmap.pixels().unwrap();
-
-            match bitmap.format() {
-                BitmapFormat::A8Unorm => {
-                    let img = RawImage2d {
-                        data: Cow::Borrowed(bitmap_pixels),
-                        width: bitmap.width(),
-                        height: bitmap.height(),
-                        format: ClientFormat::U8,
-                    };
-
-                    Texture2d::with_format(
-                        &self.context,
-                        img,
-                        UncompressedFloatFormat::U8,
-                        MipmapsOption::NoMipmap,
-                    )
-                    .map_err(|e| e.into())
-                    .map(EitherTexture::Regular2d)
-                }
-                BitmapFormat::Bgra8UnormSrgb => {
-                    // FIXME: the number of pixels sometimes may not be `width * height * 4`
-                    // because the bitmap will have padding for each row.
-                    // Normally, this is fixable by using `UNPACK_ROW_LENGTH` in OpenGL,
-                    // but glium doesn't support it for now
-
-                    let expected_row_bytes = bitmap.width() * 4;
-                    let data = if bitmap.row_bytes() != expected_row_bytes {
-                        let mut new_data = Vec::with_capacity(
-                            bitmap.height() as usize * expected_row_bytes as usize,
-                        );
-                        for row in bitmap_pixels.chunks(bitmap.row_bytes() as usize) {
-                            new_data.extend_from_slice(&row[..expected_row_bytes as usize]);
-                        }
-                        Cow::Owned(new_data)
-                    } else {
-                        Cow::Borrowed(bitmap_pixels)
-                    };
-
-                    let img = RawImage2d {
-                        data,
-                        width: bitmap.width(),
-                        height: bitmap.height(),
-                        format: ClientFormat::U8U8U8U8,
-                    };
-
-                    SrgbTexture2d::with_format(
-                        &self.context,
-                        img,
-                        glium::texture::SrgbFormat::U8U8U8U8,
-                        MipmapsOption::NoMipmap,
-                    )
-                    .map_err(|e| e.into())
-                    .map(EitherTexture::Srgb2d)
+                .map(EitherTexture::Regular2d);
+        }
+
+        let img = bitmap_image(bitmap);
+        match bitmap.format() {
+            BitmapFormat::A8Unorm => Texture2d::with_format(
+                &self.context,
+                img,
+                UncompressedFloatFormat::U8,
+                MipmapsOption::NoMipmap,
+            )
+            .map_err(|e| e.into())
+            .map(EitherTexture::Regular2d),
+            BitmapFormat::Bgra8UnormSrgb => SrgbTexture2d::with_format(
+                &self.context,
+                img,
+                glium::texture::SrgbFormat::U8U8U8U8,
+                MipmapsOption::NoMipmap,
+            )
+            .map_err(|e| e.into())
+            .map(EitherTexture::Srgb2d),
+        }
+    }
+
+    /// When a view is resized, Ultralight destroys its render buffer texture
+    /// and creates a new one, this takes a render buffer texture of the same
+    /// size destroyed since the commands were last fully processed (in
+    /// `texture_pool`), so that the allocation is reused, and the new render
+    /// buffer starts with the previous content instead of a blank frame.
+    ///
+    /// Returns [`None`] if `bitmap` is not a render buffer texture, or no
+    /// destroyed texture has the same size.
+    fn resize_render_buffer(&mut self, bitmap: &OwnedBitmap) -> Option<EitherTexture> {
+        if !bitmap.is_empty() {
+            return None;
+        }
+
+        let pool = self
+            .texture_pool
+            .get_mut(&(bitmap.width(), bitmap.height()))?;
+        let texture = pool.pop()?;
+        if pool.is_empty() {
+            self.texture_pool.remove(&(bitmap.width(), bitmap.height()));
+        }
+        Some(EitherTexture::Regular2d(texture))
+    }
+
+    /// helper function to upload a bitmap into an existing texture, this is
+    /// only possible if the size and format of the texture matches the bitmap.
+    ///
+    /// Returns `false` if the texture must be recreated.
+    fn update_texture(texture: &EitherTexture, bitmap: &OwnedBitmap) -> bool {
+        if texture.width() != bitmap.width() || texture.height() != bitmap.height() {
+            return false;
+        }
+        // nothing to upload, the content of an empty bitmap is undefined anyway
+        if bitmap.is_empty() {
+            return true;
+        }
+
+        let rect = glium::Rect {
+            left: 0,
+            bottom: 0,
+            width: bitmap.width(),
+            height: bitmap.height(),
+        };
+        match (bitmap.format(), texture) {
+            (BitmapFormat::A8Unorm, EitherTexture::Regular2d(t)) => {
+                t.write(rect, bitmap_image(bitmap));
+                true
+            }
+            (BitmapFormat::Bgra8UnormSrgb, EitherTexture::Srgb2d(t)) => {
+                t.write(rect, bitmap_image(bitmap));
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The scissor rect of `gpu_state` clipped to the viewport, an empty scissor
+/// (nothing is drawn) if it is outside the viewport.
+///
+/// The projection used in `render` maps `y = 0` to the bottom of the render
+/// buffer (they are stored upside down), so the `top` of the scissor rect is
+/// the `bottom` of the `glium` rect.
+fn scissor_rect(gpu_state: &GpuState) -> glium::Rect {
+    let viewport = Rect {
+        left: 0,
+        top: 0,
+        right: gpu_state.viewport_width as i32,
+        bottom: gpu_state.viewport_height as i32,
+    };
+
+    match gpu_state.scissor_rect.intersect(&viewport) {
+        Some(rect) => glium::Rect {
+            left: rect.left as u32,
+            bottom: rect.top as u32,
+            width: rect.width() as u32,
+            height: rect.height() as u32,
+        },
+        None => glium::Rect {
+            left: 0,
+            bottom: 0,
+            width: 0,
+            height: 0,
+        },
+    }
+}
+
+/// helper function to get the pixels of a non-empty bitmap as an image
+/// that can be uploaded to a texture.
+fn bitmap_image(bitmap: &OwnedBitmap) -> RawImage2d<'_, u8> {
+    // since its not empty, it should have a valid pixels.
+    let bitmap_pixels = bitmap.pixels().unwrap();
+
+    match bitmap.format() {
+        BitmapFormat::A8Unorm => RawImage2d {
+            data: Cow::Borrowed(bitmap_pixels),
+            width: bitmap.width(),
+            height: bitmap.height(),
+            format: ClientFormat::U8,
+        },
+        BitmapFormat::Bgra8UnormSrgb => {
+            // FIXME: the number of pixels sometimes may not be `width * height * 4`
+            // because the bitmap will have padding for each row.
+            // Normally, this is fixable by using `UNPACK_ROW_LENGTH` in OpenGL,
+            // but glium doesn't support it for now
+
+            let expected_row_bytes = bitmap.width() * 4;
+            let data = if bitmap.row_bytes() != expected_row_bytes {
+                let mut new_data =
+                    Vec::with_capacity(bitmap.height() as usize * expected_row_bytes as usize);
+                for row in bitmap_pixels.chunks(bitmap.row_bytes() as usize) {
+                    new_data.extend_from_slice(&row[..expected_row_bytes as usize]);
                 }
+                Cow::Owned(new_data)
+            } else {
+                Cow::Borrowed(bitmap_pixels)
+            };
+
+            RawImage2d {
+                data,
+                width: bitmap.width(),
+                height: bitmap.height(),
+                format: ClientFormat::U8U8U8U8,
             }
         }
     }
 }
 
+/// Upload the pixels of `surface` updated since the last upload (see
+/// [`Surface::damage_bounds`](crate::surface::Surface::damage_bounds)) to
+/// `texture`, and clear the dirty bounds of the surface.
+///
+/// This is meant for views rendered on the CPU, where copying the whole
+/// surface every frame is wasteful when only a small part changes (eg. a
+/// blinking caret). The texture must have the same size as the surface,
+/// the pixels are uploaded as is (`BGRA`).
+///
+/// Returns `true` if anything was uploaded, nothing is uploaded (and the
+/// dirty bounds are kept) if the sizes of the texture and the surface
+/// differ.
+pub fn upload_surface_damage(surface: &mut crate::surface::Surface, texture: &Texture2d) -> bool {
+    let Some(damage) = surface.damage_bounds() else {
+        return false;
+    };
+    if texture.width() != surface.width() || texture.height() != surface.height() {
+        return false;
+    }
+
+    let width = damage.right - damage.left;
+    let height = damage.bottom - damage.top;
+    let row_len = width as usize * 4;
+
+    let data = {
+        let Some(pixels) = surface.lock_pixels() else {
+            return false;
+        };
+        let row_bytes = pixels.row_bytes() as usize;
+
+        // copy only the damaged rows/columns
+        let mut data = Vec::with_capacity(row_len * height as usize);
+        for y in damage.top..damage.bottom {
+            let start = y as usize * row_bytes + damage.left as usize * 4;
+            data.extend_from_slice(&pixels[start..start + row_len]);
+        }
+        data
+    };
+
+    texture.write(
+        glium::Rect {
+            left: damage.left,
+            bottom: damage.top,
+            width,
+            height,
+        },
+        RawImage2d {
+            data: Cow::Owned(data),
+            width,
+            height,
+            format: ClientFormat::U8U8U8U8,
+        },
+    );
+    surface.clear_dirty_bounds();
+
+    true
+}
+
 impl GliumGpuDriverReceiver {
     /// Fetch `glium` texture by id, this id can be obtained from the current
     /// `render_target` of a `view` by [`View::render_target`](crate::view::View::render_target).
@@ -590,247 +893,476 @@ impl GliumGpuDriverReceiver {
         self.texture_map.get(id).map(|(t, _)| t)
     }
 
+    /// Fetch the `glium` texture backing a render buffer, this id can be obtained
+    /// from the current `render_target` of a `view` by [`View::render_target`](crate::view::View::render_target).
+    ///
+    /// Example:
+    /// ```no_run
+    /// # fn example(receiver: &ul_next::gpu_driver::glium::GliumGpuDriverReceiver, view: &ul_next::view::View) {
+    /// let render_target = view.render_target().unwrap();
+    /// let texture = receiver.get_render_buffer_texture(render_target.render_buffer_id);
+    /// # }
+    /// ```
+    pub fn get_render_buffer_texture(&self, render_buffer_id: u32) -> Option<&EitherTexture> {
+        let render_buffer = self.render_buffer_map.get(&render_buffer_id)?;
+        self.get_texture(&render_buffer.texture_id)
+    }
+
+    /// Reserve a texture id for an external texture, the same as
+    /// [`GliumGpuDriverSender::reserve_external_texture_id`].
+    ///
+    /// # Panics
+    /// If all the external texture ids (`2^31`) were reserved.
+    pub fn reserve_external_texture_id(&self) -> u32 {
+        self.external_texture_ids.reserve()
+    }
+
+    /// Register a texture owned by the application under `id`, so that it
+    /// can be displayed in a page with an
+    /// [`ImageSource::create_from_texture`](crate::image_source::ImageSource::create_from_texture)
+    /// using the same `id`, without copying it to the CPU.
+    ///
+    /// `id` must be reserved with
+    /// [`GliumGpuDriverSender::reserve_external_texture_id`] (or
+    /// [`GliumGpuDriverReceiver::reserve_external_texture_id`]), so that it
+    /// doesn't collide with the textures of Ultralight.
+    ///
+    /// External textures are never destroyed by the driver, they are kept
+    /// until they are unregistered with
+    /// [`GliumGpuDriverReceiver::unregister_external_texture`] or the
+    /// receiver is dropped. Registering another texture with the same `id`
+    /// replaces it, and returns the previous one.
+    ///
+    /// The content of the texture can be changed at any time (eg. by drawing
+    /// into it), call [`ImageSource::invalidate`](crate::image_source::ImageSource::invalidate)
+    /// afterwards so that the page is redrawn.
+    ///
+    /// While an external texture is not registered, the pages using it
+    /// sample an empty texture instead.
+    pub fn register_external_texture(
+        &mut self,
+        id: u32,
+        texture: EitherTexture,
+    ) -> Result<Option<EitherTexture>, GliumGpuDriverError> {
+        if !is_external_texture_id(id) {
+            return Err(GliumGpuDriverError::NotExternalTextureId(id));
+        }
+        Ok(self.texture_map.insert(id, (texture, None)).map(|(t, _)| t))
+    }
+
+    /// Remove an external texture registered with
+    /// [`GliumGpuDriverReceiver::register_external_texture`], and give it
+    /// back.
+    ///
+    /// Returns [`None`] if no external texture is registered under `id`.
+    pub fn unregister_external_texture(&mut self, id: u32) -> Option<EitherTexture> {
+        if !is_external_texture_id(id) {
+            return None;
+        }
+        self.texture_map.remove(&id).map(|(t, _)| t)
+    }
+
+    /// The texture to sample for the texture `id` of a draw call, the empty
+    /// texture is used if there is no texture, or for an external texture
+    /// that is not registered (yet).
+    fn draw_call_texture(&self, id: Option<u32>) -> &EitherTexture {
+        let Some(id) = id else {
+            return &self.empty_texture;
+        };
+        match self.texture_map.get(&id) {
+            Some((t, _)) => t,
+            None if is_external_texture_id(id) => &self.empty_texture,
+            None => panic!("Texture with id {} does not exist", id),
+        }
+    }
+
+    /// Set the transformation used by [`GliumGpuDriverReceiver::draw_texture`].
+    pub fn set_output_transform(&mut self, output_transform: OutputTransform) {
+        self.output_transform = output_transform;
+    }
+
+    /// Get the transformation used by [`GliumGpuDriverReceiver::draw_texture`].
+    pub fn output_transform(&self) -> OutputTransform {
+        self.output_transform
+    }
+
+    /// Draw the texture with `id` to cover the whole `target`, applying the
+    /// current [`OutputTransform`].
+    ///
+    /// The output is written as is, without any additional sRGB conversion
+    /// from `glium`, so the `target_gamma` should match what the target expects.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use ul_next::gpu_driver::glium::{GliumGpuDriverReceiver, OutputTransform, TransferFn};
+    /// # use glium::Surface;
+    /// # fn example(
+    /// #     receiver: &mut GliumGpuDriverReceiver,
+    /// #     display: &glium::Display<glium::glutin::surface::WindowSurface>,
+    /// #     view: &ul_next::view::View,
+    /// # ) {
+    /// receiver.set_output_transform(OutputTransform {
+    ///     dithering: true,
+    ///     target_gamma: TransferFn::Srgb,
+    /// });
+    ///
+    /// let render_target = view.render_target().unwrap();
+    /// let mut target = display.draw();
+    /// receiver.draw_texture(&render_target.texture_id, &mut target).unwrap();
+    /// target.finish().unwrap();
+    /// # }
+    /// ```
+    pub fn draw_texture<S: Surface>(
+        &self,
+        id: &u32,
+        target: &mut S,
+    ) -> Result<(), GliumGpuDriverError> {
+        let texture = self
+            .get_texture(id)
+            .ok_or(GliumGpuDriverError::TextureNotFound(*id))?;
+
+        let (transfer_fn, gamma) = match self.output_transform.target_gamma {
+            TransferFn::Srgb => (0, 1.0),
+            TransferFn::Linear => (1, 1.0),
+            TransferFn::Gamma(gamma) => (2, gamma),
+        };
+
+        let uniforms = uniform! {
+            Texture: texture.sampled(),
+            SourceIsLinear: matches!(texture, EitherTexture::Srgb2d(_)),
+            TransferFn: transfer_fn,
+            Gamma: gamma,
+            Dithering: self.output_transform.dithering,
+            DitherAmplitude: 1.0f32 / 255.0,
+        };
+
+        target.draw(
+            EmptyVertexAttributes { len: 4 },
+            NoIndices(PrimitiveType::TriangleStrip),
+            &self.output_program,
+            &uniforms,
+            &DrawParameters::default(),
+        )?;
+
+        Ok(())
+    }
+
     /// Flushes and renders all pending GPU commands recieved from [`GliumGpuDriverSender`],
     /// which will be generated when calling [`Renderer::render`](crate::renderer::Renderer::render).
     ///
     /// **Note that this must be called for rendering to actually occure, as using**
     /// **[`platform::set_gpu_driver`](crate::platform::set_gpu_driver) alone**
     /// **with [`GliumGpuDriverSender`] is not enough.**
+    ///
+    /// The time given to the shaders (used by some animated effects) is the
+    /// time in seconds since this receiver was created, use
+    /// [`GliumGpuDriverReceiver::render_with_time`] to set it manually.
     pub fn render(&mut self) -> Result<(), GliumGpuDriverError> {
-        while let Ok(cmd) = self.receiver.try_recv() {
-            match cmd {
-                GliumGpuCommand::CreateTexture(id, bitmap) => {
-                    let t = self.create_texture(&bitmap)?;
-                    self.texture_map.insert(id, (t, None));
+        self.render_with_time(self.start_time.elapsed().as_secs_f64())
+    }
+
+    /// Same as [`GliumGpuDriverReceiver::render`], but with `time` (in seconds)
+    /// as the time given to the shaders.
+    ///
+    /// This is useful to get deterministic results, for example when
+    /// comparing screenshots in tests, by always passing the same time.
+    pub fn render_with_time(&mut self, time: f64) -> Result<(), GliumGpuDriverError> {
+        self.process_commands(time, None).map(|_| ())
+    }
+
+    /// Same as [`GliumGpuDriverReceiver::render`], but stops once
+    /// `max_millis` milliseconds were spent, leaving the remaining commands
+    /// queued for the next call.
+    ///
+    /// This spreads the work of a burst of commands (eg. the textures and
+    /// geometries created when a heavy page is loaded) over several frames,
+    /// instead of a single long one. The commands are executed in order,
+    /// and the command list of a [`Renderer::render`](crate::renderer::Renderer::render)
+    /// is never split, so the last command executed can exceed the budget.
+    /// At least one command is executed per call.
+    ///
+    /// While [`RenderProgress::completed`] is `false`, the render targets of
+    /// the views may be out of date, or not created yet
+    /// ([`GliumGpuDriverReceiver::get_texture`] returns [`None`]), so the
+    /// previous frame should be presented again.
+    pub fn render_with_budget(
+        &mut self,
+        max_millis: f32,
+    ) -> Result<RenderProgress, GliumGpuDriverError> {
+        // negative and NaN budgets execute a single command
+        let budget =
+            Duration::try_from_secs_f32(max_millis / 1000.0).unwrap_or(if max_millis > 0.0 {
+                Duration::MAX
+            } else {
+                Duration::ZERO
+            });
+        self.process_commands(self.start_time.elapsed().as_secs_f64(), Some(budget))
+    }
+
+    /// Execute the queued commands, until there are no more or `budget`
+    /// is exhausted.
+    fn process_commands(
+        &mut self,
+        time: f64,
+        budget: Option<Duration>,
+    ) -> Result<RenderProgress, GliumGpuDriverError> {
+        let start = Instant::now();
+        let mut progress = RenderProgress::default();
+
+        // the command left by the last call comes first
+        while let Some(cmd) = self
+            .pending_command
+            .take()
+            .or_else(|| self.receiver.try_recv().ok())
+        {
+            let exhausted = budget.is_some_and(|budget| start.elapsed() >= budget);
+            if exhausted && progress.commands_executed > 0 {
+                self.pending_command = Some(cmd);
+                return Ok(progress);
+            }
+
+            self.execute_command(cmd, time, &mut progress)?;
+            progress.commands_executed += 1;
+        }
+
+        // textures that were not reused by the processed commands are freed
+        self.texture_pool.clear();
+
+        progress.completed = true;
+        Ok(progress)
+    }
+
+    fn execute_command(
+        &mut self,
+        cmd: GliumGpuCommand,
+        time: f64,
+        progress: &mut RenderProgress,
+    ) -> Result<(), GliumGpuDriverError> {
+        match cmd {
+            GliumGpuCommand::CreateTexture(id, bitmap) => {
+                let t = match self.resize_render_buffer(&bitmap) {
+                    Some(t) => t,
+                    None => self.create_texture(&bitmap)?,
+                };
+                if bitmap.is_empty() {
+                    self.render_buffer_textures.insert(id);
                 }
-                GliumGpuCommand::UpdateTexture(id, bitmap) => {
-                    assert!(self.texture_map.contains_key(&id));
+                self.texture_map.insert(id, (t, None));
+                progress.textures_created += 1;
+            }
+            GliumGpuCommand::UpdateTexture(id, bitmap) => {
+                let entry = self.texture_map.get(&id).unwrap();
 
+                // reuse the texture if possible, to avoid reallocating
+                // glyph atlases and images on every update
+                if !Self::update_texture(&entry.0, &bitmap) {
                     let t = self.create_texture(&bitmap)?;
 
                     let entry = self.texture_map.get_mut(&id).unwrap();
                     entry.0 = t;
                 }
-                GliumGpuCommand::DestroyTexture(id) => {
-                    assert!(self.texture_map.contains_key(&id));
-                    self.texture_map.remove(&id);
-                }
-                GliumGpuCommand::CreateRenderBuffer(id, render_buffer) => {
-                    let entry = self.texture_map.get_mut(&render_buffer.texture_id).unwrap();
-                    entry.1 = Some(id);
-                    // make sure same texture sizes
-                    assert!(entry.0.width() == render_buffer.width);
-                    assert!(entry.0.height() == render_buffer.height);
-
-                    self.render_buffer_map.insert(id, render_buffer);
-                }
-                GliumGpuCommand::DestroyRenderBuffer(id) => {
-                    assert!(self.render_buffer_map.contains_key(&id));
-                    let render_buffer = self.render_buffer_map.remove(&id).unwrap();
-                    if let Some(entry) = self.texture_map.get_mut(&render_buffer.texture_id) {
-                        entry.1 = None;
-                    }
+            }
+            // external textures are owned by the application
+            GliumGpuCommand::DestroyTexture(id) if is_external_texture_id(id) => {}
+            GliumGpuCommand::DestroyTexture(id) => {
+                assert!(self.texture_map.contains_key(&id));
+                let (t, render_buffer) = self.texture_map.remove(&id).unwrap();
+                // only render buffer textures can be reused, and only once
+                // their render buffer is destroyed
+                let is_render_buffer_texture = self.render_buffer_textures.remove(&id);
+                if let (EitherTexture::Regular2d(t), None, true) =
+                    (t, render_buffer, is_render_buffer_texture)
+                {
+                    self.texture_pool
+                        .entry((t.width(), t.height()))
+                        .or_default()
+                        .push(t);
                 }
-                GliumGpuCommand::CreateGeometry(id, vert, index) => {
-                    let index_buffer = glium::IndexBuffer::new(
-                        &self.context,
-                        glium::index::PrimitiveType::TrianglesList,
-                        &index.buffer,
-                    )?;
-
-                    self.geometry_map.insert(
-                        id,
-                        (vert.into_glium_vertex_buffer(&self.context)?, index_buffer),
-                    );
+            }
+            GliumGpuCommand::CreateRenderBuffer(id, render_buffer) => {
+                let entry = self.texture_map.get_mut(&render_buffer.texture_id).unwrap();
+                entry.1 = Some(id);
+                // make sure same texture sizes
+                assert!(entry.0.width() == render_buffer.width);
+                assert!(entry.0.height() == render_buffer.height);
+
+                self.render_buffer_map.insert(id, render_buffer);
+            }
+            GliumGpuCommand::DestroyRenderBuffer(id) => {
+                assert!(self.render_buffer_map.contains_key(&id));
+                let render_buffer = self.render_buffer_map.remove(&id).unwrap();
+                if let Some(entry) = self.texture_map.get_mut(&render_buffer.texture_id) {
+                    entry.1 = None;
                 }
-                GliumGpuCommand::UpdateGeometry(id, vert, index) => {
-                    assert!(self.geometry_map.contains_key(&id));
+            }
+            GliumGpuCommand::CreateGeometry(id, vert, index) => {
+                let index_buffer = glium::IndexBuffer::new(
+                    &self.context,
+                    glium::index::PrimitiveType::TrianglesList,
+                    &index.buffer,
+                )?;
+
+                self.geometry_map.insert(
+                    id,
+                    (vert.into_glium_vertex_buffer(&self.context)?, index_buffer),
+                );
+            }
+            GliumGpuCommand::UpdateGeometry(id, vert, index) => {
+                assert!(self.geometry_map.contains_key(&id));
 
-                    let index_buffer = glium::IndexBuffer::new(
-                        &self.context,
-                        glium::index::PrimitiveType::TrianglesList,
-                        &index.buffer,
-                    )?;
+                let index_buffer = glium::IndexBuffer::new(
+                    &self.context,
+                    glium::index::PrimitiveType::TrianglesList,
+                    &index.buffer,
+                )?;
 
-                    *self.geometry_map.get_mut(&id).unwrap() =
-                        (vert.into_glium_vertex_buffer(&self.context)?, index_buffer);
-                }
-                GliumGpuCommand::DestroyGeometry(id) => {
-                    assert!(self.geometry_map.contains_key(&id));
-                    self.geometry_map.remove(&id);
-                }
-                GliumGpuCommand::UpdateCommandList(cmd_list) => {
-                    for cmd in cmd_list {
-                        match cmd {
-                            GpuCommand::ClearRenderBuffer {
-                                render_buffer_id: id,
-                            } => {
-                                assert!(self.render_buffer_map.contains_key(&id));
-                                let render_buffer = self.render_buffer_map.get(&id).unwrap();
-
-                                // TODO: add support
-                                assert!(!render_buffer.has_stencil_buffer);
-                                assert!(!render_buffer.has_depth_buffer);
-
-                                let t = self.texture_map.get(&render_buffer.texture_id).unwrap();
-
-                                let mut frame_buffer = SimpleFrameBuffer::new(&self.context, &t.0)?;
-
-                                frame_buffer.clear(
-                                    None,
-                                    Some((0.0, 0.0, 0.0, 0.0)),
-                                    false,
-                                    None,
-                                    None,
-                                );
-                            }
-                            GpuCommand::DrawGeometry {
-                                gpu_state,
-                                geometry_id,
-                                indices_count,
-                                indices_offset,
-                            } => {
-                                assert!(self.geometry_map.contains_key(&geometry_id));
-                                let (vertex_buffer, index_buffer) =
-                                    self.geometry_map.get(&geometry_id).unwrap();
-
-                                assert!(self
-                                    .render_buffer_map
-                                    .contains_key(&gpu_state.render_buffer_id));
-                                let render_buffer = self
-                                    .render_buffer_map
-                                    .get(&gpu_state.render_buffer_id)
-                                    .unwrap();
-
-                                // TODO: add support
-                                assert!(!render_buffer.has_stencil_buffer);
-                                assert!(!render_buffer.has_depth_buffer);
-
-                                let index_buffer_slice = index_buffer
-                                    .slice(
-                                        indices_offset as usize
-                                            ..(indices_offset as usize + indices_count as usize),
-                                    )
-                                    .ok_or(GliumGpuDriverError::DrawIndexOutOfRange {
-                                        index_buffer_size: index_buffer.len(),
-                                        draw_index_offset: indices_offset,
-                                        draw_index_size: indices_count,
-                                    })?;
-
-                                let (t, _) =
-                                    self.texture_map.get(&render_buffer.texture_id).unwrap();
-
-                                let mut frame_buffer = SimpleFrameBuffer::new(&self.context, t)?;
-
-                                let used_program = match gpu_state.shader_type {
-                                    ShaderType::Fill => &self.fill_program,
-                                    ShaderType::FillPath => &self.path_program,
-                                };
-
-                                let scalar_data =
-                                    UniformBuffer::new(&self.context, gpu_state.uniform_scalar)?;
-                                let vector_data =
-                                    UniformBuffer::new(&self.context, gpu_state.uniform_vector)?;
-                                let clip_data = UniformBuffer::new(&self.context, gpu_state.clip)?;
-
-                                // Orthographic Projection matrix applied to
-                                // the `transformation` matrix.
-                                let orth_projection_matrix = [
-                                    [2.0 / gpu_state.viewport_width as f32, 0.0, 0.0, 0.0],
-                                    [0.0, 2.0 / gpu_state.viewport_height as f32, 0.0, 0.0],
-                                    [0.0, 0.0, -0.000002, 0.0],
-                                    [-1.0, -1.0, 0.818183, 1.0],
-                                ];
-                                // trasform matrix to project matrix
-                                let mut transformation = [
-                                    [0., 0., 0., 0.],
-                                    [0., 0., 0., 0.],
-                                    [0., 0., 0., 0.],
-                                    [0., 0., 0., 0.],
-                                ];
-
-                                // multiply matrices
-                                #[allow(clippy::needless_range_loop)]
-                                for i in 0..4 {
-                                    for j in 0..4 {
-                                        for k in 0..4 {
-                                            transformation[i][j] += gpu_state.transform[i * 4 + k]
-                                                * orth_projection_matrix[k][j];
-                                        }
+                *self.geometry_map.get_mut(&id).unwrap() =
+                    (vert.into_glium_vertex_buffer(&self.context)?, index_buffer);
+            }
+            GliumGpuCommand::DestroyGeometry(id) => {
+                assert!(self.geometry_map.contains_key(&id));
+                self.geometry_map.remove(&id);
+            }
+            GliumGpuCommand::UpdateCommandList(cmd_list) => {
+                for cmd in cmd_list {
+                    match cmd {
+                        GpuCommand::ClearRenderBuffer {
+                            render_buffer_id: id,
+                        } => {
+                            assert!(self.render_buffer_map.contains_key(&id));
+                            let render_buffer = self.render_buffer_map.get(&id).unwrap();
+
+                            // TODO: add support
+                            assert!(!render_buffer.has_stencil_buffer);
+                            assert!(!render_buffer.has_depth_buffer);
+
+                            let t = self.texture_map.get(&render_buffer.texture_id).unwrap();
+
+                            let mut frame_buffer = SimpleFrameBuffer::new(&self.context, &t.0)?;
+
+                            frame_buffer.clear(None, Some((0.0, 0.0, 0.0, 0.0)), false, None, None);
+                        }
+                        GpuCommand::DrawGeometry {
+                            gpu_state,
+                            geometry_id,
+                            indices_count,
+                            indices_offset,
+                        } => {
+                            assert!(self.geometry_map.contains_key(&geometry_id));
+                            let (vertex_buffer, index_buffer) =
+                                self.geometry_map.get(&geometry_id).unwrap();
+
+                            assert!(self
+                                .render_buffer_map
+                                .contains_key(&gpu_state.render_buffer_id));
+                            let render_buffer = self
+                                .render_buffer_map
+                                .get(&gpu_state.render_buffer_id)
+                                .unwrap();
+
+                            // TODO: add support
+                            assert!(!render_buffer.has_stencil_buffer);
+                            assert!(!render_buffer.has_depth_buffer);
+
+                            let index_buffer_slice = index_buffer
+                                .slice(
+                                    indices_offset as usize
+                                        ..(indices_offset as usize + indices_count as usize),
+                                )
+                                .ok_or(GliumGpuDriverError::DrawIndexOutOfRange {
+                                    index_buffer_size: index_buffer.len(),
+                                    draw_index_offset: indices_offset,
+                                    draw_index_size: indices_count,
+                                })?;
+
+                            let (t, _) = self.texture_map.get(&render_buffer.texture_id).unwrap();
+
+                            let mut frame_buffer = SimpleFrameBuffer::new(&self.context, t)?;
+
+                            let used_program = match gpu_state.shader_type {
+                                ShaderType::Fill => &self.fill_program,
+                                ShaderType::FillPath => &self.path_program,
+                            };
+
+                            let scalar_data =
+                                UniformBuffer::new(&self.context, gpu_state.uniform_scalar)?;
+                            let vector_data =
+                                UniformBuffer::new(&self.context, gpu_state.uniform_vector)?;
+                            let clip_data = UniformBuffer::new(&self.context, gpu_state.clip)?;
+
+                            // Orthographic Projection matrix applied to
+                            // the `transformation` matrix.
+                            let orth_projection_matrix = [
+                                [2.0 / gpu_state.viewport_width as f32, 0.0, 0.0, 0.0],
+                                [0.0, 2.0 / gpu_state.viewport_height as f32, 0.0, 0.0],
+                                [0.0, 0.0, -0.000002, 0.0],
+                                [-1.0, -1.0, 0.818183, 1.0],
+                            ];
+                            // trasform matrix to project matrix
+                            let mut transformation = [
+                                [0., 0., 0., 0.],
+                                [0., 0., 0., 0.],
+                                [0., 0., 0., 0.],
+                                [0., 0., 0., 0.],
+                            ];
+
+                            // multiply matrices
+                            #[allow(clippy::needless_range_loop)]
+                            for i in 0..4 {
+                                for j in 0..4 {
+                                    for k in 0..4 {
+                                        transformation[i][j] += gpu_state.transform[i * 4 + k]
+                                            * orth_projection_matrix[k][j];
                                     }
                                 }
+                            }
 
-                                // we use the supplied texture if it exists, or
-                                // an empty texture if it doesn't.
-                                let texture1 = if let Some(id) = gpu_state.texture_1_id {
-                                    let (t, _) = self.texture_map.get(&id).unwrap();
-                                    t
+                            // we use the supplied texture if it exists, or
+                            // an empty texture if it doesn't.
+                            let texture1 = self.draw_call_texture(gpu_state.texture_1_id);
+                            let texture2 = self.draw_call_texture(gpu_state.texture_2_id);
+                            let texture3 = self.draw_call_texture(gpu_state.texture_3_id);
+
+                            let uniforms = uniform! {
+                                State: [time as f32, gpu_state.viewport_width as f32, gpu_state.viewport_height as f32, 1.0],
+                                Transform: transformation,
+                                Scalar: &scalar_data,
+                                Vector: &vector_data,
+                                ClipSize: gpu_state.clip_size,
+                                Clip: &clip_data,
+                                Texture1: texture1.sampled(),
+                                Texture2: texture2.sampled(),
+                                Texture3: texture3.sampled(),
+                            };
+
+                            let params = DrawParameters {
+                                viewport: Some(glium::Rect {
+                                    left: 0,
+                                    bottom: 0,
+                                    width: gpu_state.viewport_width,
+                                    height: gpu_state.viewport_height,
+                                }),
+                                scissor: if gpu_state.enable_scissor {
+                                    Some(scissor_rect(&gpu_state))
                                 } else {
-                                    &self.empty_texture
-                                };
-                                let texture2 = if let Some(id) = gpu_state.texture_2_id {
-                                    let (t, _) = self.texture_map.get(&id).unwrap();
-                                    t
+                                    None
+                                },
+                                blend: if gpu_state.enable_blend {
+                                    Blend::alpha_blending()
                                 } else {
-                                    &self.empty_texture
-                                };
-                                let texture3 = if let Some(id) = gpu_state.texture_3_id {
-                                    let (t, _) = self.texture_map.get(&id).unwrap();
-                                    t
-                                } else {
-                                    &self.empty_texture
-                                };
-
-                                let uniforms = uniform! {
-                                    // TODO: state time
-                                    State: [0.0, gpu_state.viewport_width as f32, gpu_state.viewport_height as f32, 1.0],
-                                    Transform: transformation,
-                                    Scalar: &scalar_data,
-                                    Vector: &vector_data,
-                                    ClipSize: gpu_state.clip_size,
-                                    Clip: &clip_data,
-                                    Texture1: texture1.sampled(),
-                                    Texture2: texture2.sampled(),
-                                    Texture3: texture3.sampled(),
-                                };
-
-                                let params = DrawParameters {
-                                    viewport: Some(glium::Rect {
-                                        left: 0,
-                                        bottom: 0,
-                                        width: gpu_state.viewport_width,
-                                        height: gpu_state.viewport_height,
-                                    }),
-                                    scissor: if gpu_state.enable_scissor {
-                                        Some(glium::Rect {
-                                            left: gpu_state.scissor_rect.left as u32,
-                                            bottom: gpu_state.scissor_rect.top as u32,
-                                            width: (gpu_state.scissor_rect.right
-                                                - gpu_state.scissor_rect.left)
-                                                as u32,
-                                            height: (gpu_state.scissor_rect.bottom
-                                                - gpu_state.scissor_rect.top)
-                                                as u32,
-                                        })
-                                    } else {
-                                        None
-                                    },
-                                    blend: if gpu_state.enable_blend {
-                                        Blend::alpha_blending()
-                                    } else {
-                                        Blend::default()
-                                    },
-                                    ..DrawParameters::default()
-                                };
-
-                                frame_buffer.draw(
-                                    vertex_buffer,
-                                    index_buffer_slice,
-                                    used_program,
-                                    &uniforms,
-                                    &params,
-                                )?;
-                            }
+                                    Blend::default()
+                                },
+                                ..DrawParameters::default()
+                            };
+
+                            progress.draws += 1;
+                            frame_buffer.draw(
+                                vertex_buffer,
+                                index_buffer_slice,
+                                used_program,
+                                &uniforms,
+                                &params,
+                            )?;
                         }
                     }
                 }
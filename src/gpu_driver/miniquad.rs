@@ -0,0 +1,769 @@
+//! A custom [`GpuDriver`] implementation for the `miniquad` backend, which
+//! can be used with `macroquad` as well.
+
+use std::{collections::HashMap, sync::mpsc, time::Instant};
+
+use miniquad::{
+    Backend, Bindings, BlendFactor, BlendState, BlendValue, BufferId, BufferLayout, BufferSource,
+    BufferType, BufferUsage, Equation, PassAction, Pipeline, PipelineParams, RenderPass,
+    RenderingBackend, ShaderMeta, ShaderSource, TextureFormat, TextureId, TextureParams,
+    UniformBlockLayout, UniformDesc, UniformType, UniformsSource, VertexAttribute, VertexFormat,
+};
+
+use crate::{
+    bitmap::{BitmapFormat, OwnedBitmap},
+    gpu_driver::ShaderType,
+    rect::Rect,
+};
+
+use super::{
+    GpuCommand, GpuDriver, GpuState, IndexBuffer, RenderBuffer, VertexBuffer, VertexBufferFormat,
+};
+
+/// Errors can occure when calling [`create_gpu_driver`] or
+/// [`MiniquadGpuDriverReceiver::render`].
+#[derive(Debug, thiserror::Error)]
+pub enum MiniquadGpuDriverError {
+    /// The `miniquad` context doesn't use the OpenGL backend, the Metal
+    /// backend has no shaders in this driver.
+    #[error("Only the OpenGL backend of `miniquad` is supported, got {0:?}")]
+    UnsupportedBackend(Backend),
+    /// The shaders failed to compile, this happens on OpenGL ES and WebGL
+    /// contexts, which don't support the GLSL 150 shaders of this driver.
+    #[error("Failed to create shader program in `miniquad`: {0:?}")]
+    ShaderCreationError(miniquad::ShaderError),
+    #[error("Texture with id {0} does not exist")]
+    TextureNotFound(u32),
+    #[error("Render buffer with id {0} does not exist")]
+    RenderBufferNotFound(u32),
+    #[error("Geometry with id {0} does not exist")]
+    GeometryNotFound(u32),
+    #[error(
+        "The index offset ({draw_index_offset}) and size ({draw_index_size}) used in draw is out of range from the selected index buffer (size = {index_buffer_size})"
+    )]
+    DrawIndexOutOfRange {
+        index_buffer_size: usize,
+        draw_index_offset: u32,
+        draw_index_size: u32,
+    },
+}
+
+/// Creates a GPU driver for `miniquad`.
+///
+/// Like the [`glium`](super::glium) driver, this creates two objects, the
+/// sender which implements [`GpuDriver`] and should be given to
+/// [`platform::set_gpu_driver`](crate::platform::set_gpu_driver), and the
+/// receiver which executes the commands using the `miniquad` context.
+///
+/// Only the OpenGL backend is supported (desktop OpenGL 3.2 or newer), since
+/// the shaders are written in GLSL.
+///
+/// **Make sure that both the sender and the receiver are alive for the whole**
+/// **lifetime of the [`Renderer`](crate::renderer::Renderer)**
+///
+/// # Errors
+/// - [`MiniquadGpuDriverError::UnsupportedBackend`] if `ctx` uses the Metal
+///   backend (the default on iOS, and optional on macOS).
+/// - [`MiniquadGpuDriverError::ShaderCreationError`] if `ctx` is an OpenGL ES
+///   or WebGL context, which would need `GLSL 100` shaders.
+///
+/// # Examples
+/// With `macroquad`, the context can be obtained with `get_internal_gl`,
+/// make sure to flush the pending `macroquad` draw calls before using it:
+/// ```no_run
+/// # use macroquad::prelude::*;
+/// # use ul_next::{gpu_driver::miniquad::create_gpu_driver, platform, renderer::Renderer};
+/// # fn example(lib: std::sync::Arc<ul_next::Library>, renderer: &Renderer) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut gl = unsafe { get_internal_gl() };
+/// gl.flush();
+/// let (sender, mut receiver) = create_gpu_driver(gl.quad_context)?;
+/// platform::set_gpu_driver(lib.clone(), sender);
+///
+/// renderer.render()?; // will dispatch and send all events to `reciever` from `ultralight`
+/// receiver.render(gl.quad_context)?; // will render all events received from `sender`
+/// # Ok(())
+/// # }
+/// ```
+pub fn create_gpu_driver(
+    ctx: &mut dyn RenderingBackend,
+) -> Result<(MiniquadGpuDriverSender, MiniquadGpuDriverReceiver), MiniquadGpuDriverError> {
+    let (sender, receiver) = mpsc::channel();
+    Ok((
+        MiniquadGpuDriverSender {
+            next_texture_id: 0,
+            next_render_buffer_id: 0,
+            next_geometry_id: 0,
+            sender,
+        },
+        MiniquadGpuDriverReceiver::new(receiver, ctx)?,
+    ))
+}
+
+enum MiniquadGpuCommand {
+    CreateTexture(u32, OwnedBitmap),
+    UpdateTexture(u32, OwnedBitmap),
+    DestroyTexture(u32),
+    CreateRenderBuffer(u32, RenderBuffer),
+    DestroyRenderBuffer(u32),
+    CreateGeometry(u32, VertexBuffer, IndexBuffer),
+    UpdateGeometry(u32, VertexBuffer, IndexBuffer),
+    DestroyGeometry(u32),
+    UpdateCommandList(Vec<GpuCommand>),
+}
+
+/// A [`GpuDriver`] implemented for integrating with `miniquad`.
+///
+/// This is the sender part, the commands are executed by
+/// [`MiniquadGpuDriverReceiver`], see [`create_gpu_driver`].
+pub struct MiniquadGpuDriverSender {
+    next_texture_id: u32,
+    next_render_buffer_id: u32,
+    next_geometry_id: u32,
+    sender: mpsc::Sender<MiniquadGpuCommand>,
+}
+
+impl MiniquadGpuDriverSender {
+    /// Send `command` to the receiver.
+    ///
+    /// If the receiver was dropped (eg. the window was closed while a
+    /// [`Renderer`](crate::renderer::Renderer) is still alive), there is
+    /// nothing to render to, so the command is dropped.
+    fn send(&self, command: MiniquadGpuCommand) {
+        let _ = self.sender.send(command);
+    }
+}
+
+impl GpuDriver for MiniquadGpuDriverSender {
+    fn begin_synchronize(&mut self) {
+        // unhandled
+    }
+
+    fn end_synchronize(&mut self) {
+        // unhandled
+    }
+
+    fn next_texture_id(&mut self) -> u32 {
+        self.next_texture_id += 1;
+        self.next_texture_id
+    }
+
+    fn create_texture(&mut self, texture_id: u32, bitmap: OwnedBitmap) {
+        self.send(MiniquadGpuCommand::CreateTexture(texture_id, bitmap));
+    }
+
+    fn update_texture(&mut self, texture_id: u32, bitmap: OwnedBitmap) {
+        self.send(MiniquadGpuCommand::UpdateTexture(texture_id, bitmap));
+    }
+
+    fn destroy_texture(&mut self, texture_id: u32) {
+        self.send(MiniquadGpuCommand::DestroyTexture(texture_id));
+    }
+
+    fn next_render_buffer_id(&mut self) -> u32 {
+        self.next_render_buffer_id += 1;
+        self.next_render_buffer_id
+    }
+
+    fn create_render_buffer(&mut self, render_buffer_id: u32, render_buffer: RenderBuffer) {
+        self.send(MiniquadGpuCommand::CreateRenderBuffer(
+            render_buffer_id,
+            render_buffer,
+        ));
+    }
+
+    fn destroy_render_buffer(&mut self, render_buffer_id: u32) {
+        self.send(MiniquadGpuCommand::DestroyRenderBuffer(render_buffer_id));
+    }
+
+    fn next_geometry_id(&mut self) -> u32 {
+        self.next_geometry_id += 1;
+        self.next_geometry_id
+    }
+
+    fn create_geometry(
+        &mut self,
+        geometry_id: u32,
+        vertex_buffer: VertexBuffer,
+        index_buffer: IndexBuffer,
+    ) {
+        self.send(MiniquadGpuCommand::CreateGeometry(
+            geometry_id,
+            vertex_buffer,
+            index_buffer,
+        ));
+    }
+
+    fn update_geometry(
+        &mut self,
+        geometry_id: u32,
+        vertex_buffer: VertexBuffer,
+        index_buffer: IndexBuffer,
+    ) {
+        self.send(MiniquadGpuCommand::UpdateGeometry(
+            geometry_id,
+            vertex_buffer,
+            index_buffer,
+        ));
+    }
+
+    fn destroy_geometry(&mut self, geometry_id: u32) {
+        self.send(MiniquadGpuCommand::DestroyGeometry(geometry_id));
+    }
+
+    fn update_command_list(&mut self, command_list: Vec<GpuCommand>) {
+        self.send(MiniquadGpuCommand::UpdateCommandList(command_list));
+    }
+}
+
+/// The uniforms of the shaders, must match the order of
+/// [`uniform_block_layout`].
+#[repr(C)]
+struct Uniforms {
+    state: [f32; 4],
+    transform: [[f32; 4]; 4],
+    scalar: [f32; 8],
+    vector: [[f32; 4]; 8],
+    clip_size: i32,
+    clip: [[[f32; 4]; 4]; 8],
+}
+
+fn uniform_block_layout() -> UniformBlockLayout {
+    UniformBlockLayout {
+        uniforms: vec![
+            UniformDesc::new("State", UniformType::Float4),
+            UniformDesc::new("Transform", UniformType::Mat4),
+            UniformDesc::new("scalar", UniformType::Float1).array(8),
+            UniformDesc::new("vector", UniformType::Float4).array(8),
+            UniformDesc::new("ClipSizeI", UniformType::Int1),
+            UniformDesc::new("clip", UniformType::Mat4).array(8),
+        ],
+    }
+}
+
+/// `miniquad` doesn't support uniform blocks or unsigned integer uniforms,
+/// so the shaders shared with the `glium` driver are adapted to use plain
+/// uniforms.
+fn adapt_shader(source: &str) -> String {
+    source
+        .replace(
+            "uniform Scalar {\n    float scalar[8];\n};",
+            "uniform float scalar[8];",
+        )
+        .replace(
+            "uniform Clip {\n    mat4 clip[8];\n};",
+            "uniform mat4 clip[8];",
+        )
+        .replace(
+            "uniform Vector {\n    vec4 vector[8];\n};",
+            "uniform vec4 vector[8];",
+        )
+        .replace(
+            "uniform uint ClipSize;",
+            "uniform int ClipSizeI;\n#define ClipSize uint(ClipSizeI)",
+        )
+        // byte attributes are not normalized by `miniquad`
+        .replace(
+            "in vec4 in_Color;",
+            "in vec4 in_ColorU8;\n#define in_Color (in_ColorU8 / 255.0)",
+        )
+}
+
+/// Create the pipelines for a shader pair, without and with blending.
+fn create_pipelines(
+    ctx: &mut dyn RenderingBackend,
+    vertex: &str,
+    fragment: &str,
+    images: &[&str],
+    stride: usize,
+    attributes: &[VertexAttribute],
+) -> Result<[Pipeline; 2], MiniquadGpuDriverError> {
+    let vertex = adapt_shader(vertex);
+    let fragment = adapt_shader(fragment);
+    let shader = ctx
+        .new_shader(
+            ShaderSource::Glsl {
+                vertex: &vertex,
+                fragment: &fragment,
+            },
+            ShaderMeta {
+                images: images.iter().map(|name| name.to_string()).collect(),
+                uniforms: uniform_block_layout(),
+            },
+        )
+        .map_err(MiniquadGpuDriverError::ShaderCreationError)?;
+
+    let layout = [BufferLayout {
+        stride: stride as i32,
+        ..Default::default()
+    }];
+
+    let opaque = ctx.new_pipeline(&layout, attributes, shader, PipelineParams::default());
+    // premultiplied alpha blending
+    let blended = ctx.new_pipeline(
+        &layout,
+        attributes,
+        shader,
+        PipelineParams {
+            color_blend: Some(BlendState::new(
+                Equation::Add,
+                BlendFactor::One,
+                BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+            )),
+            alpha_blend: Some(BlendState::new(
+                Equation::Add,
+                BlendFactor::OneMinusValue(BlendValue::DestinationAlpha),
+                BlendFactor::One,
+            )),
+            ..Default::default()
+        },
+    );
+
+    Ok([opaque, blended])
+}
+
+/// The `miniquad` buffers of a geometry.
+struct Geometry {
+    vertex_buffer: BufferId,
+    index_buffer: BufferId,
+    index_count: usize,
+}
+
+/// The receiver part of [`MiniquadGpuDriverSender`].
+///
+/// Calling [`MiniquadGpuDriverReceiver::render`] will render all the commands
+/// we get from [`MiniquadGpuDriverSender`] into textures which can be
+/// obtained by [`MiniquadGpuDriverReceiver::get_texture`].
+///
+/// The `miniquad` resources are not freed when this is dropped, since
+/// the context is not owned by the receiver.
+pub struct MiniquadGpuDriverReceiver {
+    /// receiver for the commands from the sender
+    receiver: mpsc::Receiver<MiniquadGpuCommand>,
+
+    /// create a small texture, which will be used when
+    /// the gpu driver doesn't set a texture for a draw call
+    empty_texture: TextureId,
+    /// map for (id -> texture).
+    texture_map: HashMap<u32, TextureId>,
+    /// map for (id -> (render_buffer metadata, render pass drawing to its texture)).
+    render_buffer_map: HashMap<u32, (RenderBuffer, RenderPass)>,
+    /// map for (id -> geometry).
+    geometry_map: HashMap<u32, Geometry>,
+
+    /// Pipelines for path rendering commands, without and with blending.
+    path_pipelines: [Pipeline; 2],
+    /// Pipelines for fill rendering commands, without and with blending.
+    fill_pipelines: [Pipeline; 2],
+    /// The time [`MiniquadGpuDriverReceiver::render`] measures the shader time from.
+    start_time: Instant,
+}
+
+impl MiniquadGpuDriverReceiver {
+    fn new(
+        receiver: mpsc::Receiver<MiniquadGpuCommand>,
+        ctx: &mut dyn RenderingBackend,
+    ) -> Result<Self, MiniquadGpuDriverError> {
+        let backend = ctx.info().backend;
+        if !matches!(backend, Backend::OpenGl) {
+            return Err(MiniquadGpuDriverError::UnsupportedBackend(backend));
+        }
+
+        let empty_texture = ctx.new_texture_from_rgba8(1, 1, &[0; 4]);
+
+        let path_pipelines = create_pipelines(
+            ctx,
+            include_str!("./shaders/v2f_c4f_t2f_vert.glsl"),
+            include_str!("./shaders/path_frag.glsl"),
+            &[],
+            std::mem::size_of::<ul_sys::ULVertex_2f_4ub_2f>(),
+            &[
+                VertexAttribute::new("in_Position", VertexFormat::Float2),
+                VertexAttribute::new("in_ColorU8", VertexFormat::Byte4),
+                VertexAttribute::new("in_TexCoord", VertexFormat::Float2),
+            ],
+        )?;
+        let fill_pipelines = create_pipelines(
+            ctx,
+            include_str!("./shaders/v2f_c4f_t2f_t2f_d28f_vert.glsl"),
+            include_str!("./shaders/fill_frag.glsl"),
+            &["Texture1", "Texture2", "Texture3"],
+            std::mem::size_of::<ul_sys::ULVertex_2f_4ub_2f_2f_28f>(),
+            &[
+                VertexAttribute::new("in_Position", VertexFormat::Float2),
+                VertexAttribute::new("in_ColorU8", VertexFormat::Byte4),
+                VertexAttribute::new("in_TexCoord", VertexFormat::Float2),
+                VertexAttribute::new("in_ObjCoord", VertexFormat::Float2),
+                VertexAttribute::new("in_Data0", VertexFormat::Float4),
+                VertexAttribute::new("in_Data1", VertexFormat::Float4),
+                VertexAttribute::new("in_Data2", VertexFormat::Float4),
+                VertexAttribute::new("in_Data3", VertexFormat::Float4),
+                VertexAttribute::new("in_Data4", VertexFormat::Float4),
+                VertexAttribute::new("in_Data5", VertexFormat::Float4),
+                VertexAttribute::new("in_Data6", VertexFormat::Float4),
+            ],
+        )?;
+
+        Ok(Self {
+            receiver,
+            empty_texture,
+            texture_map: HashMap::new(),
+            render_buffer_map: HashMap::new(),
+            geometry_map: HashMap::new(),
+            path_pipelines,
+            fill_pipelines,
+            start_time: Instant::now(),
+        })
+    }
+
+    /// Fetch the `miniquad` texture by id, this id can be obtained from the
+    /// current `render_target` of a `view` by [`View::render_target`](crate::view::View::render_target).
+    ///
+    /// With `macroquad`, it can be drawn with `draw_texture` after wrapping
+    /// it with `Texture2D::from_miniquad_texture`.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use macroquad::prelude::*;
+    /// # fn example(receiver: &ul_next::gpu_driver::miniquad::MiniquadGpuDriverReceiver, view: &ul_next::view::View) {
+    /// let render_target = view.render_target().unwrap();
+    /// let texture = receiver.get_texture(&render_target.texture_id).unwrap();
+    /// draw_texture(&Texture2D::from_miniquad_texture(texture), 0.0, 0.0, WHITE);
+    /// # }
+    /// ```
+    pub fn get_texture(&self, id: &u32) -> Option<TextureId> {
+        self.texture_map.get(id).copied()
+    }
+
+    /// Flushes and renders all pending GPU commands recieved from [`MiniquadGpuDriverSender`],
+    /// which will be generated when calling [`Renderer::render`](crate::renderer::Renderer::render).
+    ///
+    /// **Note that this must be called for rendering to actually occure, as using**
+    /// **[`platform::set_gpu_driver`](crate::platform::set_gpu_driver) alone**
+    /// **with [`MiniquadGpuDriverSender`] is not enough.**
+    ///
+    /// The time given to the shaders is the time in seconds since this
+    /// receiver was created, use [`MiniquadGpuDriverReceiver::render_with_time`]
+    /// to set it manually.
+    pub fn render(&mut self, ctx: &mut dyn RenderingBackend) -> Result<(), MiniquadGpuDriverError> {
+        self.render_with_time(ctx, self.start_time.elapsed().as_secs_f64())
+    }
+
+    /// Same as [`MiniquadGpuDriverReceiver::render`], but with `time` (in seconds)
+    /// as the time given to the shaders.
+    pub fn render_with_time(
+        &mut self,
+        ctx: &mut dyn RenderingBackend,
+        time: f64,
+    ) -> Result<(), MiniquadGpuDriverError> {
+        while let Ok(cmd) = self.receiver.try_recv() {
+            match cmd {
+                MiniquadGpuCommand::CreateTexture(id, bitmap) => {
+                    let texture = create_texture(ctx, &bitmap);
+                    self.texture_map.insert(id, texture);
+                }
+                MiniquadGpuCommand::UpdateTexture(id, bitmap) => {
+                    let texture = *self
+                        .texture_map
+                        .get(&id)
+                        .ok_or(MiniquadGpuDriverError::TextureNotFound(id))?;
+
+                    // reuse the texture if possible, to avoid reallocating
+                    // glyph atlases and images on every update
+                    if ctx.texture_size(texture) == (bitmap.width(), bitmap.height()) {
+                        if let Some(pixels) = texture_pixels(&bitmap) {
+                            ctx.texture_update(texture, &pixels);
+                        }
+                    } else {
+                        ctx.delete_texture(texture);
+                        self.texture_map.insert(id, create_texture(ctx, &bitmap));
+                    }
+                }
+                MiniquadGpuCommand::DestroyTexture(id) => {
+                    let texture = self
+                        .texture_map
+                        .remove(&id)
+                        .ok_or(MiniquadGpuDriverError::TextureNotFound(id))?;
+                    ctx.delete_texture(texture);
+                }
+                MiniquadGpuCommand::CreateRenderBuffer(id, render_buffer) => {
+                    let texture = self.get_texture(&render_buffer.texture_id).ok_or(
+                        MiniquadGpuDriverError::TextureNotFound(render_buffer.texture_id),
+                    )?;
+                    // TODO: add support
+                    assert!(!render_buffer.has_stencil_buffer);
+                    assert!(!render_buffer.has_depth_buffer);
+
+                    let pass = ctx.new_render_pass(texture, None);
+                    self.render_buffer_map.insert(id, (render_buffer, pass));
+                }
+                MiniquadGpuCommand::DestroyRenderBuffer(id) => {
+                    let (_, pass) = self
+                        .render_buffer_map
+                        .remove(&id)
+                        .ok_or(MiniquadGpuDriverError::RenderBufferNotFound(id))?;
+                    ctx.delete_render_pass(pass);
+                }
+                MiniquadGpuCommand::CreateGeometry(id, vertex_buffer, index_buffer) => {
+                    let geometry = create_geometry(ctx, &vertex_buffer, &index_buffer);
+                    self.geometry_map.insert(id, geometry);
+                }
+                MiniquadGpuCommand::UpdateGeometry(id, vertex_buffer, index_buffer) => {
+                    let old = self
+                        .geometry_map
+                        .insert(id, create_geometry(ctx, &vertex_buffer, &index_buffer))
+                        .ok_or(MiniquadGpuDriverError::GeometryNotFound(id))?;
+                    ctx.delete_buffer(old.vertex_buffer);
+                    ctx.delete_buffer(old.index_buffer);
+                }
+                MiniquadGpuCommand::DestroyGeometry(id) => {
+                    let geometry = self
+                        .geometry_map
+                        .remove(&id)
+                        .ok_or(MiniquadGpuDriverError::GeometryNotFound(id))?;
+                    ctx.delete_buffer(geometry.vertex_buffer);
+                    ctx.delete_buffer(geometry.index_buffer);
+                }
+                MiniquadGpuCommand::UpdateCommandList(cmd_list) => {
+                    self.execute_command_list(ctx, cmd_list, time)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn execute_command_list(
+        &self,
+        ctx: &mut dyn RenderingBackend,
+        cmd_list: Vec<GpuCommand>,
+        time: f64,
+    ) -> Result<(), MiniquadGpuDriverError> {
+        // consecutive draws to the same render buffer share the same pass
+        let mut current_render_buffer = None;
+
+        for cmd in cmd_list {
+            match cmd {
+                GpuCommand::ClearRenderBuffer { render_buffer_id } => {
+                    if current_render_buffer.take().is_some() {
+                        ctx.end_render_pass();
+                    }
+
+                    let (_, pass) = self.render_buffer_map.get(&render_buffer_id).ok_or(
+                        MiniquadGpuDriverError::RenderBufferNotFound(render_buffer_id),
+                    )?;
+                    ctx.begin_pass(Some(*pass), PassAction::clear_color(0.0, 0.0, 0.0, 0.0));
+                    ctx.end_render_pass();
+                }
+                GpuCommand::DrawGeometry {
+                    gpu_state,
+                    geometry_id,
+                    indices_count,
+                    indices_offset,
+                } => {
+                    let geometry = self
+                        .geometry_map
+                        .get(&geometry_id)
+                        .ok_or(MiniquadGpuDriverError::GeometryNotFound(geometry_id))?;
+                    if indices_offset as usize + indices_count as usize > geometry.index_count {
+                        return Err(MiniquadGpuDriverError::DrawIndexOutOfRange {
+                            index_buffer_size: geometry.index_count,
+                            draw_index_offset: indices_offset,
+                            draw_index_size: indices_count,
+                        });
+                    }
+
+                    if current_render_buffer != Some(gpu_state.render_buffer_id) {
+                        if current_render_buffer.is_some() {
+                            ctx.end_render_pass();
+                        }
+                        let (_, pass) = self
+                            .render_buffer_map
+                            .get(&gpu_state.render_buffer_id)
+                            .ok_or(MiniquadGpuDriverError::RenderBufferNotFound(
+                                gpu_state.render_buffer_id,
+                            ))?;
+                        ctx.begin_pass(Some(*pass), PassAction::Nothing);
+                        current_render_buffer = Some(gpu_state.render_buffer_id);
+                    }
+
+                    self.draw_geometry(ctx, &gpu_state, geometry, time)?;
+                    ctx.draw(indices_offset as i32, indices_count as i32, 1);
+                }
+            }
+        }
+
+        if current_render_buffer.is_some() {
+            ctx.end_render_pass();
+        }
+
+        Ok(())
+    }
+
+    /// Apply the pipeline, bindings and uniforms of a draw command.
+    fn draw_geometry(
+        &self,
+        ctx: &mut dyn RenderingBackend,
+        gpu_state: &GpuState,
+        geometry: &Geometry,
+        time: f64,
+    ) -> Result<(), MiniquadGpuDriverError> {
+        let blend = gpu_state.enable_blend as usize;
+        let (pipeline, images) = match gpu_state.shader_type {
+            ShaderType::Fill => {
+                let mut images = Vec::with_capacity(3);
+                for id in [
+                    gpu_state.texture_1_id,
+                    gpu_state.texture_2_id,
+                    gpu_state.texture_3_id,
+                ] {
+                    // we use the supplied texture if it exists, or
+                    // an empty texture if it doesn't.
+                    images.push(match id {
+                        Some(id) => self
+                            .get_texture(&id)
+                            .ok_or(MiniquadGpuDriverError::TextureNotFound(id))?,
+                        None => self.empty_texture,
+                    });
+                }
+                (self.fill_pipelines[blend], images)
+            }
+            ShaderType::FillPath => (self.path_pipelines[blend], Vec::new()),
+        };
+
+        let width = gpu_state.viewport_width as i32;
+        let height = gpu_state.viewport_height as i32;
+
+        ctx.apply_pipeline(&pipeline);
+        ctx.apply_viewport(0, 0, width, height);
+        if gpu_state.enable_scissor {
+            let viewport = Rect {
+                left: 0,
+                top: 0,
+                right: width,
+                bottom: height,
+            };
+            match gpu_state.scissor_rect.intersect(&viewport) {
+                Some(rect) => {
+                    ctx.apply_scissor_rect(rect.left, rect.top, rect.width(), rect.height())
+                }
+                None => ctx.apply_scissor_rect(0, 0, 0, 0),
+            }
+        } else {
+            ctx.apply_scissor_rect(0, 0, width, height);
+        }
+        ctx.apply_bindings(&Bindings {
+            vertex_buffers: vec![geometry.vertex_buffer],
+            index_buffer: geometry.index_buffer,
+            images,
+        });
+
+        // Orthographic Projection matrix applied to
+        // the `transformation` matrix.
+        let orth_projection_matrix = [
+            [2.0 / gpu_state.viewport_width as f32, 0.0, 0.0, 0.0],
+            [0.0, 2.0 / gpu_state.viewport_height as f32, 0.0, 0.0],
+            [0.0, 0.0, -0.000002, 0.0],
+            [-1.0, -1.0, 0.818183, 1.0],
+        ];
+        let mut transform = [[0.0; 4]; 4];
+        #[allow(clippy::needless_range_loop)]
+        for i in 0..4 {
+            for j in 0..4 {
+                for k in 0..4 {
+                    transform[i][j] +=
+                        gpu_state.transform[i * 4 + k] * orth_projection_matrix[k][j];
+                }
+            }
+        }
+
+        ctx.apply_uniforms(UniformsSource::table(&Uniforms {
+            state: [
+                time as f32,
+                gpu_state.viewport_width as f32,
+                gpu_state.viewport_height as f32,
+                1.0,
+            ],
+            transform,
+            scalar: gpu_state.uniform_scalar,
+            vector: gpu_state.uniform_vector,
+            clip_size: gpu_state.clip_size as i32,
+            clip: gpu_state.clip,
+        }));
+
+        Ok(())
+    }
+}
+
+/// Create the vertex and index buffers of a geometry.
+fn create_geometry(
+    ctx: &mut dyn RenderingBackend,
+    vertex_buffer: &VertexBuffer,
+    index_buffer: &IndexBuffer,
+) -> Geometry {
+    // the stride of the vertices is set in the pipelines
+    debug_assert!(matches!(
+        vertex_buffer.format,
+        VertexBufferFormat::Format_2f_4ub_2f | VertexBufferFormat::Format_2f_4ub_2f_2f_28f
+    ));
+
+    Geometry {
+        vertex_buffer: ctx.new_buffer(
+            BufferType::VertexBuffer,
+            BufferUsage::Immutable,
+            BufferSource::slice(&vertex_buffer.buffer),
+        ),
+        index_buffer: ctx.new_buffer(
+            BufferType::IndexBuffer,
+            BufferUsage::Immutable,
+            BufferSource::slice(&index_buffer.buffer),
+        ),
+        index_count: index_buffer.buffer.len(),
+    }
+}
+
+/// Create a texture for `bitmap`, empty bitmaps are used as render buffers.
+fn create_texture(ctx: &mut dyn RenderingBackend, bitmap: &OwnedBitmap) -> TextureId {
+    let params = TextureParams {
+        width: bitmap.width(),
+        height: bitmap.height(),
+        format: match bitmap.format() {
+            // sampled as the red channel in the shaders
+            BitmapFormat::A8Unorm => TextureFormat::Alpha,
+            BitmapFormat::Bgra8UnormSrgb => TextureFormat::RGBA8,
+        },
+        ..Default::default()
+    };
+
+    match texture_pixels(bitmap) {
+        Some(pixels) => ctx.new_texture_from_data_and_format(&pixels, params),
+        None => ctx.new_render_texture(params),
+    }
+}
+
+/// The pixels of `bitmap` without the row padding, with the `BGRA` pixels
+/// swizzled to `RGBA` since `miniquad` doesn't support `BGRA` textures.
+///
+/// Returns [`None`] for empty bitmaps.
+fn texture_pixels(bitmap: &OwnedBitmap) -> Option<Vec<u8>> {
+    if bitmap.is_empty() {
+        return None;
+    }
+    let pixels = bitmap.pixels()?;
+
+    let row_len = (bitmap.width() * bitmap.bpp()) as usize;
+    let mut result = Vec::with_capacity(row_len * bitmap.height() as usize);
+    for row in pixels
+        .chunks(bitmap.row_bytes() as usize)
+        .take(bitmap.height() as usize)
+    {
+        result.extend_from_slice(&row[..row_len]);
+    }
+
+    if let BitmapFormat::Bgra8UnormSrgb = bitmap.format() {
+        for pixel in result.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+
+    Some(result)
+}
@@ -0,0 +1,568 @@
+//! A [`GpuDriver`] that records all the calls it receives, so they can be
+//! inspected, saved and replayed later against another driver.
+//!
+//! This is useful for debugging rendering issues, and for testing driver
+//! implementations (eg. the [`glium`](super::glium) receiver) against
+//! captured traces without running `Ultralight`.
+//!
+//! With the `serde` feature, [`Recording`] and all the payloads it contains
+//! implement `Serialize` and `Deserialize`, so recordings can be written to
+//! disk in any `serde` format, or as JSON with [`Recording::save_json`].
+//!
+//! # Examples
+//! ```no_run
+//! # use ul_next::{gpu_driver::{recording::{replay, Recording, RecordingDriver}, GpuDriver}, platform};
+//! # #[cfg(feature = "serde")]
+//! # fn example(
+//! #     lib: std::sync::Arc<ul_next::Library>,
+//! #     gpu_driver_sender: impl GpuDriver + Send + 'static,
+//! #     other_driver: &mut impl GpuDriver,
+//! # ) -> std::io::Result<()> {
+//! let driver = RecordingDriver::new(Some(Box::new(gpu_driver_sender)));
+//! let recording = driver.recording();
+//! platform::set_gpu_driver(lib.clone(), driver);
+//!
+//! // ... create the renderer, update and render
+//!
+//! let recording = std::mem::take(&mut *recording.lock().unwrap());
+//! recording.save_json("frame.json")?;
+//!
+//! // later, or in a test
+//! let recording = Recording::load_json("frame.json")?;
+//! replay(&recording, other_driver);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+#[cfg(feature = "serde")]
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use crate::bitmap::OwnedBitmap;
+
+use super::{GpuCommand, GpuDriver, IndexBuffer, RenderBuffer, VertexBuffer};
+
+/// A single recorded call of a [`GpuDriver`] method.
+///
+/// The `Next*Id` variants hold the id returned by the driver.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RecordedCall {
+    /// [`GpuDriver::begin_synchronize`]
+    BeginSynchronize,
+    /// [`GpuDriver::end_synchronize`]
+    EndSynchronize,
+    /// [`GpuDriver::begin_frame`]
+    BeginFrame(u32),
+    /// [`GpuDriver::end_frame`]
+    EndFrame(u32),
+    /// [`GpuDriver::next_texture_id`]
+    NextTextureId(u32),
+    /// [`GpuDriver::create_texture`]
+    CreateTexture {
+        texture_id: u32,
+        bitmap: OwnedBitmap,
+    },
+    /// [`GpuDriver::update_texture`]
+    UpdateTexture {
+        texture_id: u32,
+        bitmap: OwnedBitmap,
+    },
+    /// [`GpuDriver::destroy_texture`]
+    DestroyTexture { texture_id: u32 },
+    /// [`GpuDriver::next_render_buffer_id`]
+    NextRenderBufferId(u32),
+    /// [`GpuDriver::create_render_buffer`]
+    CreateRenderBuffer {
+        render_buffer_id: u32,
+        render_buffer: RenderBuffer,
+    },
+    /// [`GpuDriver::destroy_render_buffer`]
+    DestroyRenderBuffer { render_buffer_id: u32 },
+    /// [`GpuDriver::next_geometry_id`]
+    NextGeometryId(u32),
+    /// [`GpuDriver::create_geometry`]
+    CreateGeometry {
+        geometry_id: u32,
+        vertex_buffer: VertexBuffer,
+        index_buffer: IndexBuffer,
+    },
+    /// [`GpuDriver::update_geometry`]
+    UpdateGeometry {
+        geometry_id: u32,
+        vertex_buffer: VertexBuffer,
+        index_buffer: IndexBuffer,
+    },
+    /// [`GpuDriver::destroy_geometry`]
+    DestroyGeometry { geometry_id: u32 },
+    /// [`GpuDriver::update_command_list`]
+    UpdateCommandList(Vec<GpuCommand>),
+}
+
+/// The calls recorded by a [`RecordingDriver`], in order.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recording {
+    calls: Vec<RecordedCall>,
+}
+
+impl Recording {
+    /// Create an empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a recording from a list of calls, for example to write a
+    /// trace by hand.
+    pub fn from_calls(calls: Vec<RecordedCall>) -> Self {
+        Self { calls }
+    }
+
+    /// The recorded calls, in order.
+    pub fn calls(&self) -> &[RecordedCall] {
+        &self.calls
+    }
+
+    /// Consume the recording, returning the recorded calls.
+    pub fn into_calls(self) -> Vec<RecordedCall> {
+        self.calls
+    }
+
+    /// The number of recorded calls.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Whether no calls were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Remove all the recorded calls.
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+
+    /// Write the recording to the file at `path` as JSON, it can be read
+    /// back with [`Recording::load_json`].
+    ///
+    /// The bitmaps and the contents of the vertex and index buffers are
+    /// included, so recordings of whole pages can be large.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()
+    }
+
+    /// Read a recording written by [`Recording::save_json`].
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn load_json<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// Issue all the recorded calls to `driver`, in order.
+    ///
+    /// The ids are taken from `driver`: each recorded `next_*_id` call
+    /// calls the same method of `driver`, and the recorded id is replaced
+    /// with the returned one in all the following calls and commands. Ids
+    /// that were not allocated in the recording (eg. when recording started
+    /// after the renderer was created) are passed as is.
+    pub fn replay(&self, driver: &mut impl GpuDriver) {
+        let mut ids = IdMap::default();
+
+        for call in &self.calls {
+            match call.clone() {
+                RecordedCall::BeginSynchronize => driver.begin_synchronize(),
+                RecordedCall::EndSynchronize => driver.end_synchronize(),
+                RecordedCall::BeginFrame(display_id) => driver.begin_frame(display_id),
+                RecordedCall::EndFrame(display_id) => driver.end_frame(display_id),
+                RecordedCall::NextTextureId(id) => {
+                    ids.textures.insert(id, driver.next_texture_id());
+                }
+                RecordedCall::CreateTexture { texture_id, bitmap } => {
+                    driver.create_texture(map_id(&ids.textures, texture_id), bitmap)
+                }
+                RecordedCall::UpdateTexture { texture_id, bitmap } => {
+                    driver.update_texture(map_id(&ids.textures, texture_id), bitmap)
+                }
+                RecordedCall::DestroyTexture { texture_id } => {
+                    driver.destroy_texture(map_id(&ids.textures, texture_id));
+                    ids.textures.remove(&texture_id);
+                }
+                RecordedCall::NextRenderBufferId(id) => {
+                    ids.render_buffers
+                        .insert(id, driver.next_render_buffer_id());
+                }
+                RecordedCall::CreateRenderBuffer {
+                    render_buffer_id,
+                    mut render_buffer,
+                } => {
+                    render_buffer.texture_id = map_id(&ids.textures, render_buffer.texture_id);
+                    driver.create_render_buffer(
+                        map_id(&ids.render_buffers, render_buffer_id),
+                        render_buffer,
+                    )
+                }
+                RecordedCall::DestroyRenderBuffer { render_buffer_id } => {
+                    driver.destroy_render_buffer(map_id(&ids.render_buffers, render_buffer_id));
+                    ids.render_buffers.remove(&render_buffer_id);
+                }
+                RecordedCall::NextGeometryId(id) => {
+                    ids.geometries.insert(id, driver.next_geometry_id());
+                }
+                RecordedCall::CreateGeometry {
+                    geometry_id,
+                    vertex_buffer,
+                    index_buffer,
+                } => driver.create_geometry(
+                    map_id(&ids.geometries, geometry_id),
+                    vertex_buffer,
+                    index_buffer,
+                ),
+                RecordedCall::UpdateGeometry {
+                    geometry_id,
+                    vertex_buffer,
+                    index_buffer,
+                } => driver.update_geometry(
+                    map_id(&ids.geometries, geometry_id),
+                    vertex_buffer,
+                    index_buffer,
+                ),
+                RecordedCall::DestroyGeometry { geometry_id } => {
+                    driver.destroy_geometry(map_id(&ids.geometries, geometry_id));
+                    ids.geometries.remove(&geometry_id);
+                }
+                RecordedCall::UpdateCommandList(commands) => driver.update_command_list(
+                    commands
+                        .into_iter()
+                        .map(|command| ids.map_command(command))
+                        .collect(),
+                ),
+            }
+        }
+    }
+}
+
+/// Issue all the calls of `recording` to `driver`, same as
+/// [`Recording::replay`].
+pub fn replay(recording: &Recording, driver: &mut impl GpuDriver) {
+    recording.replay(driver);
+}
+
+/// Mapping from the recorded ids to the ids of the replay driver.
+#[derive(Default)]
+struct IdMap {
+    textures: HashMap<u32, u32>,
+    render_buffers: HashMap<u32, u32>,
+    geometries: HashMap<u32, u32>,
+}
+
+impl IdMap {
+    fn map_command(&self, command: GpuCommand) -> GpuCommand {
+        match command {
+            GpuCommand::ClearRenderBuffer { render_buffer_id } => GpuCommand::ClearRenderBuffer {
+                render_buffer_id: map_id(&self.render_buffers, render_buffer_id),
+            },
+            GpuCommand::DrawGeometry {
+                mut gpu_state,
+                geometry_id,
+                indices_offset,
+                indices_count,
+            } => {
+                gpu_state.render_buffer_id =
+                    map_id(&self.render_buffers, gpu_state.render_buffer_id);
+                for texture_id in [
+                    &mut gpu_state.texture_1_id,
+                    &mut gpu_state.texture_2_id,
+                    &mut gpu_state.texture_3_id,
+                ] {
+                    *texture_id = texture_id.map(|id| map_id(&self.textures, id));
+                }
+
+                GpuCommand::DrawGeometry {
+                    gpu_state,
+                    geometry_id: map_id(&self.geometries, geometry_id),
+                    indices_offset,
+                    indices_count,
+                }
+            }
+        }
+    }
+}
+
+fn map_id(ids: &HashMap<u32, u32>, id: u32) -> u32 {
+    ids.get(&id).copied().unwrap_or(id)
+}
+
+/// A [`GpuDriver`] that records every call into a [`Recording`], and
+/// optionally forwards it to an inner driver.
+///
+/// Without an inner driver, ids are allocated sequentially starting from `1`,
+/// and nothing is rendered.
+///
+/// The driver is moved into
+/// [`platform::set_gpu_driver`](crate::platform::set_gpu_driver), so the
+/// recording is accessed through the shared handle returned by
+/// [`RecordingDriver::recording`].
+pub struct RecordingDriver {
+    inner: Option<Box<dyn GpuDriver + Send>>,
+    recording: Arc<Mutex<Recording>>,
+    next_texture_id: u32,
+    next_render_buffer_id: u32,
+    next_geometry_id: u32,
+}
+
+impl RecordingDriver {
+    /// Create a recording driver, forwarding the calls to `inner` if
+    /// provided.
+    pub fn new(inner: Option<Box<dyn GpuDriver + Send>>) -> Self {
+        Self {
+            inner,
+            recording: Arc::new(Mutex::new(Recording::new())),
+            next_texture_id: 1,
+            next_render_buffer_id: 1,
+            next_geometry_id: 1,
+        }
+    }
+
+    /// The shared handle of the recording, new calls are appended to it.
+    ///
+    /// The recording can be taken with `std::mem::take` to start a new one,
+    /// for example to capture a single frame.
+    pub fn recording(&self) -> Arc<Mutex<Recording>> {
+        self.recording.clone()
+    }
+}
+
+// a free function instead of a method, so it can be called while `inner`
+// is mutably borrowed
+fn record(recording: &Mutex<Recording>, call: RecordedCall) {
+    recording.lock().unwrap().calls.push(call);
+}
+
+/// Allocate the next id from `counter`, skipping `0`.
+fn next_id(counter: &mut u32) -> u32 {
+    let id = *counter;
+    *counter = counter.wrapping_add(1).max(1);
+    id
+}
+
+impl GpuDriver for RecordingDriver {
+    fn begin_synchronize(&mut self) {
+        record(&self.recording, RecordedCall::BeginSynchronize);
+        if let Some(inner) = &mut self.inner {
+            inner.begin_synchronize();
+        }
+    }
+
+    fn end_synchronize(&mut self) {
+        record(&self.recording, RecordedCall::EndSynchronize);
+        if let Some(inner) = &mut self.inner {
+            inner.end_synchronize();
+        }
+    }
+
+    fn begin_frame(&mut self, display_id: u32) {
+        record(&self.recording, RecordedCall::BeginFrame(display_id));
+        if let Some(inner) = &mut self.inner {
+            inner.begin_frame(display_id);
+        }
+    }
+
+    fn end_frame(&mut self, display_id: u32) {
+        record(&self.recording, RecordedCall::EndFrame(display_id));
+        if let Some(inner) = &mut self.inner {
+            inner.end_frame(display_id);
+        }
+    }
+
+    fn next_texture_id(&mut self) -> u32 {
+        let id = match &mut self.inner {
+            Some(inner) => inner.next_texture_id(),
+            None => next_id(&mut self.next_texture_id),
+        };
+        record(&self.recording, RecordedCall::NextTextureId(id));
+        id
+    }
+
+    fn create_texture(&mut self, texture_id: u32, bitmap: OwnedBitmap) {
+        match &mut self.inner {
+            Some(inner) => {
+                record(
+                    &self.recording,
+                    RecordedCall::CreateTexture {
+                        texture_id,
+                        bitmap: bitmap.clone(),
+                    },
+                );
+                inner.create_texture(texture_id, bitmap);
+            }
+            None => record(
+                &self.recording,
+                RecordedCall::CreateTexture { texture_id, bitmap },
+            ),
+        }
+    }
+
+    fn update_texture(&mut self, texture_id: u32, bitmap: OwnedBitmap) {
+        match &mut self.inner {
+            Some(inner) => {
+                record(
+                    &self.recording,
+                    RecordedCall::UpdateTexture {
+                        texture_id,
+                        bitmap: bitmap.clone(),
+                    },
+                );
+                inner.update_texture(texture_id, bitmap);
+            }
+            None => record(
+                &self.recording,
+                RecordedCall::UpdateTexture { texture_id, bitmap },
+            ),
+        }
+    }
+
+    fn destroy_texture(&mut self, texture_id: u32) {
+        record(&self.recording, RecordedCall::DestroyTexture { texture_id });
+        if let Some(inner) = &mut self.inner {
+            inner.destroy_texture(texture_id);
+        }
+    }
+
+    fn next_render_buffer_id(&mut self) -> u32 {
+        let id = match &mut self.inner {
+            Some(inner) => inner.next_render_buffer_id(),
+            None => next_id(&mut self.next_render_buffer_id),
+        };
+        record(&self.recording, RecordedCall::NextRenderBufferId(id));
+        id
+    }
+
+    fn create_render_buffer(&mut self, render_buffer_id: u32, render_buffer: RenderBuffer) {
+        record(
+            &self.recording,
+            RecordedCall::CreateRenderBuffer {
+                render_buffer_id,
+                render_buffer: render_buffer.clone(),
+            },
+        );
+        if let Some(inner) = &mut self.inner {
+            inner.create_render_buffer(render_buffer_id, render_buffer);
+        }
+    }
+
+    fn destroy_render_buffer(&mut self, render_buffer_id: u32) {
+        record(
+            &self.recording,
+            RecordedCall::DestroyRenderBuffer { render_buffer_id },
+        );
+        if let Some(inner) = &mut self.inner {
+            inner.destroy_render_buffer(render_buffer_id);
+        }
+    }
+
+    fn next_geometry_id(&mut self) -> u32 {
+        let id = match &mut self.inner {
+            Some(inner) => inner.next_geometry_id(),
+            None => next_id(&mut self.next_geometry_id),
+        };
+        record(&self.recording, RecordedCall::NextGeometryId(id));
+        id
+    }
+
+    fn create_geometry(
+        &mut self,
+        geometry_id: u32,
+        vertex_buffer: VertexBuffer,
+        index_buffer: IndexBuffer,
+    ) {
+        match &mut self.inner {
+            Some(inner) => {
+                record(
+                    &self.recording,
+                    RecordedCall::CreateGeometry {
+                        geometry_id,
+                        vertex_buffer: vertex_buffer.clone(),
+                        index_buffer: index_buffer.clone(),
+                    },
+                );
+                inner.create_geometry(geometry_id, vertex_buffer, index_buffer);
+            }
+            None => record(
+                &self.recording,
+                RecordedCall::CreateGeometry {
+                    geometry_id,
+                    vertex_buffer,
+                    index_buffer,
+                },
+            ),
+        }
+    }
+
+    fn update_geometry(
+        &mut self,
+        geometry_id: u32,
+        vertex_buffer: VertexBuffer,
+        index_buffer: IndexBuffer,
+    ) {
+        match &mut self.inner {
+            Some(inner) => {
+                record(
+                    &self.recording,
+                    RecordedCall::UpdateGeometry {
+                        geometry_id,
+                        vertex_buffer: vertex_buffer.clone(),
+                        index_buffer: index_buffer.clone(),
+                    },
+                );
+                inner.update_geometry(geometry_id, vertex_buffer, index_buffer);
+            }
+            None => record(
+                &self.recording,
+                RecordedCall::UpdateGeometry {
+                    geometry_id,
+                    vertex_buffer,
+                    index_buffer,
+                },
+            ),
+        }
+    }
+
+    fn destroy_geometry(&mut self, geometry_id: u32) {
+        record(
+            &self.recording,
+            RecordedCall::DestroyGeometry { geometry_id },
+        );
+        if let Some(inner) = &mut self.inner {
+            inner.destroy_geometry(geometry_id);
+        }
+    }
+
+    fn update_command_list(&mut self, command_list: Vec<GpuCommand>) {
+        match &mut self.inner {
+            Some(inner) => {
+                record(
+                    &self.recording,
+                    RecordedCall::UpdateCommandList(command_list.clone()),
+                );
+                inner.update_command_list(command_list);
+            }
+            None => record(
+                &self.recording,
+                RecordedCall::UpdateCommandList(command_list),
+            ),
+        }
+    }
+}
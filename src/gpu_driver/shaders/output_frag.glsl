@@ -0,0 +1,58 @@
+#version 150
+
+// Program Uniforms
+uniform sampler2D Texture;
+// whether sampling `Texture` returns linear values (sRGB textures)
+uniform bool SourceIsLinear;
+// 0 = sRGB (passthrough), 1 = linear, 2 = gamma power
+uniform int TransferFn;
+uniform float Gamma;
+uniform bool Dithering;
+// size of a single step in the source (8-bit) texture
+uniform float DitherAmplitude;
+
+// In Params
+in vec2 ex_TexCoord;
+
+// Out Params
+out vec4 out_Color;
+
+// 4x4 ordered dithering matrix, normalized to [0, 1)
+const float bayer[16] = float[16](
+     0.0 / 16.0,  8.0 / 16.0,  2.0 / 16.0, 10.0 / 16.0,
+    12.0 / 16.0,  4.0 / 16.0, 14.0 / 16.0,  6.0 / 16.0,
+     3.0 / 16.0, 11.0 / 16.0,  1.0 / 16.0,  9.0 / 16.0,
+    15.0 / 16.0,  7.0 / 16.0, 13.0 / 16.0,  5.0 / 16.0
+);
+
+vec3 sRGBToLinear(vec3 val) {
+    return mix(val / 12.92, pow((val + 0.055) / 1.055, vec3(2.4)), step(0.04045, val));
+}
+
+vec3 linearToSRGB(vec3 val) {
+    return mix(val * 12.92, 1.055 * pow(val, vec3(1.0 / 2.4)) - 0.055, step(0.0031308, val));
+}
+
+void main(void)
+{
+    vec4 color = texture(Texture, ex_TexCoord);
+    // work on the sRGB encoded values, since that's where the 8-bit
+    // quantization happened
+    if (SourceIsLinear) {
+        color.rgb = linearToSRGB(color.rgb);
+    }
+
+    if (Dithering) {
+        ivec2 p = ivec2(gl_FragCoord.xy) & 3;
+        float threshold = bayer[p.y * 4 + p.x] - 0.5;
+        color.rgb = clamp(color.rgb + threshold * DitherAmplitude, 0.0, 1.0);
+    }
+
+    if (TransferFn == 1) {
+        color.rgb = sRGBToLinear(color.rgb);
+    } else if (TransferFn == 2) {
+        color.rgb = pow(sRGBToLinear(color.rgb), vec3(1.0 / Gamma));
+    }
+
+    out_Color = color;
+}
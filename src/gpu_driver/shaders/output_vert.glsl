@@ -0,0 +1,14 @@
+#version 150
+
+// Out Params
+out vec2 ex_TexCoord;
+
+// Full screen triangle strip generated from the vertex id, no vertex
+// buffers needed.
+void main(void)
+{
+    vec2 position = vec2(float(gl_VertexID & 1), float((gl_VertexID >> 1) & 1));
+    // Ultralight textures are top-down
+    ex_TexCoord = vec2(position.x, 1.0 - position.y);
+    gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+}
@@ -0,0 +1,274 @@
+//! Rendering pages to bitmaps without any window (headless).
+//!
+//! This wraps the setup needed to use [`Renderer`] directly for the common
+//! case of rendering an HTML string or a URL into an image, for example:
+//!
+//! ```no_run
+//! # use ul_next::{headless::{self, HeadlessOptions}, Library};
+//! # let lib: std::sync::Arc<Library> = unsafe { std::mem::zeroed() };
+//! let bitmap = headless::render_html_to_bitmap(
+//!     lib.clone(),
+//!     "<h1>Hello</h1>",
+//!     800,
+//!     600,
+//!     &HeadlessOptions::default(),
+//! )
+//! .unwrap();
+//!
+//! bitmap.to_bitmap(lib).unwrap().write_to_png("hello.png").unwrap();
+//! ```
+//!
+//! # Platform handlers
+//!
+//! The first render creates a [`Renderer`] which is reused by later renders
+//! (Ultralight only supports creating one renderer per process), so the
+//! `lib` argument of the later calls is ignored. The renderer can't be moved
+//! to other threads, so rendering from another thread than the first one
+//! returns [`HeadlessError::WrongThread`]. Before creating the renderer, the
+//! following is installed:
+//! - The font loader, using
+//!   [`platform::enable_platform_fontloader`](crate::platform::enable_platform_fontloader).
+//!   This requires `AppCore`, so without it [`HeadlessError::FontLoaderUnavailable`]
+//!   is returned, as there is no other way to provide a font loader currently.
+//! - The file system, only if none was set with
+//!   [`platform::set_filesystem`](crate::platform::set_filesystem), using
+//!   [`platform::enable_platform_filesystem`](crate::platform::enable_platform_filesystem)
+//!   with the current directory as the base directory. Ultralight loads its
+//!   `resources/` (ICU data and certificates) from it.
+//!
+//! No logger or GPU driver is installed, the views are always rendered with
+//! the CPU renderer.
+//!
+//! Because of this, these functions shouldn't be used if the application
+//! creates its own [`Renderer`] or [`App`](crate::app::App).
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, OnceLock},
+    thread::ThreadId,
+    time::Duration,
+};
+
+use crate::{
+    bitmap::OwnedBitmap,
+    config::Config,
+    error::{CreationError, Error},
+    renderer::Renderer,
+    view::{View, ViewConfig},
+    Library,
+};
+
+/// Errors that can occur when rendering with [`render_html_to_bitmap`] or
+/// [`render_url_to_bitmap`].
+#[derive(Debug, thiserror::Error)]
+pub enum HeadlessError {
+    /// There is no way to load fonts, `AppCore` is needed for the platform
+    /// font loader.
+    #[error(
+        "No font loader available, load the library with AppCore \
+        (`Library::load_with_appcore` or the `appcore_linked` feature)"
+    )]
+    FontLoaderUnavailable,
+    /// Failed to create the renderer or the view.
+    #[error("Failed to create the renderer or view: {0}")]
+    CreationError(#[from] CreationError),
+    /// Failed to update or render, see [`Error::ReentrantCall`].
+    #[error("Failed to update or render: {0}")]
+    Render(#[from] Error),
+    /// The page failed to load.
+    #[error("Failed to load `{url}`: {description}")]
+    LoadFailed {
+        /// The URL that failed to load.
+        url: String,
+        /// Human readable description of the error.
+        description: String,
+    },
+    /// The page didn't finish loading before [`HeadlessOptions::timeout`].
+    #[error("Timed out waiting for the page to load")]
+    Timeout,
+    /// The rendered pixels couldn't be read from the view surface.
+    #[error("Failed to read the pixels of the rendered view")]
+    NoSurface,
+    /// The renderer was created by another thread, all the renders must be
+    /// done from the same thread.
+    #[error("The headless renderer was created by another thread")]
+    WrongThread,
+}
+
+/// Options for [`render_html_to_bitmap`] and [`render_url_to_bitmap`].
+#[derive(Debug, Clone)]
+pub struct HeadlessOptions {
+    /// The maximum time to wait for the page to finish loading.
+    ///
+    /// (Default = 10 seconds)
+    pub timeout: Duration,
+    /// Whether the background of the page can be transparent,
+    /// see [`ViewConfigBuilder::is_transparent`](crate::view::ViewConfigBuilder::is_transparent).
+    ///
+    /// (Default = false)
+    pub transparent: bool,
+    /// The device scale, the size of the resulting bitmap is the size
+    /// of the view multiplied by this.
+    ///
+    /// (Default = 1.0)
+    pub device_scale: f64,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            transparent: false,
+            device_scale: 1.0,
+        }
+    }
+}
+
+/// Render an HTML string into a bitmap of `width`x`height` view pixels.
+///
+/// See the [module documentation](self) for the platform handlers installed.
+pub fn render_html_to_bitmap(
+    lib: Arc<Library>,
+    html: &str,
+    width: u32,
+    height: u32,
+    options: &HeadlessOptions,
+) -> Result<OwnedBitmap, HeadlessError> {
+    render_to_bitmap(lib, width, height, options, |view| view.load_html(html))
+}
+
+/// Load `url` and render it into a bitmap of `width`x`height` view pixels.
+///
+/// See the [module documentation](self) for the platform handlers installed.
+pub fn render_url_to_bitmap(
+    lib: Arc<Library>,
+    url: &str,
+    width: u32,
+    height: u32,
+    options: &HeadlessOptions,
+) -> Result<OwnedBitmap, HeadlessError> {
+    render_to_bitmap(lib, width, height, options, |view| view.load_url(url))
+}
+
+/// The interval to call [`Renderer::update`] while waiting for the page to load.
+const UPDATE_INTERVAL: Duration = Duration::from_millis(5);
+
+/// The thread that created the renderer, set once for the whole process.
+static RENDERER_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+thread_local! {
+    /// The renderer, only ever set in the thread of [`RENDERER_THREAD`].
+    static RENDERER: RefCell<Option<Renderer>> = const { RefCell::new(None) };
+}
+
+enum LoadState {
+    Loading,
+    Finished,
+    Failed { url: String, description: String },
+}
+
+fn render_to_bitmap(
+    lib: Arc<Library>,
+    width: u32,
+    height: u32,
+    options: &HeadlessOptions,
+    load: impl FnOnce(&View) -> Result<(), CreationError>,
+) -> Result<OwnedBitmap, HeadlessError> {
+    let current_thread = std::thread::current().id();
+    if *RENDERER_THREAD.get_or_init(|| current_thread) != current_thread {
+        return Err(HeadlessError::WrongThread);
+    }
+
+    RENDERER.with(|renderer| {
+        let mut renderer = renderer.borrow_mut();
+        if renderer.is_none() {
+            *renderer = Some(create_renderer(lib.clone())?);
+        }
+        let renderer = renderer.as_ref().unwrap();
+
+        let view_config = ViewConfig::start()
+            .is_accelerated(false)
+            .is_transparent(options.transparent)
+            .initial_device_scale(options.device_scale)
+            .try_build(lib.clone())?;
+        let view = renderer.create_view(width, height, &view_config, None)?;
+
+        let state = Rc::new(RefCell::new(LoadState::Loading));
+        {
+            let state = state.clone();
+            view.set_finish_loading_callback(move |_view, _frame_id, is_main_frame, _url| {
+                if is_main_frame {
+                    *state.borrow_mut() = LoadState::Finished;
+                }
+            });
+        }
+        {
+            let state = state.clone();
+            view.set_fail_loading_callback(
+                move |_view, _frame_id, is_main_frame, url, description, _domain, _code| {
+                    if is_main_frame {
+                        *state.borrow_mut() = LoadState::Failed { url, description };
+                    }
+                },
+            );
+        }
+
+        load(&view)?;
+
+        let start = std::time::Instant::now();
+        loop {
+            renderer.update()?;
+
+            match &*state.borrow() {
+                LoadState::Loading => {}
+                LoadState::Finished => break,
+                LoadState::Failed { url, description } => {
+                    return Err(HeadlessError::LoadFailed {
+                        url: url.clone(),
+                        description: description.clone(),
+                    })
+                }
+            }
+
+            if start.elapsed() > options.timeout {
+                return Err(HeadlessError::Timeout);
+            }
+            std::thread::sleep(UPDATE_INTERVAL);
+        }
+
+        renderer.render()?;
+
+        let mut bitmap = view
+            .surface()
+            .and_then(|surface| surface.bitmap())
+            .ok_or(HeadlessError::NoSurface)?;
+        OwnedBitmap::from_bitmap(&mut bitmap).ok_or(HeadlessError::NoSurface)
+    })
+}
+
+fn create_renderer(lib: Arc<Library>) -> Result<Renderer, HeadlessError> {
+    install_platform(&lib)?;
+
+    let config = Config::start().try_build(lib)?;
+    Ok(Renderer::create(config)?)
+}
+
+#[cfg(any(feature = "appcore_linked", feature = "loaded"))]
+fn install_platform(lib: &Arc<Library>) -> Result<(), HeadlessError> {
+    use crate::platform;
+
+    if lib.lib.try_appcore().is_none() {
+        return Err(HeadlessError::FontLoaderUnavailable);
+    }
+
+    platform::enable_platform_fontloader(lib.clone());
+    if !platform::is_filesystem_set() {
+        platform::enable_platform_filesystem(lib.clone(), ".")?;
+    }
+    Ok(())
+}
+
+#[cfg(not(any(feature = "appcore_linked", feature = "loaded")))]
+fn install_platform(_lib: &Arc<Library>) -> Result<(), HeadlessError> {
+    Err(HeadlessError::FontLoaderUnavailable)
+}
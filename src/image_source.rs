@@ -43,6 +43,10 @@ use crate::{bitmap::Bitmap, error::CreationError, string::UlString, Library, Rec
 /// specified texture ID and UV coordinates. You should bind your own texture when the specified
 /// texture ID is used.
 ///
+/// With the `glium` driver (feature `glium`), the texture ID should instead be reserved with
+/// `GliumGpuDriverSender::reserve_external_texture_id` and the texture registered with
+/// `GliumGpuDriverReceiver::register_external_texture`, the driver then binds it for you.
+///
 /// Note: If the GPU renderer is not enabled for the View or pixel data is needed for other
 ///       purposes, the library will sample the backing bitmap instead.
 ///
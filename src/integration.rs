@@ -0,0 +1,11 @@
+//! Glue code to use Ultralight with windowing libraries.
+//!
+//! The conversion of single events is available in [`event`](crate::event)
+//! (eg. [`KeyEvent::from_sdl2`](crate::event::KeyEvent::from_sdl2)), the
+//! modules here put them together with the GPU drivers of
+//! [`gpu_driver`](crate::gpu_driver), to get a view on screen with a few
+//! calls.
+
+#[cfg(feature = "sdl2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+pub mod sdl2;
@@ -0,0 +1,318 @@
+//! Rendering a [`View`] in an [`sdl2`] window, and forwarding the SDL events
+//! to it.
+//!
+//! [`handle_event`] forwards the input events of SDL to a view, and
+//! [`Sdl2GlDriverHost`] (with the `glium` feature) creates the OpenGL
+//! context of a window and the [`glium`](crate::gpu_driver::glium) GPU
+//! driver on top of it.
+//!
+//! See the `sdl2_gl` example for a complete program.
+use sdl2::{
+    event::{Event, WindowEvent},
+    video::Window,
+};
+
+use crate::{
+    error::Error,
+    event::{KeyEvent, MouseEvent, ScrollEvent},
+    view::View,
+};
+
+#[cfg(feature = "glium")]
+pub use gl::{Sdl2GlDriverHost, Sdl2GlDriverHostError};
+
+/// Forward `event` to `view`.
+///
+/// Handles:
+/// - keyboard and text input events, with [`KeyEvent::from_sdl2`] (text
+///   input must be started with [`sdl2::keyboard::TextInputUtil::start`]
+///   to receive the typed text).
+/// - mouse motion and button events, with [`MouseEvent::from_sdl2`], the
+///   positions are scaled with the [`window_scale`] of `window`.
+/// - mouse wheel events, with [`ScrollEvent::from_sdl2`].
+/// - [`WindowEvent::SizeChanged`], resizing the view to the new drawable
+///   size of `window` (see [`Window::drawable_size`]).
+/// - [`WindowEvent::FocusGained`] and [`WindowEvent::FocusLost`], focusing
+///   and unfocusing the view.
+///
+/// Events of all the windows are handled, check the `window_id` of the
+/// event before calling this when there are several windows.
+///
+/// Returns whether the event was forwarded to the view.
+pub fn handle_event(event: &Event, window: &Window, view: &View) -> Result<bool, Error> {
+    let lib = view.lib().clone();
+
+    match event {
+        Event::KeyDown { .. } | Event::KeyUp { .. } | Event::TextInput { .. } => {
+            match KeyEvent::from_sdl2(lib, event)? {
+                Some(key_event) => {
+                    view.fire_key_event(key_event);
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+        Event::MouseMotion { .. } | Event::MouseButtonDown { .. } | Event::MouseButtonUp { .. } => {
+            match MouseEvent::from_sdl2(lib, event, window_scale(window))? {
+                Some(mouse_event) => {
+                    view.fire_mouse_event(mouse_event);
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+        Event::MouseWheel { .. } => match ScrollEvent::from_sdl2(lib, event)? {
+            Some(scroll_event) => {
+                view.fire_scroll_event(scroll_event);
+                Ok(true)
+            }
+            None => Ok(false),
+        },
+        Event::Window { win_event, .. } => match win_event {
+            WindowEvent::SizeChanged(width, height) if *width > 0 && *height > 0 => {
+                let (width, height) = window.drawable_size();
+                view.resize(width, height)?;
+                Ok(true)
+            }
+            WindowEvent::FocusGained => {
+                view.focus();
+                Ok(true)
+            }
+            WindowEvent::FocusLost => {
+                view.unfocus();
+                Ok(true)
+            }
+            _ => Ok(false),
+        },
+        _ => Ok(false),
+    }
+}
+
+/// The ratio between the drawable size of `window` (in pixels) and its
+/// size (in window coordinates, used by the mouse events), this is more than
+/// `1.0` for windows created with
+/// [`WindowBuilder::allow_highdpi`](sdl2::video::WindowBuilder::allow_highdpi)
+/// on high DPI displays.
+pub fn window_scale(window: &Window) -> f64 {
+    let (width, _) = window.size();
+    let (drawable_width, _) = window.drawable_size();
+    if width == 0 {
+        1.0
+    } else {
+        drawable_width as f64 / width as f64
+    }
+}
+
+#[cfg(feature = "glium")]
+mod gl {
+    use std::{ffi::c_void, rc::Rc};
+
+    use glium::{
+        backend::{Backend, Context, Facade},
+        debug::DebugCallbackBehavior,
+        Frame, IncompatibleOpenGl, Surface, SwapBuffersError,
+    };
+    use sdl2::{
+        video::{GLContext, Window},
+        VideoSubsystem,
+    };
+
+    use crate::{
+        gpu_driver::glium::{
+            create_gpu_driver, GliumGpuDriverError, GliumGpuDriverReceiver, GliumGpuDriverSender,
+        },
+        view::View,
+    };
+
+    /// Errors that can occur in [`Sdl2GlDriverHost`].
+    #[derive(Debug, thiserror::Error)]
+    pub enum Sdl2GlDriverHostError {
+        /// SDL failed to create or activate the OpenGL context of the window.
+        #[error("Failed to use the OpenGL context: {0}")]
+        GlContext(String),
+        /// The OpenGL version of the context is not supported by `glium`.
+        #[error("{0}")]
+        IncompatibleOpenGl(#[from] IncompatibleOpenGl),
+        /// Failed to create the GPU driver or to draw with it.
+        #[error("{0}")]
+        GpuDriver(#[from] GliumGpuDriverError),
+        /// Failed to present the frame.
+        #[error("{0}")]
+        SwapBuffers(#[from] SwapBuffersError),
+        /// The view drawn is not accelerated, so it has no render target
+        /// (see [`ViewConfigBuilder::is_accelerated`](crate::view::ViewConfigBuilder::is_accelerated)).
+        #[error("The view is not accelerated")]
+        NotAccelerated,
+    }
+
+    /// The `glium` backend of an SDL window and its OpenGL context.
+    struct Sdl2Backend {
+        video: VideoSubsystem,
+        window: Window,
+        gl_context: GLContext,
+    }
+
+    unsafe impl Backend for Sdl2Backend {
+        fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
+            self.window.gl_swap_window();
+            Ok(())
+        }
+
+        unsafe fn get_proc_address(&self, symbol: &str) -> *const c_void {
+            self.video.gl_get_proc_address(symbol) as *const c_void
+        }
+
+        fn get_framebuffer_dimensions(&self) -> (u32, u32) {
+            self.window.drawable_size()
+        }
+
+        fn resize(&self, _new_size: (u32, u32)) {
+            // SDL resizes the default framebuffer with the window
+        }
+
+        fn is_current(&self) -> bool {
+            self.gl_context.is_current()
+        }
+
+        unsafe fn make_current(&self) {
+            if let Err(err) = self.window.gl_make_current(&self.gl_context) {
+                panic!("Failed to make the OpenGL context current: {}", err);
+            }
+        }
+    }
+
+    /// The OpenGL context of an SDL window, with the
+    /// [`glium`](crate::gpu_driver::glium) GPU driver drawing in it.
+    ///
+    /// The window must be created with
+    /// [`WindowBuilder::opengl`](sdl2::video::WindowBuilder::opengl), and
+    /// the attributes of the context (see [`VideoSubsystem::gl_attr`])
+    /// must be set before creating the window, `glium` needs at least
+    /// OpenGL 3.3 (core profile).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ul_next::{config::Config, integration::{self, sdl2::Sdl2GlDriverHost}, platform, renderer::Renderer, view::ViewConfig};
+    /// # fn example(
+    /// #     lib: std::sync::Arc<ul_next::Library>,
+    /// #     config: Config,
+    /// #     view_config: &ViewConfig,
+    /// #     video: &sdl2::VideoSubsystem,
+    /// #     window: sdl2::video::Window,
+    /// #     event_pump: &mut sdl2::EventPump,
+    /// # ) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut host = Sdl2GlDriverHost::new(&video, window)?;
+    /// platform::set_gpu_driver(lib.clone(), host.take_gpu_driver().unwrap());
+    ///
+    /// let renderer = Renderer::create(config)?;
+    /// // ... create an accelerated view
+    /// # let view = renderer.create_view(800, 600, view_config, None)?;
+    ///
+    /// loop {
+    ///     for event in event_pump.poll_iter() {
+    ///         integration::sdl2::handle_event(&event, host.window(), &view)?;
+    ///     }
+    ///
+    ///     renderer.update()?;
+    ///     renderer.render()?;
+    ///     host.draw_view(&view)?;
+    /// }
+    /// # }
+    /// ```
+    pub struct Sdl2GlDriverHost {
+        window: Window,
+        context: Rc<Context>,
+        sender: Option<GliumGpuDriverSender>,
+        receiver: GliumGpuDriverReceiver,
+    }
+
+    impl Sdl2GlDriverHost {
+        /// Create the OpenGL context of `window`, and the GPU driver using it.
+        ///
+        /// The context is made current, and must stay current on this thread
+        /// while drawing.
+        pub fn new(video: &VideoSubsystem, window: Window) -> Result<Self, Sdl2GlDriverHostError> {
+            let gl_context = window
+                .gl_create_context()
+                .map_err(Sdl2GlDriverHostError::GlContext)?;
+            window
+                .gl_make_current(&gl_context)
+                .map_err(Sdl2GlDriverHostError::GlContext)?;
+
+            let backend = Sdl2Backend {
+                video: video.clone(),
+                window: window.clone(),
+                gl_context,
+            };
+            let context = unsafe { Context::new(backend, true, DebugCallbackBehavior::default())? };
+            let (sender, receiver) = create_gpu_driver(&context)?;
+
+            Ok(Self {
+                window,
+                context,
+                sender: Some(sender),
+                receiver,
+            })
+        }
+
+        /// Take the sender part of the GPU driver, to pass it to
+        /// [`platform::set_gpu_driver`](crate::platform::set_gpu_driver)
+        /// before creating the renderer.
+        ///
+        /// Returns [`None`] if it was already taken.
+        pub fn take_gpu_driver(&mut self) -> Option<GliumGpuDriverSender> {
+            self.sender.take()
+        }
+
+        /// The window drawn into.
+        pub fn window(&self) -> &Window {
+            &self.window
+        }
+
+        /// The receiver part of the GPU driver, to get the textures of the
+        /// views (see [`GliumGpuDriverReceiver::get_texture`]).
+        pub fn receiver(&self) -> &GliumGpuDriverReceiver {
+            &self.receiver
+        }
+
+        /// The receiver part of the GPU driver, to change the output
+        /// transform or register external textures.
+        pub fn receiver_mut(&mut self) -> &mut GliumGpuDriverReceiver {
+            &mut self.receiver
+        }
+
+        /// The size of the window in pixels, which is larger than its size
+        /// in window coordinates on high-DPI displays.
+        pub fn drawable_size(&self) -> (u32, u32) {
+            self.window.drawable_size()
+        }
+
+        /// Run the GPU commands of the last
+        /// [`Renderer::render`](crate::renderer::Renderer::render), then
+        /// draw `view` to cover the whole window and present the frame.
+        pub fn draw_view(&mut self, view: &View) -> Result<(), Sdl2GlDriverHostError> {
+            self.receiver.render()?;
+            let render_target = view
+                .render_target()
+                .ok_or(Sdl2GlDriverHostError::NotAccelerated)?;
+
+            let mut frame = Frame::new(
+                self.context.clone(),
+                self.context.get_framebuffer_dimensions(),
+            );
+            frame.clear_color(0.0, 0.0, 0.0, 0.0);
+            let result = self
+                .receiver
+                .draw_texture(&render_target.texture_id, &mut frame);
+            // the frame must be finished even if drawing failed
+            frame.finish()?;
+            result.map_err(Into::into)
+        }
+    }
+
+    impl Facade for Sdl2GlDriverHost {
+        fn get_context(&self) -> &Rc<Context> {
+            &self.context
+        }
+    }
+}
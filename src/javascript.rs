@@ -22,13 +22,22 @@
 
 mod class;
 mod context;
+#[cfg(feature = "serde")]
+mod json;
 mod object;
 mod string;
 mod typed_array;
 mod value;
 
+pub use class::{JSClass, JSClassBuilder};
 pub use context::JSContext;
-pub use object::{JSObject, JSPropertyAttributes, JSPropertyNameArray};
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use json::{from_value, to_value, JsSerdeError};
+pub use object::{
+    JSObject, JSPropertyAttributes, JSPropertyGetter, JSPropertyNameArray, JSPropertyNames,
+    JSPropertySetter,
+};
 pub use string::JSString;
-pub use typed_array::{JSTypedArray, JSTypedArrayType};
-pub use value::{AsJSValue, JSType, JSValue};
+pub use typed_array::{JSTypedArray, JSTypedArrayElement, JSTypedArrayType};
+pub use value::{AsJSValue, JSException, JSType, JSValue};
@@ -1,3 +1,17 @@
+use std::{
+    any::Any,
+    cell::{RefCell, RefMut},
+    collections::{BTreeMap, HashMap},
+    ffi::c_void,
+    fmt,
+    rc::Rc,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::Library;
+
+use super::{JSContext, JSObject, JSPropertyAttributes, JSString, JSValue};
+
 pub(crate) const EMPTY_CLASS_DEF: ul_sys::JSClassDefinition = ul_sys::JSClassDefinition {
     version: 0,
     attributes: 0,
@@ -17,3 +31,461 @@ pub(crate) const EMPTY_CLASS_DEF: ul_sys::JSClassDefinition = ul_sys::JSClassDef
     hasInstance: None,
     convertToType: None,
 };
+
+/// The kind of private data of an object created by this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NativeObjectKind {
+    /// Created by [`JSObject::new_function_with_callback`].
+    Function,
+    /// Created by [`JSObject::new_with_class`], the private data is [`NativeObject`].
+    ClassInstance,
+}
+
+/// The start of the private data of every object created by this crate,
+/// the trampolines only get the object, and need the library to call into it.
+#[repr(C)]
+pub(crate) struct NativeHeader {
+    pub(crate) lib: Arc<Library>,
+    pub(crate) kind: NativeObjectKind,
+}
+
+type GetPrivateFn = unsafe extern "C" fn(ul_sys::JSObjectRef) -> *mut c_void;
+
+/// The `JSObjectGetPrivate` of the library of each alive object created with
+/// a [`NativeHeader`], by address.
+///
+/// The trampolines only get the object, so this is used to get its header
+/// (and the library in it), the objects of different [`Library`]s can be
+/// alive at the same time.
+static NATIVE_OBJECTS: Mutex<BTreeMap<usize, GetPrivateFn>> = Mutex::new(BTreeMap::new());
+
+/// Must be called after creating `object` with a [`NativeHeader`] with `lib`.
+pub(crate) fn register_native_object(lib: &Library, object: ul_sys::JSObjectRef) {
+    let get_private: GetPrivateFn = lib.ultralight().JSObjectGetPrivate;
+    NATIVE_OBJECTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(object as usize, get_private);
+}
+
+/// Must be called when `object` is finalized, before its address can be
+/// reused.
+pub(crate) fn unregister_native_object(object: ul_sys::JSObjectRef) {
+    NATIVE_OBJECTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remove(&(object as usize));
+}
+
+/// Get the private data of an object created with a [`NativeHeader`] by
+/// this crate, or null if it has no private data.
+///
+/// # Safety
+/// `object` must be alive.
+pub(crate) unsafe fn native_private(object: ul_sys::JSObjectRef) -> *mut NativeHeader {
+    let get_private = NATIVE_OBJECTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&(object as usize))
+        .copied();
+    match get_private {
+        Some(get_private) => get_private(object) as *mut NativeHeader,
+        None => std::ptr::null_mut(),
+    }
+}
+
+type MethodFn = dyn for<'c> Fn(
+    &'c JSContext,
+    &JSObject<'c>,
+    &[JSValue<'c>],
+) -> Result<JSValue<'c>, JSValue<'c>>;
+type GetterFn = dyn for<'c> Fn(&'c JSContext, &JSObject<'c>) -> Result<JSValue<'c>, JSValue<'c>>;
+type SetterFn =
+    dyn for<'c> Fn(&'c JSContext, &JSObject<'c>, &JSValue<'c>) -> Result<(), JSValue<'c>>;
+type FinalizeFn = dyn FnMut(Box<dyn Any>);
+
+struct ClassProperty {
+    getter: Box<GetterFn>,
+    setter: Option<Box<SetterFn>>,
+}
+
+struct ClassInner {
+    lib: Arc<Library>,
+    internal: ul_sys::JSClassRef,
+    name: String,
+    methods: Vec<(String, Rc<MethodFn>)>,
+    properties: HashMap<String, ClassProperty>,
+    finalize: RefCell<Option<Box<FinalizeFn>>>,
+}
+
+impl Drop for ClassInner {
+    fn drop(&mut self) {
+        unsafe {
+            self.lib.ultralight().JSClassRelease(self.internal);
+        }
+    }
+}
+
+/// The private data of objects created with [`JSObject::new_with_class`].
+#[repr(C)]
+pub(crate) struct NativeObject {
+    header: NativeHeader,
+    class: Rc<ClassInner>,
+    data: RefCell<Box<dyn Any>>,
+}
+
+impl NativeObject {
+    pub(crate) fn data<T: Any>(&self) -> Option<RefMut<'_, T>> {
+        let data = self.data.try_borrow_mut().ok()?;
+        RefMut::filter_map(data, |data| data.downcast_mut::<T>()).ok()
+    }
+}
+
+/// Builder for [`JSClass`], a class of JavaScript objects backed by Rust data.
+///
+/// # Example
+/// ```rust,no_run
+/// # use ul_next::javascript::*;
+/// # let ctx: JSContext = unsafe {std::mem::zeroed()};
+/// # let lib: std::sync::Arc<ul_next::Library> = unsafe {std::mem::zeroed()};
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// let class = JSClassBuilder::new("Counter")
+///     .method("increment", |ctx, this, _args| {
+///         this.private_data::<Counter>().unwrap().count += 1;
+///         Ok(JSValue::new_undefined(ctx))
+///     })
+///     .property("count", |ctx, this| {
+///         let count = this.private_data::<Counter>().unwrap().count;
+///         Ok(JSValue::new_number(ctx, count as f64))
+///     })
+///     .build(lib);
+///
+/// let counter = JSObject::new_with_class(&ctx, &class, Box::new(Counter { count: 0 }));
+/// ctx.global_object()
+///     .set_property("counter", &counter, JSPropertyAttributes::default())
+///     .unwrap();
+/// ```
+pub struct JSClassBuilder {
+    name: String,
+    methods: Vec<(String, Rc<MethodFn>)>,
+    properties: HashMap<String, ClassProperty>,
+    finalize: Option<Box<FinalizeFn>>,
+}
+
+impl JSClassBuilder {
+    /// Starts building a class with the given name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            methods: Vec::new(),
+            properties: HashMap::new(),
+            finalize: None,
+        }
+    }
+
+    /// Adds a method to the objects of the class.
+    ///
+    /// The method is called with the `this` object of the call, use
+    /// [`JSObject::private_data`] on it to access the Rust data.
+    pub fn method<F>(mut self, name: &str, method: F) -> Self
+    where
+        for<'c> F: Fn(&'c JSContext, &JSObject<'c>, &[JSValue<'c>]) -> Result<JSValue<'c>, JSValue<'c>>
+            + 'static,
+    {
+        self.methods.push((name.to_string(), Rc::new(method)));
+        self
+    }
+
+    /// Adds a read-only property to the objects of the class, implemented by
+    /// `getter`.
+    ///
+    /// Assigning the property is ignored, see
+    /// [`JSClassBuilder::property_with_setter`] to handle it.
+    pub fn property<G>(mut self, name: &str, getter: G) -> Self
+    where
+        for<'c> G: Fn(&'c JSContext, &JSObject<'c>) -> Result<JSValue<'c>, JSValue<'c>> + 'static,
+    {
+        self.properties.insert(
+            name.to_string(),
+            ClassProperty {
+                getter: Box::new(getter),
+                setter: None,
+            },
+        );
+        self
+    }
+
+    /// Adds a property to the objects of the class, implemented by `getter`
+    /// and `setter`.
+    pub fn property_with_setter<G, S>(mut self, name: &str, getter: G, setter: S) -> Self
+    where
+        for<'c> G: Fn(&'c JSContext, &JSObject<'c>) -> Result<JSValue<'c>, JSValue<'c>> + 'static,
+        for<'c> S:
+            Fn(&'c JSContext, &JSObject<'c>, &JSValue<'c>) -> Result<(), JSValue<'c>> + 'static,
+    {
+        self.properties.insert(
+            name.to_string(),
+            ClassProperty {
+                getter: Box::new(getter),
+                setter: Some(Box::new(setter)),
+            },
+        );
+        self
+    }
+
+    /// Sets a function to be called with the Rust data of an object when
+    /// it is garbage collected.
+    ///
+    /// The data is dropped after the function returns (or directly if
+    /// there is no finalizer).
+    ///
+    /// Note that this is called during garbage collection, so it must not
+    /// call any JavaScript API.
+    pub fn finalize<F>(mut self, finalize: F) -> Self
+    where
+        F: FnMut(Box<dyn Any>) + 'static,
+    {
+        self.finalize = Some(Box::new(finalize));
+        self
+    }
+
+    /// Creates the class.
+    pub fn build(self, lib: Arc<Library>) -> JSClass {
+        // interior null bytes can't be represented in the class name
+        let c_name = crate::string::to_c_string_lossy(&self.name);
+
+        let class_def = ul_sys::JSClassDefinition {
+            className: c_name.as_ptr(),
+            finalize: Some(finalize_native_object),
+            getProperty: Some(get_property),
+            setProperty: Some(set_property),
+            getPropertyNames: Some(get_property_names),
+            ..EMPTY_CLASS_DEF
+        };
+
+        let internal = unsafe { lib.ultralight().JSClassCreate(&class_def) };
+
+        JSClass {
+            inner: Rc::new(ClassInner {
+                lib,
+                internal,
+                name: self.name,
+                methods: self.methods,
+                properties: self.properties,
+                finalize: RefCell::new(self.finalize),
+            }),
+        }
+    }
+}
+
+/// A class of JavaScript objects backed by Rust data, created with
+/// [`JSClassBuilder`].
+///
+/// Objects of the class are created with [`JSObject::new_with_class`].
+#[derive(Clone)]
+pub struct JSClass {
+    inner: Rc<ClassInner>,
+}
+
+impl JSClass {
+    /// Returns the name of the class.
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// Creates a new object of this class, used by [`JSObject::new_with_class`].
+    pub(crate) fn make_object<'a>(&self, ctx: &'a JSContext, data: Box<dyn Any>) -> JSObject<'a> {
+        let private_data = Box::into_raw(Box::new(NativeObject {
+            header: NativeHeader {
+                lib: ctx.lib.clone(),
+                kind: NativeObjectKind::ClassInstance,
+            },
+            class: self.inner.clone(),
+            data: RefCell::new(data),
+        }));
+
+        let obj = unsafe {
+            ctx.lib
+                .ultralight()
+                .JSObjectMake(ctx.internal, self.inner.internal, private_data as _)
+        };
+        register_native_object(&ctx.lib, obj);
+
+        let obj = JSObject {
+            value: JSValue::from_raw(ctx, obj),
+        };
+
+        for (name, method) in &self.inner.methods {
+            let method = method.clone();
+            let function = JSObject::new_function_with_callback(ctx, move |ctx, this, args| {
+                method(ctx, this, args)
+            });
+            // can't fail, the object doesn't have any setters for methods
+            let _ = obj.set_property(name, &function, JSPropertyAttributes::new().dont_enum(true));
+        }
+
+        obj
+    }
+}
+
+impl fmt::Debug for JSClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JSClass")
+            .field("name", &self.inner.name)
+            .finish()
+    }
+}
+
+/// Get the private data of an object created with [`JSClass::make_object`].
+///
+/// Objects without private data return [`None`], the others must have been
+/// created by this crate.
+///
+/// # Safety
+/// `object` must be alive for `'a`.
+pub(crate) unsafe fn native_object<'a>(
+    lib: &Library,
+    object: ul_sys::JSObjectRef,
+) -> Option<&'a NativeObject> {
+    let header = (lib.ultralight().JSObjectGetPrivate(object) as *const NativeHeader).as_ref()?;
+    if header.kind != NativeObjectKind::ClassInstance {
+        return None;
+    }
+    Some(&*(header as *const NativeHeader as *const NativeObject))
+}
+
+/// Same as [`native_object`], for the trampolines of the classes, which
+/// don't have the library.
+///
+/// # Safety
+/// `object` must be alive for `'a`, and be an object of a [`JSClass`].
+unsafe fn class_object<'a>(object: ul_sys::JSObjectRef) -> Option<&'a NativeObject> {
+    let header = native_private(object).as_ref()?;
+    native_object(&header.lib, object)
+}
+
+unsafe extern "C" fn finalize_native_object(object: ul_sys::JSObjectRef) {
+    let _guard = scopeguard::guard_on_unwind((), |()| {
+        ::std::process::abort();
+    });
+
+    crate::callback_panic::catch_callback_panic(|| {
+        if class_object(object).is_none() {
+            return;
+        }
+        let private_data = native_private(object) as *mut NativeObject;
+        unregister_native_object(object);
+        let NativeObject { class, data, .. } = *Box::from_raw(private_data);
+
+        let mut finalize = class.finalize.borrow_mut();
+        if let Some(finalize) = finalize.as_mut() {
+            finalize(data.into_inner());
+        }
+    })
+}
+
+unsafe extern "C" fn get_property(
+    ctx: ul_sys::JSContextRef,
+    object: ul_sys::JSObjectRef,
+    property_name: ul_sys::JSStringRef,
+    exception: *mut ul_sys::JSValueRef,
+) -> ul_sys::JSValueRef {
+    let _guard = scopeguard::guard_on_unwind((), |()| {
+        ::std::process::abort();
+    });
+
+    // `null` means that the property is not handled by the class
+    crate::callback_panic::catch_callback_panic(|| -> ul_sys::JSValueRef {
+        let Some(native) = class_object(object) else {
+            return std::ptr::null();
+        };
+        let lib = native.header.lib.clone();
+
+        let name = String::from(&JSString::copy_from_raw(lib.clone(), property_name));
+        let Some(property) = native.class.properties.get(&name) else {
+            return std::ptr::null();
+        };
+
+        let ctx = JSContext::copy_from_raw(lib, ctx);
+        let this = JSObject::copy_from_raw(&ctx, object);
+
+        // bound to a local, a temporary in the tail expression would
+        // outlive `ctx`
+        let result = (property.getter)(&ctx, &this);
+        match result {
+            Ok(value) => value.into_raw(),
+            Err(value) => {
+                if !exception.is_null() {
+                    *exception = value.into_raw();
+                }
+                std::ptr::null()
+            }
+        }
+    })
+}
+
+unsafe extern "C" fn set_property(
+    ctx: ul_sys::JSContextRef,
+    object: ul_sys::JSObjectRef,
+    property_name: ul_sys::JSStringRef,
+    value: ul_sys::JSValueRef,
+    exception: *mut ul_sys::JSValueRef,
+) -> bool {
+    let _guard = scopeguard::guard_on_unwind((), |()| {
+        ::std::process::abort();
+    });
+
+    // `false` means that the property is not handled by the class
+    crate::callback_panic::catch_callback_panic(|| {
+        let Some(native) = class_object(object) else {
+            return false;
+        };
+        let lib = native.header.lib.clone();
+
+        let name = String::from(&JSString::copy_from_raw(lib.clone(), property_name));
+        let Some(property) = native.class.properties.get(&name) else {
+            return false;
+        };
+        let Some(setter) = &property.setter else {
+            // read-only, ignore the assignment
+            return true;
+        };
+
+        let ctx = JSContext::copy_from_raw(lib, ctx);
+        let this = JSObject::copy_from_raw(&ctx, object);
+        let value = JSValue::copy_from_raw(&ctx, value);
+
+        if let Err(value) = setter(&ctx, &this, &value) {
+            if !exception.is_null() {
+                *exception = value.into_raw();
+            }
+        }
+        true
+    })
+}
+
+unsafe extern "C" fn get_property_names(
+    _ctx: ul_sys::JSContextRef,
+    object: ul_sys::JSObjectRef,
+    property_names: ul_sys::JSPropertyNameAccumulatorRef,
+) {
+    let _guard = scopeguard::guard_on_unwind((), |()| {
+        ::std::process::abort();
+    });
+
+    crate::callback_panic::catch_callback_panic(|| {
+        let Some(native) = class_object(object) else {
+            return;
+        };
+        let lib = &native.header.lib;
+
+        for name in native.class.properties.keys() {
+            let name = JSString::new(lib.clone(), name);
+            lib.ultralight()
+                .JSPropertyNameAccumulatorAddName(property_names, name.internal);
+        }
+    })
+}
@@ -33,6 +33,21 @@ impl JSContext {
     }
 
     /// Get the global object for this context.
+    ///
+    /// This is the object holding the global variables of the page (`window`
+    /// in a [`View`](crate::view::View)), properties set on it are visible to
+    /// all scripts as globals.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use ul_next::javascript::{JSContext, JSObject, JSPropertyAttributes};
+    /// # fn example(ctx: &JSContext) {
+    /// let bridge = JSObject::new(ctx);
+    /// ctx.global_object()
+    ///     .set_property("myAPI", &bridge, JSPropertyAttributes::default())
+    ///     .unwrap();
+    /// # }
+    /// ```
     pub fn global_object(&self) -> JSObject {
         JSObject::copy_from_raw(self, unsafe {
             self.lib
@@ -79,19 +94,38 @@ impl JSContext {
 
     /// Evaluate a JavaScript script in this context.
     ///
+    /// # Arguments
+    /// * `script` - The script to evaluate.
+    /// * `this` - The object to use as `this`, or [`None`] to use the global object.
+    /// * `source_url` - A URL for the script's source file, this is only used when
+    ///   reporting exceptions.
+    /// * `starting_line` - The script's starting line number in the file
+    ///   located at `source_url`, this is only used when reporting exceptions.
+    ///   The value is one-based, so the first line is line `1` and invalid values
+    ///   are clamped to `1`.
+    ///
     /// If an exception is thrown during evaluation, it will be returned as an
     /// [`Err`] value, otherwise the result of the script evaluation will be
     /// returned as an [`Ok`] value.
-    pub fn evaluate_script(&self, script: &str) -> Result<JSValue, JSValue> {
+    pub fn evaluate_script(
+        &self,
+        script: &str,
+        this: Option<&JSObject>,
+        source_url: Option<&str>,
+        starting_line: i32,
+    ) -> Result<JSValue, JSValue> {
         let script = JSString::new(self.lib.clone(), script);
+        let source_url = source_url.map(|s| JSString::new(self.lib.clone(), s));
         let mut exception = std::ptr::null();
         let ret = unsafe {
             self.lib.ultralight().JSEvaluateScript(
                 self.internal,
                 script.internal,
-                std::ptr::null_mut(),
-                std::ptr::null_mut(),
-                0,
+                this.map_or(std::ptr::null_mut(), |v| v.internal as _),
+                source_url
+                    .as_ref()
+                    .map_or(std::ptr::null_mut(), |s| s.internal),
+                starting_line,
                 &mut exception,
             )
         };
@@ -107,25 +141,39 @@ impl JSContext {
 
     /// Checks for syntax errors in a string of JavaScript.
     ///
-    /// `true` if the script is syntactically correct, otherwise `false`.
-    pub fn check_script_syntax(&self, script: &str) -> Result<bool, JSValue> {
+    /// `source_url` and `starting_line` are only used when reporting exceptions,
+    /// see [`JSContext::evaluate_script`].
+    ///
+    /// Returns [`Ok`] if the script is syntactically correct, otherwise
+    /// the syntax error is returned in [`Err`].
+    pub fn check_script_syntax(
+        &self,
+        script: &str,
+        source_url: Option<&str>,
+        starting_line: i32,
+    ) -> Result<(), JSValue> {
         let script = JSString::new(self.lib.clone(), script);
+        let source_url = source_url.map(|s| JSString::new(self.lib.clone(), s));
         let mut exception = std::ptr::null();
         let ret = unsafe {
             self.lib.ultralight().JSCheckScriptSyntax(
                 self.internal,
                 script.internal,
-                std::ptr::null_mut(),
-                0,
+                source_url
+                    .as_ref()
+                    .map_or(std::ptr::null_mut(), |s| s.internal),
+                starting_line,
                 &mut exception,
             )
         };
 
         if !exception.is_null() {
-            return Err(JSValue::copy_from_raw(self, exception));
+            Err(JSValue::copy_from_raw(self, exception))
+        } else if !ret {
+            Err(JSValue::new_string(self, "Script has syntax errors"))
+        } else {
+            Ok(())
         }
-
-        Ok(ret)
     }
 
     /// Performs a JavaScript garbage collection.
@@ -0,0 +1,73 @@
+//! Converting between Rust types and JavaScript values with [`serde`].
+//!
+//! The values go through their JSON representation, using
+//! [`JSValue::to_json_string`] and [`JSValue::new_from_json`], so they
+//! follow the rules of `JSON.stringify` and `JSON.parse`: object properties
+//! that are `undefined` or functions are skipped, `NaN` and infinities
+//! become `null`, and `Date`s become strings.
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{JSContext, JSException, JSValue};
+
+/// Errors that can occur in [`from_value`] and [`to_value`].
+#[derive(Debug, thiserror::Error)]
+pub enum JsSerdeError {
+    /// The value couldn't be (de)serialized to/from `T`.
+    #[error("JSON conversion failed: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A JavaScript exception was thrown, for example when serializing
+    /// an object with circular references, or a script passed to
+    /// [`View::evaluate_script_json`](crate::view::View::evaluate_script_json)
+    /// failed.
+    ///
+    /// The exception is converted to a string, since the value is tied to
+    /// its [`JSContext`].
+    #[error("JavaScript exception: {0}")]
+    Exception(String),
+}
+
+impl From<JSValue<'_>> for JsSerdeError {
+    fn from(exception: JSValue<'_>) -> Self {
+        Self::Exception(JSException(exception).to_string())
+    }
+}
+
+/// Deserialize a JavaScript value into `T`.
+///
+/// `undefined` is deserialized like `null`, so it can be read as an
+/// [`Option`] or `()`.
+///
+/// # Example
+/// ```rust,no_run
+/// # use ul_next::javascript::*;
+/// # let context: JSContext = unsafe { std::mem::zeroed() };
+/// let value = context.evaluate_script("[1, 2, 3]", None, None, 1).unwrap();
+/// let numbers: Vec<u32> = from_value(&value).unwrap();
+/// ```
+pub fn from_value<T: DeserializeOwned>(value: &JSValue) -> Result<T, JsSerdeError> {
+    if value.is_undefined() {
+        return Ok(serde_json::from_str("null")?);
+    }
+
+    let json = String::from(&value.to_json_string()?);
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Serialize `value` into a JavaScript value in `ctx`.
+///
+/// # Example
+/// ```rust,no_run
+/// # use ul_next::javascript::*;
+/// # let context: JSContext = unsafe { std::mem::zeroed() };
+/// let value = to_value(&context, &vec![Some(1), None]).unwrap();
+/// assert!(value.is_array());
+/// ```
+pub fn to_value<'a, T: Serialize + ?Sized>(
+    ctx: &'a JSContext,
+    value: &T,
+) -> Result<JSValue<'a>, JsSerdeError> {
+    let json = serde_json::to_string(value)?;
+    // `serde_json` always produces valid JSON
+    JSValue::new_from_json(ctx, &json)
+        .ok_or_else(|| JsSerdeError::Exception(format!("Failed to parse the JSON `{}`", json)))
+}
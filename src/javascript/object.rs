@@ -1,16 +1,10 @@
 use core::fmt;
-use std::{
-    ops::Deref,
-    sync::{Arc, OnceLock},
-};
-
-use crate::Library;
+use std::{any::Any, cell::RefMut, ops::Deref};
 
-use super::{AsJSValue, JSContext, JSString, JSValue};
-
-// TODO: major hack, not sure how to get access to the Library
-//       from inside the trampoline
-static LIBRARY: OnceLock<Arc<Library>> = OnceLock::new();
+use super::{
+    class::{self, NativeHeader, NativeObjectKind},
+    AsJSValue, JSClass, JSContext, JSString, JSValue,
+};
 
 /// Attributes for JavaScript properties.
 ///
@@ -70,6 +64,22 @@ impl JSPropertyAttributes {
     }
 }
 
+/// The getter of an accessor property, see [`JSObject::define_property`].
+pub type JSPropertyGetter =
+    Box<dyn for<'c> FnMut(&'c JSContext, &JSObject<'c>) -> Result<JSValue<'c>, JSValue<'c>>>;
+
+/// The setter of an accessor property, called with the assigned value, see
+/// [`JSObject::define_property`].
+pub type JSPropertySetter =
+    Box<dyn for<'c> FnMut(&'c JSContext, &JSObject<'c>, &JSValue<'c>) -> Result<(), JSValue<'c>>>;
+
+/// The private data of functions created with [`JSObject::new_function_with_callback`].
+#[repr(C)]
+struct FunctionData<Env> {
+    header: NativeHeader,
+    callback: Env,
+}
+
 /// A JavaScript object.
 #[derive(Clone, Debug)]
 pub struct JSObject<'a> {
@@ -113,8 +123,6 @@ impl<'a> JSObject<'a> {
         for<'c> F:
             FnMut(&'c JSContext, &JSObject<'c>, &[JSValue<'c>]) -> Result<JSValue<'c>, JSValue<'c>>,
     {
-        LIBRARY.get_or_init(|| ctx.lib.clone());
-
         unsafe extern "C" fn finalize<Env>(function: ul_sys::JSObjectRef)
         where
             for<'c> Env: FnMut(
@@ -127,11 +135,13 @@ impl<'a> JSObject<'a> {
                 ::std::process::abort();
             });
 
-            let lib = ffi_unwrap!(LIBRARY.get(), "library undefined ptr",);
+            crate::callback_panic::catch_callback_panic(|| {
+                let private_data = class::native_private(function) as *mut FunctionData<Env>;
+                ffi_unwrap!(private_data.as_ref(), "null ptr",);
+                class::unregister_native_object(function);
 
-            let private_data = lib.ultralight().JSObjectGetPrivate(function) as *mut Box<Env>;
-
-            let _ = Box::from_raw(private_data);
+                let _ = Box::from_raw(private_data);
+            })
         }
 
         unsafe extern "C" fn trampoline<Env>(
@@ -153,32 +163,40 @@ impl<'a> JSObject<'a> {
                 ::std::process::abort();
             });
 
-            let lib = ffi_unwrap!(LIBRARY.get(), "library undefined ptr",);
-
-            let private_data = lib.ultralight().JSObjectGetPrivate(function) as *mut Box<Env>;
-            let callback: &mut Box<Env> = ffi_unwrap!(private_data.as_mut(), "null ptr",);
-
-            let ctx = JSContext::copy_from_raw(lib.clone(), ctx);
-            let this = JSObject::copy_from_raw(&ctx, this_object);
-            let args = std::slice::from_raw_parts(arguments, argument_count)
-                .iter()
-                .map(|v| JSValue::copy_from_raw(&ctx, *v))
-                .collect::<Vec<_>>();
-
-            let ret = callback(&ctx, &this, &args);
-            match ret {
-                Ok(value) => value.into_raw(),
-                Err(value) => {
-                    if !exception.is_null() {
-                        *exception = value.into_raw();
+            // if the callback panics, `null` is returned without setting an exception
+            crate::callback_panic::catch_callback_panic(|| -> ul_sys::JSValueRef {
+                let private_data = class::native_private(function) as *mut FunctionData<Env>;
+                let FunctionData { header, callback } =
+                    ffi_unwrap!(private_data.as_mut(), "null ptr",);
+
+                let ctx = JSContext::copy_from_raw(header.lib.clone(), ctx);
+                let this = JSObject::copy_from_raw(&ctx, this_object);
+                let args = std::slice::from_raw_parts(arguments, argument_count)
+                    .iter()
+                    .map(|v| JSValue::copy_from_raw(&ctx, *v))
+                    .collect::<Vec<_>>();
+
+                let ret = callback(&ctx, &this, &args);
+                match ret {
+                    Ok(value) => value.into_raw(),
+                    Err(value) => {
+                        if !exception.is_null() {
+                            *exception = value.into_raw();
+                        }
+                        std::ptr::null_mut()
                     }
-                    std::ptr::null_mut()
                 }
-            }
+            })
         }
 
         let c_callback: ul_sys::JSObjectCallAsFunctionCallback = Some(trampoline::<F>);
-        let callback_data: *mut Box<F> = Box::into_raw(Box::new(Box::new(callback)));
+        let callback_data = Box::into_raw(Box::new(FunctionData {
+            header: NativeHeader {
+                lib: ctx.lib.clone(),
+                kind: NativeObjectKind::Function,
+            },
+            callback,
+        }));
 
         let class_def = ul_sys::JSClassDefinition {
             finalize: Some(finalize::<F>),
@@ -198,12 +216,33 @@ impl<'a> JSObject<'a> {
 
             obj
         };
+        class::register_native_object(&ctx.lib, obj);
 
         Self {
             value: JSValue::from_raw(ctx, obj),
         }
     }
 
+    /// Creates an object of a [`JSClass`], with `data` as its Rust private data.
+    ///
+    /// The data can be accessed with [`JSObject::private_data`], and is given
+    /// to the finalizer of the class (see [`JSClassBuilder::finalize`](super::JSClassBuilder::finalize))
+    /// when the object is garbage collected.
+    pub fn new_with_class<T: Any>(ctx: &'a JSContext, class: &JSClass, data: Box<T>) -> Self {
+        class.make_object(ctx, data)
+    }
+
+    /// Returns the Rust private data of an object created with
+    /// [`JSObject::new_with_class`].
+    ///
+    /// Returns [`None`] if the object isn't of a [`JSClass`], if the data is
+    /// not of type `T`, or if the data is already borrowed (e.g. calling a
+    /// method of the same object while holding the data).
+    pub fn private_data<T: Any>(&self) -> Option<RefMut<'_, T>> {
+        let native = unsafe { class::native_object(&self.ctx.lib, self.internal as _) }?;
+        native.data::<T>()
+    }
+
     /// Creates a function with a given script as its body.
     ///
     /// Use this method when you want to execute a script repeatedly,
@@ -461,6 +500,123 @@ impl JSObject<'_> {
         }
     }
 
+    /// Defines an accessor property on an object by name, with Rust
+    /// callbacks as its getter and setter.
+    ///
+    /// This is the same as calling `Object.defineProperty` with `get` and
+    /// `set` functions created with [`JSObject::new_function_with_callback`],
+    /// which receive the object the property is accessed on (the `this`
+    /// value). The value returned by `getter` is the value of the property,
+    /// and `setter` is called with the assigned value, returning [`Err`]
+    /// from either throws the value as an exception. Without a `getter` the
+    /// property reads as `undefined`, and without a `setter` assigning to it
+    /// is ignored (or throws in strict mode).
+    ///
+    /// In `attributes`, [`dont_enum`](JSPropertyAttributes::dont_enum)
+    /// and [`dont_delete`](JSPropertyAttributes::dont_delete) are used as the
+    /// `enumerable` and `configurable` fields of the property descriptor,
+    /// [`read_only`](JSPropertyAttributes::read_only) doesn't apply to
+    /// accessor properties and is ignored.
+    ///
+    /// Returns [`Err`] if an exception is thrown, for example if the
+    /// property already exists and is not configurable.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use std::{cell::Cell, rc::Rc};
+    /// # use ul_next::javascript::{JSObject, JSPropertyAttributes, JSValue};
+    /// # fn example<'a>(obj: &'a JSObject<'a>) -> Result<(), JSValue<'a>> {
+    /// let value = Rc::new(Cell::new(0.0));
+    /// let setter_value = value.clone();
+    /// obj.define_property(
+    ///     "value",
+    ///     Some(Box::new(move |ctx, _this| {
+    ///         Ok(JSValue::new_number(ctx, value.get()))
+    ///     })),
+    ///     Some(Box::new(move |_ctx, _this, new_value| {
+    ///         setter_value.set(new_value.as_number()?);
+    ///         Ok(())
+    ///     })),
+    ///     JSPropertyAttributes::new(),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn define_property(
+        &self,
+        name: &str,
+        getter: Option<JSPropertyGetter>,
+        setter: Option<JSPropertySetter>,
+        attributes: JSPropertyAttributes,
+    ) -> Result<(), JSValue> {
+        let get = getter.map(|mut getter| {
+            JSObject::new_function_with_callback(self.ctx, move |ctx, this, _args| {
+                getter(ctx, this)
+            })
+        });
+        let set = setter.map(|mut setter| {
+            JSObject::new_function_with_callback(self.ctx, move |ctx, this, args| {
+                let undefined = JSValue::new_undefined(ctx);
+                setter(ctx, this, args.first().unwrap_or(&undefined))?;
+                Ok(undefined)
+            })
+        });
+        Self::define_accessor_property(self, name, get, set, attributes)
+    }
+
+    /// Call `Object.defineProperty` with the `get` and `set` functions, see
+    /// [`JSObject::call_object_builtin`].
+    fn define_accessor_property<'c>(
+        this: &JSObject<'c>,
+        name: &str,
+        get: Option<JSObject<'c>>,
+        set: Option<JSObject<'c>>,
+        attributes: JSPropertyAttributes,
+    ) -> Result<(), JSValue<'c>> {
+        let ctx = this.ctx;
+        // the errors borrow the local objects, so they are copied to `ctx`
+        let reroot = |value: JSValue| JSValue::copy_from_raw(ctx, value.internal);
+        let no_attributes = JSPropertyAttributes::new();
+
+        let descriptor = JSObject::new(ctx);
+        if let Some(get) = get {
+            descriptor
+                .set_property("get", &get, no_attributes)
+                .map_err(reroot)?;
+        }
+        if let Some(set) = set {
+            descriptor
+                .set_property("set", &set, no_attributes)
+                .map_err(reroot)?;
+        }
+        descriptor
+            .set_property(
+                "enumerable",
+                &JSValue::new_boolean(ctx, !attributes.dont_enum),
+                no_attributes,
+            )
+            .map_err(reroot)?;
+        descriptor
+            .set_property(
+                "configurable",
+                &JSValue::new_boolean(ctx, !attributes.dont_delete),
+                no_attributes,
+            )
+            .map_err(reroot)?;
+
+        Self::call_object_builtin(
+            this,
+            "defineProperty",
+            &[
+                this.value.clone(),
+                JSValue::new_string(ctx, name),
+                descriptor.value.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
     /// Sets a property on an object by numeric index.
     ///
     /// Returns [`Err`] if an exception is thrown.
@@ -500,6 +656,14 @@ impl JSObject<'_> {
         JSPropertyNameArray::from_raw(self.ctx, names)
     }
 
+    /// Iterates over the names of an object's enumerable properties.
+    ///
+    /// Unlike converting [`JSObject::get_property_names`] to a [`Vec`], the
+    /// names are only copied when the iterator reaches them.
+    pub fn enumerate_properties(&self) -> JSPropertyNames {
+        self.get_property_names().into_iter()
+    }
+
     /// Tests whether an object has a property.
     pub fn has_property(&self, name: &str) -> bool {
         let name = JSString::new(self.ctx.lib.clone(), name);
@@ -641,6 +805,115 @@ impl JSObject<'_> {
             Ok(result)
         }
     }
+
+    /// Gets an object's prototype.
+    ///
+    /// This is the same as `Object.getPrototypeOf(object)` in JavaScript.
+    pub fn get_prototype(&self) -> JSValue {
+        let result_raw = unsafe {
+            self.ctx
+                .lib
+                .ultralight()
+                .JSObjectGetPrototype(self.ctx.internal, self.internal as _)
+        };
+
+        JSValue::copy_from_raw(self.ctx, result_raw)
+    }
+
+    /// Sets an object's prototype.
+    ///
+    /// `prototype` can be any object or the `null` value, other values
+    /// are ignored.
+    pub fn set_prototype(&self, prototype: &JSValue) {
+        unsafe {
+            self.ctx.lib.ultralight().JSObjectSetPrototype(
+                self.ctx.internal,
+                self.internal as _,
+                prototype.internal,
+            )
+        }
+    }
+
+    /// Freezes the object, new properties can't be added to it, and the
+    /// existing properties can't be removed or changed.
+    ///
+    /// This is the same as `Object.freeze(object)` in JavaScript, the
+    /// object's own properties are frozen, but the objects they reference
+    /// are not.
+    ///
+    /// Returns [`Err`] if an exception is thrown.
+    pub fn freeze(&self) -> Result<(), JSValue> {
+        self.call_object_function("freeze").map(|_| ())
+    }
+
+    /// Seals the object, new properties can't be added to it, and the
+    /// existing properties can't be removed, but they can still be changed
+    /// if they are writable.
+    ///
+    /// This is the same as `Object.seal(object)` in JavaScript.
+    ///
+    /// Returns [`Err`] if an exception is thrown.
+    pub fn seal(&self) -> Result<(), JSValue> {
+        self.call_object_function("seal").map(|_| ())
+    }
+
+    /// Whether the object is frozen (see [`JSObject::freeze`]).
+    ///
+    /// This is the same as `Object.isFrozen(object)` in JavaScript.
+    ///
+    /// Returns [`Err`] if an exception is thrown.
+    pub fn is_frozen(&self) -> Result<bool, JSValue> {
+        self.call_object_function_bool("isFrozen")
+    }
+
+    /// Whether the object is sealed (see [`JSObject::seal`]).
+    ///
+    /// This is the same as `Object.isSealed(object)` in JavaScript.
+    ///
+    /// Returns [`Err`] if an exception is thrown.
+    pub fn is_sealed(&self) -> Result<bool, JSValue> {
+        self.call_object_function_bool("isSealed")
+    }
+
+    /// Call the function `name` of the `Object` of the global object of the
+    /// context with `args`.
+    ///
+    /// Note that the scripts of the page can replace these functions
+    /// (eg. `Object.freeze = () => {}`), the replaced ones are called then.
+    fn call_object_builtin<'c>(
+        this: &JSObject<'c>,
+        name: &str,
+        args: &[JSValue<'c>],
+    ) -> Result<JSValue<'c>, JSValue<'c>> {
+        let ctx = this.ctx;
+        // the results borrow the local objects, they are valid in `ctx`
+        let reroot = |value: JSValue| JSValue::copy_from_raw(ctx, value.internal);
+
+        let global = ctx.global_object();
+        let object = global
+            .get_property("Object")
+            .and_then(|object| object.as_object())
+            .map_err(reroot)?;
+        let function = object
+            .get_property(name)
+            .and_then(|function| function.as_object())
+            .map_err(reroot)?;
+        function
+            .call_as_function(Some(&object), args)
+            .map(reroot)
+            .map_err(reroot)
+    }
+
+    /// Call the function `name` of the `Object` builtin with this object
+    /// as the only argument.
+    fn call_object_function(&self, name: &str) -> Result<JSValue, JSValue> {
+        Self::call_object_builtin(self, name, std::slice::from_ref(&self.value))
+    }
+
+    fn call_object_function_bool(&self, name: &str) -> Result<bool, JSValue> {
+        self.call_object_function(name)
+            .map(|result| result.as_boolean())
+    }
 }
 
 impl<'a> AsRef<JSValue<'a>> for JSObject<'a> {
@@ -738,6 +1011,51 @@ impl From<JSPropertyNameArray<'_>> for Vec<String> {
     }
 }
 
+impl<'a> IntoIterator for JSPropertyNameArray<'a> {
+    type Item = JSString;
+    type IntoIter = JSPropertyNames<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        JSPropertyNames {
+            array: self,
+            index: 0,
+            len,
+        }
+    }
+}
+
+/// An iterator over the names of a [`JSPropertyNameArray`].
+///
+/// This is created by [`JSObject::enumerate_properties`], and keeps the
+/// array alive until it is dropped.
+#[derive(Clone, Debug)]
+pub struct JSPropertyNames<'a> {
+    array: JSPropertyNameArray<'a>,
+    index: usize,
+    len: usize,
+}
+
+impl Iterator for JSPropertyNames<'_> {
+    type Item = JSString;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        let name = self.array.get(self.index);
+        self.index += 1;
+        name
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for JSPropertyNames<'_> {}
+
 impl Clone for JSPropertyNameArray<'_> {
     fn clone(&self) -> Self {
         let array = unsafe {
@@ -1,5 +1,5 @@
 use core::fmt;
-use std::{ffi::c_char, sync::Arc};
+use std::sync::Arc;
 
 use crate::Library;
 
@@ -36,12 +36,17 @@ impl JSString {
     }
 
     /// Creates a new JavaScript string from a Rust string.
+    ///
+    /// The string may contain NUL characters, they are kept in the
+    /// JavaScript string.
     pub fn new(lib: Arc<Library>, string: &str) -> Self {
-        let cstring = std::ffi::CString::new(string).unwrap();
+        // JavaScript strings are UTF-16, and creating them from UTF-8 needs
+        // a NUL-terminated string, which would end at the first NUL
+        let chars = string.encode_utf16().collect::<Vec<u16>>();
 
         let string = unsafe {
             lib.ultralight()
-                .JSStringCreateWithUTF8CString(cstring.as_ptr())
+                .JSStringCreateWithCharacters(chars.as_ptr(), chars.len())
         };
 
         Self {
@@ -62,26 +67,22 @@ impl JSString {
 }
 
 impl From<&JSString> for String {
+    /// Converts the string from UTF-16, unpaired surrogates (which are
+    /// valid in JavaScript strings) are replaced with `U+FFFD`.
     fn from(string: &JSString) -> Self {
-        let max_size = unsafe {
-            string
+        let chars = unsafe {
+            let len = string.lib.ultralight().JSStringGetLength(string.internal);
+            let ptr = string
                 .lib
                 .ultralight()
-                .JSStringGetMaximumUTF8CStringSize(string.internal)
+                .JSStringGetCharactersPtr(string.internal);
+            if ptr.is_null() || len == 0 {
+                return String::new();
+            }
+            std::slice::from_raw_parts(ptr, len)
         };
 
-        let mut buffer: Vec<u8> = Vec::with_capacity(max_size);
-
-        unsafe {
-            let actual_size = string.lib.ultralight().JSStringGetUTF8CString(
-                string.internal,
-                buffer.as_mut_ptr().cast::<c_char>(),
-                max_size,
-            );
-            buffer.set_len(actual_size - 1);
-        }
-
-        String::from_utf8(buffer).unwrap()
+        String::from_utf16_lossy(chars)
     }
 }
 
@@ -21,6 +21,42 @@ pub enum JSTypedArrayType {
     BigUint64Array = ul_sys::JSTypedArrayType_kJSTypedArrayTypeBigUint64Array,
 }
 
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Rust types that can be elements of a [`JSTypedArray`], used in
+/// [`JSTypedArray::from_vec`] and [`JSTypedArray::to_vec`].
+///
+/// This trait is sealed and can't be implemented outside this crate.
+pub trait JSTypedArrayElement: sealed::Sealed + Copy + 'static {
+    /// The type of typed array holding elements of this type.
+    const ARRAY_TYPE: JSTypedArrayType;
+}
+
+macro_rules! impl_typed_array_element {
+    ($($ty:ty => $array_type:ident),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl JSTypedArrayElement for $ty {
+                const ARRAY_TYPE: JSTypedArrayType = JSTypedArrayType::$array_type;
+            }
+        )*
+    };
+}
+
+impl_typed_array_element! {
+    u8 => Uint8Array,
+    i8 => Int8Array,
+    u16 => Uint16Array,
+    i16 => Int16Array,
+    u32 => Uint32Array,
+    i32 => Int32Array,
+    f32 => Float32Array,
+    f64 => Float64Array,
+}
+
 /// A JavaScript Typed Array object.
 #[derive(Clone, Debug)]
 pub struct JSTypedArray<'a> {
@@ -108,6 +144,109 @@ impl<'a> JSTypedArray<'a> {
         }
     }
 
+    /// Creates a JavaScript Typed Array object from a [`Vec`], the type of
+    /// the array is determined by `T` (e.g. `Vec<f32>` creates a `Float32Array`).
+    ///
+    /// The data is not copied, the [`Vec`] is moved into the array and dropped
+    /// when the array is garbage collected.
+    ///
+    /// Returns [`Err`] if an exception occurred while creating the object.
+    pub fn from_vec<T: JSTypedArrayElement>(
+        ctx: &'a JSContext,
+        data: Vec<T>,
+    ) -> Result<Self, JSValue<'a>> {
+        extern "C" fn deallocator<T>(
+            _bytes: *mut std::ffi::c_void,
+            deallocator_context: *mut std::ffi::c_void,
+        ) {
+            drop(unsafe { Box::from_raw(deallocator_context as *mut Vec<T>) })
+        }
+
+        let mut exception = std::ptr::null();
+        let mut data = Box::new(data);
+
+        let bytes = data.as_mut_ptr();
+        let bytes_len = std::mem::size_of_val(data.as_slice());
+        let data = Box::into_raw(data);
+
+        let value = unsafe {
+            ctx.lib.ultralight().JSObjectMakeTypedArrayWithBytesNoCopy(
+                ctx.internal,
+                T::ARRAY_TYPE as _,
+                bytes as _,
+                bytes_len,
+                Some(deallocator::<T>),
+                data as _,
+                &mut exception,
+            )
+        };
+
+        if !exception.is_null() {
+            Err(JSValue::from_raw(ctx, exception))
+        } else if value.is_null() {
+            Err(JSValue::new_string(ctx, "Failed to create typed array"))
+        } else {
+            Ok(Self {
+                value: JSValue::from_raw(ctx, value),
+            })
+        }
+    }
+
+    /// Copies the elements of a JavaScript Typed Array object into a [`Vec`].
+    ///
+    /// `T` must match the type of the array (e.g. `f32` for `Float32Array`),
+    /// `u8` can be used for both `Uint8Array` and `Uint8ClampedArray`.
+    ///
+    /// Returns [`Err`] if the type doesn't match, or if an exception occurred
+    /// while reading the array.
+    pub fn to_vec<T: JSTypedArrayElement>(&self) -> Result<Vec<T>, JSValue<'a>> {
+        let ty = self.ty()?;
+        let compatible = ty == T::ARRAY_TYPE
+            || (T::ARRAY_TYPE == JSTypedArrayType::Uint8Array
+                && ty == JSTypedArrayType::Uint8ClampedArray);
+        if !compatible {
+            return Err(JSValue::new_string(
+                self.value.ctx,
+                &format!(
+                    "Typed array type mismatch: expected {:?}, found {:?}",
+                    T::ARRAY_TYPE,
+                    ty
+                ),
+            ));
+        }
+
+        let len = self.len()?;
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut exception = std::ptr::null();
+
+        // the pointer already includes the byte offset of the array
+        let bytes = unsafe {
+            self.value
+                .ctx
+                .lib
+                .ultralight()
+                .JSObjectGetTypedArrayBytesPtr(
+                    self.value.ctx.internal,
+                    self.value.internal as _,
+                    &mut exception,
+                )
+        };
+
+        if !exception.is_null() {
+            Err(JSValue::from_raw(self.value.ctx, exception))
+        } else if bytes.is_null() {
+            Err(JSValue::new_string(
+                self.value.ctx,
+                "Failed to get typed array bytes",
+            ))
+        } else {
+            Ok(unsafe { std::slice::from_raw_parts(bytes as *const T, len) }.to_vec())
+        }
+    }
+
     /// Returns the length of a JavaScript Typed Array object.
     ///
     /// Returns [`Err`] if an exception occurred while getting the length.
@@ -417,13 +417,25 @@ impl<'a> JSValue<'a> {
     ///
     /// Returns an [`Err`] if an exception is thrown.
     pub fn to_json_string(&self) -> Result<JSString, JSValue<'a>> {
+        self.to_json_string_indented(0)
+    }
+
+    /// Converts a JavaScript value to JSON serialized representation of a JS
+    /// value, like `JSON.stringify(value, null, indent)`.
+    ///
+    /// `indent` is the number of spaces to indent nested values with, no
+    /// whitespace is added if it's `0`, and it's clamped to `10`.
+    ///
+    /// Returns an [`Err`] if an exception is thrown, or if the value can't be
+    /// represented in JSON (such as `undefined` or functions).
+    pub fn to_json_string_indented(&self, indent: u32) -> Result<JSString, JSValue<'a>> {
         let mut exception = std::ptr::null();
 
         let result = unsafe {
             self.ctx.lib.ultralight().JSValueCreateJSONString(
                 self.ctx.internal,
                 self.internal,
-                0,
+                indent.min(10),
                 &mut exception,
             )
         };
@@ -466,6 +478,42 @@ impl fmt::Debug for JSValue<'_> {
     }
 }
 
+/// Formats the value as its JSON representation (see [`JSValue::to_json_string`]).
+///
+/// Values that can't be represented in JSON (such as `undefined` or functions)
+/// are converted to string instead. If serializing throws an exception, which
+/// happens for objects with circular references, `[Circular]` is written.
+impl fmt::Display for JSValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut exception = std::ptr::null();
+
+        let result = unsafe {
+            self.ctx.lib.ultralight().JSValueCreateJSONString(
+                self.ctx.internal,
+                self.internal,
+                0,
+                &mut exception,
+            )
+        };
+
+        if !exception.is_null() {
+            // drop the exception value
+            let _ = JSValue::from_raw(self.ctx, exception);
+            return f.write_str("[Circular]");
+        }
+
+        if !result.is_null() {
+            let json = JSString::from_raw(self.ctx.lib.clone(), result);
+            return write!(f, "{}", json);
+        }
+
+        match self.as_string() {
+            Ok(string) => write!(f, "{}", string),
+            Err(_) => write!(f, "[{:?}]", self.get_type()),
+        }
+    }
+}
+
 impl Drop for JSValue<'_> {
     fn drop(&mut self) {
         unsafe {
@@ -476,3 +524,62 @@ impl Drop for JSValue<'_> {
         }
     }
 }
+
+/// A JavaScript exception, wrapping the thrown [`JSValue`].
+///
+/// Unlike [`JSValue`], this is displayed using the string conversion of the
+/// value, so `Error` objects are shown as `Name: message`.
+///
+/// This implements [`std::error::Error`], so it can be used with `?` in
+/// functions returning `Result<_, Box<dyn Error + 'a>>`. Since the value is
+/// tied to its [`JSContext`], use [`ToString::to_string`] to get an error
+/// that outlives the context.
+///
+/// # Example
+/// ```rust,no_run
+/// # use ul_next::javascript::*;
+/// # fn f<'a>(function: &'a JSObject<'a>) -> Result<(), Box<dyn std::error::Error + 'a>> {
+/// let _result = function.call_as_function(None, &[]).map_err(JSException)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct JSException<'a>(pub JSValue<'a>);
+
+impl<'a> JSException<'a> {
+    /// Returns the thrown value.
+    pub fn into_value(self) -> JSValue<'a> {
+        self.0
+    }
+}
+
+impl<'a> From<JSValue<'a>> for JSException<'a> {
+    fn from(value: JSValue<'a>) -> Self {
+        Self(value)
+    }
+}
+
+impl<'a> Deref for JSException<'a> {
+    type Target = JSValue<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Debug for JSException<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("JSException").field(&self.0).finish()
+    }
+}
+
+impl fmt::Display for JSException<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_string() {
+            Ok(string) => write!(f, "{}", string),
+            Err(_) => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl std::error::Error for JSException<'_> {}
@@ -13,6 +13,36 @@
 //!
 //! This library also contain a custom [`glium`](crate::gpu_driver::glium)
 //! [`GpuDriver`] implementation that can be used for easier integration.
+//!
+//! # Thread safety
+//!
+//! `Ultralight` is not thread safe, all the objects created from a
+//! [`Renderer`] (or [`App`]) must be used on the thread that created it.
+//! Because of that, [`Renderer`], [`Session`], [`View`], [`Surface`],
+//! [`Bitmap`](bitmap::Bitmap), the events and the JavaScript types are
+//! neither [`Send`] nor [`Sync`], and wrapping them in a
+//! [`Mutex`](std::sync::Mutex) doesn't make them usable from other threads.
+//!
+//! ```compile_fail
+//! fn assert_send<T: Send>() {}
+//! assert_send::<ul_next::View>();
+//! ```
+//! ```compile_fail
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<ul_next::Renderer>();
+//! ```
+//!
+//! To drive the UI from other threads, keep the [`Renderer`] and its views
+//! on one thread, and send plain data (commands, strings,
+//! [`OwnedBitmap`](bitmap::OwnedBitmap)s) to it through a channel, which the
+//! UI thread handles before calling [`Renderer::update`]. This is the same
+//! pattern used by the [`glium`](crate::gpu_driver::glium) GPU driver,
+//! where the [`Send`] sender given to [`platform::set_gpu_driver`] only
+//! forwards the commands to the receiver on the rendering thread.
+//!
+//! The platform implementations ([`GpuDriver`],
+//! [`Logger`](platform::Logger), [`FileSystem`](platform::FileSystem), ...)
+//! must be [`Send`], since they are stored globally.
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 #[cfg(not(any(feature = "linked", feature = "loaded")))]
@@ -24,16 +54,22 @@ compile_error!(
 pub(crate) mod config_macros;
 #[macro_use]
 pub(crate) mod callback_macros;
+pub(crate) mod callback_panic;
 
 #[cfg(any(feature = "appcore_linked", feature = "loaded"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "appcore_linked", feature = "loaded"))))]
 pub mod app;
 pub mod bitmap;
+#[cfg(feature = "compat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compat")))]
+pub mod compat;
 pub mod config;
 pub mod error;
 pub mod event;
 pub mod gpu_driver;
+pub mod headless;
 pub mod image_source;
+pub mod integration;
 pub mod key_code;
 #[cfg(any(feature = "appcore_linked", feature = "loaded"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "appcore_linked", feature = "loaded"))))]
@@ -50,7 +86,7 @@ pub mod window;
 
 pub mod javascript;
 
-use std::{ffi::CStr, sync::Arc};
+use std::{any::Any, ffi::CStr, sync::Arc};
 
 #[cfg(any(feature = "appcore_linked", feature = "loaded"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "appcore_linked", feature = "loaded"))))]
@@ -70,7 +106,7 @@ pub use window::Window;
 
 use ul_sys::library::Library as LibrarySys;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 /// The version of the `Ultralight` library.
 ///
 /// Use the [`Library::version`] method to get the current version of the library.
@@ -80,6 +116,15 @@ pub struct Version {
     pub patch: u32,
 }
 
+impl Version {
+    /// Whether a library of version `self` can be used with bindings
+    /// generated for `other`, which requires the same major and minor
+    /// versions.
+    pub fn is_compatible_with(&self, other: &Version) -> bool {
+        self.major == other.major && self.minor == other.minor
+    }
+}
+
 /// Convert the version into a string in the format `MAJOR.MINOR.PATCH`.
 impl std::fmt::Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -123,9 +168,30 @@ impl Library {
     ///
     /// No function loaded directly or indirectly from this [`Library`]
     /// may be called after it is [dropped][drop()].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoadingError::VersionMismatch`](error::LoadingError::VersionMismatch)
+    /// if the major or minor version of the library differs from
+    /// [`Library::expected_version`].
+    #[cfg(feature = "loaded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "loaded")))]
+    pub unsafe fn load() -> Result<Arc<Library>, error::LoadingError> {
+        Self::check_version(Self::load_unchecked()?)
+    }
+
+    /// Same as [`load()`][Library::load],
+    /// without checking the version of the library.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`load()`][Library::load].
+    /// In addition, if the version of the library differs from
+    /// [`Library::expected_version`], the layout of the structs shared with
+    /// it may not match the bindings, and using them is undefined behavior.
     #[cfg(feature = "loaded")]
     #[cfg_attr(docsrs, doc(cfg(feature = "loaded")))]
-    pub unsafe fn load() -> Result<Arc<Library>, ul_sys::library::LoadingError> {
+    pub unsafe fn load_unchecked() -> Result<Arc<Library>, error::LoadingError> {
         Ok(Arc::new(Library {
             lib: LibrarySys::load()?,
         }))
@@ -150,9 +216,30 @@ impl Library {
     ///
     /// No function loaded directly or indirectly from this [`Library`]
     /// may be called after it is [dropped][drop()].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoadingError::VersionMismatch`](error::LoadingError::VersionMismatch)
+    /// if the major or minor version of the library differs from
+    /// [`Library::expected_version`].
     #[cfg(feature = "loaded")]
     #[cfg_attr(docsrs, doc(cfg(feature = "loaded")))]
-    pub unsafe fn load_with_appcore() -> Result<Arc<Library>, ul_sys::library::LoadingError> {
+    pub unsafe fn load_with_appcore() -> Result<Arc<Library>, error::LoadingError> {
+        Self::check_version(Self::load_with_appcore_unchecked()?)
+    }
+
+    /// Same as [`load_with_appcore()`][Library::load_with_appcore],
+    /// without checking the version of the library.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`load_with_appcore()`][Library::load_with_appcore].
+    /// In addition, if the version of the library differs from
+    /// [`Library::expected_version`], the layout of the structs shared with
+    /// it may not match the bindings, and using them is undefined behavior.
+    #[cfg(feature = "loaded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "loaded")))]
+    pub unsafe fn load_with_appcore_unchecked() -> Result<Arc<Library>, error::LoadingError> {
         Ok(Arc::new(Library {
             lib: LibrarySys::load_with_appcore()?,
         }))
@@ -170,11 +257,34 @@ impl Library {
     ///
     /// No function loaded directly or indirectly from this [`Library`]
     /// may be called after it is [dropped][drop()].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoadingError::VersionMismatch`](error::LoadingError::VersionMismatch)
+    /// if the major or minor version of the library differs from
+    /// [`Library::expected_version`].
     #[cfg(feature = "loaded")]
     #[cfg_attr(docsrs, doc(cfg(feature = "loaded")))]
     pub unsafe fn load_from<P: AsRef<::std::ffi::OsStr>>(
         ultralight_path: P,
-    ) -> Result<Arc<Library>, ul_sys::library::LoadingError> {
+    ) -> Result<Arc<Library>, error::LoadingError> {
+        Self::check_version(Self::load_from_unchecked(ultralight_path)?)
+    }
+
+    /// Same as [`load_from()`][Library::load_from],
+    /// without checking the version of the library.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`load_from()`][Library::load_from].
+    /// In addition, if the version of the library differs from
+    /// [`Library::expected_version`], the layout of the structs shared with
+    /// it may not match the bindings, and using them is undefined behavior.
+    #[cfg(feature = "loaded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "loaded")))]
+    pub unsafe fn load_from_unchecked<P: AsRef<::std::ffi::OsStr>>(
+        ultralight_path: P,
+    ) -> Result<Arc<Library>, error::LoadingError> {
         Ok(Arc::new(Library {
             lib: LibrarySys::load_from(ultralight_path.as_ref())?,
         }))
@@ -197,11 +307,35 @@ impl Library {
     ///
     /// No function loaded directly or indirectly from this [`Library`]
     /// may be called after it is [dropped][drop()].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoadingError::VersionMismatch`](error::LoadingError::VersionMismatch)
+    /// if the major or minor version of the library differs from
+    /// [`Library::expected_version`].
+    #[cfg(feature = "loaded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "loaded")))]
+    pub unsafe fn load_from_appcore<P>(appcore_path: P) -> Result<Arc<Library>, error::LoadingError>
+    where
+        P: AsRef<::std::ffi::OsStr>,
+    {
+        Self::check_version(Self::load_from_appcore_unchecked(appcore_path)?)
+    }
+
+    /// Same as [`load_from_appcore()`][Library::load_from_appcore],
+    /// without checking the version of the library.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`load_from_appcore()`][Library::load_from_appcore].
+    /// In addition, if the version of the library differs from
+    /// [`Library::expected_version`], the layout of the structs shared with
+    /// it may not match the bindings, and using them is undefined behavior.
     #[cfg(feature = "loaded")]
     #[cfg_attr(docsrs, doc(cfg(feature = "loaded")))]
-    pub unsafe fn load_from_appcore<P>(
+    pub unsafe fn load_from_appcore_unchecked<P>(
         appcore_path: P,
-    ) -> Result<Arc<Library>, ul_sys::library::LoadingError>
+    ) -> Result<Arc<Library>, error::LoadingError>
     where
         P: AsRef<::std::ffi::OsStr>,
     {
@@ -212,6 +346,30 @@ impl Library {
 }
 
 impl Library {
+    /// The version of the Ultralight SDK the bindings were generated from.
+    ///
+    /// The libraries loaded at runtime must have the same major and minor
+    /// versions (see [`Version::is_compatible_with`]).
+    pub const fn expected_version() -> Version {
+        Version {
+            major: ul_sys::ULTRALIGHT_VERSION_MAJOR,
+            minor: ul_sys::ULTRALIGHT_VERSION_MINOR,
+            patch: ul_sys::ULTRALIGHT_VERSION_PATCH,
+        }
+    }
+
+    /// Return `lib` if its version is compatible with the bindings.
+    #[cfg(feature = "loaded")]
+    fn check_version(lib: Arc<Library>) -> Result<Arc<Library>, error::LoadingError> {
+        let found = lib.version();
+        let expected = Self::expected_version();
+        if found.is_compatible_with(&expected) {
+            Ok(lib)
+        } else {
+            Err(error::LoadingError::VersionMismatch { expected, found })
+        }
+    }
+
     pub(crate) fn ultralight(&self) -> &ul_sys::library::Ultralight {
         self.lib.ultralight()
     }
@@ -232,6 +390,23 @@ impl Library {
         }
     }
 
+    /// Take the payload of the last panic that happened in a user callback
+    /// called by Ultralight (view callbacks, platform implementations,
+    /// JavaScript functions, etc.).
+    ///
+    /// Panics in callbacks don't unwind into Ultralight, instead they are
+    /// caught and a default value (null, `false` or zero) is returned to
+    /// Ultralight. This allows the application to check for them at a safe
+    /// point, and handle them or re-raise them with [`std::panic::resume_unwind`].
+    /// See also [`Renderer::set_resume_callback_panics`](crate::renderer::Renderer::set_resume_callback_panics).
+    ///
+    /// Note that the payload is stored per thread and not per [`Library`],
+    /// this returns the last panic of a callback that ran on the current
+    /// thread.
+    pub fn take_last_callback_panic(&self) -> Option<Box<dyn Any + Send>> {
+        callback_panic::take_last_callback_panic()
+    }
+
     /// Get the full WebKit version string
     pub fn webkit_version(&self) -> String {
         unsafe {
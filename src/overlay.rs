@@ -1,69 +1,87 @@
 //! Web-content overlay. Displays a web-page within an area of the main window.
-use std::sync::Arc;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
-use crate::{view::View, Library};
+use crate::{error::CreationError, view::View, window::Window, Library};
+
+/// The overlays of a [`Window`](crate::window::Window) that are still
+/// alive. Each overlay removes itself when dropped.
+pub(crate) type OverlayRegistry = Rc<RefCell<Vec<ul_sys::ULOverlay>>>;
 
 /// Web-content overlay. Displays a web-page within an area of the main window.
 ///
 /// Each `Overlay` is essentially a View and an on-screen quad. You should
 /// create the Overlay then load content into the underlying View.
 ///
-/// Can be created with [`Window::create_overlay`](crate::window::Window::create_overlay)
-/// or [`Window::create_overlay_with_view`](crate::window::Window::create_overlay_with_view).
+/// Can be created with [`Window::try_create_overlay`](crate::window::Window::try_create_overlay)
+/// or [`Window::try_create_overlay_with_view`](crate::window::Window::try_create_overlay_with_view).
 pub struct Overlay {
     lib: Arc<Library>,
     internal: ul_sys::ULOverlay,
 
     view: View,
+    registry: OverlayRegistry,
+    window: Window,
 }
 
 impl Overlay {
     /// Internal function helper to create an overlay.
-    /// (See [`Window::create_overlay`](crate::window::Window::create_overlay))
+    /// (See [`Window::try_create_overlay`](crate::window::Window::try_create_overlay))
     pub(crate) unsafe fn create(
         lib: Arc<Library>,
-        window: ul_sys::ULWindow,
+        window: Window,
+        registry: &OverlayRegistry,
         width: u32,
         height: u32,
         x: i32,
         y: i32,
     ) -> Option<Self> {
-        let internal_overlay = lib.appcore().ulCreateOverlay(window, width, height, x, y);
+        let internal_overlay = lib
+            .appcore()
+            .ulCreateOverlay(window.to_ul(), width, height, x, y);
 
         if internal_overlay.is_null() {
             return None;
         }
 
         let raw_view = lib.appcore().ulOverlayGetView(internal_overlay);
+        // a new view, not the one destroyed with an overlay
+        View::forget_state(raw_view);
         // the overlay owns the view, we can't need to destroy it on drop
         let view = View::from_raw(lib.clone(), raw_view)?;
+        registry.borrow_mut().push(internal_overlay);
         Some(Self {
             lib,
             internal: internal_overlay,
             view,
+            registry: registry.clone(),
+            window,
         })
     }
 
     /// Internal function helper to create an overlay with a view
-    /// (See [`Window::create_overlay_with_view`](crate::window::Window::create_overlay_with_view))
+    /// (See [`Window::try_create_overlay_with_view`](crate::window::Window::try_create_overlay_with_view))
     pub(crate) unsafe fn create_with_view(
         lib: Arc<Library>,
-        window_raw: ul_sys::ULWindow,
+        window: Window,
+        registry: &OverlayRegistry,
         view: View,
         x: i32,
         y: i32,
     ) -> Option<Self> {
         let internal = lib
             .appcore()
-            .ulCreateOverlayWithView(window_raw, view.to_ul(), x, y);
+            .ulCreateOverlayWithView(window.to_ul(), view.to_ul(), x, y);
         if internal.is_null() {
             return None;
         }
 
+        registry.borrow_mut().push(internal);
         Some(Self {
             lib,
             internal,
             view,
+            registry: registry.clone(),
+            window,
         })
     }
 }
@@ -74,6 +92,14 @@ impl Overlay {
         &self.view
     }
 
+    /// Get the Window this overlay was created in.
+    ///
+    /// The overlay keeps a handle to the window, so the window is not
+    /// destroyed before the overlay (see [`Window::clone_handle`]).
+    pub fn window(&self) -> &Window {
+        &self.window
+    }
+
     /// Get the width (in pixels).
     pub fn width(&self) -> u32 {
         unsafe { self.lib.appcore().ulOverlayGetWidth(self.internal) }
@@ -139,6 +165,33 @@ impl Overlay {
         }
     }
 
+    /// Set the opacity of the overlay content, clamped to `0.0..=1.0`.
+    ///
+    /// AppCore doesn't support blending overlays, so this sets the CSS
+    /// `opacity` of the `<body>` of the page. For the window to show through,
+    /// the view of the overlay must be transparent (see
+    /// [`ViewConfigBuilder::is_transparent`](crate::view::ViewConfigBuilder::is_transparent),
+    /// and use [`Window::try_create_overlay_with_view`](crate::window::Window::try_create_overlay_with_view)),
+    /// and the page must not set an opaque background on the `<html>` element.
+    ///
+    /// The opacity is re-applied when a new page is loaded, like
+    /// [`View::set_page_zoom`].
+    pub fn set_opacity(&self, opacity: f32) -> Result<(), CreationError> {
+        let opacity = if opacity.is_nan() {
+            1.0
+        } else {
+            opacity.clamp(0.0, 1.0)
+        };
+        self.view.set_content_opacity(opacity)
+    }
+
+    /// Get the opacity of the overlay content set by [`Overlay::set_opacity`].
+    ///
+    /// (Default = 1.0)
+    pub fn opacity(&self) -> f32 {
+        self.view.content_opacity()
+    }
+
     // only found in C++ and not in the C API yet.
     // pub fn need_repaint(&self) -> bool {
     // }
@@ -146,7 +199,12 @@ impl Overlay {
 
 impl Drop for Overlay {
     fn drop(&mut self) {
+        self.registry
+            .borrow_mut()
+            .retain(|&overlay| overlay != self.internal);
         unsafe {
+            // the view is destroyed with the overlay
+            View::forget_state(self.view.to_ul());
             self.lib.appcore().ulDestroyOverlay(self.internal);
         }
     }
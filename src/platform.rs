@@ -4,19 +4,31 @@
 //! The configurations applied to the platform should be set before creating
 //! a [`Renderer`](crate::renderer::Renderer) instance.
 use std::{
-    path::Path,
-    sync::{Arc, Mutex},
+    ffi::c_void,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::{pin, Pin},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
 };
 
 #[allow(unused_imports)]
-use crate::error::CreationError;
+use crate::error::{CreationError, UnknownEnumValue};
 
 use crate::{
     gpu_driver::{self, GpuDriver},
-    string::UlString,
+    string::{path_to_str, UlString},
     Library,
 };
 
+pub mod clipboards;
+pub mod filesystems;
+pub mod loggers;
+
 // static globals for holding Rust implementations of platform structs,
 // these will be used on callbacks from the C APIs.
 lazy_static::lazy_static! {
@@ -27,6 +39,24 @@ lazy_static::lazy_static! {
     pub(crate) static ref GPUDRIVER: InternalPlatform<Box<dyn GpuDriver + Send>> = InternalPlatform::new();
 }
 
+/// Whether the logger of `AppCore` was enabled with [`enable_default_logger`],
+/// it can't be restored once replaced.
+static NATIVE_LOGGER: AtomicBool = AtomicBool::new(false);
+
+/// The base directory of the last [`enable_platform_filesystem`] call,
+/// resolved against the working directory at the time of the call, cleared
+/// when the file system is replaced or cleared.
+static PLATFORM_FILESYSTEM_BASE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// The base directory of the platform file system, if it was enabled with
+/// [`enable_platform_filesystem`].
+pub(crate) fn platform_filesystem_base() -> Option<PathBuf> {
+    PLATFORM_FILESYSTEM_BASE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
 pub(crate) struct InternalPlatform<T> {
     pub(crate) lib: Mutex<Option<Arc<Library>>>,
     pub(crate) obj: Mutex<Option<T>>,
@@ -41,6 +71,32 @@ impl<T> InternalPlatform<T> {
     }
 }
 
+/// Whether or not a file system was set with [`set_filesystem`].
+#[cfg(any(feature = "appcore_linked", feature = "loaded"))]
+pub(crate) fn is_filesystem_set() -> bool {
+    FILESYSTEM
+        .obj
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_some()
+}
+
+/// Whether or not a GPU driver was set with [`set_gpu_driver`] for `lib`.
+pub(crate) fn is_gpu_driver_set(lib: &Arc<Library>) -> bool {
+    let is_lib = GPUDRIVER
+        .lib
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .is_some_and(|set_lib| Arc::ptr_eq(set_lib, lib));
+    is_lib
+        && GPUDRIVER
+            .obj
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+}
+
 #[derive(Clone, Copy, Debug)]
 /// Log levels for the logger. (See [`Logger::log_message`])
 pub enum LogLevel {
@@ -53,14 +109,14 @@ pub enum LogLevel {
 }
 
 impl TryFrom<u32> for LogLevel {
-    type Error = ();
+    type Error = UnknownEnumValue;
 
-    fn try_from(value: u32) -> Result<Self, ()> {
+    fn try_from(value: u32) -> Result<Self, UnknownEnumValue> {
         match value {
             ul_sys::ULLogLevel_kLogLevel_Info => Ok(LogLevel::Info),
             ul_sys::ULLogLevel_kLogLevel_Warning => Ok(LogLevel::Warning),
             ul_sys::ULLogLevel_kLogLevel_Error => Ok(LogLevel::Error),
-            _ => Err(()),
+            _ => Err(UnknownEnumValue::new("LogLevel", value)),
         }
     }
 }
@@ -84,6 +140,10 @@ pub trait Logger {
 /// instead of [`App::new`](crate::app::App::new), you will
 /// need to provide your own implementation of this.
 /// (See [`platform::set_clipboard`](set_clipboard))
+///
+/// Ready-made implementations are available in [`clipboards`], including
+/// one using the system clipboard with the `clipboard` feature (see
+/// [`platform::enable_system_clipboard`](enable_system_clipboard)).
 pub trait Clipboard {
     /// Clear the clipboard.
     fn clear(&mut self);
@@ -99,6 +159,80 @@ pub trait Clipboard {
     fn write_plain_text(&mut self, text: &str);
 }
 
+/// A [`Clipboard`] which reads the clipboard asynchronously.
+///
+/// On some platforms (eg. Wayland and macOS), reading the clipboard is
+/// asynchronous by nature. Implementing this trait instead of [`Clipboard`]
+/// allows using async I/O for reading, while the library still expects
+/// the text synchronously: all types implementing this implement
+/// [`Clipboard`], where [`Clipboard::read_plain_text`] blocks the current
+/// thread until the future of [`read_plain_text_async`](Self::read_plain_text_async)
+/// completes, using a minimal executor.
+///
+/// The future is polled on the thread calling into the library (the thread
+/// calling [`Renderer::update`](crate::renderer::Renderer::update) or running
+/// the [`App`](crate::app::App)), so it must not depend on that thread being
+/// free to make progress (eg. waiting for the event loop of the same thread),
+/// or it will block forever.
+///
+/// (See [`platform::set_async_clipboard`](set_async_clipboard))
+pub trait AsyncClipboard {
+    /// Clear the clipboard.
+    fn clear(&mut self);
+
+    /// Read plaintext from the clipboard asynchronously.
+    ///
+    /// Invoked when the library wants to read from the system's clipboard.
+    fn read_plain_text_async(&mut self) -> Pin<Box<dyn Future<Output = Option<String>>>>;
+
+    /// Write plaintext to the clipboard.
+    ///
+    /// Invoked when the library wants to write to the system's clipboard.
+    fn write_plain_text(&mut self, text: &str);
+}
+
+impl<T: AsyncClipboard> Clipboard for T {
+    fn clear(&mut self) {
+        AsyncClipboard::clear(self)
+    }
+
+    fn read_plain_text(&mut self) -> Option<String> {
+        block_on(self.read_plain_text_async())
+    }
+
+    fn write_plain_text(&mut self, text: &str) {
+        AsyncClipboard::write_plain_text(self, text)
+    }
+}
+
+/// Wakes a thread blocked in [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run `future` to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            // spurious wakeups only cause an extra poll
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
 /// This is used for loading File URLs (eg, <file:///page.html>).
 ///
 /// You can provide the library with your own FileSystem implementation so that file assets are
@@ -150,16 +284,54 @@ pub struct FontFile {
 }
 
 impl FontFile {
-    // TODO: support buffers
-    // pub fn from_buffer(...) -> Option<Self> {
-    //}
+    /// Create a font file from in-memory font data (TTF/OTF file contents).
+    ///
+    /// The data is moved into the font file without copying, and is freed
+    /// when Ultralight no longer needs it. This is useful for fonts embedded
+    /// in the binary with [`include_bytes!`].
+    ///
+    /// Returns [`None`] if Ultralight failed to create the font file.
+    pub fn from_buffer(lib: Arc<Library>, data: impl Into<Vec<u8>>) -> Option<Self> {
+        unsafe extern "C" fn destroy_buffer(user_data: *mut c_void, _data: *mut c_void) {
+            drop(Box::from_raw(user_data as *mut Vec<u8>));
+        }
+
+        let data = Box::new(data.into());
+        let data_ptr = data.as_ptr() as *mut c_void;
+        let data_len = data.len();
+        let user_data = Box::into_raw(data) as *mut c_void;
+
+        unsafe {
+            let buffer = lib.ultralight().ulCreateBuffer(
+                data_ptr,
+                data_len,
+                user_data,
+                Some(destroy_buffer),
+            );
+            if buffer.is_null() {
+                destroy_buffer(user_data, data_ptr);
+                return None;
+            }
+
+            let internal = lib.ultralight().ulFontFileCreateFromBuffer(buffer);
+            // the font file keeps its own reference to the buffer
+            lib.ultralight().ulDestroyBuffer(buffer);
+
+            if internal.is_null() {
+                return None;
+            }
+            Some(FontFile { lib, internal })
+        }
+    }
 
     /// Create a font file from an on-disk file path.
     ///
-    /// The file path should already exist.
+    /// The file path should already exist, and be valid UTF-8, otherwise
+    /// [`None`] is returned.
     pub fn from_path<P: AsRef<Path>>(lib: Arc<Library>, path: P) -> Option<Self> {
         unsafe {
-            let path = UlString::from_str(lib.clone(), path.as_ref().to_str().unwrap()).unwrap();
+            let path = path_to_str(path.as_ref()).ok()?;
+            let path = UlString::from_str(lib.clone(), path).ok()?;
 
             let internal = lib.ultralight().ulFontFileCreateFromFilePath(path.to_ul());
             if internal.is_null() {
@@ -302,25 +474,48 @@ platform_set_interface_macro! {
     }
 }
 
+/// Set a custom [`AsyncClipboard`] implementation.
+///
+/// This is the same as [`platform::set_clipboard`](set_clipboard), but makes
+/// it clear that the reads of the clipboard are asynchronous, see
+/// [`AsyncClipboard`] for how they are performed.
+///
+/// You should call this before [`Renderer::create`] or [`App::new`].
+///
+/// [`App::new`]: crate::app::App::new
+/// [`Renderer::create`]: crate::renderer::Renderer::create
+pub fn set_async_clipboard<T: AsyncClipboard + Send + 'static>(lib: Arc<Library>, clipboard: T) {
+    set_clipboard(lib, clipboard);
+}
+
+/// Set a custom FileSystem implementation.
+///
+/// The library uses this to load all file URLs (eg, <file:///page.html>).
+///
+/// You can provide the library with your own FileSystem implementation
+/// so that file assets are loaded from your own pipeline.
+///
+/// You should call this before [`Renderer::create`] or [`App::new`].
+///
+/// Note: [`App::new`] will use the default platform file system if you never call this.
+///
+/// If you're not using [`App::new`], (eg, using [`Renderer::create`]) you
+/// can still use the default platform file system by calling
+/// [`platform::enable_platform_filesystem`](enable_platform_filesystem).
+///
+/// [`App::new`]: crate::app::App::new
+/// [`Renderer::create`]: crate::renderer::Renderer::create
+pub fn set_filesystem<T: FileSystem + Send + 'static>(lib: Arc<Library>, filesystem: T) {
+    // the platform file system is replaced
+    PLATFORM_FILESYSTEM_BASE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    set_filesystem_interface(lib, filesystem);
+}
+
 platform_set_interface_macro! {
-    /// Set a custom FileSystem implementation.
-    ///
-    /// The library uses this to load all file URLs (eg, <file:///page.html>).
-    ///
-    /// You can provide the library with your own FileSystem implementation
-    /// so that file assets are loaded from your own pipeline.
-    ///
-    /// You should call this before [`Renderer::create`] or [`App::new`].
-    ///
-    /// Note: [`App::new`] will use the default platform file system if you never call this.
-    ///
-    /// If you're not using [`App::new`], (eg, using [`Renderer::create`]) you
-    /// can still use the default platform file system by calling
-    /// [`platform::enable_platform_filesystem`](enable_platform_filesystem).
-    ///
-    /// [`App::new`]: crate::app::App::new
-    /// [`Renderer::create`]: crate::renderer::Renderer::create
-    pub set_filesystem<FileSystem>(lib, filesystem -> FILESYSTEM) -> ulPlatformSetFileSystem(ULFileSystem) {
+    set_filesystem_interface<FileSystem>(lib, filesystem -> FILESYSTEM) -> ulPlatformSetFileSystem(ULFileSystem) {
         // TODO: handle errors
         file_exists((ul_path: ul_sys::ULString) -> bool) -> ((path: &str)) {
             let path = UlString::copy_raw_to_string(&lib, ul_path).unwrap();
@@ -403,6 +598,66 @@ platform_set_interface_macro! {
 //     }
 // }
 
+/// Set a closure as the Logger implementation.
+///
+/// This is a convenience over [`platform::set_logger`](set_logger) to avoid
+/// defining a struct implementing [`Logger`].
+///
+/// # Examples
+/// ```no_run
+/// # use ul_next::platform;
+/// # let lib: std::sync::Arc<ul_next::Library> = unsafe { std::mem::zeroed() };
+/// platform::set_logger_fn(lib.clone(), |level, message| {
+///     println!("[{:?}] {}", level, message);
+/// });
+/// ```
+pub fn set_logger_fn<F>(lib: Arc<Library>, logger: F)
+where
+    F: FnMut(LogLevel, String) + Send + 'static,
+{
+    struct FnLogger<F>(F);
+
+    impl<F: FnMut(LogLevel, String)> Logger for FnLogger<F> {
+        fn log_message(&mut self, log_level: LogLevel, message: String) {
+            (self.0)(log_level, message)
+        }
+    }
+
+    set_logger(lib, FnLogger(logger))
+}
+
+/// Forward `Ultralight` log messages to the [`log`](https://docs.rs/log) crate.
+///
+/// This sets [`loggers::LogCrateLogger`] as the logger, see it for how the
+/// messages are logged.
+///
+/// You should call this before [`App::new`] or [`Renderer::create`].
+///
+/// [`App::new`]: crate::app::App::new
+/// [`Renderer::create`]: crate::renderer::Renderer::create
+#[cfg(feature = "log")]
+#[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+pub fn enable_log_crate_logger(lib: Arc<Library>) {
+    set_logger(lib, loggers::LogCrateLogger)
+}
+
+/// Use the clipboard of the operating system, with the
+/// [`arboard`](https://docs.rs/arboard) crate.
+///
+/// This sets [`clipboards::SystemClipboard`] as the clipboard, see it for
+/// how a missing clipboard (eg. in headless environments) is handled.
+///
+/// [`App`](crate::app::App) already uses the system clipboard, this is only
+/// needed with [`Renderer::create`]. You should call this before
+/// [`Renderer::create`].
+///
+/// [`Renderer::create`]: crate::renderer::Renderer::create
+#[cfg(feature = "clipboard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+pub fn enable_system_clipboard(lib: Arc<Library>) {
+    set_clipboard(lib, clipboards::SystemClipboard)
+}
+
 /// Set a custom GPUDriver implementation.
 ///
 /// This should be used if you have enabled the GPU renderer in the Config and are using
@@ -414,15 +669,172 @@ platform_set_interface_macro! {
 /// as a custom implementation for `glium` in [`glium`](crate::gpu_driver::glium).
 ///
 /// You should call this before [`Renderer::create`](crate::renderer::Renderer::create).
+///
+/// Calling this again replaces the previous driver, which is dropped, see
+/// also [`platform::clear_gpu_driver`](clear_gpu_driver).
 pub fn set_gpu_driver<G: GpuDriver + Send + 'static>(lib: Arc<Library>, driver: G) {
     gpu_driver::set_gpu_driver(lib, driver)
 }
 
+/// Drop the implementation stored in `platform`, Ultralight must not call
+/// it anymore.
+fn clear_platform<T>(platform: &InternalPlatform<T>) {
+    // taken out first, so that the lock is not held while dropping it
+    let obj = platform
+        .obj
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    platform
+        .lib
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    drop(obj);
+}
+
+/// Remove the Logger set with [`platform::set_logger`](set_logger) (or
+/// [`platform::enable_default_logger`](enable_default_logger)), and drop it.
+///
+/// Nothing is logged after this.
+///
+/// # Safety
+///
+/// This must not be called while a [`Renderer`](crate::renderer::Renderer)
+/// is alive, Ultralight would call the removed logger.
+pub unsafe fn clear_logger(lib: Arc<Library>) {
+    lib.ultralight().ulPlatformSetLogger(Default::default());
+    NATIVE_LOGGER.store(false, Ordering::Relaxed);
+    clear_platform(&LOGGER);
+}
+
+/// Collects the messages logged while capturing, see [`capture_log`].
+struct CaptureLogger(Arc<Mutex<Vec<String>>>);
+
+impl Logger for CaptureLogger {
+    fn log_message(&mut self, _log_level: LogLevel, message: String) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(message);
+    }
+}
+
+/// Stands in for the missing logger after [`capture_log`] installed one,
+/// Ultralight keeps calling the logger once it's set.
+struct DiscardLogger;
+
+impl Logger for DiscardLogger {
+    fn log_message(&mut self, _log_level: LogLevel, _message: String) {}
+}
+
+/// Run `f`, and return the messages logged during it instead of passing
+/// them to the current logger.
+///
+/// A logger set with [`set_logger`] is swapped out and restored after `f`.
+/// Without one, a logger is installed for `f` only if `can_install` is
+/// `true` and the logger of `AppCore` was not enabled, because it couldn't
+/// be restored, and it discards the messages after `f` (it can't be
+/// removed while the renderer is alive). Otherwise, returns [`None`]
+/// without calling `f`.
+pub(crate) fn capture_log(
+    lib: &Arc<Library>,
+    can_install: bool,
+    f: impl FnOnce(),
+) -> Option<Vec<String>> {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+
+    // swapped under a single lock, so that the logger is never missing
+    let previous = {
+        let mut logger = LOGGER.obj.lock().unwrap_or_else(PoisonError::into_inner);
+        if logger.is_some() {
+            logger.replace(Box::new(CaptureLogger(messages.clone())))
+        } else {
+            None
+        }
+    };
+    match previous {
+        Some(previous) => {
+            f();
+            LOGGER
+                .obj
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .replace(previous);
+        }
+        None => {
+            if !can_install || NATIVE_LOGGER.load(Ordering::Relaxed) {
+                return None;
+            }
+            set_logger(lib.clone(), CaptureLogger(messages.clone()));
+            f();
+            LOGGER
+                .obj
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .replace(Box::new(DiscardLogger));
+        }
+    }
+
+    let messages = std::mem::take(&mut *messages.lock().unwrap_or_else(PoisonError::into_inner));
+    Some(messages)
+}
+
+/// Remove the Clipboard set with [`platform::set_clipboard`](set_clipboard)
+/// or [`platform::set_async_clipboard`](set_async_clipboard), and drop it.
+///
+/// # Safety
+///
+/// This must not be called while a [`Renderer`](crate::renderer::Renderer)
+/// is alive, Ultralight would call the removed clipboard.
+pub unsafe fn clear_clipboard(lib: Arc<Library>) {
+    lib.ultralight().ulPlatformSetClipboard(Default::default());
+    clear_platform(&CLIPBOARD);
+}
+
+/// Remove the FileSystem set with [`platform::set_filesystem`](set_filesystem)
+/// (or [`platform::enable_platform_filesystem`](enable_platform_filesystem)),
+/// and drop it.
+///
+/// # Safety
+///
+/// This must not be called while a [`Renderer`](crate::renderer::Renderer)
+/// is alive, Ultralight would call the removed file system.
+pub unsafe fn clear_filesystem(lib: Arc<Library>) {
+    lib.ultralight().ulPlatformSetFileSystem(Default::default());
+    PLATFORM_FILESYSTEM_BASE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    clear_platform(&FILESYSTEM);
+}
+
+/// Remove the GpuDriver set with [`platform::set_gpu_driver`](set_gpu_driver),
+/// and drop it.
+///
+/// This allows a new driver to be set for the next
+/// [`Renderer`](crate::renderer::Renderer), for example when the window (and
+/// the receiver of [`glium`](crate::gpu_driver::glium)) of the previous one
+/// was closed. Note that [`platform::set_gpu_driver`](set_gpu_driver) also
+/// drops the previous driver.
+///
+/// # Safety
+///
+/// This must not be called while a [`Renderer`](crate::renderer::Renderer)
+/// using the driver is alive, Ultralight would call the removed driver.
+pub unsafe fn clear_gpu_driver(lib: Arc<Library>) {
+    lib.ultralight().ulPlatformSetGPUDriver(Default::default());
+    clear_platform(&GPUDRIVER);
+}
+
 /// Initializes the default logger (writes the log to a file).
 ///
 /// This is only needed if you are not calling [`App::new`](crate::app::App::new)
 ///
 /// You should specify a writable log path to write the log to for example “./ultralight.log”.
+/// Relative paths are resolved against the current working directory.
+///
+/// Returns [`CreationError::InvalidPath`] if `log_path` is not valid UTF-8.
 #[cfg(any(feature = "appcore_linked", feature = "loaded"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "appcore_linked", feature = "loaded"))))]
 pub fn enable_default_logger<P: AsRef<Path>>(
@@ -430,10 +842,10 @@ pub fn enable_default_logger<P: AsRef<Path>>(
     log_path: P,
 ) -> Result<(), CreationError> {
     unsafe {
-        // TODO: handle error
-        let log_path = UlString::from_str(lib.clone(), log_path.as_ref().to_str().unwrap())?;
+        let log_path = UlString::from_str(lib.clone(), path_to_str(log_path.as_ref())?)?;
         lib.appcore().ulEnableDefaultLogger(log_path.to_ul());
     }
+    NATIVE_LOGGER.store(true, Ordering::Relaxed);
     Ok(())
 }
 
@@ -452,7 +864,11 @@ pub fn enable_platform_fontloader(lib: Arc<Library>) {
 ///
 /// This is only needed if you are not calling [`App::new`](crate::app::App::new)
 ///
-/// You can specify a base directory path to resolve relative paths against
+/// You can specify a base directory path to resolve relative paths against,
+/// if `base_dir` itself is relative, it's resolved against the current
+/// working directory.
+///
+/// Returns [`CreationError::InvalidPath`] if `base_dir` is not valid UTF-8.
 #[cfg(any(feature = "appcore_linked", feature = "loaded"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "appcore_linked", feature = "loaded"))))]
 pub fn enable_platform_filesystem<P: AsRef<Path>>(
@@ -460,9 +876,14 @@ pub fn enable_platform_filesystem<P: AsRef<Path>>(
     base_dir: P,
 ) -> Result<(), CreationError> {
     unsafe {
-        // TODO: handle error
-        let base_dir = UlString::from_str(lib.clone(), base_dir.as_ref().to_str().unwrap())?;
-        lib.appcore().ulEnablePlatformFileSystem(base_dir.to_ul());
+        let base_dir_string = UlString::from_str(lib.clone(), path_to_str(base_dir.as_ref())?)?;
+        lib.appcore()
+            .ulEnablePlatformFileSystem(base_dir_string.to_ul());
     }
+    *PLATFORM_FILESYSTEM_BASE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = std::env::current_dir()
+        .map(|cwd| cwd.join(base_dir.as_ref()))
+        .ok();
     Ok(())
 }
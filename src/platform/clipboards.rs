@@ -0,0 +1,149 @@
+//! Ready-made [`Clipboard`] implementations.
+//!
+//! These can be passed to [`platform::set_clipboard`](super::set_clipboard).
+use std::sync::{Arc, Mutex};
+
+use super::Clipboard;
+
+/// A [`Clipboard`] keeping the text in memory, it's not shared with the
+/// system clipboard.
+///
+/// This is useful for headless rendering and tests: the handle can be
+/// cloned before passing it to [`platform::set_clipboard`](super::set_clipboard),
+/// and all the clones share the same text.
+///
+/// # Examples
+/// ```no_run
+/// # use ul_next::platform::{self, clipboards::MemoryClipboard};
+/// # let lib: std::sync::Arc<ul_next::Library> = unsafe { std::mem::zeroed() };
+/// let clipboard = MemoryClipboard::new();
+/// platform::set_clipboard(lib.clone(), clipboard.clone());
+///
+/// // ... copy something in a view
+/// println!("copied: {}", clipboard.text());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct MemoryClipboard {
+    text: Arc<Mutex<String>>,
+}
+
+impl MemoryClipboard {
+    /// Create an empty clipboard.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a clipboard containing `text`.
+    pub fn with_text(text: &str) -> Self {
+        let clipboard = Self::new();
+        clipboard.set_text(text);
+        clipboard
+    }
+
+    /// The text in the clipboard.
+    pub fn text(&self) -> String {
+        self.text.lock().unwrap().clone()
+    }
+
+    /// Replace the text in the clipboard, the next paste in a view will use
+    /// it.
+    pub fn set_text(&self, text: &str) {
+        *self.text.lock().unwrap() = text.to_string();
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn clear(&mut self) {
+        self.text.lock().unwrap().clear();
+    }
+
+    fn read_plain_text(&mut self) -> Option<String> {
+        Some(self.text())
+    }
+
+    fn write_plain_text(&mut self, text: &str) {
+        self.set_text(text);
+    }
+}
+
+#[cfg(feature = "clipboard")]
+pub use system::SystemClipboard;
+
+#[cfg(feature = "clipboard")]
+mod system {
+    use std::{
+        cell::RefCell,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use super::Clipboard;
+
+    thread_local! {
+        // the clipboard is created lazily on the thread calling into
+        // Ultralight, and kept alive so that the copied text stays
+        // available on X11, where the owner of the selection serves it
+        static CLIPBOARD: RefCell<Option<arboard::Clipboard>> = const { RefCell::new(None) };
+    }
+
+    static WARNED: AtomicBool = AtomicBool::new(false);
+
+    /// A [`Clipboard`] using the clipboard of the operating system, with
+    /// the [`arboard`](https://docs.rs/arboard) crate.
+    ///
+    /// When the clipboard is not available (eg. no display server in a
+    /// headless environment), a warning is printed once (with `log` if the
+    /// `log` feature is enabled, or to `stderr` otherwise), copying does
+    /// nothing and pasting returns no text.
+    ///
+    /// This is what [`platform::enable_system_clipboard`](crate::platform::enable_system_clipboard) sets.
+    #[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct SystemClipboard;
+
+    impl SystemClipboard {
+        /// Run `f` with the clipboard of this thread, creating it if needed.
+        fn with<R>(
+            f: impl FnOnce(&mut arboard::Clipboard) -> Result<R, arboard::Error>,
+        ) -> Option<R> {
+            CLIPBOARD.with(|clipboard| {
+                let mut clipboard = clipboard.borrow_mut();
+                if clipboard.is_none() {
+                    *clipboard = Some(arboard::Clipboard::new().map_err(warn_once).ok()?);
+                }
+                match f(clipboard.as_mut().unwrap()) {
+                    Ok(result) => Some(result),
+                    // the clipboard is empty or doesn't contain text
+                    Err(arboard::Error::ContentNotAvailable) => None,
+                    Err(err) => {
+                        warn_once(err);
+                        None
+                    }
+                }
+            })
+        }
+    }
+
+    impl Clipboard for SystemClipboard {
+        fn clear(&mut self) {
+            Self::with(|clipboard| clipboard.set_text(""));
+        }
+
+        fn read_plain_text(&mut self) -> Option<String> {
+            Self::with(|clipboard| clipboard.get_text())
+        }
+
+        fn write_plain_text(&mut self, text: &str) {
+            Self::with(|clipboard| clipboard.set_text(text));
+        }
+    }
+
+    fn warn_once(err: arboard::Error) {
+        if WARNED.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        #[cfg(feature = "log")]
+        log::warn!("The system clipboard is not available: {}", err);
+        #[cfg(not(feature = "log"))]
+        eprintln!("warning: The system clipboard is not available: {}", err);
+    }
+}
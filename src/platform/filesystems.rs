@@ -0,0 +1,427 @@
+//! Ready-made [`FileSystem`] implementations.
+//!
+//! These can be passed to [`platform::set_filesystem`](super::set_filesystem).
+//!
+//! Paths given by Ultralight are normalized with the same rules in all the
+//! file systems here, so `file:///index.html`, `/index.html`, `./index.html`
+//! and `index.html` all refer to the same file. Paths going above the root
+//! of the file system (with `..`) are rejected.
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use super::FileSystem;
+
+/// The mime type returned for files with an unknown extension.
+const UNKNOWN_MIME_TYPE: &str = "application/octet-stream";
+
+/// The charset returned for all files.
+const DEFAULT_CHARSET: &str = "utf-8";
+
+/// Get the mime type of a file from the extension of `path`.
+///
+/// Returns `application/octet-stream` if the extension is not known.
+pub fn mime_type_from_path(path: &str) -> &'static str {
+    let extension = match path.rsplit_once('.') {
+        Some((_, extension)) if !extension.contains(['/', '\\']) => extension.to_ascii_lowercase(),
+        _ => return UNKNOWN_MIME_TYPE,
+    };
+
+    match extension.as_str() {
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" | "map" => "application/json",
+        "txt" => "text/plain",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "wasm" => "application/wasm",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => UNKNOWN_MIME_TYPE,
+    }
+}
+
+/// Normalize a path relative to the root of a file system.
+///
+/// Removes the `file://` scheme, any query or fragment, leading `/` and
+/// `.` components, and resolves `..` components. Backslashes are treated as
+/// separators. Returns `None` if the path goes above the root.
+fn normalize_path(path: &str) -> Option<String> {
+    let path = path.strip_prefix("file://").unwrap_or(path);
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    let mut components = Vec::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            component => components.push(component),
+        }
+    }
+
+    Some(components.join("/"))
+}
+
+/// A [`FileSystem`] serving files from memory.
+///
+/// This is useful when shipping the web assets inside the binary
+/// (eg. with [`include_bytes!`]), so no resource directory is needed on disk.
+///
+/// # Examples
+/// ```no_run
+/// # use ul_next::{platform::{self, filesystems::MemoryFileSystem}, view::View};
+/// # fn example(lib: std::sync::Arc<ul_next::Library>, view: &View) {
+/// let mut fs = MemoryFileSystem::new();
+/// fs.insert("index.html", &b"<html><link rel=\"stylesheet\" href=\"style.css\"></html>"[..]);
+/// fs.insert("style.css", &b"body { color: red; }"[..]);
+///
+/// platform::set_filesystem(lib.clone(), fs);
+///
+/// // later
+/// view.load_url("file:///index.html").unwrap();
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFileSystem {
+    files: HashMap<String, MemoryFile>,
+}
+
+#[derive(Debug, Clone)]
+struct MemoryFile {
+    data: Cow<'static, [u8]>,
+    /// `None` to use the mime type of the extension.
+    mime_type: Option<String>,
+}
+
+impl MemoryFileSystem {
+    /// Create an empty file system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, the mime type is detected from the extension of `path`
+    /// (see [`mime_type_from_path`]).
+    ///
+    /// `data` can be borrowed for `'static` (eg. from [`include_bytes!`])
+    /// to avoid copying it. `path` is normalized (see the
+    /// [module documentation](self)), paths going above the root are ignored.
+    /// Adding a file to an existing path replaces it.
+    pub fn insert(&mut self, path: &str, data: impl Into<Cow<'static, [u8]>>) {
+        self.insert_file(path, data.into(), None);
+    }
+
+    /// Add a file with the given `mime_type` (eg. "text/html").
+    ///
+    /// `path` is relative to the root of the file system, ie. the file
+    /// `file:///index.html` is added with the path `index.html`.
+    /// Adding a file to an existing path replaces it.
+    pub fn add_file(&mut self, path: &str, data: &[u8], mime_type: &str) {
+        self.insert_file(path, Cow::Owned(data.to_vec()), Some(mime_type.to_string()));
+    }
+
+    /// Remove a file, returns `true` if the file existed.
+    pub fn remove_file(&mut self, path: &str) -> bool {
+        normalize_path(path).is_some_and(|path| self.files.remove(&path).is_some())
+    }
+
+    fn insert_file(&mut self, path: &str, data: Cow<'static, [u8]>, mime_type: Option<String>) {
+        if let Some(path) = normalize_path(path) {
+            self.files.insert(path, MemoryFile { data, mime_type });
+        }
+    }
+
+    fn get(&self, path: &str) -> Option<&MemoryFile> {
+        self.files.get(&normalize_path(path)?)
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn file_exists(&mut self, path: &str) -> bool {
+        self.get(path).is_some()
+    }
+
+    fn get_file_mime_type(&mut self, path: &str) -> String {
+        match self.get(path) {
+            Some(MemoryFile {
+                mime_type: Some(mime_type),
+                ..
+            }) => mime_type.clone(),
+            _ => mime_type_from_path(&normalize_path(path).unwrap_or_default()).to_string(),
+        }
+    }
+
+    fn get_file_charset(&mut self, _path: &str) -> String {
+        DEFAULT_CHARSET.to_string()
+    }
+
+    fn open_file(&mut self, path: &str) -> Option<Vec<u8>> {
+        self.get(path).map(|file| file.data.to_vec())
+    }
+}
+
+/// A [`FileSystem`] serving files from a directory on disk.
+///
+/// Reads are confined to the root directory: paths going above it with `..`
+/// are rejected, and so are symbolic links pointing outside of it.
+///
+/// The mime type is detected from the extension of the file
+/// (see [`mime_type_from_path`]).
+#[derive(Debug, Clone)]
+pub struct DirectoryFileSystem {
+    root: PathBuf,
+}
+
+impl DirectoryFileSystem {
+    /// Create a file system serving the files inside `root`.
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// The root directory of this file system.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get the path on disk of the file `path`, if it exists inside the root.
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        let path = self.root.join(normalize_path(path)?);
+
+        // check after resolving symbolic links
+        let root = self.root.canonicalize().ok()?;
+        let path = path.canonicalize().ok()?;
+        (path.starts_with(root) && path.is_file()).then_some(path)
+    }
+}
+
+impl FileSystem for DirectoryFileSystem {
+    fn file_exists(&mut self, path: &str) -> bool {
+        self.resolve(path).is_some()
+    }
+
+    fn get_file_mime_type(&mut self, path: &str) -> String {
+        mime_type_from_path(&normalize_path(path).unwrap_or_default()).to_string()
+    }
+
+    fn get_file_charset(&mut self, _path: &str) -> String {
+        DEFAULT_CHARSET.to_string()
+    }
+
+    fn open_file(&mut self, path: &str) -> Option<Vec<u8>> {
+        std::fs::read(self.resolve(path)?).ok()
+    }
+}
+
+/// A [`FileSystem`] serving the resources of Ultralight (ICU data, SSL
+/// certificates, ...) from memory, and every other file from an optional
+/// fallback file system.
+///
+/// Paths inside the resource prefix (see
+/// [`ConfigBuilder::resource_path_prefix`](crate::config::ConfigBuilder::resource_path_prefix))
+/// are only looked up in the resources, so nothing can be loaded from the
+/// fallback under the prefix. This is installed by
+/// [`ConfigBuilder::embedded_resources`](crate::config::ConfigBuilder::embedded_resources),
+/// use it directly to also serve the files of the application.
+///
+/// # Examples
+/// ```no_run
+/// # use std::collections::HashMap;
+/// # use ul_next::platform::{self, filesystems::{DirectoryFileSystem, ResourceFileSystem}};
+/// # let lib: std::sync::Arc<ul_next::Library> = unsafe { std::mem::zeroed() };
+/// let mut resources = HashMap::new();
+/// resources.insert("icudt67l.dat".to_string(), std::fs::read("resources/icudt67l.dat").unwrap());
+/// resources.insert("cacert.pem".to_string(), std::fs::read("resources/cacert.pem").unwrap());
+///
+/// let fs = ResourceFileSystem::new("resources/", resources)
+///     .with_fallback(DirectoryFileSystem::new("./assets".into()));
+/// platform::set_filesystem(lib.clone(), fs);
+/// ```
+pub struct ResourceFileSystem {
+    /// The normalized prefix, without the trailing `/`.
+    prefix: String,
+    resources: MemoryFileSystem,
+    fallback: Option<Box<dyn FileSystem + Send>>,
+}
+
+impl ResourceFileSystem {
+    /// Create a file system serving `resources` under `prefix`.
+    ///
+    /// The keys of `resources` are relative to the prefix, ie. the file
+    /// `file:///resources/cacert.pem` is the resource `cacert.pem` with the
+    /// default prefix `resources/`.
+    pub fn new(prefix: &str, resources: HashMap<String, Vec<u8>>) -> Self {
+        let prefix = normalize_path(prefix).unwrap_or_default();
+        let mut files = MemoryFileSystem::new();
+        for (name, data) in resources {
+            // `..` in `name` can't leave the prefix, as it is normalized
+            // relative to the prefix first
+            if let Some(name) = normalize_path(&name) {
+                files.insert(&format!("{}/{}", prefix, name), data);
+            }
+        }
+
+        Self {
+            prefix,
+            resources: files,
+            fallback: None,
+        }
+    }
+
+    /// Serve the paths outside of the resource prefix from `fallback`,
+    /// without it, they don't exist.
+    pub fn with_fallback<F: FileSystem + Send + 'static>(mut self, fallback: F) -> Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Whether `path` is inside the resource prefix.
+    fn is_resource(&self, path: &str) -> bool {
+        if self.prefix.is_empty() {
+            return true;
+        }
+        normalize_path(path).is_some_and(|path| {
+            path.strip_prefix(&self.prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// The file system responsible for `path`.
+    fn target(&mut self, path: &str) -> Option<&mut dyn FileSystem> {
+        if self.is_resource(path) {
+            Some(&mut self.resources)
+        } else {
+            self.fallback
+                .as_deref_mut()
+                .map(|fs| fs as &mut dyn FileSystem)
+        }
+    }
+}
+
+impl FileSystem for ResourceFileSystem {
+    fn file_exists(&mut self, path: &str) -> bool {
+        self.target(path).is_some_and(|fs| fs.file_exists(path))
+    }
+
+    fn get_file_mime_type(&mut self, path: &str) -> String {
+        match self.target(path) {
+            Some(fs) => fs.get_file_mime_type(path),
+            None => UNKNOWN_MIME_TYPE.to_string(),
+        }
+    }
+
+    fn get_file_charset(&mut self, path: &str) -> String {
+        match self.target(path) {
+            Some(fs) => fs.get_file_charset(path),
+            None => DEFAULT_CHARSET.to_string(),
+        }
+    }
+
+    fn open_file(&mut self, path: &str) -> Option<Vec<u8>> {
+        self.target(path)?.open_file(path)
+    }
+}
+
+/// A [`FileSystem`] serving the files of a ZIP archive, so that all the web
+/// assets of an application can be shipped as a single file (or embedded in
+/// the binary with [`include_bytes!`] and a [`std::io::Cursor`]).
+///
+/// The entry table of the archive is read once when created, and the files
+/// are decompressed each time they are opened. The names of the entries are
+/// normalized like the paths given by Ultralight (see the
+/// [module documentation](self)), and the mime type is detected from the
+/// extension (see [`mime_type_from_path`]).
+///
+/// # Examples
+/// ```no_run
+/// # use ul_next::{platform::{self, filesystems::ZipFileSystem}, view::View};
+/// # fn example(lib: std::sync::Arc<ul_next::Library>, view: &View) -> Result<(), Box<dyn std::error::Error>> {
+/// let archive = std::fs::File::open("assets.zip")?;
+/// platform::set_filesystem(lib.clone(), ZipFileSystem::new(archive)?);
+///
+/// // later, serves `index.html` at the root of the archive
+/// view.load_url("file:///index.html").unwrap();
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "zip")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zip")))]
+pub struct ZipFileSystem<R> {
+    archive: zip::ZipArchive<R>,
+    /// The index of each file in the archive, by normalized path.
+    entries: HashMap<String, usize>,
+}
+
+#[cfg(feature = "zip")]
+impl<R: std::io::Read + std::io::Seek> ZipFileSystem<R> {
+    /// Read the entry table of the ZIP archive in `reader`.
+    ///
+    /// Directories and entries with names going above the root of the
+    /// archive are ignored.
+    pub fn new(reader: R) -> Result<Self, zip::result::ZipError> {
+        let mut archive = zip::ZipArchive::new(reader)?;
+
+        let mut entries = HashMap::new();
+        for index in 0..archive.len() {
+            let file = archive.by_index_raw(index)?;
+            if file.is_dir() {
+                continue;
+            }
+            if let Some(path) = normalize_path(file.name()) {
+                entries.insert(path, index);
+            }
+        }
+
+        Ok(Self { archive, entries })
+    }
+
+    /// The normalized paths of the files in the archive.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    fn index(&self, path: &str) -> Option<usize> {
+        self.entries.get(&normalize_path(path)?).copied()
+    }
+}
+
+#[cfg(feature = "zip")]
+impl<R: std::io::Read + std::io::Seek> FileSystem for ZipFileSystem<R> {
+    fn file_exists(&mut self, path: &str) -> bool {
+        self.index(path).is_some()
+    }
+
+    fn get_file_mime_type(&mut self, path: &str) -> String {
+        mime_type_from_path(&normalize_path(path).unwrap_or_default()).to_string()
+    }
+
+    fn get_file_charset(&mut self, _path: &str) -> String {
+        DEFAULT_CHARSET.to_string()
+    }
+
+    fn open_file(&mut self, path: &str) -> Option<Vec<u8>> {
+        use std::io::Read;
+
+        let index = self.index(path)?;
+        let mut file = self.archive.by_index(index).ok()?;
+        let mut data = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut data).ok()?;
+        Some(data)
+    }
+}
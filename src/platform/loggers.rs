@@ -0,0 +1,53 @@
+//! Ready-made [`Logger`] implementations.
+//!
+//! These can be passed to [`platform::set_logger`](super::set_logger).
+use super::{LogLevel, Logger};
+
+/// A [`Logger`] forwarding messages to the [`log`](https://docs.rs/log)
+/// crate, so they end up in whatever `log` backend the application
+/// uses (`env_logger`, `simplelog`, ...).
+///
+/// Messages are logged with the target `ultralight`, and the levels are mapped
+/// as follows:
+/// - [`LogLevel::Info`] => [`log::Level::Info`]
+/// - [`LogLevel::Warning`] => [`log::Level::Warn`]
+/// - [`LogLevel::Error`] => [`log::Level::Error`]
+///
+/// This is what [`platform::enable_log_crate_logger`](super::enable_log_crate_logger) sets.
+///
+/// # Examples
+/// ```no_run
+/// # use ul_next::platform::{self, loggers::LogCrateLogger};
+/// # let lib: std::sync::Arc<ul_next::Library> = unsafe { std::mem::zeroed() };
+/// // after setting up a `log` implementation (eg. `env_logger::init()`)
+/// platform::set_logger(lib.clone(), LogCrateLogger);
+/// ```
+#[cfg(feature = "log")]
+#[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogCrateLogger;
+
+#[cfg(feature = "log")]
+impl Logger for LogCrateLogger {
+    fn log_message(&mut self, log_level: LogLevel, message: String) {
+        let level = match log_level {
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Warning => log::Level::Warn,
+            LogLevel::Error => log::Level::Error,
+        };
+        log::log!(target: "ultralight", level, "{}", message);
+    }
+}
+
+/// A [`Logger`] printing all messages to the standard error, prefixed with
+/// their level, eg. `[ultralight] [Warning] ...`.
+///
+/// Useful for quick debugging without setting up a logging backend.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StderrLogger;
+
+impl Logger for StderrLogger {
+    fn log_message(&mut self, log_level: LogLevel, message: String) {
+        eprintln!("[ultralight] [{:?}] {}", log_level, message);
+    }
+}
@@ -1,6 +1,12 @@
 //! A container for Rectangle structure.
-#[derive(Clone, Copy, Debug)]
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Rectangle structure
+///
+/// The `right` and `bottom` edges are exclusive, and the `y` axis points
+/// down, so a valid rectangle has `left < right` and `top < bottom`.
 pub struct Rect<T> {
     pub left: T,
     pub top: T,
@@ -8,10 +14,130 @@ pub struct Rect<T> {
     pub bottom: T,
 }
 
-impl Rect<i32> {
-    /// Whether the rectangle is empty or not.
+fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+impl<T: Copy + PartialOrd> Rect<T> {
+    /// Whether the rectangle has no area, i.e. its width or height is zero
+    /// or negative (this includes the all-zeros rectangle `Ultralight` uses
+    /// for empty bounds).
+    ///
+    /// Rectangles with `NaN` edges are empty as well.
     pub fn is_empty(&self) -> bool {
-        self.left == 0 && self.top == 0 && self.right == 0 && self.bottom == 0
+        !(self.left < self.right && self.top < self.bottom)
+    }
+
+    /// Whether the point `(x, y)` is inside the rectangle, the `right` and
+    /// `bottom` edges are not included.
+    pub fn contains_point(&self, x: T, y: T) -> bool {
+        self.left <= x && x < self.right && self.top <= y && y < self.bottom
+    }
+
+    /// The overlapping area of both rectangles, or [`None`] if they don't
+    /// overlap (including when one of them is empty).
+    pub fn intersect(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let result = Rect {
+            left: partial_max(self.left, other.left),
+            top: partial_max(self.top, other.top),
+            right: partial_min(self.right, other.right),
+            bottom: partial_min(self.bottom, other.bottom),
+        };
+
+        (!result.is_empty()).then_some(result)
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    ///
+    /// Empty rectangles are ignored, so the union of a rectangle with an
+    /// empty one is the rectangle itself.
+    pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return *other;
+        }
+
+        Rect {
+            left: partial_min(self.left, other.left),
+            top: partial_min(self.top, other.top),
+            right: partial_max(self.right, other.right),
+            bottom: partial_max(self.bottom, other.bottom),
+        }
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> Rect<T> {
+    /// The width of the rectangle (`right - left`).
+    ///
+    /// This is negative for inverted rectangles, and overflows for inverted
+    /// rectangles of unsigned types, check [`Rect::is_empty`] first if
+    /// that's possible.
+    pub fn width(&self) -> T {
+        self.right - self.left
+    }
+
+    /// The height of the rectangle (`bottom - top`), see [`Rect::width`].
+    pub fn height(&self) -> T {
+        self.bottom - self.top
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Rect<T> {
+    /// Move the rectangle by `dx` horizontally and `dy` vertically.
+    pub fn translate(&self, dx: T, dy: T) -> Rect<T> {
+        Rect {
+            left: self.left + dx,
+            top: self.top + dy,
+            right: self.right + dx,
+            bottom: self.bottom + dy,
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Rect<T> {
+    /// Multiply all the edges by `factor`, for example to convert between
+    /// device pixels and CSS pixels.
+    ///
+    /// A negative `factor` inverts the rectangle.
+    pub fn scale(&self, factor: T) -> Rect<T> {
+        Rect {
+            left: self.left * factor,
+            top: self.top * factor,
+            right: self.right * factor,
+            bottom: self.bottom * factor,
+        }
+    }
+}
+
+impl From<Rect<i32>> for Rect<f32> {
+    fn from(r: Rect<i32>) -> Self {
+        Rect {
+            left: r.left as f32,
+            top: r.top as f32,
+            right: r.right as f32,
+            bottom: r.bottom as f32,
+        }
+    }
+}
+
+/// `[left, top, right, bottom]`, for example to be passed as a shader uniform.
+impl From<Rect<f32>> for [f32; 4] {
+    fn from(r: Rect<f32>) -> Self {
+        [r.left, r.top, r.right, r.bottom]
     }
 }
 
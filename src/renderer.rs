@@ -7,24 +7,112 @@
 //! Before creating a renderer [`Renderer::create`] you must supply a custom
 //! [`GpuDriver`](crate::gpu_driver::GpuDriver) in
 //! [`platform::set_gpu_driver`](crate::platform::set_gpu_driver).
-use std::{ffi::CString, sync::Arc};
+use std::{
+    cell::{Cell, RefCell},
+    ffi::CString,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
+    callback_panic,
     config::Config,
-    error::CreationError,
+    error::{CreationError, Error},
     event::{GamepadAxisEvent, GamepadButtonEvent, GamepadEvent},
+    platform,
     string::UlString,
     view::{View, ViewConfig},
     Library,
 };
 
+pub mod gamepad;
+
+pub mod inspector;
+
+mod defer;
+mod display_pump;
+mod memory;
+mod scheduler;
+mod snapshot;
+mod view_builder;
+
+pub use defer::DeferQueue;
+pub use display_pump::DisplayPump;
+pub use memory::{MemoryReport, MemoryStats, MemoryStatsEntry};
+pub use scheduler::{FrameDriver, PaintTarget, ScheduledViewId, Scheduler, SchedulerStats};
+pub use snapshot::SnapshotError;
+pub use view_builder::ViewBuilder;
+
+/// The views created with [`Renderer::create_view`] that are still alive,
+/// in creation order. Each view removes itself when dropped.
+pub(crate) type ViewRegistry = Rc<RefCell<Vec<ul_sys::ULView>>>;
+
+/// Get the resident set size of the current process in bytes.
+#[cfg(feature = "sysinfo")]
+fn process_memory_usage() -> Option<usize> {
+    use sysinfo::{ProcessRefreshKind, System};
+
+    thread_local! {
+        static SYSTEM: std::cell::RefCell<System> = std::cell::RefCell::new(System::new());
+    }
+
+    let pid = sysinfo::get_current_pid().ok()?;
+    SYSTEM.with(|system| {
+        let mut system = system.borrow_mut();
+        if !system.refresh_process_specifics(pid, ProcessRefreshKind::new().with_memory()) {
+            return None;
+        }
+        system
+            .process(pid)
+            .and_then(|process| usize::try_from(process.memory()).ok())
+    })
+}
+
+/// The memory usage can't be read without `sysinfo`.
+#[cfg(not(feature = "sysinfo"))]
+fn process_memory_usage() -> Option<usize> {
+    None
+}
+
+/// The underlying `ULSession`, shared by all the handles of a [`Session`],
+/// and destroyed when the last one is dropped.
+struct SessionHandle {
+    lib: Arc<Library>,
+    internal: ul_sys::ULSession,
+    need_to_destroy: bool,
+}
+
+impl Drop for SessionHandle {
+    fn drop(&mut self) {
+        if self.need_to_destroy {
+            unsafe {
+                self.lib.ultralight().ulDestroySession(self.internal);
+            }
+        }
+    }
+}
+
 /// A Session stores local data such as cookies, local storage, and application
 /// cache for one or more [`View`]s.
 /// (See [`Renderer::create_session`](crate::renderer::Renderer::create_session))
+///
+/// A session can be shared between views with [`Session::clone_handle`],
+/// and every [`View`] created with a session keeps a handle to it (see
+/// [`View::session`]), so dropping the [`Session`] returned by
+/// [`Renderer::create_session`] while its views are alive is fine, it is
+/// destroyed when the last handle is dropped.
+///
+/// Handles of [`Renderer::default_session`] are only valid as long as the
+/// [`Renderer`] is alive, like the views themselves.
+///
+/// Ultralight doesn't provide a way to clear the data of a session. To
+/// start from a clean state (eg. between tests), create the views with a
+/// new non-persistent session (`renderer.create_session(false, name)`)
+/// with a unique `name`, its data is dropped with the session.
 pub struct Session {
-    lib: Arc<Library>,
-    internal: ul_sys::ULSession,
-    need_to_destroy: bool,
+    handle: Rc<SessionHandle>,
 
     is_persistent: bool,
     name: String,
@@ -55,9 +143,11 @@ impl Session {
             UlString::copy_raw_to_string(&lib, lib.ultralight().ulSessionGetDiskPath(internal))?;
 
         Ok(Self {
-            lib,
-            internal,
-            need_to_destroy: true,
+            handle: Rc::new(SessionHandle {
+                lib,
+                internal,
+                need_to_destroy: true,
+            }),
 
             is_persistent,
             name: name.to_string(),
@@ -83,9 +173,11 @@ impl Session {
         let is_persistent = lib.ultralight().ulSessionIsPersistent(raw);
 
         Ok(Self {
-            lib,
-            internal: raw,
-            need_to_destroy: false,
+            handle: Rc::new(SessionHandle {
+                lib,
+                internal: raw,
+                need_to_destroy: false,
+            }),
 
             is_persistent,
             name,
@@ -97,7 +189,7 @@ impl Session {
     /// Returns the underlying [`ul_sys::ULSession`] struct, to be used locally for
     /// calling the underlying C API.
     pub(crate) unsafe fn to_ul(&self) -> ul_sys::ULSession {
-        self.internal
+        self.handle.internal
     }
 }
 
@@ -121,15 +213,126 @@ impl Session {
     pub fn is_persistent(&self) -> bool {
         self.is_persistent
     }
+
+    /// Create another handle to the same session, without copying its data.
+    ///
+    /// The session is destroyed when all of its handles (including the ones
+    /// held by views) are dropped.
+    pub fn clone_handle(&self) -> Session {
+        Session {
+            handle: self.handle.clone(),
+            is_persistent: self.is_persistent,
+            name: self.name.clone(),
+            id: self.id,
+            disk_path: self.disk_path.clone(),
+        }
+    }
+
+    /// Whether `self` and `other` are handles to the same session.
+    pub fn same_session(&self, other: &Session) -> bool {
+        self.handle.internal == other.handle.internal
+    }
 }
 
-impl Drop for Session {
-    fn drop(&mut self) {
-        if self.need_to_destroy {
-            unsafe {
-                self.lib.ultralight().ulDestroySession(self.internal);
-            }
+/// The default frame budget used by [`FrameStats`], one frame at 60 Hz.
+const DEFAULT_FRAME_BUDGET: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+/// Timing statistics of [`Renderer::render`] calls.
+///
+/// See [`Renderer::frame_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameStats {
+    /// The number of frames rendered (calls to [`Renderer::render`]).
+    pub frames_rendered: u64,
+    /// The number of display refreshes missed because rendering took longer
+    /// than the frame budget (see [`Renderer::set_frame_budget`]).
+    pub frames_skipped: u64,
+    /// The duration of the last frame in milliseconds.
+    pub last_frame_duration_ms: f64,
+    /// The average duration of all frames in milliseconds.
+    pub average_frame_duration_ms: f64,
+}
+
+#[derive(Clone, Copy)]
+struct FrameTimer {
+    stats: FrameStats,
+    total_duration_ms: f64,
+    budget: Duration,
+}
+
+impl FrameTimer {
+    fn new(budget: Duration) -> Self {
+        Self {
+            stats: FrameStats::default(),
+            total_duration_ms: 0.0,
+            budget,
+        }
+    }
+
+    fn record(&mut self, duration: Duration) {
+        let duration_ms = duration.as_secs_f64() * 1000.0;
+
+        self.stats.frames_rendered += 1;
+        if !self.budget.is_zero() {
+            self.stats.frames_skipped += (duration.as_nanos() / self.budget.as_nanos()) as u64;
+        }
+        self.total_duration_ms += duration_ms;
+        self.stats.last_frame_duration_ms = duration_ms;
+        self.stats.average_frame_duration_ms =
+            self.total_duration_ms / self.stats.frames_rendered as f64;
+    }
+}
+
+/// The minimum time between two reads of the memory usage of the process
+/// for [`Renderer::set_memory_pressure_threshold`], reading it is not free.
+const MEMORY_PRESSURE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// State of the automatic purging of [`Renderer::set_memory_pressure_threshold`].
+#[derive(Clone, Copy)]
+struct MemoryPressure {
+    threshold: Option<usize>,
+    last_check: Option<Instant>,
+    /// Whether the memory was purged and the usage didn't go below the low
+    /// watermark since, it's not purged again until it does.
+    purged: bool,
+}
+
+impl MemoryPressure {
+    fn new() -> Self {
+        Self {
+            threshold: None,
+            last_check: None,
+            purged: false,
+        }
+    }
+
+    /// Whether the memory should be purged now, reading the usage with
+    /// `usage` at most once per [`MEMORY_PRESSURE_CHECK_INTERVAL`].
+    fn should_purge(&mut self, usage: impl FnOnce() -> Option<usize>) -> bool {
+        let Some(threshold) = self.threshold else {
+            return false;
+        };
+        let now = Instant::now();
+        if self.last_check.is_some_and(|last_check| {
+            now.duration_since(last_check) < MEMORY_PRESSURE_CHECK_INTERVAL
+        }) {
+            return false;
+        }
+        self.last_check = Some(now);
+
+        let Some(usage) = usage() else {
+            return false;
+        };
+        // 10% below the threshold
+        let low_watermark = threshold - threshold / 10;
+        if usage < low_watermark {
+            self.purged = false;
         }
+        if usage > threshold && !self.purged {
+            self.purged = true;
+            return true;
+        }
+        false
     }
 }
 
@@ -145,6 +348,13 @@ pub struct Renderer {
 
     need_to_destroy: bool,
     default_session: Session,
+    ephemeral_cache_path: Option<PathBuf>,
+    frame_timer: Cell<FrameTimer>,
+    resume_callback_panics: Cell<bool>,
+    memory_pressure: Cell<MemoryPressure>,
+    views: ViewRegistry,
+    gamepad_slots: gamepad::GamepadSlots,
+    deferred: DeferQueue,
 }
 
 impl Renderer {
@@ -165,6 +375,13 @@ impl Renderer {
             internal: raw,
             need_to_destroy: false,
             default_session,
+            ephemeral_cache_path: None,
+            frame_timer: Cell::new(FrameTimer::new(DEFAULT_FRAME_BUDGET)),
+            resume_callback_panics: Cell::new(false),
+            memory_pressure: Cell::new(MemoryPressure::new()),
+            views: ViewRegistry::default(),
+            gamepad_slots: gamepad::GamepadSlots::new(),
+            deferred: DeferQueue::default(),
         })
     }
 
@@ -193,8 +410,16 @@ impl Renderer {
     /// You should not call this if you are using [`App::new`](crate::app::App::new),
     /// it creates its own renderer and provides default implementations for
     /// various platform handlers automatically.
+    ///
+    /// Returns [`CreationError::GpuDriverNotSet`] if `config` is accelerated
+    /// (see [`ConfigBuilder::is_accelerated`](crate::config::ConfigBuilder::is_accelerated))
+    /// but no GPU driver was set with
+    /// [`platform::set_gpu_driver`](crate::platform::set_gpu_driver).
     pub fn create(config: Config) -> Result<Self, CreationError> {
         let lib = config.lib();
+        if config.is_accelerated() && !platform::is_gpu_driver_set(lib) {
+            return Err(CreationError::GpuDriverNotSet);
+        }
         let internal = unsafe { lib.ultralight().ulCreateRenderer(config.to_ul()) };
         if internal.is_null() {
             return Err(CreationError::NullReference);
@@ -207,6 +432,13 @@ impl Renderer {
             internal,
             need_to_destroy: true,
             default_session,
+            ephemeral_cache_path: config.ephemeral_cache_path().map(Path::to_path_buf),
+            frame_timer: Cell::new(FrameTimer::new(DEFAULT_FRAME_BUDGET)),
+            resume_callback_panics: Cell::new(false),
+            memory_pressure: Cell::new(MemoryPressure::new()),
+            views: ViewRegistry::default(),
+            gamepad_slots: gamepad::GamepadSlots::new(),
+            deferred: DeferQueue::default(),
         })
     }
 }
@@ -214,8 +446,21 @@ impl Renderer {
 impl Renderer {
     /// Update timers and dispatch internal callbacks. You should call this often
     /// from your main application loop.
-    pub fn update(&self) {
+    ///
+    /// If [`Renderer::set_resume_callback_panics`] is enabled, panics of
+    /// callbacks called during the update are resumed here.
+    ///
+    /// The closures scheduled with [`Renderer::defer`] are run first.
+    ///
+    /// Returns [`Error::ReentrantCall`] without updating when called from a
+    /// callback called by Ultralight, as it would re-enter it, see
+    /// [`DeferQueue`].
+    pub fn update(&self) -> Result<(), Error> {
+        callback_panic::check_not_reentrant("Renderer::update")?;
+        self.run_deferred();
         unsafe { self.lib.ultralight().ulUpdate(self.internal) };
+        self.resume_callback_panic();
+        Ok(())
     }
 
     /// Render all active views to their respective render-targets/surfaces.
@@ -225,19 +470,130 @@ impl Renderer {
     ///
     /// [`View`]s are only repainted if they actually need painting.
     /// (See [`View::needs_paint`](crate::view::View::needs_paint))
-    pub fn render(&self) {
+    ///
+    /// The time spent in this call is recorded in [`Renderer::frame_stats`].
+    ///
+    /// If a threshold was set with [`Renderer::set_memory_pressure_threshold`],
+    /// the memory is purged after rendering when the process uses more than it.
+    ///
+    /// Returns [`Error::ReentrantCall`] without rendering when called from a
+    /// callback called by Ultralight, as it would re-enter it, see
+    /// [`DeferQueue`].
+    pub fn render(&self) -> Result<(), Error> {
+        callback_panic::check_not_reentrant("Renderer::render")?;
+        let start = Instant::now();
         unsafe { self.lib.ultralight().ulRender(self.internal) };
+
+        let mut timer = self.frame_timer.get();
+        timer.record(start.elapsed());
+        self.frame_timer.set(timer);
+
+        self.purge_memory_on_pressure();
+        self.resume_callback_panic();
+        Ok(())
+    }
+
+    /// Set whether panics in user callbacks (view callbacks, platform
+    /// implementations, JavaScript functions, etc.) should be resumed
+    /// after [`Renderer::update`] and [`Renderer::render`] return.
+    ///
+    /// Panics in callbacks can't unwind through Ultralight, so they are
+    /// caught and stored, see [`Library::take_last_callback_panic`].
+    /// When this is enabled, the stored panic is taken and resumed with
+    /// [`std::panic::resume_unwind`] once the control is back in Rust.
+    ///
+    /// (Default = false)
+    pub fn set_resume_callback_panics(&self, enabled: bool) {
+        self.resume_callback_panics.set(enabled);
+    }
+
+    fn resume_callback_panic(&self) {
+        if !self.resume_callback_panics.get() {
+            return;
+        }
+        if let Some(payload) = self.lib.take_last_callback_panic() {
+            std::panic::resume_unwind(payload);
+        }
+    }
+
+    /// Get the timing statistics of [`Renderer::render`] calls since the
+    /// renderer was created or since the last [`Renderer::reset_frame_stats`].
+    ///
+    /// Note that rendering done by [`App`](crate::app::App) in its run loop
+    /// doesn't go through [`Renderer::render`], and so is not recorded.
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_timer.get().stats
+    }
+
+    /// Reset all the counters of [`Renderer::frame_stats`].
+    pub fn reset_frame_stats(&self) {
+        let budget = self.frame_timer.get().budget;
+        self.frame_timer.set(FrameTimer::new(budget));
+    }
+
+    /// Set the frame budget used to compute [`FrameStats::frames_skipped`],
+    /// this should be the refresh interval of the display.
+    ///
+    /// (Default = 1 / 60 seconds)
+    pub fn set_frame_budget(&self, budget: Duration) {
+        let mut timer = self.frame_timer.get();
+        timer.budget = budget;
+        self.frame_timer.set(timer);
     }
 
     /// Attempt to release as much memory as possible.
-    /// Don't call this from any callbacks or driver code.
-    pub fn purge_memory(&self) {
+    /// Don't call this from any callbacks or driver code, calls from
+    /// callbacks called by Ultralight return [`Error::ReentrantCall`] (see
+    /// [`DeferQueue`]).
+    pub fn purge_memory(&self) -> Result<(), Error> {
+        callback_panic::check_not_reentrant("Renderer::purge_memory")?;
         unsafe { self.lib.ultralight().ulPurgeMemory(self.internal) };
+        Ok(())
+    }
+
+    /// Set the memory usage of the process, in bytes, above which
+    /// [`Renderer::purge_memory`] is called automatically after
+    /// [`Renderer::render`], or [`None`] to disable it.
+    ///
+    /// The memory usage is the resident set size of the whole process
+    /// (not only Ultralight), read with the `sysinfo` crate, so this requires
+    /// the `sysinfo` feature. Without it (or on platforms where `sysinfo`
+    /// can't read it), the usage is unknown and the memory is never purged
+    /// automatically.
+    ///
+    /// The usage is read at most once per second, and once the memory was
+    /// purged, it's not purged again until the usage goes 10% below the
+    /// threshold, so that an application staying above the threshold doesn't
+    /// purge it continuously. The threshold should be set above the usage of
+    /// the application with its views loaded.
+    ///
+    /// (Default = None)
+    pub fn set_memory_pressure_threshold(&self, bytes: Option<usize>) {
+        let mut pressure = MemoryPressure::new();
+        pressure.threshold = bytes;
+        self.memory_pressure.set(pressure);
+    }
+
+    /// Get the threshold set by [`Renderer::set_memory_pressure_threshold`].
+    pub fn memory_pressure_threshold(&self) -> Option<usize> {
+        self.memory_pressure.get().threshold
+    }
+
+    fn purge_memory_on_pressure(&self) {
+        let mut pressure = self.memory_pressure.get();
+        let purge = pressure.should_purge(process_memory_usage);
+        self.memory_pressure.set(pressure);
+        if purge {
+            // `render` already checked that it's not called from a callback
+            let _ = self.purge_memory();
+        }
     }
 
     /// Print detailed memory usage statistics to the log.
     /// (See [`platform::set_logger`](crate::platform::set_logger) or
     /// [`platform::enable_default_logger`](crate::platform::enable_default_logger))
+    ///
+    /// See [`Renderer::memory_usage`] to get them as structured data.
     pub fn log_memory_usage(&self) {
         unsafe { self.lib.ultralight().ulLogMemoryUsage(self.internal) };
     }
@@ -276,15 +632,41 @@ impl Renderer {
     /// * `height` - The initial height, in pixels.
     /// * `config` - The configuration for the view.
     /// * `session` - The session to store local data in. Passing [`None`] will
-    ///   use the default session.
+    ///   use the default session. The View keeps a handle to the session
+    ///   (see [`View::session`]), so it can be dropped after this call.
+    ///
+    /// Returns [`CreationError::GpuDriverNotSet`] if `config` is accelerated
+    /// (see [`ViewConfigBuilder::is_accelerated`](crate::view::ViewConfigBuilder::is_accelerated))
+    /// but no GPU driver was set with
+    /// [`platform::set_gpu_driver`](crate::platform::set_gpu_driver).
+    /// Views of the renderer owned by [`App`](crate::app::App) are not checked,
+    /// since AppCore provides its own GPU driver.
     pub fn create_view(
         &self,
         width: u32,
         height: u32,
         view_config: &ViewConfig,
         session: Option<&Session>,
-    ) -> Option<View> {
-        unsafe { View::create(self.internal, width, height, view_config, session) }
+    ) -> Result<View, CreationError> {
+        // renderers not created by us (i.e. by `App`) have their own GPU driver
+        if self.need_to_destroy
+            && view_config.is_accelerated()
+            && !platform::is_gpu_driver_set(&self.lib)
+        {
+            return Err(CreationError::GpuDriverNotSet);
+        }
+
+        unsafe {
+            View::create(
+                self.internal,
+                width,
+                height,
+                view_config,
+                session,
+                Some(&self.views),
+            )
+        }
+        .ok_or(CreationError::NullReference)
     }
 
     /// Start the remote inspector server.
@@ -316,7 +698,14 @@ impl Renderer {
     ///
     /// This updates animations, smooth scroll, and `window.requestAnimationFrame()` for all Views
     /// matching the display id.
+    ///
+    /// The display is passed to [`GpuDriver::begin_frame`](crate::gpu_driver::GpuDriver::begin_frame)
+    /// and [`GpuDriver::end_frame`](crate::gpu_driver::GpuDriver::end_frame) in the next
+    /// call to [`Renderer::render`].
+    ///
+    /// See [`DisplayPump`] for a helper tracking the refresh rate of each display.
     pub fn refresh_display(&self, display_id: u32) {
+        crate::gpu_driver::display_refreshed(display_id);
         unsafe {
             self.lib
                 .ultralight()
@@ -409,5 +798,10 @@ impl Drop for Renderer {
                 self.lib.ultralight().ulDestroyRenderer(self.internal);
             }
         }
+        if let Some(path) = self.ephemeral_cache_path.take() {
+            // nothing we can do about errors here, the directory may not
+            // have been created at all
+            let _ = std::fs::remove_dir_all(path);
+        }
     }
 }
@@ -0,0 +1,94 @@
+//! Running work scheduled from callbacks at the next update, see
+//! [`Renderer::defer`].
+use std::{cell::RefCell, rc::Rc};
+
+use super::Renderer;
+
+type Deferred = Box<dyn FnOnce(&Renderer)>;
+
+/// A queue of closures run at the start of the next [`Renderer::update`].
+///
+/// Callbacks called by Ultralight (view callbacks, JavaScript functions,
+/// platform implementations, ...) must not call back into the renderer:
+/// [`Renderer::update`], [`Renderer::render`], [`Renderer::purge_memory`]
+/// and [`View::resize`](crate::view::View::resize) return
+/// [`Error::ReentrantCall`](crate::error::Error::ReentrantCall) when called
+/// from them. Deferring the work with this queue runs it once Ultralight
+/// returned.
+///
+/// The queue is a cheap handle that can be cloned into the callbacks, get
+/// it with [`Renderer::defer_queue`].
+///
+/// # Example
+/// ```no_run
+/// # use std::rc::Rc;
+/// # use ul_next::{renderer::Renderer, view::View};
+/// # fn example(renderer: &Renderer, view: View) {
+/// let view = Rc::new(view);
+/// let queue = renderer.defer_queue();
+/// let weak_view = Rc::downgrade(&view);
+/// view.set_finish_loading_callback(move |_view, _frame_id, is_main_frame, _url| {
+///     if !is_main_frame {
+///         return;
+///     }
+///     // resizing here would re-enter Ultralight, so it's done after the
+///     // current `Renderer::update`
+///     let weak_view = weak_view.clone();
+///     queue.defer(move |renderer| {
+///         if let Some(view) = weak_view.upgrade() {
+///             view.resize(1280, 720).unwrap();
+///             renderer.render().unwrap();
+///         }
+///     });
+/// });
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct DeferQueue {
+    queue: Rc<RefCell<Vec<Deferred>>>,
+}
+
+impl DeferQueue {
+    /// Run `f` at the start of the next [`Renderer::update`].
+    pub fn defer<F: FnOnce(&Renderer) + 'static>(&self, f: F) {
+        self.queue.borrow_mut().push(Box::new(f));
+    }
+
+    /// The number of closures waiting for the next update.
+    pub fn len(&self) -> usize {
+        self.queue.borrow().len()
+    }
+
+    /// Whether no closure is waiting for the next update.
+    pub fn is_empty(&self) -> bool {
+        self.queue.borrow().is_empty()
+    }
+}
+
+impl Renderer {
+    /// Run `f` at the start of the next [`Renderer::update`], see
+    /// [`DeferQueue`].
+    pub fn defer<F: FnOnce(&Renderer) + 'static>(&self, f: F) {
+        self.deferred.defer(f);
+    }
+
+    /// A handle to the queue of [`Renderer::defer`], that can be moved
+    /// into callbacks.
+    pub fn defer_queue(&self) -> DeferQueue {
+        self.deferred.clone()
+    }
+
+    /// Run the deferred closures, including the ones deferred while running.
+    pub(super) fn run_deferred(&self) {
+        loop {
+            // taken out first, so closures can defer more work
+            let deferred = std::mem::take(&mut *self.deferred.queue.borrow_mut());
+            if deferred.is_empty() {
+                break;
+            }
+            for f in deferred {
+                f(self);
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Driving `requestAnimationFrame` and animations from the refresh of
+//! the displays, see [`DisplayPump`].
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::view::View;
+
+use super::Renderer;
+
+/// Refresh intervals longer than this are considered stalls (eg. the window
+/// was minimized), and are not used to estimate the refresh rate.
+const MAX_REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Per-display refresh timing.
+#[derive(Default, Clone, Copy)]
+struct DisplayState {
+    last_refresh: Option<Instant>,
+    refresh_interval: Option<Duration>,
+}
+
+/// Notifies the [`Renderer`] of display refreshes (vsync), and keeps
+/// track of the refresh rate of each display.
+///
+/// Without an [`App`](crate::app::App), `Ultralight` doesn't know when the
+/// displays refresh, so `requestAnimationFrame`, CSS animations and smooth
+/// scrolling only advance when [`Renderer::refresh_display`] is called for the
+/// display of a view. This helper wraps that:
+/// - Register each view with the id of the display it's shown on with
+///   [`DisplayPump::register`] (or use
+///   [`ViewConfigBuilder::display_id`](crate::view::ViewConfigBuilder::display_id)).
+/// - Call [`DisplayPump::on_vsync`] from the frame loop after presenting
+///   to a display, eg. after swapping buffers of a `winit` window with vsync
+///   enabled, or when `WindowEvent::RedrawRequested` is received.
+/// - Call [`Renderer::update`] at least every
+///   [`DisplayPump::recommended_update_interval`], so that the refreshed
+///   content is painted at the rate of the fastest display.
+///
+/// The display ids are chosen by the application, for example the index of
+/// the monitor the window is on.
+///
+/// # Examples
+/// ```no_run
+/// # use ul_next::{renderer::{DisplayPump, Renderer}, view::View};
+/// # fn example(renderer: &Renderer, view: &View) {
+/// let mut pump = DisplayPump::new(renderer);
+/// pump.register(view, 0);
+///
+/// loop {
+///     // ... draw and swap buffers (blocks until vsync)
+///     pump.on_vsync(0);
+///     renderer.update().unwrap();
+///     renderer.render().unwrap();
+/// }
+/// # }
+/// ```
+pub struct DisplayPump<'a> {
+    renderer: &'a Renderer,
+    displays: HashMap<u32, DisplayState>,
+}
+
+impl<'a> DisplayPump<'a> {
+    /// Create a display pump for the views of `renderer`.
+    pub fn new(renderer: &'a Renderer) -> Self {
+        Self {
+            renderer,
+            displays: HashMap::new(),
+        }
+    }
+
+    /// Set the display of `view` to `display_id` (see [`View::set_display_id`]).
+    ///
+    /// This should be called again when the view is moved to another display.
+    pub fn register(&mut self, view: &View, display_id: u32) {
+        view.set_display_id(display_id);
+        self.displays.entry(display_id).or_default();
+    }
+
+    /// Forget the refresh timing of `display_id`, for example when the
+    /// monitor is disconnected.
+    pub fn remove_display(&mut self, display_id: u32) {
+        self.displays.remove(&display_id);
+    }
+
+    /// Notify the renderer that `display_id` has refreshed now
+    /// (see [`Renderer::refresh_display`]).
+    pub fn on_vsync(&mut self, display_id: u32) {
+        self.on_vsync_at(display_id, Instant::now());
+    }
+
+    /// Same as [`DisplayPump::on_vsync`], but with the time of the refresh,
+    /// for example the presentation time reported by the graphics API.
+    pub fn on_vsync_at(&mut self, display_id: u32, time: Instant) {
+        self.renderer.refresh_display(display_id);
+
+        let display = self.displays.entry(display_id).or_default();
+        if let Some(interval) = display
+            .last_refresh
+            .and_then(|last| time.checked_duration_since(last))
+            .filter(|interval| !interval.is_zero() && *interval <= MAX_REFRESH_INTERVAL)
+        {
+            // smooth out the jitter of the frame loop
+            display.refresh_interval = Some(match display.refresh_interval {
+                Some(average) => (average * 7 + interval) / 8,
+                None => interval,
+            });
+        }
+        display.last_refresh = Some(time);
+    }
+
+    /// The time of the last refresh of `display_id`, [`None`] if it was
+    /// never refreshed.
+    pub fn last_refresh(&self, display_id: u32) -> Option<Instant> {
+        self.displays.get(&display_id)?.last_refresh
+    }
+
+    /// The estimated refresh interval of `display_id`, known after it was
+    /// refreshed at least twice.
+    pub fn refresh_interval(&self, display_id: u32) -> Option<Duration> {
+        self.displays.get(&display_id)?.refresh_interval
+    }
+
+    /// How often [`Renderer::update`] should be called, which is the
+    /// refresh interval of the fastest display.
+    ///
+    /// Returns [`None`] if no refresh interval is known yet, in that case
+    /// update after every [`DisplayPump::on_vsync`].
+    pub fn recommended_update_interval(&self) -> Option<Duration> {
+        self.displays
+            .values()
+            .filter_map(|display| display.refresh_interval)
+            .min()
+    }
+
+    /// Whether [`Renderer::update`] should be called, if it was last
+    /// called at `last_update`, according to
+    /// [`DisplayPump::recommended_update_interval`].
+    pub fn should_update(&self, last_update: Instant) -> bool {
+        self.recommended_update_interval()
+            .map_or(true, |interval| last_update.elapsed() >= interval)
+    }
+}
@@ -0,0 +1,369 @@
+//! Safe handles for the gamepads exposed to the JavaScript
+//! [Gamepad API](https://developer.mozilla.org/en-US/docs/Web/API/Gamepad_API).
+//!
+//! See [`Renderer::connect_gamepad`] and [`Gamepad`]. The low level
+//! functions ([`Renderer::set_gamepad_details`],
+//! [`Renderer::fire_gamepad_event`], ...) are still available, but then
+//! the caller is responsible for describing the gamepad before connecting
+//! it, keeping the indices consistent and normalizing the values.
+use std::{cell::RefCell, collections::BTreeSet};
+
+use crate::{
+    error::CreationError,
+    event::{GamepadAxisEvent, GamepadButtonEvent, GamepadEvent, GamepadEventType},
+};
+
+use super::Renderer;
+
+#[cfg(feature = "gilrs")]
+mod gilrs_bridge;
+
+#[cfg(feature = "gilrs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gilrs")))]
+pub use gilrs_bridge::GilrsBridge;
+
+/// Errors that can occur when using a [`Gamepad`].
+#[derive(Debug, thiserror::Error)]
+pub enum GamepadError {
+    /// The axis index is not less than the number of axes of the gamepad.
+    #[error("Axis {index} is out of range, the gamepad has {count} axes")]
+    AxisOutOfRange { index: u32, count: u32 },
+    /// The button index is not less than the number of buttons of the
+    /// gamepad.
+    #[error("Button {index} is out of range, the gamepad has {count} buttons")]
+    ButtonOutOfRange { index: u32, count: u32 },
+    /// The value is `NaN`.
+    #[error("Invalid gamepad value {0}")]
+    InvalidValue(f64),
+    /// Failed to create or fire the underlying event.
+    #[error("{0}")]
+    CreationError(#[from] CreationError),
+}
+
+/// The gamepad slots (the `index` of [`Renderer::set_gamepad_details`],
+/// and `gamepad.index` in JavaScript) in use by a [`GamepadBackend`].
+#[derive(Debug, Default)]
+pub struct GamepadSlots {
+    used: RefCell<BTreeSet<u32>>,
+}
+
+impl GamepadSlots {
+    /// Create an empty set of slots.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take the lowest free slot.
+    pub fn allocate(&self) -> u32 {
+        let mut used = self.used.borrow_mut();
+        // the slots are sorted, so the first gap is the lowest free slot
+        let slot = used
+            .iter()
+            .enumerate()
+            .find(|&(i, &slot)| i as u32 != slot)
+            .map_or(used.len() as u32, |(i, _)| i as u32);
+        used.insert(slot);
+        slot
+    }
+
+    /// Make `slot` available to [`GamepadSlots::allocate`] again.
+    pub fn release(&self, slot: u32) {
+        self.used.borrow_mut().remove(&slot);
+    }
+
+    /// Whether `slot` is in use.
+    pub fn is_used(&self, slot: u32) -> bool {
+        self.used.borrow().contains(&slot)
+    }
+}
+
+/// The receiver of the events of a [`Gamepad`].
+///
+/// This is implemented by [`Renderer`], it can be implemented by other
+/// types to check the events sent by a [`Gamepad`] without Ultralight,
+/// for example in tests.
+pub trait GamepadBackend {
+    /// The slots used by the gamepads of this backend.
+    fn gamepad_slots(&self) -> &GamepadSlots;
+
+    /// Describe the gamepad at `index`, see [`Renderer::set_gamepad_details`].
+    fn describe_gamepad(
+        &self,
+        index: u32,
+        id: &str,
+        axis_count: u32,
+        button_count: u32,
+    ) -> Result<(), CreationError>;
+
+    /// Fire a connection event, see [`Renderer::fire_gamepad_event`].
+    fn gamepad_connection_changed(&self, index: u32, connected: bool) -> Result<(), CreationError>;
+
+    /// Fire an axis event, see [`Renderer::fire_gamepad_axis_event`].
+    fn gamepad_axis_changed(
+        &self,
+        index: u32,
+        axis_index: u32,
+        value: f64,
+    ) -> Result<(), CreationError>;
+
+    /// Fire a button event, see [`Renderer::fire_gamepad_button_event`].
+    fn gamepad_button_changed(
+        &self,
+        index: u32,
+        button_index: u32,
+        value: f64,
+    ) -> Result<(), CreationError>;
+}
+
+impl GamepadBackend for Renderer {
+    fn gamepad_slots(&self) -> &GamepadSlots {
+        &self.gamepad_slots
+    }
+
+    fn describe_gamepad(
+        &self,
+        index: u32,
+        id: &str,
+        axis_count: u32,
+        button_count: u32,
+    ) -> Result<(), CreationError> {
+        self.set_gamepad_details(index, id, axis_count, button_count)
+    }
+
+    fn gamepad_connection_changed(&self, index: u32, connected: bool) -> Result<(), CreationError> {
+        let ty = if connected {
+            GamepadEventType::Connected
+        } else {
+            GamepadEventType::Disconnected
+        };
+        self.fire_gamepad_event(GamepadEvent::new(self.lib.clone(), index, ty)?)
+    }
+
+    fn gamepad_axis_changed(
+        &self,
+        index: u32,
+        axis_index: u32,
+        value: f64,
+    ) -> Result<(), CreationError> {
+        self.fire_gamepad_axis_event(GamepadAxisEvent::new(
+            self.lib.clone(),
+            index,
+            axis_index,
+            value,
+        )?)
+    }
+
+    fn gamepad_button_changed(
+        &self,
+        index: u32,
+        button_index: u32,
+        value: f64,
+    ) -> Result<(), CreationError> {
+        self.fire_gamepad_button_event(GamepadButtonEvent::new(
+            self.lib.clone(),
+            index,
+            button_index,
+            value,
+        )?)
+    }
+}
+
+/// A connected gamepad, created with [`Renderer::connect_gamepad`] (or
+/// [`Gamepad::connect`] for other backends).
+///
+/// The gamepad takes the lowest free slot of the backend, is described
+/// and connected before being returned, and is disconnected when dropped,
+/// which frees its slot.
+///
+/// Axis values are clamped to `[-1, 1]` and button values to `[0, 1]`,
+/// all of them start at `0`. Setting a value that is the same as the
+/// current one doesn't fire any event.
+///
+/// # Example
+/// ```no_run
+/// # use ul_next::renderer::{gamepad::GamepadError, Renderer};
+/// # fn example(renderer: &Renderer) -> Result<(), GamepadError> {
+/// let mut gamepad = renderer.connect_gamepad("My Gamepad", 4, 17)?;
+///
+/// gamepad.set_axis(0, -0.5)?;
+/// gamepad.set_button(0, 1.0)?;
+///
+/// // fires the disconnection event
+/// drop(gamepad);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Gamepad<'a, B: GamepadBackend + ?Sized = Renderer> {
+    backend: &'a B,
+    index: u32,
+    id: String,
+    axes: Vec<f64>,
+    buttons: Vec<f64>,
+    connected: bool,
+}
+
+impl<'a, B: GamepadBackend + ?Sized> Gamepad<'a, B> {
+    /// Describe and connect a new gamepad to `backend`.
+    ///
+    /// # Arguments
+    /// * `backend` - The receiver of the events, usually a [`Renderer`].
+    /// * `id` - The id of the gamepad, available in JavaScript as
+    ///   `gamepad.id`.
+    /// * `axis_count` - The number of axes of the gamepad.
+    /// * `button_count` - The number of buttons of the gamepad.
+    pub fn connect(
+        backend: &'a B,
+        id: &str,
+        axis_count: u32,
+        button_count: u32,
+    ) -> Result<Self, GamepadError> {
+        let index = backend.gamepad_slots().allocate();
+
+        let connected = backend
+            .describe_gamepad(index, id, axis_count, button_count)
+            .and_then(|_| backend.gamepad_connection_changed(index, true));
+        if let Err(err) = connected {
+            backend.gamepad_slots().release(index);
+            return Err(err.into());
+        }
+
+        Ok(Self {
+            backend,
+            index,
+            id: id.to_string(),
+            axes: vec![0.0; axis_count as usize],
+            buttons: vec![0.0; button_count as usize],
+            connected: true,
+        })
+    }
+
+    /// The slot of the gamepad, available in JavaScript as `gamepad.index`.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The id of the gamepad, available in JavaScript as `gamepad.id`.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The number of axes of the gamepad.
+    pub fn axis_count(&self) -> u32 {
+        self.axes.len() as u32
+    }
+
+    /// The number of buttons of the gamepad.
+    pub fn button_count(&self) -> u32 {
+        self.buttons.len() as u32
+    }
+
+    /// The current value of the axis at `axis_index`.
+    pub fn axis(&self, axis_index: u32) -> Option<f64> {
+        self.axes.get(axis_index as usize).copied()
+    }
+
+    /// The current value of the button at `button_index`.
+    pub fn button(&self, button_index: u32) -> Option<f64> {
+        self.buttons.get(button_index as usize).copied()
+    }
+
+    /// Set the value of the axis at `axis_index`, clamped to `[-1, 1]`.
+    ///
+    /// Returns whether an event was fired, which is not the case if the
+    /// value didn't change.
+    pub fn set_axis(&mut self, axis_index: u32, value: f64) -> Result<bool, GamepadError> {
+        let count = self.axis_count();
+        let current =
+            self.axes
+                .get_mut(axis_index as usize)
+                .ok_or(GamepadError::AxisOutOfRange {
+                    index: axis_index,
+                    count,
+                })?;
+        let value = normalize(value, -1.0)?;
+        if *current == value {
+            return Ok(false);
+        }
+
+        self.backend
+            .gamepad_axis_changed(self.index, axis_index, value)?;
+        *current = value;
+        Ok(true)
+    }
+
+    /// Set the value of the button at `button_index`, clamped to `[0, 1]`,
+    /// any value greater than `0` is considered "pressed".
+    ///
+    /// Returns whether an event was fired, which is not the case if the
+    /// value didn't change.
+    pub fn set_button(&mut self, button_index: u32, value: f64) -> Result<bool, GamepadError> {
+        let count = self.button_count();
+        let current =
+            self.buttons
+                .get_mut(button_index as usize)
+                .ok_or(GamepadError::ButtonOutOfRange {
+                    index: button_index,
+                    count,
+                })?;
+        let value = normalize(value, 0.0)?;
+        if *current == value {
+            return Ok(false);
+        }
+
+        self.backend
+            .gamepad_button_changed(self.index, button_index, value)?;
+        *current = value;
+        Ok(true)
+    }
+
+    /// Disconnect the gamepad, this is the same as dropping it, but
+    /// reports the errors.
+    pub fn disconnect(mut self) -> Result<(), GamepadError> {
+        self.disconnect_inner().map_err(Into::into)
+    }
+
+    fn disconnect_inner(&mut self) -> Result<(), CreationError> {
+        if !self.connected {
+            return Ok(());
+        }
+        self.connected = false;
+        // free the slot even if the event failed, the gamepad can't be
+        // used anymore anyway
+        let result = self.backend.gamepad_connection_changed(self.index, false);
+        self.backend.gamepad_slots().release(self.index);
+        result
+    }
+}
+
+impl<B: GamepadBackend + ?Sized> Drop for Gamepad<'_, B> {
+    fn drop(&mut self) {
+        // nothing we can do about errors here
+        let _ = self.disconnect_inner();
+    }
+}
+
+/// Clamp `value` to `[min, 1]`.
+fn normalize(value: f64, min: f64) -> Result<f64, GamepadError> {
+    if value.is_nan() {
+        return Err(GamepadError::InvalidValue(value));
+    }
+    Ok(value.clamp(min, 1.0))
+}
+
+impl Renderer {
+    /// Describe and connect a new gamepad, see [`Gamepad`].
+    ///
+    /// # Arguments
+    /// * `id` - The id of the gamepad, available in JavaScript as
+    ///   `gamepad.id`.
+    /// * `axis_count` - The number of axes of the gamepad.
+    /// * `button_count` - The number of buttons of the gamepad.
+    pub fn connect_gamepad(
+        &self,
+        id: &str,
+        axis_count: u32,
+        button_count: u32,
+    ) -> Result<Gamepad<'_>, GamepadError> {
+        Gamepad::connect(self, id, axis_count, button_count)
+    }
+}
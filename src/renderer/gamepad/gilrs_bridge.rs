@@ -0,0 +1,264 @@
+//! Forwarding [`gilrs`] gamepad events to a [`Renderer`].
+//!
+//! See [`GilrsBridge`].
+use std::collections::{HashMap, HashSet};
+
+use gilrs::{Axis, Button, EventType, GamepadId, Gilrs};
+
+use crate::{
+    error::CreationError,
+    event::{GamepadAxisEvent, GamepadButtonEvent, GamepadEvent, GamepadEventType},
+    renderer::Renderer,
+};
+
+use super::GamepadBackend;
+
+/// The number of axes of the
+/// [standard gamepad layout](https://w3c.github.io/gamepad/#remapping).
+const AXIS_COUNT: u32 = 4;
+/// The number of buttons of the
+/// [standard gamepad layout](https://w3c.github.io/gamepad/#remapping).
+const BUTTON_COUNT: u32 = 17;
+
+/// The index of `axis` in the standard gamepad layout.
+fn standard_axis_index(axis: Axis) -> Option<u32> {
+    Some(match axis {
+        Axis::LeftStickX => 0,
+        Axis::LeftStickY => 1,
+        Axis::RightStickX => 2,
+        Axis::RightStickY => 3,
+        _ => return None,
+    })
+}
+
+/// The index of `button` in the standard gamepad layout.
+fn standard_button_index(button: Button) -> Option<u32> {
+    Some(match button {
+        Button::South => 0,
+        Button::East => 1,
+        Button::West => 2,
+        Button::North => 3,
+        Button::LeftTrigger => 4,
+        Button::RightTrigger => 5,
+        Button::LeftTrigger2 => 6,
+        Button::RightTrigger2 => 7,
+        Button::Select => 8,
+        Button::Start => 9,
+        Button::LeftThumb => 10,
+        Button::RightThumb => 11,
+        Button::DPadUp => 12,
+        Button::DPadDown => 13,
+        Button::DPadLeft => 14,
+        Button::DPadRight => 15,
+        Button::Mode => 16,
+        _ => return None,
+    })
+}
+
+/// Translates [`gilrs`] events into the gamepad events of a [`Renderer`],
+/// making the gamepads available to the JavaScript
+/// [Gamepad API](https://developer.mozilla.org/en-US/docs/Web/API/Gamepad_API).
+///
+/// Gamepads are announced with the
+/// [standard layout](https://w3c.github.io/gamepad/#remapping), which is
+/// also the layout `gilrs` maps the gamepads to: 4 axes (left stick X/Y,
+/// right stick X/Y) and 17 buttons, indexed like `gamepad.axes` and
+/// `gamepad.buttons` of the standard mapping. Inputs without a place in
+/// this layout (eg. [`Axis::LeftZ`]) are ignored.
+///
+/// Axis values are clamped to `[-1, 1]`, with the Y axes inverted so that
+/// down is positive as in the Gamepad API, and button values are clamped
+/// to `[0, 1]`.
+///
+/// # Index mapping
+///
+/// Each [`GamepadId`] is given a slot (the `index` used in
+/// [`Renderer::set_gamepad_details`], and `gamepad.index` in JavaScript)
+/// the first time it is connected, the lowest slot not used by another
+/// gamepad of the renderer (including the ones of
+/// [`Renderer::connect_gamepad`]). Slots are never reused by other
+/// gamepads, and since `gilrs`
+/// gives the same [`GamepadId`] to a gamepad that is reconnected, it gets
+/// back its previous slot.
+///
+/// # Example
+/// ```no_run
+/// # use gilrs::Gilrs;
+/// # use ul_next::renderer::{gamepad::GilrsBridge, Renderer};
+/// # fn example(renderer: &Renderer) {
+/// let mut bridge = GilrsBridge::new(Gilrs::new().unwrap(), renderer).unwrap();
+///
+/// loop {
+///     bridge.pump().unwrap();
+///     renderer.update().unwrap();
+///     renderer.render().unwrap();
+/// }
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "gilrs")))]
+pub struct GilrsBridge<'a> {
+    gilrs: Gilrs,
+    renderer: &'a Renderer,
+    slots: HashMap<GamepadId, u32>,
+    /// The ids of the connected gamepads, to ignore duplicate
+    /// connection events.
+    connected: HashSet<GamepadId>,
+}
+
+impl<'a> GilrsBridge<'a> {
+    /// Create a new bridge forwarding the events of `gilrs` to `renderer`.
+    ///
+    /// The gamepads that are already connected are announced to the
+    /// renderer immediately.
+    pub fn new(gilrs: Gilrs, renderer: &'a Renderer) -> Result<Self, CreationError> {
+        let mut bridge = Self {
+            gilrs,
+            renderer,
+            slots: HashMap::new(),
+            connected: HashSet::new(),
+        };
+
+        let ids = bridge
+            .gilrs
+            .gamepads()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        for id in ids {
+            bridge.connect(id)?;
+        }
+
+        Ok(bridge)
+    }
+
+    /// Translate all the pending `gilrs` events into the corresponding
+    /// [`Renderer`] gamepad events.
+    ///
+    /// This should be called regularly, for example before every
+    /// [`Renderer::update`].
+    ///
+    /// If an event fails to be created, the error is returned and the
+    /// remaining events are handled on the next call.
+    pub fn pump(&mut self) -> Result<(), CreationError> {
+        let result = self.pump_events();
+        // advance the frame counter of `gilrs` even on errors, it is used
+        // to detect the changes in the gamepad states
+        self.gilrs.inc();
+        result
+    }
+
+    fn pump_events(&mut self) -> Result<(), CreationError> {
+        while let Some(event) = self.gilrs.next_event() {
+            let id = event.id;
+            match event.event {
+                EventType::Connected => self.connect(id)?,
+                EventType::Disconnected => self.disconnect(id)?,
+                EventType::AxisChanged(axis, value, _) => {
+                    let (Some(&index), Some(axis_index)) =
+                        (self.slots.get(&id), standard_axis_index(axis))
+                    else {
+                        continue;
+                    };
+                    let mut value = value.clamp(-1.0, 1.0) as f64;
+                    if matches!(axis, Axis::LeftStickY | Axis::RightStickY) {
+                        value = -value;
+                    }
+
+                    self.renderer
+                        .fire_gamepad_axis_event(GamepadAxisEvent::new(
+                            self.renderer.lib.clone(),
+                            index,
+                            axis_index,
+                            value,
+                        )?)?;
+                }
+                EventType::ButtonChanged(button, value, _) => {
+                    let (Some(&index), Some(button_index)) =
+                        (self.slots.get(&id), standard_button_index(button))
+                    else {
+                        continue;
+                    };
+
+                    self.renderer
+                        .fire_gamepad_button_event(GamepadButtonEvent::new(
+                            self.renderer.lib.clone(),
+                            index,
+                            button_index,
+                            value.clamp(0.0, 1.0) as f64,
+                        )?)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the slot given to the gamepad `id`, if it was connected before.
+    ///
+    /// See the [index mapping](Self#index-mapping).
+    pub fn slot(&self, id: GamepadId) -> Option<u32> {
+        self.slots.get(&id).copied()
+    }
+
+    /// Get the underlying [`Gilrs`] instance.
+    pub fn gilrs(&self) -> &Gilrs {
+        &self.gilrs
+    }
+
+    /// Get the underlying [`Gilrs`] instance mutably.
+    ///
+    /// Events taken out of it with [`Gilrs::next_event`] won't be
+    /// forwarded to the renderer.
+    pub fn gilrs_mut(&mut self) -> &mut Gilrs {
+        &mut self.gilrs
+    }
+
+    /// Consume the bridge, returning the underlying [`Gilrs`] instance.
+    pub fn into_inner(self) -> Gilrs {
+        self.gilrs
+    }
+
+    fn connect(&mut self, id: GamepadId) -> Result<(), CreationError> {
+        if self.connected.contains(&id) {
+            return Ok(());
+        }
+
+        let index = match self.slots.get(&id) {
+            Some(&index) => index,
+            None => {
+                let index = self.renderer.gamepad_slots().allocate();
+                self.slots.insert(id, index);
+                index
+            }
+        };
+        let name = self.gilrs.gamepad(id).name().to_string();
+
+        self.renderer
+            .set_gamepad_details(index, &name, AXIS_COUNT, BUTTON_COUNT)?;
+        self.renderer.fire_gamepad_event(GamepadEvent::new(
+            self.renderer.lib.clone(),
+            index,
+            GamepadEventType::Connected,
+        )?)?;
+        self.connected.insert(id);
+
+        Ok(())
+    }
+
+    fn disconnect(&mut self, id: GamepadId) -> Result<(), CreationError> {
+        if !self.connected.remove(&id) {
+            return Ok(());
+        }
+        let Some(&index) = self.slots.get(&id) else {
+            return Ok(());
+        };
+
+        self.renderer.fire_gamepad_event(GamepadEvent::new(
+            self.renderer.lib.clone(),
+            index,
+            GamepadEventType::Disconnected,
+        )?)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+//! Managing a local inspector View for a [`View`].
+//!
+//! See [`LocalInspector`].
+use std::{
+    cell::{Cell, Ref, RefCell},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::{
+    error::{CreationError, Error},
+    platform,
+    surface::Surface,
+    view::{RenderTarget, View, ViewConfig},
+};
+
+use super::Renderer;
+
+/// The path of the inspector entry point, relative to the assets directory.
+const INSPECTOR_MAIN_PATH: &str = "inspector/Main.html";
+
+/// Errors that can occur when creating a [`LocalInspector`].
+#[derive(Debug, thiserror::Error)]
+pub enum InspectorError {
+    /// The inspector assets were not found in the assets directory.
+    #[error("Inspector assets not found, `{0}` doesn't exist")]
+    AssetsNotFound(PathBuf),
+    /// Failed to create the inspector view.
+    #[error("Failed to create the inspector view: {0}")]
+    CreationError(#[from] CreationError),
+}
+
+/// State shared between the [`LocalInspector`] and the callback creating
+/// the inspector view.
+struct InspectorState {
+    view: RefCell<Option<View>>,
+    /// Whether the inspector is shown, the view is kept when it's closed.
+    open: Cell<bool>,
+    size: Cell<(u32, u32)>,
+}
+
+/// A local inspector (the WebKit developer tools) for a [`View`].
+///
+/// This handles [`View::set_create_inspector_view_callback`] of the
+/// inspected view, creates the inspector [`View`] when Ultralight requests it
+/// and keeps ownership of it. The application only has to display
+/// [`LocalInspector::view`] and forward input events to it, like any other
+/// [`View`].
+///
+/// The inspector view is accelerated if the inspected view is, in that case
+/// use [`LocalInspector::render_target`] to display it, otherwise use
+/// [`LocalInspector::surface`].
+///
+/// # Assets
+///
+/// The inspector is a web application that is not included in the Ultralight
+/// library, it's shipped in the `inspector` directory of the Ultralight SDK.
+/// When opened, the inspector view loads `file:///inspector/Main.html`, so
+/// this directory must be available in the file system set with
+/// [`platform::enable_platform_filesystem`](crate::platform::enable_platform_filesystem)
+/// or [`platform::set_filesystem`](crate::platform::set_filesystem).
+///
+/// The `assets_dir` given to [`LocalInspector::new`] is the directory
+/// containing `inspector/Main.html`, usually the base directory of the file
+/// system, it's only used to check that the assets are present.
+pub struct LocalInspector<'a> {
+    target: &'a View,
+    state: Rc<InspectorState>,
+}
+
+impl<'a> LocalInspector<'a> {
+    /// Create a local inspector for `target`, the inspector view will be
+    /// created with the size `width`x`height` when opened.
+    ///
+    /// This replaces the callback set with
+    /// [`View::set_create_inspector_view_callback`] on `target`, and like
+    /// other callbacks, `target` must not be moved while the inspector is used.
+    ///
+    /// Returns [`InspectorError::AssetsNotFound`] if `assets_dir` doesn't
+    /// contain `inspector/Main.html` (see [Assets](#assets)).
+    pub fn new(
+        renderer: &Renderer,
+        target: &'a View,
+        assets_dir: impl AsRef<Path>,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, InspectorError> {
+        let main_path = assets_dir.as_ref().join(INSPECTOR_MAIN_PATH);
+        if !main_path.is_file() {
+            return Err(InspectorError::AssetsNotFound(main_path));
+        }
+
+        let is_accelerated = target.is_accelerated();
+        if renderer.need_to_destroy && is_accelerated && !platform::is_gpu_driver_set(&renderer.lib)
+        {
+            return Err(CreationError::GpuDriverNotSet.into());
+        }
+
+        let view_config = ViewConfig::start()
+            .is_accelerated(is_accelerated)
+            .initial_device_scale(target.device_scale())
+            .try_build(renderer.lib.clone())?;
+
+        let state = Rc::new(InspectorState {
+            view: RefCell::new(None),
+            open: Cell::new(false),
+            size: Cell::new((width, height)),
+        });
+
+        {
+            let state = state.clone();
+            let lib = renderer.lib.clone();
+            let raw_renderer = renderer.internal;
+
+            target.set_create_inspector_view_callback(move |_view, is_local, _inspected_url| {
+                // remote inspectors are created by the remote inspector server
+                if !is_local {
+                    return None;
+                }
+
+                let (width, height) = state.size.get();
+                let view =
+                    unsafe { View::create(raw_renderer, width, height, &view_config, None, None) }?;
+                // Ultralight doesn't take ownership of the returned view,
+                // so we keep the owned view, and return a handle to it.
+                let handle = unsafe { View::from_raw(lib.clone(), view.to_ul()) };
+                *state.view.borrow_mut() = Some(view);
+                state.open.set(true);
+                handle
+            });
+        }
+
+        Ok(Self { target, state })
+    }
+
+    /// Open the inspector, this creates the inspector view the first time
+    /// it's opened.
+    pub fn open(&self) {
+        if self.state.view.borrow().is_some() {
+            self.state.open.set(true);
+        } else {
+            self.target.create_local_inspector_view();
+        }
+    }
+
+    /// Close the inspector, this hides the inspector view.
+    ///
+    /// The view is not destroyed, as Ultralight keeps using it while the
+    /// inspected view is alive, it's shown again by the next
+    /// [`LocalInspector::open`].
+    pub fn close(&self) {
+        self.state.open.set(false);
+        if let Some(view) = self.state.view.borrow().as_ref() {
+            view.unfocus();
+        }
+    }
+
+    /// Open the inspector if it's closed, or close it if it's open.
+    pub fn toggle(&self) {
+        if self.is_open() {
+            self.close();
+        } else {
+            self.open();
+        }
+    }
+
+    /// Whether the inspector is open (shown).
+    pub fn is_open(&self) -> bool {
+        self.state.open.get()
+    }
+
+    /// The inspector view, if the inspector is open.
+    ///
+    /// Input events should be forwarded to this view, and it should be
+    /// displayed by the application.
+    pub fn view(&self) -> Option<Ref<'_, View>> {
+        if !self.is_open() {
+            return None;
+        }
+        Ref::filter_map(self.state.view.borrow(), |view| view.as_ref()).ok()
+    }
+
+    /// The inspected view.
+    pub fn target(&self) -> &'a View {
+        self.target
+    }
+
+    /// The render target of the inspector view, if the inspector is open
+    /// and the inspected view is accelerated.
+    ///
+    /// See [`View::render_target`].
+    pub fn render_target(&self) -> Option<RenderTarget> {
+        self.view().and_then(|view| view.render_target())
+    }
+
+    /// The surface of the inspector view, if the inspector is open
+    /// and the inspected view is not accelerated.
+    ///
+    /// See [`View::surface`].
+    pub fn surface(&self) -> Option<Surface> {
+        self.view().and_then(|view| view.surface())
+    }
+
+    /// Resize the inspector view, if it was never opened, the new size will
+    /// be used when it's opened.
+    ///
+    /// Returns the error of [`View::resize`], the new size is still used
+    /// for the next time it's opened.
+    pub fn resize(&self, width: u32, height: u32) -> Result<(), Error> {
+        self.state.size.set((width, height));
+        if let Some(view) = self.state.view.borrow().as_ref() {
+            view.resize(width, height)?;
+        }
+        Ok(())
+    }
+
+    /// The size of the inspector view.
+    pub fn size(&self) -> (u32, u32) {
+        self.state.size.get()
+    }
+}
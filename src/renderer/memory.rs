@@ -0,0 +1,190 @@
+//! Reading the memory usage of the renderer as structured data, see
+//! [`Renderer::memory_usage`].
+use crate::{error::Error, platform};
+
+use super::Renderer;
+
+/// A line of the memory usage log with a size, see [`MemoryStats::entries`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryStatsEntry {
+    /// The header line without a value this entry is indented under, if any
+    /// (eg. `JavaScript Heap`).
+    pub section: Option<String>,
+    /// The text before the value (eg. `Size`).
+    pub label: String,
+    /// The value, in bytes.
+    pub bytes: u64,
+}
+
+/// The memory usage of a renderer, see [`Renderer::memory_usage`].
+///
+/// Ultralight only reports its memory usage as text in the log, so this is
+/// parsed from the lines written by [`Renderer::log_memory_usage`]. The
+/// format of these lines is not documented and may change between versions
+/// of Ultralight, so the known fields are [`None`] when no line matches
+/// them. All the lines with a size are available in
+/// [`MemoryStats::entries`], and the raw lines in [`MemoryStats::lines`].
+///
+/// Lines are parsed as `label: value unit`, where the unit is one of `B`,
+/// `bytes`, `KB`, `MB`, `GB` (and their `KiB` forms), all of them being
+/// powers of `1024`. A line ending with `:` starts a section, which
+/// contains the following indented lines, and is used with their label to
+/// recognize the known fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// The total memory used by the renderer, in bytes.
+    pub total_bytes: Option<u64>,
+    /// The memory used by the JavaScript heap, in bytes.
+    pub javascript_heap_bytes: Option<u64>,
+    /// The memory used by the page cache (the back/forward cache), in bytes.
+    pub page_cache_bytes: Option<u64>,
+    /// The memory used by decoded images, in bytes.
+    pub image_cache_bytes: Option<u64>,
+    /// The memory used by fonts and glyphs, in bytes.
+    pub font_cache_bytes: Option<u64>,
+    /// All the lines with a size, in order.
+    pub entries: Vec<MemoryStatsEntry>,
+    /// The raw lines of the log.
+    pub lines: Vec<String>,
+}
+
+impl MemoryStats {
+    /// Parse the lines written by [`Renderer::log_memory_usage`], a message
+    /// may contain several lines.
+    pub fn from_log<I, S>(messages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut stats = Self::default();
+        let mut section = None;
+
+        for message in messages {
+            for line in message.as_ref().lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    continue;
+                }
+                stats.lines.push(line.to_string());
+
+                let Some((label, value)) = trimmed.rsplit_once(':') else {
+                    continue;
+                };
+                let label = label.trim().trim_start_matches(['-', '*']).trim();
+                let value = value.trim();
+                if value.is_empty() {
+                    if !label.is_empty() {
+                        section = Some(label.to_string());
+                    }
+                    continue;
+                }
+                if !line.starts_with(char::is_whitespace) {
+                    // entries of a section are indented
+                    section = None;
+                }
+                if let Some(bytes) = parse_size(value) {
+                    stats.add_entry(MemoryStatsEntry {
+                        section: section.clone(),
+                        label: label.to_string(),
+                        bytes,
+                    });
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Add `entry`, and use it for the first known field it matches.
+    fn add_entry(&mut self, entry: MemoryStatsEntry) {
+        let name = match &entry.section {
+            Some(section) => format!("{} {}", section, entry.label),
+            None => entry.label.clone(),
+        }
+        .to_ascii_lowercase();
+
+        let field = if name.contains("javascript") || name.contains("js heap") {
+            &mut self.javascript_heap_bytes
+        } else if name.contains("page cache") {
+            &mut self.page_cache_bytes
+        } else if name.contains("image") {
+            &mut self.image_cache_bytes
+        } else if name.contains("font") || name.contains("glyph") {
+            &mut self.font_cache_bytes
+        } else if name.contains("total") {
+            &mut self.total_bytes
+        } else {
+            self.entries.push(entry);
+            return;
+        };
+        field.get_or_insert(entry.bytes);
+        self.entries.push(entry);
+    }
+}
+
+/// Parse a size like `12.5 MB` or `1,024 bytes` to bytes.
+fn parse_size(value: &str) -> Option<u64> {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number = number.replace(',', "").parse::<f64>().ok()?;
+
+    // ignore any comment after the unit, eg. `12 MB (3 objects)`
+    let unit = unit.split_whitespace().next().unwrap_or_default();
+    let multiplier: u64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" | "byte" | "bytes" => 1,
+        "kb" | "kib" | "k" => 1 << 10,
+        "mb" | "mib" | "m" => 1 << 20,
+        "gb" | "gib" | "g" => 1 << 30,
+        _ => return None,
+    };
+    Some((number * multiplier as f64).round() as u64)
+}
+
+/// The memory usage before and after [`Renderer::purge_memory_and_report`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// The memory usage before purging.
+    pub before: Option<MemoryStats>,
+    /// The memory usage after purging.
+    pub after: Option<MemoryStats>,
+}
+
+impl MemoryReport {
+    /// The bytes released from the total, if it was reported both times.
+    pub fn freed_bytes(&self) -> Option<u64> {
+        let before = self.before.as_ref()?.total_bytes?;
+        let after = self.after.as_ref()?.total_bytes?;
+        Some(before.saturating_sub(after))
+    }
+}
+
+impl Renderer {
+    /// Get the memory usage of the renderer, by capturing and parsing the
+    /// output of [`Renderer::log_memory_usage`] (see [`MemoryStats`]).
+    ///
+    /// A logger set with [`platform::set_logger`] doesn't receive the lines
+    /// written during this call. Without one, a logger is installed (and
+    /// discards the messages after this call), except if the logger of
+    /// `AppCore` is in use (with
+    /// [`platform::enable_default_logger`] or for the renderer of an
+    /// [`App`](crate::app::App)), as it can't be restored: [`None`] is
+    /// returned in that case.
+    pub fn memory_usage(&self) -> Option<MemoryStats> {
+        let lines =
+            platform::capture_log(&self.lib, self.need_to_destroy, || self.log_memory_usage())?;
+        Some(MemoryStats::from_log(lines))
+    }
+
+    /// Call [`Renderer::purge_memory`], and report the memory usage before
+    /// and after it (see [`Renderer::memory_usage`]).
+    ///
+    /// Returns the error of [`Renderer::purge_memory`].
+    pub fn purge_memory_and_report(&self) -> Result<MemoryReport, Error> {
+        let before = self.memory_usage();
+        self.purge_memory()?;
+        let after = self.memory_usage();
+        Ok(MemoryReport { before, after })
+    }
+}
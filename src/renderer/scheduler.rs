@@ -0,0 +1,327 @@
+//! Painting only the visible views of a renderer, with a limit of views
+//! painted per frame, see [`Scheduler`].
+use crate::{error::Error, view::View};
+
+use super::Renderer;
+
+/// The renderer driven by a [`Scheduler`].
+///
+/// This is implemented by [`Renderer`], it can be implemented by other
+/// types to check the behavior of a [`Scheduler`] without Ultralight, for
+/// example in tests.
+pub trait FrameDriver {
+    /// Update timers and dispatch callbacks, see [`Renderer::update`].
+    fn update(&self) -> Result<(), Error>;
+
+    /// Paint the views that need painting, see [`Renderer::render`].
+    fn render(&self) -> Result<(), Error>;
+}
+
+impl FrameDriver for Renderer {
+    fn update(&self) -> Result<(), Error> {
+        Renderer::update(self)
+    }
+
+    fn render(&self) -> Result<(), Error> {
+        Renderer::render(self)
+    }
+}
+
+/// A view scheduled by a [`Scheduler`].
+///
+/// This is implemented by [`View`], see [`FrameDriver`] for the reason.
+pub trait PaintTarget {
+    /// Whether the view needs to be painted, see [`View::needs_paint`].
+    fn needs_paint(&self) -> bool;
+
+    /// Set whether the view needs to be painted, see [`View::set_needs_paint`].
+    fn set_needs_paint(&self, needs_paint: bool);
+}
+
+impl PaintTarget for View {
+    fn needs_paint(&self) -> bool {
+        View::needs_paint(self)
+    }
+
+    fn set_needs_paint(&self, needs_paint: bool) {
+        View::set_needs_paint(self, needs_paint)
+    }
+}
+
+/// The handle of a view registered in a [`Scheduler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScheduledViewId(u64);
+
+/// See [`Scheduler::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SchedulerStats {
+    /// The number of registered views painted in the last
+    /// [`Scheduler::tick`].
+    pub painted_last_frame: usize,
+    /// The number of visible views that needed painting in the last
+    /// [`Scheduler::tick`], but were deferred to a later frame because of
+    /// [`Scheduler::set_max_paints_per_frame`].
+    pub deferred: usize,
+    /// The number of hidden views that need painting, and will be painted
+    /// once visible.
+    pub hidden_pending: usize,
+}
+
+struct Entry<'a, V: ?Sized> {
+    id: ScheduledViewId,
+    view: &'a V,
+    visible: bool,
+    priority: i32,
+    /// The view needs painting, but its `needs_paint` flag was cleared by
+    /// the scheduler.
+    pending: bool,
+    /// The number of frames the view was deferred since its last paint.
+    waited: u64,
+}
+
+/// Schedules the painting of views, so that hidden views are not painted,
+/// and the number of views painted per frame can be limited.
+///
+/// Each frame, [`Scheduler::tick`] calls [`Renderer::update`], then clears
+/// the `needs_paint` flag (see [`View::set_needs_paint`]) of the registered
+/// views that must not be painted in this frame, so that
+/// [`Renderer::render`] skips them. The scheduler remembers these views, and
+/// sets their flag again in the first frame they are allowed to be painted,
+/// so a paint is never lost, only delayed.
+///
+/// When there are more views to paint than the limit set with
+/// [`Scheduler::set_max_paints_per_frame`], the views with the highest
+/// priority are painted first. The priority of a deferred view grows by one
+/// each frame it waits, so all the views are eventually painted, and views
+/// of the same priority are painted in turns.
+///
+/// Only the `needs_paint` flag is changed, so display refreshes (see
+/// [`Renderer::refresh_display`]) still advance the animations of hidden
+/// views. Views that are not registered are painted as usual.
+///
+/// When a view is unregistered, or the scheduler is dropped, the flag of the
+/// views with a pending paint is set again.
+///
+/// # Example
+/// ```no_run
+/// # use ul_next::{renderer::{Renderer, Scheduler}, view::View};
+/// # fn example(renderer: &Renderer, main_view: &View, tab_view: &View) {
+/// let mut scheduler = Scheduler::new();
+/// scheduler.set_max_paints_per_frame(Some(4));
+/// let main = scheduler.register(main_view, true, 10);
+/// let tab = scheduler.register(tab_view, false, 0);
+///
+/// loop {
+///     scheduler.tick(renderer).unwrap();
+///     // ... draw the views
+///
+///     // the user switched tabs
+///     scheduler.set_visible(main, false);
+///     scheduler.set_visible(tab, true);
+/// }
+/// # }
+/// ```
+pub struct Scheduler<'a, V: PaintTarget + ?Sized = View> {
+    entries: Vec<Entry<'a, V>>,
+    next_id: u64,
+    max_paints_per_frame: Option<usize>,
+    stats: SchedulerStats,
+}
+
+impl<V: PaintTarget + ?Sized> Default for Scheduler<'_, V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            next_id: 0,
+            max_paints_per_frame: None,
+            stats: SchedulerStats::default(),
+        }
+    }
+}
+
+impl<'a, V: PaintTarget + ?Sized> Scheduler<'a, V> {
+    /// Create a scheduler without views and without a limit of views painted
+    /// per frame.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `view`.
+    ///
+    /// # Arguments
+    /// * `visible` - Whether the view is shown, hidden views are not painted.
+    /// * `priority` - Views with a higher priority are painted first when
+    ///   the number of views painted per frame is limited.
+    pub fn register(&mut self, view: &'a V, visible: bool, priority: i32) -> ScheduledViewId {
+        let id = ScheduledViewId(self.next_id);
+        self.next_id += 1;
+        self.entries.push(Entry {
+            id,
+            view,
+            visible,
+            priority,
+            pending: false,
+            waited: 0,
+        });
+        id
+    }
+
+    /// Unregister the view of `id`, its pending paint (if any) is restored.
+    ///
+    /// Returns whether the view was registered.
+    pub fn unregister(&mut self, id: ScheduledViewId) -> bool {
+        let Some(index) = self.entries.iter().position(|entry| entry.id == id) else {
+            return false;
+        };
+        let entry = self.entries.remove(index);
+        if entry.pending {
+            entry.view.set_needs_paint(true);
+        }
+        true
+    }
+
+    /// Set whether the view of `id` is shown, it will be painted in the next
+    /// [`Scheduler::tick`] if it has a pending paint.
+    ///
+    /// Returns whether the view is registered.
+    pub fn set_visible(&mut self, id: ScheduledViewId, visible: bool) -> bool {
+        self.entry_mut(id)
+            .map(|entry| entry.visible = visible)
+            .is_some()
+    }
+
+    /// Whether the view of `id` is shown, [`None`] if it's not registered.
+    pub fn is_visible(&self, id: ScheduledViewId) -> Option<bool> {
+        self.entry(id).map(|entry| entry.visible)
+    }
+
+    /// Set the priority of the view of `id`.
+    ///
+    /// Returns whether the view is registered.
+    pub fn set_priority(&mut self, id: ScheduledViewId, priority: i32) -> bool {
+        self.entry_mut(id)
+            .map(|entry| entry.priority = priority)
+            .is_some()
+    }
+
+    /// The priority of the view of `id`, [`None`] if it's not registered.
+    pub fn priority(&self, id: ScheduledViewId) -> Option<i32> {
+        self.entry(id).map(|entry| entry.priority)
+    }
+
+    /// Whether the view of `id` has a paint held back by the scheduler,
+    /// [`None`] if it's not registered.
+    pub fn is_pending(&self, id: ScheduledViewId) -> Option<bool> {
+        self.entry(id).map(|entry| entry.pending)
+    }
+
+    /// Limit the number of registered views painted in each
+    /// [`Scheduler::tick`], [`None`] for no limit.
+    ///
+    /// A limit of `0` defers all the views, which can be used to pause
+    /// painting.
+    ///
+    /// (Default = [`None`])
+    pub fn set_max_paints_per_frame(&mut self, max: Option<usize>) {
+        self.max_paints_per_frame = max;
+    }
+
+    /// The limit set with [`Scheduler::set_max_paints_per_frame`].
+    pub fn max_paints_per_frame(&self) -> Option<usize> {
+        self.max_paints_per_frame
+    }
+
+    /// The number of registered views.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no view is registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The statistics of the last [`Scheduler::tick`].
+    pub fn stats(&self) -> SchedulerStats {
+        self.stats
+    }
+
+    /// Update the renderer, choose the views painted in this frame, then
+    /// render.
+    ///
+    /// Returns the error of [`FrameDriver::update`] or
+    /// [`FrameDriver::render`], nothing is scheduled if the update failed.
+    pub fn tick<R: FrameDriver + ?Sized>(&mut self, renderer: &R) -> Result<(), Error> {
+        renderer.update()?;
+        self.schedule();
+        renderer.render()
+    }
+
+    /// Set the `needs_paint` flag of the views allowed to be painted in this
+    /// frame, and clear it for the others.
+    fn schedule(&mut self) {
+        for entry in &mut self.entries {
+            if entry.view.needs_paint() {
+                entry.pending = true;
+            }
+        }
+
+        let mut candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.visible && entry.pending)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        // the sort is stable, so views of the same effective priority keep
+        // the registration order
+        candidates.sort_by_key(|&index| {
+            let entry = &self.entries[index];
+            std::cmp::Reverse(i64::from(entry.priority).saturating_add(entry.waited as i64))
+        });
+
+        let painted = self
+            .max_paints_per_frame
+            .map_or(candidates.len(), |max| max.min(candidates.len()));
+        let deferred = candidates.len() - painted;
+        for &index in &candidates[..painted] {
+            let entry = &mut self.entries[index];
+            entry.view.set_needs_paint(true);
+            entry.pending = false;
+            entry.waited = 0;
+        }
+        for &index in &candidates[painted..] {
+            self.entries[index].waited += 1;
+        }
+
+        let mut hidden_pending = 0;
+        for entry in self.entries.iter().filter(|entry| entry.pending) {
+            entry.view.set_needs_paint(false);
+            if !entry.visible {
+                hidden_pending += 1;
+            }
+        }
+
+        self.stats = SchedulerStats {
+            painted_last_frame: painted,
+            deferred,
+            hidden_pending,
+        };
+    }
+
+    fn entry(&self, id: ScheduledViewId) -> Option<&Entry<'a, V>> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    fn entry_mut(&mut self, id: ScheduledViewId) -> Option<&mut Entry<'a, V>> {
+        self.entries.iter_mut().find(|entry| entry.id == id)
+    }
+}
+
+impl<V: PaintTarget + ?Sized> Drop for Scheduler<'_, V> {
+    fn drop(&mut self) {
+        for entry in self.entries.iter().filter(|entry| entry.pending) {
+            entry.view.set_needs_paint(true);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Capturing all the views of a renderer to PNG files, see
+//! [`Renderer::take_snapshot_png`].
+use std::path::{Path, PathBuf};
+
+use crate::{bitmap::BitmapError, error::Error, view::View};
+
+use super::Renderer;
+
+/// Errors that can occur in [`Renderer::take_snapshot_png`].
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    /// Failed to create the output directory.
+    #[error("Failed to create the snapshot directory: {0}")]
+    Io(#[from] std::io::Error),
+    /// Failed to render the views, see [`Error::ReentrantCall`].
+    #[error("Failed to render the views: {0}")]
+    Render(#[from] Error),
+    /// Failed to write the PNG file of a view.
+    #[error("Failed to write the snapshot `{}`: {source}", .path.display())]
+    Bitmap {
+        path: PathBuf,
+        #[source]
+        source: BitmapError,
+    },
+}
+
+impl Renderer {
+    /// Render, then write the content of every view created with
+    /// [`Renderer::create_view`] that is still alive to
+    /// `{output_dir}/view_{i}.png`, `i` being the index of the view in
+    /// creation order.
+    ///
+    /// `output_dir` is created if it doesn't exist, existing files are
+    /// overwritten. GPU-accelerated views have no CPU surface, so they are
+    /// skipped with a warning (printed with `log` if the `log` feature is
+    /// enabled, or to `stderr` otherwise), and the index is not reused.
+    ///
+    /// Returns the paths of the written files.
+    pub fn take_snapshot_png<P: AsRef<Path>>(
+        &self,
+        output_dir: P,
+    ) -> Result<Vec<PathBuf>, SnapshotError> {
+        let output_dir = output_dir.as_ref();
+        std::fs::create_dir_all(output_dir)?;
+
+        self.render()?;
+
+        // copy the list, so that the registry is not borrowed while calling
+        // into Ultralight
+        let views = self.views.borrow().clone();
+        let mut written = Vec::with_capacity(views.len());
+
+        for (i, raw_view) in views.into_iter().enumerate() {
+            let Some(view) = (unsafe { View::from_raw(self.lib.clone(), raw_view) }) else {
+                continue;
+            };
+
+            let Some(bitmap) = view.surface().and_then(|surface| surface.bitmap()) else {
+                warn_skipped(i);
+                continue;
+            };
+
+            let path = output_dir.join(format!("view_{}.png", i));
+            bitmap
+                .write_to_png(&path)
+                .map_err(|source| SnapshotError::Bitmap {
+                    path: path.clone(),
+                    source,
+                })?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+}
+
+fn warn_skipped(index: usize) {
+    #[cfg(feature = "log")]
+    log::warn!(
+        "Skipping view {} in the snapshot, it is accelerated or has no surface",
+        index
+    );
+    #[cfg(not(feature = "log"))]
+    eprintln!(
+        "warning: Skipping view {} in the snapshot, it is accelerated or has no surface",
+        index
+    );
+}
@@ -0,0 +1,116 @@
+//! Creating a view in one chain of calls, see [`Renderer::create_view_builder`].
+use crate::{
+    error::Error,
+    view::{View, ViewConfig, ViewConfigBuilder},
+};
+
+use super::{Renderer, Session};
+
+/// Generates the methods of [`ViewBuilder`] forwarding to the
+/// [`ViewConfigBuilder`] method with the same name.
+macro_rules! forward_config {
+    ($($name:ident($arg:ident: $ty:ty)),* $(,)?) => {
+        $(
+            #[doc = concat!("See [`ViewConfigBuilder::", stringify!($name), "`].")]
+            pub fn $name(mut self, $arg: $ty) -> Self {
+                self.config = self.config.$name($arg);
+                self
+            }
+        )*
+    };
+}
+
+/// Builder for a [`View`], created with [`Renderer::create_view_builder`].
+///
+/// It accepts the options of [`ViewConfigBuilder`], and the size and
+/// session of the view, which are the arguments of
+/// [`Renderer::create_view`].
+///
+/// # Example
+/// ```no_run
+/// # fn example(renderer: &ul_next::renderer::Renderer) -> Result<(), ul_next::error::Error> {
+/// let view = renderer
+///     .create_view_builder()
+///     .size(800, 600)
+///     .is_accelerated(true)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ViewBuilder<'a> {
+    renderer: &'a Renderer,
+    width: u32,
+    height: u32,
+    session: Option<&'a Session>,
+    config: ViewConfigBuilder,
+}
+
+impl<'a> ViewBuilder<'a> {
+    /// The width of the view in pixels.
+    ///
+    /// (Default = `800`)
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The height of the view in pixels.
+    ///
+    /// (Default = `600`)
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// The width and height of the view in pixels.
+    pub fn size(self, width: u32, height: u32) -> Self {
+        self.width(width).height(height)
+    }
+
+    /// The session to store local data in, see [`Renderer::create_view`].
+    ///
+    /// (Default = the default session of the renderer)
+    pub fn session(mut self, session: &'a Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    forward_config! {
+        is_accelerated(is_accelerated: bool),
+        is_transparent(is_transparent: bool),
+        initial_device_scale(initial_device_scale: f64),
+        initial_focus(initial_focus: bool),
+        enable_images(enable_images: bool),
+        enable_javascript(enable_javascript: bool),
+        font_family_standard(font_family_standard: &str),
+        font_family_fixed(font_family_fixed: &str),
+        font_family_serif(font_family_serif: &str),
+        font_family_sans_serif(font_family_sans_serif: &str),
+        user_agent(user_agent: &str),
+        display_id(display_id: u32),
+    }
+
+    /// Create the [`ViewConfig`], then the view,
+    /// with [`Renderer::create_view`].
+    pub fn build(self) -> Result<View, Error> {
+        let config = self.config.try_build(self.renderer.lib.clone())?;
+        Ok(self
+            .renderer
+            .create_view(self.width, self.height, &config, self.session)?)
+    }
+}
+
+impl Renderer {
+    /// Start creating a [`View`] with a [`ViewBuilder`], combining the
+    /// options of the [`ViewConfig`] and the
+    /// arguments of [`Renderer::create_view`].
+    pub fn create_view_builder(&self) -> ViewBuilder<'_> {
+        ViewBuilder {
+            renderer: self,
+            width: 800,
+            height: 600,
+            session: None,
+            config: ViewConfig::start(),
+        }
+    }
+}
@@ -1,7 +1,22 @@
-use std::{os::raw::c_char, slice, sync::Arc};
+use std::{collections::VecDeque, ffi::CString, os::raw::c_char, path::Path, slice, sync::Arc};
 
 use crate::{error::CreationError, Library};
 
+/// Convert `s` to a NUL-terminated C string, removing any NUL characters
+/// inside it (which would otherwise end the string early).
+pub(crate) fn to_c_string_lossy(s: &str) -> CString {
+    let bytes = s.bytes().filter(|&b| b != 0).collect::<Vec<_>>();
+    // SAFETY: all the NUL bytes were removed
+    unsafe { CString::from_vec_unchecked(bytes) }
+}
+
+/// Convert `path` to `&str`, Ultralight only accepts UTF-8 paths, so
+/// other paths return [`CreationError::InvalidPath`].
+pub(crate) fn path_to_str(path: &Path) -> Result<&str, CreationError> {
+    path.to_str()
+        .ok_or_else(|| CreationError::InvalidPath(path.to_path_buf()))
+}
+
 /// A rust wrapper around [`ul_sys::ULString`], which is used in ultralight
 /// functions.
 pub(crate) struct UlString {
@@ -63,6 +78,101 @@ impl UlString {
     }
 }
 
+/// A small LRU cache of strings copied out of [`ul_sys::ULString`]s.
+///
+/// Some callbacks (like the URL change callback) are fired many times in a row
+/// with the same content, for example on pages doing a lot of `pushState`.
+/// Instead of converting and validating the whole string each time, we
+/// compare the raw data against the recently seen strings and clone the
+/// cached copy if it matches.
+pub(crate) struct UlStringCache {
+    capacity: usize,
+    // most recently used entry is at the front
+    entries: VecDeque<String>,
+}
+
+impl UlStringCache {
+    /// The default number of entries kept in the cache.
+    pub(crate) const DEFAULT_CAPACITY: usize = 2;
+
+    /// Creates a new cache holding at most `capacity` strings.
+    ///
+    /// A `capacity` of `0` disables caching, and every lookup will copy.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// The maximum number of strings kept in the cache.
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the maximum number of strings kept in the cache, dropping the
+    /// least recently used ones if there are more.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.entries.truncate(capacity);
+    }
+
+    /// Returns a copy of the `raw` string, cloning a cached one if the content
+    /// is identical.
+    ///
+    /// The comparison is done directly against the string data returned by
+    /// `ulStringGetData`, so on a cache hit the only allocation is the
+    /// returned clone.
+    pub(crate) unsafe fn get_or_copy(
+        &mut self,
+        lib: &Arc<Library>,
+        raw: ul_sys::ULString,
+    ) -> Result<String, CreationError> {
+        if raw.is_null() {
+            return Err(CreationError::NullReference);
+        }
+
+        let raw_data = lib.ultralight().ulStringGetData(raw);
+        if raw_data.is_null() {
+            return Err(CreationError::NullReference);
+        }
+        let raw_bytes = slice::from_raw_parts(
+            raw_data as *const u8,
+            lib.ultralight().ulStringGetLength(raw),
+        );
+
+        if let Some(index) = self.entries.iter().position(|entry| {
+            let entry = entry.as_bytes();
+            // cheap checks first, most of the time different strings will
+            // differ in length or at the edges.
+            entry.len() == raw_bytes.len()
+                && entry.first() == raw_bytes.first()
+                && entry.last() == raw_bytes.last()
+                && entry == raw_bytes
+        }) {
+            let entry = self.entries.remove(index).unwrap();
+            let string = entry.clone();
+            self.entries.push_front(entry);
+            return Ok(string);
+        }
+
+        let string = String::from_utf8(raw_bytes.to_vec())?;
+        if self.capacity > 0 {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_back();
+            }
+            self.entries.push_front(string.clone());
+        }
+        Ok(string)
+    }
+}
+
+impl Default for UlStringCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
 impl Drop for UlString {
     fn drop(&mut self) {
         unsafe {
@@ -7,7 +7,7 @@ use std::{
     sync::Arc,
 };
 
-use crate::{rect::Rect, Library};
+use crate::{bitmap::Bitmap, rect::Rect, Library};
 
 /// An RAII implementation of a “scoped lock” of a pixel buffer for [`Surface`].
 /// When this structure is dropped (falls out of scope), the lock will be unlocked.
@@ -22,6 +22,24 @@ impl<'a> PixelsGuard<'a> {
     unsafe fn new(lock: &'a mut Surface, pixels: &'a mut [u8]) -> PixelsGuard<'a> {
         PixelsGuard { lock, pixels }
     }
+
+    /// Get the width (in pixels) of the locked surface.
+    pub fn width(&self) -> u32 {
+        self.lock.width()
+    }
+
+    /// Get the height (in pixels) of the locked surface.
+    pub fn height(&self) -> u32 {
+        self.lock.height()
+    }
+
+    /// Get the number of bytes between each row of pixels.
+    ///
+    /// Rows may be padded, so use this instead of `width * 4` when
+    /// indexing into the pixels.
+    pub fn row_bytes(&self) -> u32 {
+        self.lock.row_bytes()
+    }
 }
 
 impl Deref for PixelsGuard<'_> {
@@ -173,6 +191,29 @@ impl Surface {
         }
     }
 
+    /// Get the region of the pixel buffer updated since the last call to
+    /// [`clear_dirty_bounds`](Surface::clear_dirty_bounds), clamped to the
+    /// size of the surface, or [`None`] if nothing was updated.
+    ///
+    /// This is the same as [`dirty_bounds`](Surface::dirty_bounds), but
+    /// ready to be used as a region to copy, for example to upload only
+    /// the changed pixels to a GPU texture.
+    pub fn damage_bounds(&self) -> Option<Rect<u32>> {
+        let dirty = self.dirty_bounds();
+
+        let left = dirty.left.max(0) as u32;
+        let top = dirty.top.max(0) as u32;
+        let right = (dirty.right.max(0) as u32).min(self.width());
+        let bottom = (dirty.bottom.max(0) as u32).min(self.height());
+
+        (right > left && bottom > top).then_some(Rect {
+            left,
+            top,
+            right,
+            bottom,
+        })
+    }
+
     /// Clear the dirty bounds.
     ///
     /// You should call this after you're done displaying the Surface.
@@ -184,6 +225,21 @@ impl Surface {
         }
     }
 
+    /// Get the underlying [`Bitmap`] of this surface.
+    ///
+    /// This is the pixel buffer of the default `BitmapSurface` that is used by
+    /// the renderer. The bitmap is owned by the surface, and is only valid as
+    /// long as the [`View`](crate::view::View) is not resized or destroyed.
+    pub fn bitmap(&self) -> Option<Bitmap> {
+        unsafe {
+            let bitmap = self
+                .lib
+                .ultralight()
+                .ulBitmapSurfaceGetBitmap(self.internal);
+            Bitmap::from_raw(self.lib.clone(), bitmap)
+        }
+    }
+
     //pub fn user_data(&self) -> *mut std::ffi::c_void {
     //    unsafe { ul_sys::ulSurfaceGetUserData(self.internal) }
     //}
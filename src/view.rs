@@ -1,18 +1,59 @@
 //! The View is a component used to load and display web content.
-use std::{ops::Deref, sync::Arc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ops::Deref,
+    rc::Rc,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+};
 
 use crate::{
     bitmap::BitmapFormat,
-    error::CreationError,
+    error::{CreationError, Error, UnknownEnumValue},
     event::{KeyEvent, MouseEvent, ScrollEvent},
     javascript::JSContext,
     rect::Rect,
-    renderer::Session,
-    string::UlString,
+    renderer::{Session, ViewRegistry},
+    string::{UlString, UlStringCache},
     surface::Surface,
     Library,
 };
 
+mod background;
+mod base_url;
+mod console;
+mod cookies;
+mod data;
+mod drag;
+mod find;
+mod ime;
+mod input;
+#[cfg(feature = "serde")]
+mod json;
+mod opacity;
+mod post;
+mod script_file;
+mod scroll;
+mod stylesheet;
+mod throttle;
+mod zoom;
+
+pub use console::{ConsoleCapture, ConsoleMessage};
+pub use cookies::{Cookie, CookieError, CookieSameSite};
+pub use drag::DragError;
+pub use find::{FindError, FindOptions};
+pub use ime::ImeError;
+pub use input::{InputCoalescer, InputForwarder, InputTarget};
+pub use scroll::ScrollError;
+pub use stylesheet::StylesheetHandle;
+pub use throttle::{ThrottlePolicy, ThrottlingStats};
+
+/// A script of [`View::execute_js_on_dom_ready`] and the sender of its result.
+type DomReadyScript = (String, Sender<Result<String, String>>);
+
 /// Cursor types (See [`View::set_change_cursor_callback`] and [`Window::set_cursor`][crate::window::Window::set_cursor])
 #[derive(Clone, Copy, Debug)]
 pub enum Cursor {
@@ -63,7 +104,7 @@ pub enum Cursor {
 }
 
 impl TryFrom<ul_sys::ULCursor> for Cursor {
-    type Error = ();
+    type Error = UnknownEnumValue;
 
     fn try_from(value: ul_sys::ULCursor) -> Result<Self, Self::Error> {
         match value {
@@ -111,7 +152,7 @@ impl TryFrom<ul_sys::ULCursor> for Cursor {
             ul_sys::ULCursor_kCursor_WestResize => Ok(Self::WestResize),
             ul_sys::ULCursor_kCursor_ZoomIn => Ok(Self::ZoomIn),
             ul_sys::ULCursor_kCursor_ZoomOut => Ok(Self::ZoomOut),
-            _ => Err(()),
+            _ => Err(UnknownEnumValue::new("Cursor", value)),
         }
     }
 }
@@ -187,7 +228,7 @@ pub enum ConsoleMessageSource {
 }
 
 impl TryFrom<ul_sys::ULMessageSource> for ConsoleMessageSource {
-    type Error = ();
+    type Error = UnknownEnumValue;
     fn try_from(value: ul_sys::ULMessageSource) -> Result<Self, Self::Error> {
         match value {
             ul_sys::ULMessageSource_kMessageSource_XML => Ok(ConsoleMessageSource::XML),
@@ -205,13 +246,13 @@ impl TryFrom<ul_sys::ULMessageSource> for ConsoleMessageSource {
                 Ok(ConsoleMessageSource::ContentBlocker)
             }
             ul_sys::ULMessageSource_kMessageSource_Other => Ok(ConsoleMessageSource::Other),
-            _ => Err(()),
+            _ => Err(UnknownEnumValue::new("ConsoleMessageSource", value)),
         }
     }
 }
 
 /// Console message levels (See [`View::set_add_console_message_callback`])
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ConsoleMessageLevel {
     Log = ul_sys::ULMessageLevel_kMessageLevel_Log as isize,
     Warning = ul_sys::ULMessageLevel_kMessageLevel_Warning as isize,
@@ -221,15 +262,29 @@ pub enum ConsoleMessageLevel {
 }
 
 impl TryFrom<ul_sys::ULMessageLevel> for ConsoleMessageLevel {
-    type Error = ();
-    fn try_from(value: ul_sys::ULMessageLevel) -> Result<Self, ()> {
+    type Error = UnknownEnumValue;
+    fn try_from(value: ul_sys::ULMessageLevel) -> Result<Self, UnknownEnumValue> {
         match value {
             ul_sys::ULMessageLevel_kMessageLevel_Log => Ok(ConsoleMessageLevel::Log),
             ul_sys::ULMessageLevel_kMessageLevel_Warning => Ok(ConsoleMessageLevel::Warning),
             ul_sys::ULMessageLevel_kMessageLevel_Error => Ok(ConsoleMessageLevel::Error),
             ul_sys::ULMessageLevel_kMessageLevel_Debug => Ok(ConsoleMessageLevel::Debug),
             ul_sys::ULMessageLevel_kMessageLevel_Info => Ok(ConsoleMessageLevel::Info),
-            _ => Err(()),
+            _ => Err(UnknownEnumValue::new("ConsoleMessageLevel", value)),
+        }
+    }
+}
+
+impl ConsoleMessageLevel {
+    /// The severity of the level, used by [`View::set_console_message_filter`].
+    ///
+    /// From lowest to highest: `Debug`, `Log` and `Info`, `Warning`, `Error`.
+    fn severity(self) -> u8 {
+        match self {
+            ConsoleMessageLevel::Debug => 0,
+            ConsoleMessageLevel::Log | ConsoleMessageLevel::Info => 1,
+            ConsoleMessageLevel::Warning => 2,
+            ConsoleMessageLevel::Error => 3,
         }
     }
 }
@@ -238,6 +293,7 @@ impl TryFrom<ul_sys::ULMessageLevel> for ConsoleMessageLevel {
 pub struct ViewConfig {
     lib: Arc<Library>,
     internal: ul_sys::ULViewConfig,
+    is_accelerated: bool,
 }
 
 impl ViewConfig {
@@ -252,6 +308,11 @@ impl ViewConfig {
     pub(crate) unsafe fn to_ul(&self) -> ul_sys::ULViewConfig {
         self.internal
     }
+
+    /// Whether or not views created with this config will use the GPU renderer.
+    pub(crate) fn is_accelerated(&self) -> bool {
+        self.is_accelerated
+    }
 }
 
 impl Drop for ViewConfig {
@@ -263,7 +324,7 @@ impl Drop for ViewConfig {
 }
 
 /// Builder for the [`ViewConfig`] struct.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct ViewConfigBuilder {
     is_accelerated: Option<bool>,
     is_transparent: Option<bool>,
@@ -276,7 +337,7 @@ pub struct ViewConfigBuilder {
     font_family_serif: Option<String>,
     font_family_sans_serif: Option<String>,
     user_agent: Option<String>,
-    // display_id: Option<u32>,
+    display_id: Option<u32>,
 }
 
 impl ViewConfigBuilder {
@@ -385,31 +446,44 @@ impl ViewConfigBuilder {
         self
     }
 
-    // TODO: ulViewConfigSetDisplayId isn't found in the library we use from github, but is found
-    //       in the one from the website. fix that.
-    // /// A user-generated id for the display (monitor, TV, or screen) that this View will be shown on.
-    // ///
-    // /// Animations are driven based on the physical refresh rate of the display. Multiple Views can
-    // /// share the same display.
-    // ///
-    // /// Note: This is automatically managed for you when [`App`][crate::app::App] is used.
-    // ///
-    // /// See also [`Renderer::refresh_display`][crate::renderer::Renderer::refresh_display].
-    // pub fn display_id(mut self, display_id: u32) -> Self {
-    //     self.display_id = Some(display_id);
-    //     self
-    // }
+    /// A user-generated id for the display (monitor, TV, or screen) that this View will be shown on.
+    ///
+    /// Animations are driven based on the physical refresh rate of the display. Multiple Views can
+    /// share the same display.
+    ///
+    /// Note: This is automatically managed for you when [`App`][crate::app::App] is used.
+    ///
+    /// See also [`Renderer::refresh_display`][crate::renderer::Renderer::refresh_display]
+    /// and [`DisplayPump`][crate::renderer::DisplayPump].
+    pub fn display_id(mut self, display_id: u32) -> Self {
+        self.display_id = Some(display_id);
+        self
+    }
 
     /// Builds the [`ViewConfig`] struct using the settings configured in this builder.
     ///
-    /// Returns [`None`] if failed to create [`ViewConfig`].
+    /// Returns [`None`] on failure, use [`ViewConfigBuilder::try_build`] to get the
+    /// error.
+    #[deprecated(note = "use `try_build` instead, it returns the error")]
     pub fn build(self, lib: Arc<Library>) -> Option<ViewConfig> {
+        self.try_build(lib).ok()
+    }
+
+    /// Builds the [`ViewConfig`] struct using the settings configured in this builder.
+    pub fn try_build(self, lib: Arc<Library>) -> Result<ViewConfig, CreationError> {
         let internal = unsafe { lib.ultralight().ulCreateViewConfig() };
 
         if internal.is_null() {
-            return None;
+            return Err(CreationError::NullReference);
         }
 
+        // destroys `internal` if setting one of the options fails
+        let view_config = ViewConfig {
+            lib: lib.clone(),
+            internal,
+            is_accelerated: self.is_accelerated.unwrap_or(false),
+        };
+
         set_config!(
             internal,
             self.is_accelerated,
@@ -465,9 +539,13 @@ impl ViewConfigBuilder {
             self.user_agent,
             lib.ultralight().ulViewConfigSetUserAgent
         );
-        // set_config!(internal, self.display_id, ulViewConfigSetDisplayId);
+        set_config!(
+            internal,
+            self.display_id,
+            lib.ultralight().ulViewConfigSetDisplayId
+        );
 
-        Some(ViewConfig { lib, internal })
+        Ok(view_config)
     }
 }
 
@@ -502,6 +580,97 @@ impl Drop for ViewJSContextGuard<'_> {
     }
 }
 
+/// The state of a `ULView` kept by this crate, shared by all the [`View`]s of
+/// the same `ULView` (including the ones given to the callbacks), and by the
+/// callbacks that need it.
+struct ViewState {
+    // used by the title/url callbacks, which are usually fired repeatedly
+    // with the same strings, see `View::set_string_cache_capacity`
+    string_cache: RefCell<UlStringCache>,
+    // see `View::set_page_zoom`
+    page_zoom: Cell<f64>,
+    // see `View::set_page_scale_factor`
+    page_scale_factor: Cell<f64>,
+    // see `View::set_content_background_color`
+    content_background_color: Cell<Option<(u8, u8, u8, u8)>>,
+    // see `Overlay::set_opacity`
+    content_opacity: Cell<Option<f32>>,
+    dom_ready_callback_set: Cell<bool>,
+    // see `View::execute_js_on_dom_ready`
+    dom_ready_scripts: RefCell<Vec<DomReadyScript>>,
+    // see `View::inject_stylesheet`
+    stylesheets: stylesheet::SharedStylesheets,
+    // see `View::set_console_message_filter`
+    console_message_filter: Cell<Option<ConsoleMessageLevel>>,
+    console_message_callback_set: Cell<bool>,
+    // see `View::enable_console_capture`
+    console_captures: RefCell<Vec<ConsoleCapture>>,
+    window_object_ready_callback_set: Cell<bool>,
+    // see `View::set_background_throttling`
+    throttle_policy: Cell<Option<ThrottlePolicy>>,
+    // see `View::set_background_hidden`
+    background_hidden: Cell<bool>,
+}
+
+thread_local! {
+    /// The state of the views of this thread, by `ULView`.
+    static VIEW_STATES: RefCell<HashMap<usize, Rc<ViewState>>> = RefCell::new(HashMap::new());
+}
+
+impl ViewState {
+    fn new() -> Self {
+        Self {
+            string_cache: RefCell::new(UlStringCache::default()),
+            page_zoom: Cell::new(1.0),
+            page_scale_factor: Cell::new(1.0),
+            content_background_color: Cell::new(None),
+            content_opacity: Cell::new(None),
+            dom_ready_callback_set: Cell::new(false),
+            dom_ready_scripts: RefCell::new(Vec::new()),
+            stylesheets: Default::default(),
+            console_message_filter: Cell::new(None),
+            console_message_callback_set: Cell::new(false),
+            console_captures: RefCell::new(Vec::new()),
+            window_object_ready_callback_set: Cell::new(false),
+            throttle_policy: Cell::new(None),
+            background_hidden: Cell::new(false),
+        }
+    }
+
+    /// Get the state of `view`, creating it the first time.
+    fn of(view: ul_sys::ULView) -> Rc<Self> {
+        VIEW_STATES.with(|states| {
+            states
+                .borrow_mut()
+                .entry(view as usize)
+                .or_insert_with(|| Rc::new(Self::new()))
+                .clone()
+        })
+    }
+
+    /// Create the state of a new `view`, replacing the state of a destroyed
+    /// view with the same address that wasn't forgotten.
+    fn reset(view: ul_sys::ULView) -> Rc<Self> {
+        Self::forget(view);
+        Self::of(view)
+    }
+
+    /// Remove the state of `view` when it's destroyed.
+    ///
+    /// The callbacks of the view may still hold the state (they are never
+    /// freed), so what is attached to the view is cleared: the pending
+    /// scripts are dropped (disconnecting their receivers), the stylesheet
+    /// handles become inactive, and the captures stop receiving messages.
+    fn forget(view: ul_sys::ULView) {
+        let state = VIEW_STATES.with(|states| states.borrow_mut().remove(&(view as usize)));
+        if let Some(state) = state {
+            state.dom_ready_scripts.borrow_mut().clear();
+            state.stylesheets.borrow_mut().clear();
+            state.console_captures.borrow_mut().clear();
+        }
+    }
+}
+
 /// The View class is used to load and display web content.
 ///
 /// View is an offscreen web-page container that can be used to display web-content in your
@@ -522,6 +691,11 @@ pub struct View {
     lib: Arc<Library>,
     internal: ul_sys::ULView,
     need_to_destroy: bool,
+    state: Rc<ViewState>,
+    // keeps the session alive, see `View::session`
+    session: Option<Session>,
+    // see `Renderer::take_snapshot_png`
+    registry: Option<ViewRegistry>,
 }
 
 impl View {
@@ -531,14 +705,30 @@ impl View {
         if raw.is_null() {
             None
         } else {
-            Some(Self {
-                lib,
-                internal: raw,
-                need_to_destroy: false,
-            })
+            Some(Self::with_state(lib, raw, ViewState::of(raw)))
+        }
+    }
+
+    /// A handle to `raw` that doesn't destroy it, using `state` (which must
+    /// be the state of `raw`).
+    fn with_state(lib: Arc<Library>, raw: ul_sys::ULView, state: Rc<ViewState>) -> Self {
+        Self {
+            lib,
+            internal: raw,
+            need_to_destroy: false,
+            state,
+            session: None,
+            registry: None,
         }
     }
 
+    /// Drop the state of a `ULView` that is being destroyed, used for the
+    /// views not destroyed by [`View`] (e.g. the view of an overlay).
+    #[cfg(any(feature = "appcore_linked", feature = "loaded"))]
+    pub(crate) fn forget_state(raw: ul_sys::ULView) {
+        ViewState::forget(raw);
+    }
+
     /// Internal function helper to create a view.
     /// (See [`Renderer::create_view`](crate::renderer::Renderer::create_view))
     pub(crate) unsafe fn create(
@@ -547,6 +737,7 @@ impl View {
         height: u32,
         view_config: &ViewConfig,
         session: Option<&Session>,
+        registry: Option<&ViewRegistry>,
     ) -> Option<Self> {
         let lib = view_config.lib.clone();
         let internal = lib.ultralight().ulCreateView(
@@ -564,6 +755,12 @@ impl View {
                 lib,
                 internal,
                 need_to_destroy: true,
+                state: ViewState::reset(internal),
+                session: session.map(Session::clone_handle),
+                registry: registry.map(|registry| {
+                    registry.borrow_mut().push(internal);
+                    registry.clone()
+                }),
             })
         }
     }
@@ -574,6 +771,12 @@ impl View {
     pub(crate) unsafe fn to_ul(&self) -> ul_sys::ULView {
         self.internal
     }
+
+    /// The library this view was created with, to create events for it.
+    #[cfg(feature = "sdl2")]
+    pub(crate) fn lib(&self) -> &Arc<Library> {
+        &self.lib
+    }
 }
 
 impl View {
@@ -630,6 +833,16 @@ impl View {
         unsafe { self.lib.ultralight().ulViewIsTransparent(self.internal) }
     }
 
+    /// The session the View was created with in
+    /// [`Renderer::create_view`](crate::renderer::Renderer::create_view).
+    ///
+    /// Returns [`None`] if the View uses the default session (created with
+    /// `session: None`), or was not created by us (eg. the View of an
+    /// [`Overlay`](crate::overlay::Overlay)).
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_ref()
+    }
+
     /// Check if the main frame of the page is currently loading.
     pub fn is_loading(&self) -> bool {
         unsafe { self.lib.ultralight().ulViewIsLoading(self.internal) }
@@ -695,12 +908,19 @@ impl View {
     /// # Arguments
     /// * `width` - The new width in pixels.
     /// * `height` - The new height in pixels.
-    pub fn resize(&self, width: u32, height: u32) {
+    ///
+    /// Returns [`Error::ReentrantCall`]
+    /// without resizing when called from a callback called by Ultralight,
+    /// as it would re-enter it, see
+    /// [`DeferQueue`](crate::renderer::DeferQueue).
+    pub fn resize(&self, width: u32, height: u32) -> Result<(), Error> {
+        crate::callback_panic::check_not_reentrant("View::resize")?;
         unsafe {
             self.lib
                 .ultralight()
                 .ulViewResize(self.internal, width, height);
         }
+        Ok(())
     }
 
     /// Acquire the page's [`JSContext`] for use with JavaScriptCore API.
@@ -860,6 +1080,23 @@ impl View {
         }
     }
 
+    /// Set the number of strings kept by the cache of the title and url
+    /// callbacks (see [`View::set_change_title_callback`] and
+    /// [`View::set_change_url_callback`]).
+    ///
+    /// These callbacks are often called repeatedly with the same strings
+    /// (e.g. pages calling `pushState` often), so the recently seen strings
+    /// are compared with the new one without converting it, and cloned if
+    /// they match. `0` disables the cache, the default is `2`.
+    pub fn set_string_cache_capacity(&self, capacity: usize) {
+        self.state.string_cache.borrow_mut().set_capacity(capacity);
+    }
+
+    /// Get the capacity set by [`View::set_string_cache_capacity`].
+    pub fn string_cache_capacity(&self) -> usize {
+        self.state.string_cache.borrow().capacity()
+    }
+
     // looking at the CPP header, the strings seems to be references
     // but the C headers doesn't say we must not destroy them.
     // For now we don't destroy.
@@ -873,7 +1110,7 @@ impl View {
         pub fn set_change_title_callback(&self, callback: FnMut(view: &View, title: String)) :
             [View::lib.ultralight()][s] ulViewSetChangeTitleCallback(ul_view: ul_sys::ULView, ul_title: ul_sys::ULString) {
                let view = &View::from_raw(s.lib.clone(), ul_view).unwrap();
-               let title = UlString::copy_raw_to_string(&s.lib,ul_title).unwrap();
+               let title = view.state.string_cache.borrow_mut().get_or_copy(&s.lib,ul_title).unwrap();
         }
     }
 
@@ -886,7 +1123,7 @@ impl View {
         pub fn set_change_url_callback(&self, callback: FnMut(view: &View, url: String)) :
             [View::lib.ultralight()][s] ulViewSetChangeURLCallback(ul_view: ul_sys::ULView, ul_url: ul_sys::ULString) {
                let view = &View::from_raw(s.lib.clone(), ul_view).unwrap();
-               let url = UlString::copy_raw_to_string(&s.lib,ul_url).unwrap();
+               let url = view.state.string_cache.borrow_mut().get_or_copy(&s.lib,ul_url).unwrap();
         }
     }
 
@@ -916,40 +1153,142 @@ impl View {
         }
     }
 
+    /// Only call the callback of [`View::set_add_console_message_callback`]
+    /// for messages with a level at least as severe as `min_level`, other
+    /// messages are dropped before converting them for the callback.
+    ///
+    /// From lowest to highest severity, the levels are
+    /// [`Debug`](ConsoleMessageLevel::Debug), [`Log`](ConsoleMessageLevel::Log)
+    /// and [`Info`](ConsoleMessageLevel::Info) (same severity),
+    /// [`Warning`](ConsoleMessageLevel::Warning) and [`Error`](ConsoleMessageLevel::Error),
+    /// so [`Debug`](ConsoleMessageLevel::Debug) passes all messages.
+    ///
+    /// The filter is shared by all the [`View`]s of the same view, including
+    /// the one given to the callbacks.
+    ///
+    /// (Default = all messages are passed)
+    pub fn set_console_message_filter(&self, min_level: ConsoleMessageLevel) {
+        self.state.console_message_filter.set(Some(min_level));
+    }
+
+    /// Get the minimum level set by [`View::set_console_message_filter`].
+    pub fn console_message_filter(&self) -> Option<ConsoleMessageLevel> {
+        self.state.console_message_filter.get()
+    }
+
+    /// Called when a message is added to the console (useful for errors / debug)
+    ///
+    /// Messages can be filtered by level with [`View::set_console_message_filter`],
+    /// and buffered with [`View::enable_console_capture`], which keeps
+    /// working after setting this callback.
+    ///
+    /// # Callback Arguments
+    /// * `view: &View` - The view that fired the event (eg. self)
+    /// * `message_source: ConsoleMessageSource` - The source of the message
+    /// * `message_level: ConsoleMessageLevel` - The level of the message
+    /// * `message: String` - The message
+    /// * `line_number: i32` - The line number of the message
+    /// * `column_number: i32` - The column number of the message
+    /// * `source_id: String` - The source id of the message
+    pub fn set_add_console_message_callback<F>(&self, callback: F)
+    where
+        F: FnMut(&View, ConsoleMessageSource, ConsoleMessageLevel, String, u32, u32, String)
+            + 'static,
+    {
+        self.state.console_message_callback_set.set(true);
+        self.set_add_console_message_callback_internal(callback);
+    }
+
+    /// Make sure the console message callback is installed, so that the
+    /// messages are captured even if the user didn't set a callback.
+    fn ensure_console_message_hook(&self) {
+        if !self.state.console_message_callback_set.get() {
+            self.state.console_message_callback_set.set(true);
+            self.set_add_console_message_callback_internal(|_, _, _, _, _, _, _| {});
+        }
+    }
+
+    // the public `set_add_console_message_callback` wraps this, since the
+    // messages must still reach the captures of `enable_console_capture`
+    // when the user sets their own callback.
+    fn set_add_console_message_callback_internal<F>(&self, mut callback: F)
+    where
+        F: FnMut(&View, ConsoleMessageSource, ConsoleMessageLevel, String, u32, u32, String)
+            + 'static,
+    {
+        // captured by value, `self` may be moved after this call
+        let lib = self.lib.clone();
+        let state = self.state.clone();
+        self.set_raw_add_console_message_callback(
+            move |ul_view,
+                  ul_message_source,
+                  ul_message_level,
+                  ul_message,
+                  line_number,
+                  column_number,
+                  ul_source_id| {
+                let view = &View::with_state(lib.clone(), ul_view, state.clone());
+                let message_level = ConsoleMessageLevel::try_from(ul_message_level).unwrap();
+                let filtered_out = view
+                    .state
+                    .console_message_filter
+                    .get()
+                    .is_some_and(|min_level| message_level.severity() < min_level.severity());
+                // nothing to convert if no one wants the message
+                if filtered_out && !view.is_console_captured() {
+                    return;
+                }
+                let message_source = ConsoleMessageSource::try_from(ul_message_source).unwrap();
+                let message = unsafe { UlString::copy_raw_to_string(&lib, ul_message) }.unwrap();
+                let source_id =
+                    unsafe { UlString::copy_raw_to_string(&lib, ul_source_id) }.unwrap();
+                if view.is_console_captured() {
+                    view.capture_console_message(&ConsoleMessage {
+                        source: message_source,
+                        level: message_level,
+                        message: message.clone(),
+                        line: line_number,
+                        column: column_number,
+                        source_id: source_id.clone(),
+                        timestamp: std::time::SystemTime::now(),
+                    });
+                }
+                if filtered_out {
+                    return;
+                }
+                callback(
+                    view,
+                    message_source,
+                    message_level,
+                    message,
+                    line_number,
+                    column_number,
+                    source_id,
+                );
+            },
+        );
+    }
+
     set_callback! {
-        /// Called when a message is added to the console (useful for errors / debug)
-        ///
-        /// # Callback Arguments
-        /// * `view: &View` - The view that fired the event (eg. self)
-        /// * `message_source: ConsoleMessageSource` - The source of the message
-        /// * `message_level: ConsoleMessageLevel` - The level of the message
-        /// * `message: String` - The message
-        /// * `line_number: i32` - The line number of the message
-        /// * `column_number: i32` - The column number of the message
-        /// * `source_id: String` - The source id of the message
-        pub fn set_add_console_message_callback(&self, callback: FnMut(
-                view: &View,
-                message_source: ConsoleMessageSource,
-                message_level: ConsoleMessageLevel,
-                message: String,
-                line_number:u32,
-                column_number:u32,
-                source_id: String)) :
-            [View::lib.ultralight()][s] ulViewSetAddConsoleMessageCallback(
+        /// Set the Ultralight console message callback, only used by
+        /// [`View::set_add_console_message_callback_internal`].
+        fn set_raw_add_console_message_callback(&self, callback: FnMut(
+                ul_view: ul_sys::ULView,
+                ul_message_source: ul_sys::ULMessageSource,
+                ul_message_level: ul_sys::ULMessageLevel,
+                ul_message: ul_sys::ULString,
+                line_number: u32,
+                column_number: u32,
+                ul_source_id: ul_sys::ULString)) :
+            [View::lib.ultralight()] ulViewSetAddConsoleMessageCallback(
                ul_view: ul_sys::ULView,
                ul_message_source: ul_sys::ULMessageSource,
                ul_message_level: ul_sys::ULMessageLevel,
                ul_message: ul_sys::ULString,
                line_number: u32,
-               column_number :u32,
+               column_number: u32,
                ul_source_id: ul_sys::ULString
-            ) {
-               let view = &View::from_raw(s.lib.clone(), ul_view).unwrap();
-               let message_source = ConsoleMessageSource::try_from(ul_message_source).unwrap();
-               let message_level = ConsoleMessageLevel::try_from(ul_message_level).unwrap();
-               let message = UlString::copy_raw_to_string(&s.lib,ul_message).unwrap();
-               let source_id = UlString::copy_raw_to_string(&s.lib,ul_source_id).unwrap();
-        }
+            ) {}
     }
 
     set_callback! {
@@ -1033,6 +1372,9 @@ impl View {
         /// * `inspected_url: String` - The url of the page that initiated this request
         ///
         /// You should return [`None`] if you want to block the action.
+        ///
+        /// For local inspectors, [`LocalInspector`](crate::renderer::inspector::LocalInspector)
+        /// handles this callback and keeps the created View alive.
         pub fn set_create_inspector_view_callback(&self, callback: FnMut(
                 view: &View,
                 is_local: bool,
@@ -1139,64 +1481,204 @@ impl View {
         }
     }
 
+    /// Set callback for when the JavaScript window object is reset for a new page load.
+    ///
+    /// This is called before any scripts are executed on the page and is the earliest time to setup any
+    /// initial JavaScript state or bindings.
+    ///
+    /// The document is not guaranteed to be loaded/parsed at this point. If you need to make any
+    /// JavaScript calls that are dependent on DOM elements or scripts on the page, use DOMReady
+    /// instead.
+    ///
+    /// The window object is lazily initialized (this will not be called on pages with no scripts).
+    ///
+    /// # Callback Arguments
+    /// * `view: &View` - The view that fired the event (eg. self)
+    /// * `frame_id: u64` - A unique ID for the frame
+    /// * `is_main_frame: bool` - Whether or not this is the main frame
+    /// * `url: String` - The url that is being loaded
+    /// * `js_context: &JSContext` - The JavaScript context of the view, locked
+    ///   for the duration of the callback, so there is no need to call
+    ///   [`View::lock_js_context`]
+    pub fn set_window_object_ready_callback<F>(&self, callback: F)
+    where
+        F: FnMut(&View, u64, bool, String, &JSContext) + 'static,
+    {
+        self.state.window_object_ready_callback_set.set(true);
+        self.set_window_object_ready_callback_internal(callback);
+    }
+
+    /// Make sure the window object ready callback is installed, so that the
+    /// background throttling is installed again after navigation.
+    pub(crate) fn ensure_window_object_ready_hook(&self) {
+        if !self.state.window_object_ready_callback_set.get() {
+            self.state.window_object_ready_callback_set.set(true);
+            self.set_window_object_ready_callback_internal(|_, _, _, _, _| {});
+        }
+    }
+
+    // the public `set_window_object_ready_callback` wraps this, since we need
+    // the callback to install the background throttling before the scripts
+    // of the new page run.
+    fn set_window_object_ready_callback_internal<F>(&self, mut callback: F)
+    where
+        F: FnMut(&View, u64, bool, String, &JSContext) + 'static,
+    {
+        // captured by value, `self` may be moved after this call
+        let lib = self.lib.clone();
+        let state = self.state.clone();
+        self.set_raw_window_object_ready_callback(
+            move |ul_view, frame_id, is_main_frame, ul_url| {
+                let view = &View::with_state(lib.clone(), ul_view, state.clone());
+                let url = unsafe { UlString::copy_raw_to_string(&lib, ul_url) }.unwrap();
+                if is_main_frame {
+                    let _ = view.apply_background_throttling();
+                }
+                let js_context_guard = view.lock_js_context();
+                callback(view, frame_id, is_main_frame, url, &js_context_guard);
+            },
+        );
+    }
+
     set_callback! {
-        /// Set callback for when the JavaScript window object is reset for a new page load.
-        ///
-        /// This is called before any scripts are executed on the page and is the earliest time to setup any
-        /// initial JavaScript state or bindings.
-        ///
-        /// The document is not guaranteed to be loaded/parsed at this point. If you need to make any
-        /// JavaScript calls that are dependent on DOM elements or scripts on the page, use DOMReady
-        /// instead.
-        ///
-        /// The window object is lazily initialized (this will not be called on pages with no scripts).
-        ///
-        /// # Callback Arguments
-        /// * `view: &View` - The view that fired the event (eg. self)
-        /// * `frame_id: u64` - A unique ID for the frame
-        /// * `is_main_frame: bool` - Whether or not this is the main frame
-        /// * `url: String` - The url that is being loaded
-        pub fn set_window_object_ready_callback(&self, callback: FnMut(
-                view: &View,
+        /// Set the Ultralight window object ready callback, only used by
+        /// [`View::set_window_object_ready_callback_internal`].
+        fn set_raw_window_object_ready_callback(&self, callback: FnMut(
+                ul_view: ul_sys::ULView,
                 frame_id: u64,
                 is_main_frame: bool,
-                url: String)) :
-            [View::lib.ultralight()][s] ulViewSetWindowObjectReadyCallback(
+                ul_url: ul_sys::ULString)) :
+            [View::lib.ultralight()] ulViewSetWindowObjectReadyCallback(
                ul_view: ul_sys::ULView,
                frame_id: u64,
                is_main_frame: bool,
                ul_url: ul_sys::ULString
-            ) {
-               let view = &View::from_raw(s.lib.clone(), ul_view).unwrap();
-               let url = UlString::copy_raw_to_string(&s.lib,ul_url).unwrap();
+            ) {}
+    }
+
+    /// Called when all JavaScript has been parsed and the document is ready.
+    ///
+    /// This is the best time to make any JavaScript calls that are dependent
+    /// on DOM elements or scripts on the page.
+    ///
+    /// # Callback Arguments
+    /// * `view: &View` - The view that fired the event (eg. self)
+    /// * `frame_id: u64` - A unique ID for the frame
+    /// * `is_main_frame: bool` - Whether or not this is the main frame
+    /// * `url: String` - The url that is being loaded
+    /// * `js_context: &JSContext` - The JavaScript context of the view, locked
+    ///   for the duration of the callback, so there is no need to call
+    ///   [`View::lock_js_context`]
+    pub fn set_dom_ready_callback<F>(&self, callback: F)
+    where
+        F: FnMut(&View, u64, bool, String, &JSContext) + 'static,
+    {
+        self.state.dom_ready_callback_set.set(true);
+        self.set_dom_ready_callback_internal(callback);
+    }
+
+    /// Make sure the DOM ready callback is installed, so that the page
+    /// overrides (zoom, background) are applied again after navigation.
+    pub(crate) fn ensure_dom_ready_hook(&self) {
+        if !self.state.dom_ready_callback_set.get() {
+            self.state.dom_ready_callback_set.set(true);
+            self.set_dom_ready_callback_internal(|_, _, _, _, _| {});
+        }
+    }
+
+    /// Evaluate `script` once the DOM of the next page loaded in the main
+    /// frame is ready, returns a [`Receiver`] for the result of
+    /// [`View::evaluate_script`].
+    ///
+    /// The script only runs once, before the callback of
+    /// [`View::set_dom_ready_callback`], which is kept as is. This must be
+    /// called before loading the page, otherwise the script runs on the next
+    /// navigation.
+    ///
+    /// The exception is returned in [`Err`], and failing to create the
+    /// script string is reported as an exception too. If the view is dropped
+    /// before the DOM is ready, the sender is dropped and the [`Receiver`]
+    /// is disconnected.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(renderer: &ul_next::renderer::Renderer, view: &ul_next::view::View) -> Result<(), ul_next::error::CreationError> {
+    /// let title = view.execute_js_on_dom_ready("document.title".to_string());
+    /// view.load_url("https://example.com")?;
+    ///
+    /// loop {
+    ///     renderer.update().unwrap();
+    ///     if let Ok(title) = title.try_recv() {
+    ///         println!("title: {:?}", title);
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_js_on_dom_ready(&self, script: String) -> Receiver<Result<String, String>> {
+        let (sender, receiver) = mpsc::channel();
+        self.state
+            .dom_ready_scripts
+            .borrow_mut()
+            .push((script, sender));
+        self.ensure_dom_ready_hook();
+        receiver
+    }
+
+    /// Run the scripts of [`View::execute_js_on_dom_ready`].
+    fn run_dom_ready_scripts(&self) {
+        // taken out, so that the scripts can queue new ones for the next page
+        let scripts = std::mem::take(&mut *self.state.dom_ready_scripts.borrow_mut());
+        for (script, sender) in scripts {
+            let result = self
+                .evaluate_script(&script)
+                .unwrap_or_else(|err| Err(err.to_string()));
+            // the receiver may have been dropped, nothing to do then
+            let _ = sender.send(result);
         }
     }
 
+    // the public `set_dom_ready_callback` wraps this, since we need the
+    // callback to re-apply the page zoom, scale, background, opacity and
+    // stylesheets after navigation.
+    fn set_dom_ready_callback_internal<F>(&self, mut callback: F)
+    where
+        F: FnMut(&View, u64, bool, String, &JSContext) + 'static,
+    {
+        // captured by value, `self` may be moved after this call
+        let lib = self.lib.clone();
+        let state = self.state.clone();
+        self.set_raw_dom_ready_callback(move |ul_view, frame_id, is_main_frame, ul_url| {
+            let view = &View::with_state(lib.clone(), ul_view, state.clone());
+            let url = unsafe { UlString::copy_raw_to_string(&lib, ul_url) }.unwrap();
+            if is_main_frame {
+                let _ = view.apply_page_zoom();
+                let _ = view.apply_page_scale_factor();
+                let _ = view.apply_content_background_color();
+                let _ = view.apply_content_opacity();
+                let _ = view.apply_stylesheets();
+                view.run_dom_ready_scripts();
+            }
+            let js_context_guard = view.lock_js_context();
+            callback(view, frame_id, is_main_frame, url, &js_context_guard);
+        });
+    }
+
     set_callback! {
-        /// Called when all JavaScript has been parsed and the document is ready.
-        ///
-        /// This is the best time to make any JavaScript calls that are dependent
-        /// on DOM elements or scripts on the page.
-        ///
-        /// # Callback Arguments
-        /// * `view: &View` - The view that fired the event (eg. self)
-        /// * `frame_id: u64` - A unique ID for the frame
-        /// * `is_main_frame: bool` - Whether or not this is the main frame
-        /// * `url: String` - The url that is being loaded
-        pub fn set_dom_ready_callback(&self, callback: FnMut(
-                view: &View,
+        /// Set the Ultralight DOM ready callback, only used by
+        /// [`View::set_dom_ready_callback_internal`].
+        fn set_raw_dom_ready_callback(&self, callback: FnMut(
+                ul_view: ul_sys::ULView,
                 frame_id: u64,
                 is_main_frame: bool,
-                url: String)) :
-            [View::lib.ultralight()][s] ulViewSetDOMReadyCallback(
+                ul_url: ul_sys::ULString)) :
+            [View::lib.ultralight()] ulViewSetDOMReadyCallback(
                ul_view: ul_sys::ULView,
                frame_id: u64,
                is_main_frame: bool,
                ul_url: ul_sys::ULString
-            ) {
-               let view = &View::from_raw(s.lib.clone(), ul_view).unwrap();
-               let url = UlString::copy_raw_to_string(&s.lib, ul_url).unwrap();
-        }
+            ) {}
     }
 
     set_callback! {
@@ -1238,6 +1720,9 @@ impl View {
     /// You must handle [`View::set_create_inspector_view_callback`] so that
     /// the library has a View to display the inspector in. This function will
     /// call the callback only if an inspector view is not currently active.
+    ///
+    /// See [`LocalInspector`](crate::renderer::inspector::LocalInspector)
+    /// for a helper doing both.
     pub fn create_local_inspector_view(&self) {
         unsafe {
             self.lib
@@ -1249,7 +1734,11 @@ impl View {
 
 impl Drop for View {
     fn drop(&mut self) {
+        if let Some(registry) = &self.registry {
+            registry.borrow_mut().retain(|&view| view != self.internal);
+        }
         if self.need_to_destroy {
+            ViewState::forget(self.internal);
             unsafe {
                 self.lib.ultralight().ulDestroyView(self.internal);
             }
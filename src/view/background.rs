@@ -0,0 +1,75 @@
+//! Default background color of the page content.
+use crate::error::CreationError;
+
+use super::View;
+
+impl View {
+    /// Set the color used to fill the page before its own background is
+    /// painted, the color components are `(r, g, b, a)`.
+    ///
+    /// Ultralight doesn't have an API for this, so it is done by inserting a
+    /// stylesheet setting the background of the root element before all other
+    /// stylesheets of the page, so backgrounds set by the page still take
+    /// priority. Since this needs the DOM, the color is applied when the DOM
+    /// is ready (see [`View::set_page_zoom`] for how it is re-applied after
+    /// navigation).
+    pub fn set_content_background_color(
+        &self,
+        r: u8,
+        g: u8,
+        b: u8,
+        a: u8,
+    ) -> Result<(), CreationError> {
+        self.state.content_background_color.set(Some((r, g, b, a)));
+        self.ensure_dom_ready_hook();
+
+        self.apply_content_background_color()
+    }
+
+    /// Get the color set by [`View::set_content_background_color`].
+    ///
+    /// If not set, returns the default, which is transparent for transparent
+    /// views (see [`View::is_transparent`]), and white otherwise.
+    pub fn content_background_color(&self) -> (u8, u8, u8, u8) {
+        self.state
+            .content_background_color
+            .get()
+            .unwrap_or_else(|| {
+                if self.is_transparent() {
+                    (0, 0, 0, 0)
+                } else {
+                    (255, 255, 255, 255)
+                }
+            })
+    }
+
+    /// Apply the background color to the current page.
+    pub(super) fn apply_content_background_color(&self) -> Result<(), CreationError> {
+        let Some((r, g, b, a)) = self.state.content_background_color.get() else {
+            return Ok(());
+        };
+
+        let script = format!(
+            r#"(function () {{
+    var root = document.documentElement;
+    if (!root) return;
+    var style = document.getElementById("__ulNextBackground");
+    if (!style) {{
+        style = document.createElement("style");
+        style.id = "__ulNextBackground";
+        var head = document.head || root;
+        head.insertBefore(style, head.firstChild);
+    }}
+    style.textContent = "html {{ background-color: rgba({}, {}, {}, {}); }}";
+}})();"#,
+            r,
+            g,
+            b,
+            a as f64 / 255.0
+        );
+
+        // the script can't throw
+        let _ = self.evaluate_script(&script)?;
+        Ok(())
+    }
+}
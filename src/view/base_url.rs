@@ -0,0 +1,69 @@
+//! Loading HTML strings with a base URL, implemented by injecting a
+//! `<base>` element into the document.
+use crate::error::CreationError;
+
+use super::{post::escape_html, View};
+
+impl View {
+    /// Load a raw string of HTML like [`View::load_html`], resolving the
+    /// relative URLs in it (images, stylesheets, scripts and links) against
+    /// `base_url`.
+    ///
+    /// `base_url` can be any absolute URL, for example `file:///assets/`
+    /// to load resources from the file system (see
+    /// [`platform::set_filesystem`](crate::platform::set_filesystem)), or
+    /// a custom scheme URL. Note that like browsers, the last path segment
+    /// of `base_url` is dropped if it doesn't end with `/`.
+    ///
+    /// Ultralight doesn't have an API to set the base URL of an HTML string,
+    /// so this inserts `<base href="base_url">` at the start of the `<head>`
+    /// of the document (creating it if needed) before loading it. The URL of
+    /// the page is still `about:blank`.
+    pub fn load_html_with_base_url(&self, html: &str, base_url: &str) -> Result<(), CreationError> {
+        self.load_html(&inject_base_url(html, base_url))
+    }
+}
+
+/// Find the end (after `>`) of the first `<tag ...>` in `html`, the tag name
+/// is matched case insensitively.
+fn find_tag_end(html: &str, tag: &str) -> Option<usize> {
+    let bytes = html.as_bytes();
+    let mut search_from = 0;
+    while let Some(offset) = html[search_from..].find('<') {
+        let start = search_from + offset + 1;
+        let name_end = start + tag.len();
+        search_from = start;
+
+        let is_tag = bytes
+            .get(start..name_end)
+            .is_some_and(|name| name.eq_ignore_ascii_case(tag.as_bytes()))
+            && bytes
+                .get(name_end)
+                .is_some_and(|&c| c == b'>' || c == b'/' || c.is_ascii_whitespace());
+        if is_tag {
+            return html[name_end..].find('>').map(|end| name_end + end + 1);
+        }
+    }
+    None
+}
+
+/// Insert a `<base>` element with `base_url` into the `<head>` of `html`.
+fn inject_base_url(html: &str, base_url: &str) -> String {
+    let base = format!("<base href=\"{}\">", escape_html(base_url));
+
+    let (position, insert) = if let Some(head_end) = find_tag_end(html, "head") {
+        (head_end, base)
+    } else if let Some(html_end) = find_tag_end(html, "html") {
+        (html_end, format!("<head>{base}</head>"))
+    } else {
+        // the parser creates the `<head>`, but the element must come after
+        // the doctype to not switch the page to quirks mode
+        (find_tag_end(html, "!doctype").unwrap_or(0), base)
+    };
+
+    let mut result = String::with_capacity(html.len() + insert.len());
+    result.push_str(&html[..position]);
+    result.push_str(&insert);
+    result.push_str(&html[position..]);
+    result
+}
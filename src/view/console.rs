@@ -0,0 +1,172 @@
+//! Buffering the console messages of a view, see
+//! [`View::enable_console_capture`].
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard},
+    time::SystemTime,
+};
+
+use super::{ConsoleMessageLevel, ConsoleMessageSource, View};
+
+/// A message added to the console of a view, captured by a
+/// [`ConsoleCapture`].
+#[derive(Clone, Debug)]
+pub struct ConsoleMessage {
+    /// The source of the message.
+    pub source: ConsoleMessageSource,
+    /// The level of the message.
+    pub level: ConsoleMessageLevel,
+    /// The message.
+    pub message: String,
+    /// The line number of the message.
+    pub line: u32,
+    /// The column number of the message.
+    pub column: u32,
+    /// The source id of the message (usually the URL of the script).
+    pub source_id: String,
+    /// When the message was received.
+    pub timestamp: SystemTime,
+}
+
+struct CaptureState {
+    messages: VecDeque<ConsoleMessage>,
+    capacity: usize,
+    min_level: Option<ConsoleMessageLevel>,
+}
+
+/// A ring buffer of the console messages of a view, created with
+/// [`View::enable_console_capture`].
+///
+/// The handle can be cloned and sent to other threads, all the clones share
+/// the same buffer. When the buffer is full, the oldest message is dropped.
+#[derive(Clone)]
+pub struct ConsoleCapture {
+    state: Arc<Mutex<CaptureState>>,
+}
+
+impl ConsoleCapture {
+    fn new(capacity: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CaptureState {
+                messages: VecDeque::with_capacity(capacity),
+                capacity,
+                min_level: None,
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CaptureState> {
+        // the state is always consistent, even if a thread panicked
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Add `message` to the buffer, if it passes the level filter.
+    fn push(&self, message: &ConsoleMessage) {
+        let mut state = self.lock();
+        if state.capacity == 0 {
+            return;
+        }
+        if let Some(min_level) = state.min_level {
+            if message.level.severity() < min_level.severity() {
+                return;
+            }
+        }
+        if state.messages.len() == state.capacity {
+            state.messages.pop_front();
+        }
+        state.messages.push_back(message.clone());
+    }
+
+    /// Remove and return all the buffered messages, oldest first.
+    pub fn drain(&self) -> Vec<ConsoleMessage> {
+        self.lock().messages.drain(..).collect()
+    }
+
+    /// Iterate over a copy of the buffered messages, oldest first, without
+    /// removing them.
+    pub fn iter(&self) -> impl Iterator<Item = ConsoleMessage> {
+        self.lock().messages.clone().into_iter()
+    }
+
+    /// Remove all the buffered messages.
+    pub fn clear(&self) {
+        self.lock().messages.clear();
+    }
+
+    /// The number of buffered messages.
+    pub fn len(&self) -> usize {
+        self.lock().messages.len()
+    }
+
+    /// Whether there are no buffered messages.
+    pub fn is_empty(&self) -> bool {
+        self.lock().messages.is_empty()
+    }
+
+    /// The maximum number of buffered messages.
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    /// Only capture messages with a level at least as severe as `min_level`
+    /// (see [`View::set_console_message_filter`] for the order of the
+    /// levels), [`None`] captures all of them.
+    ///
+    /// Messages already in the buffer are kept.
+    pub fn set_level_filter(&self, min_level: Option<ConsoleMessageLevel>) {
+        self.lock().min_level = min_level;
+    }
+
+    /// The minimum level set by [`ConsoleCapture::set_level_filter`].
+    pub fn level_filter(&self) -> Option<ConsoleMessageLevel> {
+        self.lock().min_level
+    }
+}
+
+impl View {
+    /// Start buffering the console messages of this view, keeping the last
+    /// `capacity` messages.
+    ///
+    /// This works alongside [`View::set_add_console_message_callback`], the
+    /// callback still receives the messages, whether it is set before or
+    /// after. The filter of [`View::set_console_message_filter`] only
+    /// applies to the callback, use [`ConsoleCapture::set_level_filter`] to
+    /// filter the captured messages.
+    ///
+    /// Calling this again creates another independent capture, the messages
+    /// are captured until the view is destroyed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(view: &ul_next::view::View) -> Result<(), ul_next::error::CreationError> {
+    /// let console = view.enable_console_capture(100);
+    /// view.evaluate_script("console.warn('careful')")?;
+    ///
+    /// for message in console.drain() {
+    ///     println!("[{:?}] {}", message.level, message.message);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enable_console_capture(&self, capacity: usize) -> ConsoleCapture {
+        let capture = ConsoleCapture::new(capacity);
+        self.state
+            .console_captures
+            .borrow_mut()
+            .push(capture.clone());
+        self.ensure_console_message_hook();
+        capture
+    }
+
+    /// Whether a capture was enabled with [`View::enable_console_capture`].
+    pub(super) fn is_console_captured(&self) -> bool {
+        !self.state.console_captures.borrow().is_empty()
+    }
+
+    /// Add `message` to all the captures of this view.
+    pub(super) fn capture_console_message(&self, message: &ConsoleMessage) {
+        for capture in self.state.console_captures.borrow().iter() {
+            capture.push(message);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Reading and writing the cookies of the page loaded in a view, see
+//! [`View::cookies`].
+//!
+//! Ultralight doesn't expose the cookie store of a session in its C API, so
+//! the cookies are accessed with `document.cookie` in the page.
+use std::{fmt, time::Duration};
+
+use crate::error::CreationError;
+
+use super::{find::js_string_literal, View};
+
+/// Errors that can occur in [`View::cookies`] and [`View::set_cookie`].
+#[derive(Debug, thiserror::Error)]
+pub enum CookieError {
+    /// The script accessing the cookies threw an exception, for example
+    /// when the page has no origin that can store cookies (eg. pages loaded
+    /// with [`View::load_html`], see [`View::load_html_with_base_url`]).
+    #[error("Cookie script failed: {0}")]
+    ScriptException(String),
+    /// The cookie can't be written, its name or value contains characters
+    /// not allowed in cookies.
+    #[error("Invalid cookie `{0}`")]
+    InvalidCookie(String),
+    /// Failed to create the script string or to read its result.
+    #[error("{0}")]
+    CreationError(#[from] CreationError),
+}
+
+/// The `SameSite` attribute of a [`Cookie`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CookieSameSite {
+    /// The cookie is only sent with requests from the same site.
+    Strict,
+    /// The cookie is also sent when navigating to the site from another one.
+    Lax,
+    /// The cookie is sent with all requests, requires [`Cookie::secure`].
+    None,
+}
+
+/// A cookie of a page, see [`View::cookies`] and [`View::set_cookie`].
+///
+/// The attributes other than the name and value are only used when writing
+/// a cookie, they are [`None`] (or `false`) in the cookies read from a
+/// page, as `document.cookie` doesn't report them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    /// The name of the cookie.
+    pub name: String,
+    /// The value of the cookie.
+    pub value: String,
+    /// The domain the cookie is sent to, [`None`] for the host of the page
+    /// only.
+    pub domain: Option<String>,
+    /// The path the cookie is sent to, [`None`] for the path of the page.
+    pub path: Option<String>,
+    /// How long the cookie is kept, [`None`] for a session cookie (removed
+    /// when the session is destroyed). A duration of zero removes the
+    /// cookie.
+    pub max_age: Option<Duration>,
+    /// Whether the cookie is only sent over HTTPS.
+    pub secure: bool,
+    /// The `SameSite` attribute, [`None`] for the default of the engine.
+    pub same_site: Option<CookieSameSite>,
+}
+
+impl Cookie {
+    /// Create a session cookie with `name` and `value`, without attributes.
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: None,
+            path: None,
+            max_age: None,
+            secure: false,
+            same_site: None,
+        }
+    }
+
+    /// Parse the value of `document.cookie` (`name=value` pairs separated
+    /// by `;`).
+    pub fn parse_list(cookies: &str) -> Vec<Cookie> {
+        cookies
+            .split(';')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((name, value)) => Cookie::new(name.trim(), value.trim()),
+                // a cookie without `=` has an empty name
+                None => Cookie::new("", pair),
+            })
+            .collect()
+    }
+
+    fn validate(&self) -> Result<(), CookieError> {
+        let invalid_char = |c: char| c == ';' || c.is_control();
+        let invalid = self.name.contains(|c: char| invalid_char(c) || c == '=')
+            || self.name.trim() != self.name
+            || self.value.contains(invalid_char)
+            || [&self.domain, &self.path]
+                .into_iter()
+                .flatten()
+                .any(|attribute| attribute.contains(invalid_char));
+        if invalid {
+            Err(CookieError::InvalidCookie(self.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Formats the cookie as in a `Set-Cookie` header, eg.
+/// `id=42; Path=/; Max-Age=3600; Secure`.
+impl fmt::Display for Cookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)?;
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain={}", domain)?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, "; Path={}", path)?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age.as_secs())?;
+        }
+        if self.secure {
+            f.write_str("; Secure")?;
+        }
+        match self.same_site {
+            Some(CookieSameSite::Strict) => f.write_str("; SameSite=Strict"),
+            Some(CookieSameSite::Lax) => f.write_str("; SameSite=Lax"),
+            Some(CookieSameSite::None) => f.write_str("; SameSite=None"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl View {
+    /// Get the cookies of the page loaded in the view, as seen by its
+    /// scripts.
+    ///
+    /// Cookies are stored in the [`Session`](crate::renderer::Session) of
+    /// the view, so views sharing a session see the same cookies.
+    ///
+    /// # Limitations
+    /// Only the cookies visible to `document.cookie` are returned: the ones
+    /// matching the URL of the page, without `HttpOnly` cookies (usually
+    /// the session cookies set by a login flow), and only with their name
+    /// and value. JavaScript must be enabled in the view.
+    pub fn cookies(&self) -> Result<Vec<Cookie>, CookieError> {
+        let cookies = self
+            .evaluate_script("document.cookie")?
+            .map_err(CookieError::ScriptException)?;
+        Ok(Cookie::parse_list(&cookies))
+    }
+
+    /// Set a cookie for the page loaded in the view, as if it was set by
+    /// its scripts.
+    ///
+    /// The cookie is ignored by the engine (without an error) if it doesn't
+    /// match the URL of the page (eg. another [`Cookie::domain`]), or if
+    /// [`Cookie::secure`] is set and the page is not loaded over HTTPS. See
+    /// [`View::cookies`] for the limitations.
+    pub fn set_cookie(&self, cookie: Cookie) -> Result<(), CookieError> {
+        cookie.validate()?;
+
+        let script = format!(
+            "document.cookie = {}; true",
+            js_string_literal(&cookie.to_string())
+        );
+        self.evaluate_script(&script)?
+            .map_err(CookieError::ScriptException)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+//! Loading content of any MIME type from memory, see [`View::load_data`].
+use crate::error::CreationError;
+
+use super::View;
+
+impl View {
+    /// Load `data` as a document of type `mime_type`, like the response
+    /// of a request with `Content-Type: {mime_type}; charset={charset}`.
+    ///
+    /// `charset` can be empty, in that case the default of the MIME type is
+    /// used. `base_url` is used to resolve the relative URLs of HTML
+    /// documents (see [`View::load_html_with_base_url`]), it can be empty.
+    ///
+    /// Ultralight can only load HTML strings directly, so UTF-8 HTML is
+    /// loaded with [`View::load_html_with_base_url`], and everything else
+    /// (other types, or HTML in other charsets) is loaded from a base64
+    /// `data:` URL. In that case `base_url` is not used, and the document
+    /// has an opaque origin, so for example it can't access local storage
+    /// or load `file:///` resources.
+    ///
+    /// Returns [`CreationError::UnsupportedContentType`] if `mime_type` is
+    /// not a valid `type/subtype` pair (without parameters), or `charset`
+    /// is not a valid charset name.
+    pub fn load_data(
+        &self,
+        data: &[u8],
+        mime_type: &str,
+        charset: &str,
+        base_url: &str,
+    ) -> Result<(), CreationError> {
+        if !is_mime_type(mime_type) || !charset.bytes().all(is_token_byte) {
+            return Err(CreationError::UnsupportedContentType(format!(
+                "{mime_type}; charset={charset}"
+            )));
+        }
+
+        let is_utf8 = charset.is_empty()
+            || charset.eq_ignore_ascii_case("utf-8")
+            || charset.eq_ignore_ascii_case("utf8");
+        if is_utf8 && mime_type.eq_ignore_ascii_case("text/html") {
+            if let Ok(html) = std::str::from_utf8(data) {
+                return if base_url.is_empty() {
+                    self.load_html(html)
+                } else {
+                    self.load_html_with_base_url(html, base_url)
+                };
+            }
+        }
+
+        let mut url = format!("data:{mime_type}");
+        if !charset.is_empty() {
+            url.push_str(";charset=");
+            url.push_str(charset);
+        }
+        url.push_str(";base64,");
+        base64_encode(data, &mut url);
+        self.load_url(&url)
+    }
+}
+
+/// Whether `byte` can be used in a MIME type or parameter token (RFC 2045).
+fn is_token_byte(byte: u8) -> bool {
+    byte.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?=".contains(&byte)
+}
+
+/// Whether `mime_type` is a `type/subtype` pair.
+fn is_mime_type(mime_type: &str) -> bool {
+    mime_type.split_once('/').is_some_and(|(ty, subtype)| {
+        !ty.is_empty()
+            && !subtype.is_empty()
+            && ty.bytes().all(is_token_byte)
+            && subtype.bytes().all(is_token_byte)
+    })
+}
+
+/// Append the standard base64 encoding (with padding) of `data` to `out`.
+fn base64_encode(data: &[u8], out: &mut String) {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    out.reserve((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Firing HTML5 drag and drop events, implemented by dispatching DOM
+//! events with JavaScript since Ultralight doesn't support drag and drop.
+use crate::{error::CreationError, event::DragEvent};
+
+use super::{find::js_string_literal, View};
+
+/// Errors that can occur in [`View::fire_drag_event`].
+#[derive(Debug, thiserror::Error)]
+pub enum DragError {
+    /// The script used to dispatch the event threw an exception, for example
+    /// when JavaScript is disabled in the view.
+    #[error("Drag event script failed: {0}")]
+    ScriptException(String),
+    /// The result of the script couldn't be parsed.
+    #[error("Invalid drag event result `{0}`")]
+    InvalidResult(String),
+    /// Failed to create the script string or to read its result.
+    #[error("{0}")]
+    CreationError(#[from] CreationError),
+}
+
+/// Dispatches a drag event of `type` at (`x`, `y`) with the `data` of the
+/// `dataTransfer`, returns the result of `dispatchEvent`.
+///
+/// `DataTransfer` can't be constructed in all WebKit versions, in that case
+/// a plain object with the same methods is used instead.
+const DISPATCH_FN: &str = r#"function(type, x, y, data) {
+  var target = document.elementFromPoint(x, y) || document.body || document.documentElement;
+  if (!target) {
+    return true;
+  }
+
+  var transfer = null;
+  var isNative = false;
+  if (data) {
+    try {
+      transfer = new DataTransfer();
+      for (var format in data) {
+        transfer.setData(format, data[format]);
+      }
+      isNative = true;
+    } catch (e) {
+      transfer = {
+        dropEffect: 'none',
+        effectAllowed: 'all',
+        files: [],
+        items: [],
+        types: Object.keys(data),
+        getData: function(format) {
+          return Object.prototype.hasOwnProperty.call(data, format) ? data[format] : '';
+        },
+        setData: function(format, value) {
+          data[format] = String(value);
+          this.types = Object.keys(data);
+        },
+        clearData: function(format) {
+          if (format === undefined) {
+            data = {};
+          } else {
+            delete data[format];
+          }
+          this.types = Object.keys(data);
+        },
+        setDragImage: function() {}
+      };
+    }
+  }
+
+  var init = {
+    bubbles: true,
+    cancelable: type !== 'dragleave' && type !== 'dragend',
+    composed: true,
+    view: window,
+    clientX: x,
+    clientY: y,
+    screenX: x,
+    screenY: y,
+    dataTransfer: isNative ? transfer : null
+  };
+  var event;
+  try {
+    event = new DragEvent(type, init);
+  } catch (e) {
+    event = new MouseEvent(type, init);
+  }
+  if (transfer && event.dataTransfer !== transfer) {
+    Object.defineProperty(event, 'dataTransfer', { value: transfer });
+  }
+  return target.dispatchEvent(event);
+}"#;
+
+impl View {
+    /// Fire a drag and drop event, at the element under the position of the
+    /// event.
+    ///
+    /// Returns `false` if the page canceled the event with
+    /// `preventDefault()`, for example a drop target cancels `dragover` to
+    /// accept the drop, and `true` otherwise.
+    ///
+    /// Note that JavaScript must be enabled in the view
+    /// (see [`ViewConfigBuilder::enable_javascript`](super::ViewConfigBuilder::enable_javascript)).
+    pub fn fire_drag_event(&self, drag_event: DragEvent) -> Result<bool, DragError> {
+        let data = match &drag_event.data_transfer {
+            Some(data) => {
+                let fields = data
+                    .iter()
+                    .map(|(format, value)| {
+                        format!(
+                            "{}: {}",
+                            js_string_literal(format),
+                            js_string_literal(value)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", fields.join(", "))
+            }
+            None => "null".to_string(),
+        };
+        let script = format!(
+            "({})({}, {}, {}, {})",
+            DISPATCH_FN,
+            js_string_literal(drag_event.ty.dom_name()),
+            drag_event.x,
+            drag_event.y,
+            data
+        );
+
+        let result = self
+            .evaluate_script(&script)?
+            .map_err(DragError::ScriptException)?;
+        match result.trim() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(DragError::InvalidResult(result)),
+        }
+    }
+}
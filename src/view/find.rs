@@ -0,0 +1,215 @@
+//! Reading the text of the page and finding text in it, implemented with
+//! JavaScript since Ultralight doesn't provide a find API.
+use crate::error::CreationError;
+
+use super::View;
+
+/// Errors that can occur in [`View::inner_text`], [`View::find_in_page`],
+/// [`View::scroll_to_match`] and [`View::clear_find`].
+#[derive(Debug, thiserror::Error)]
+pub enum FindError {
+    /// The script threw an exception, for example when JavaScript is
+    /// disabled in the view.
+    #[error("Find script failed: {0}")]
+    ScriptException(String),
+    /// The result of the script couldn't be parsed.
+    #[error("Invalid find result `{0}`")]
+    InvalidResult(String),
+    /// The match index given to [`View::scroll_to_match`] is out of range.
+    #[error("Match index {index} is out of range, the page has {count} matches")]
+    MatchOutOfRange { index: u32, count: u32 },
+    /// Failed to create the script string or to read its result.
+    #[error("{0}")]
+    CreationError(#[from] CreationError),
+}
+
+/// Options for [`View::find_in_page`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FindOptions {
+    /// Whether letter case must match, `false` by default.
+    pub case_sensitive: bool,
+    /// Whether only whole words should match, `false` by default.
+    ///
+    /// Word boundaries are the same as `\b` in JavaScript regular
+    /// expressions, so this has no effect at the edges of the needle that
+    /// are not word characters (`[A-Za-z0-9_]`).
+    pub whole_word: bool,
+}
+
+/// Removes the highlights added by [`FIND_FN`], returns the number of
+/// highlights removed.
+const CLEAR_FN: &str = r#"function() {
+  var marks = document.querySelectorAll('mark[data-ul-find]');
+  for (var i = 0; i < marks.length; i++) {
+    var mark = marks[i];
+    var parent = mark.parentNode;
+    parent.replaceChild(document.createTextNode(mark.textContent), mark);
+    parent.normalize();
+  }
+  return marks.length;
+}"#;
+
+/// Highlights the matches of `needle` with `<mark data-ul-find="index">`
+/// elements, returns the number of matches.
+const FIND_FN: &str = r#"function(needle, caseSensitive, wholeWord) {
+  var root = document.body || document.documentElement;
+  if (!root || needle.length === 0) {
+    return 0;
+  }
+
+  var pattern = needle.replace(/[.*+?^${}()|[\]\\\/]/g, '\\$&');
+  if (wholeWord) {
+    pattern = '\\b' + pattern + '\\b';
+  }
+  var regex = new RegExp(pattern, caseSensitive ? 'g' : 'gi');
+
+  var walker = document.createTreeWalker(root, NodeFilter.SHOW_TEXT, {
+    acceptNode: function(node) {
+      var parent = node.parentNode;
+      if (!parent || /^(SCRIPT|STYLE|NOSCRIPT|TEXTAREA)$/.test(parent.nodeName)) {
+        return NodeFilter.FILTER_REJECT;
+      }
+      return NodeFilter.FILTER_ACCEPT;
+    }
+  });
+  var nodes = [];
+  while (walker.nextNode()) {
+    nodes.push(walker.currentNode);
+  }
+
+  var count = 0;
+  for (var i = 0; i < nodes.length; i++) {
+    var node = nodes[i];
+    var text = node.nodeValue;
+    var last = 0;
+    var fragment = null;
+    var match;
+    regex.lastIndex = 0;
+    while ((match = regex.exec(text)) !== null) {
+      fragment = fragment || document.createDocumentFragment();
+      fragment.appendChild(document.createTextNode(text.slice(last, match.index)));
+      var mark = document.createElement('mark');
+      mark.setAttribute('data-ul-find', count++);
+      mark.style.cssText = 'background: #ffeb3b; color: inherit;';
+      mark.textContent = match[0];
+      fragment.appendChild(mark);
+      last = match.index + match[0].length;
+    }
+    if (fragment) {
+      fragment.appendChild(document.createTextNode(text.slice(last)));
+      node.parentNode.replaceChild(fragment, node);
+    }
+  }
+  return count;
+}"#;
+
+/// Scrolls to the match at `index` and marks it as the current one,
+/// returns the number of matches.
+const SCROLL_TO_MATCH_FN: &str = r#"function(index) {
+  var marks = document.querySelectorAll('mark[data-ul-find]');
+  if (index >= marks.length) {
+    return marks.length;
+  }
+  for (var i = 0; i < marks.length; i++) {
+    marks[i].style.background = i === index ? '#ff9632' : '#ffeb3b';
+  }
+  try {
+    marks[index].scrollIntoView({ block: 'center', inline: 'nearest' });
+  } catch (e) {
+    marks[index].scrollIntoView();
+  }
+  return marks.length;
+}"#;
+
+const INNER_TEXT_SCRIPT: &str = r#"(function() {
+  var root = document.body || document.documentElement;
+  return root ? root.innerText : '';
+})()"#;
+
+impl View {
+    /// Get the rendered text of the page (`document.body.innerText`).
+    ///
+    /// Returns an empty string if the page has no content.
+    pub fn inner_text(&self) -> Result<String, FindError> {
+        self.run_find_script(INNER_TEXT_SCRIPT)
+    }
+
+    /// Find and highlight all the occurrences of `needle` in the text of the
+    /// page, returns the number of matches.
+    ///
+    /// The highlights of a previous call are removed first, they can also be
+    /// removed with [`View::clear_find`]. The matches are numbered in document
+    /// order, and can be scrolled to with [`View::scroll_to_match`].
+    ///
+    /// `needle` is matched literally, and matches spanning multiple elements
+    /// (eg. `foo <b>bar</b>`) are not found. The highlights are `<mark>`
+    /// elements inserted in the page, which may affect the styling of pages
+    /// depending on the exact structure of the document.
+    pub fn find_in_page(&self, needle: &str, options: FindOptions) -> Result<u32, FindError> {
+        let script = format!(
+            "({})(); ({})({}, {}, {})",
+            CLEAR_FN,
+            FIND_FN,
+            js_string_literal(needle),
+            options.case_sensitive,
+            options.whole_word
+        );
+        let count = self.run_find_script(&script)?;
+        parse_count(count)
+    }
+
+    /// Scroll to the match at `index` of the last [`View::find_in_page`], and
+    /// highlight it as the current match.
+    ///
+    /// Returns [`FindError::MatchOutOfRange`] if there is no match at `index`.
+    pub fn scroll_to_match(&self, index: u32) -> Result<(), FindError> {
+        let script = format!("({})({})", SCROLL_TO_MATCH_FN, index);
+        let count = parse_count(self.run_find_script(&script)?)?;
+
+        if index < count {
+            Ok(())
+        } else {
+            Err(FindError::MatchOutOfRange { index, count })
+        }
+    }
+
+    /// Remove the highlights added by [`View::find_in_page`].
+    pub fn clear_find(&self) -> Result<(), FindError> {
+        self.run_find_script(&format!("({})()", CLEAR_FN))
+            .map(|_| ())
+    }
+
+    fn run_find_script(&self, script: &str) -> Result<String, FindError> {
+        self.evaluate_script(script)?
+            .map_err(FindError::ScriptException)
+    }
+}
+
+fn parse_count(count: String) -> Result<u32, FindError> {
+    count
+        .trim()
+        .parse()
+        .map_err(|_| FindError::InvalidResult(count))
+}
+
+/// Quote `s` as a JavaScript string literal.
+pub(super) fn js_string_literal(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            // line terminators in JavaScript, but not in JSON
+            '\u{2028}' => result.push_str("\\u2028"),
+            '\u{2029}' => result.push_str("\\u2029"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
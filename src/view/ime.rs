@@ -0,0 +1,183 @@
+//! Inserting text and IME compositions in the focused element.
+//!
+//! Ultralight doesn't expose the input method API of WebCore, so only the
+//! final text can be delivered with key events (see [`View::insert_text`]).
+//! The composition helpers ([`View::ime_set_composition`] and
+//! [`View::ime_commit`]) edit the focused element with JavaScript instead.
+use crate::{
+    error::CreationError,
+    event::{KeyEvent, KeyEventCreationInfo, KeyEventModifiers, KeyEventType},
+    key_code::VirtualKeyCode,
+};
+
+use super::{find::js_string_literal, View};
+
+/// Errors that can occur in [`View::ime_set_composition`] and
+/// [`View::ime_commit`].
+#[derive(Debug, thiserror::Error)]
+pub enum ImeError {
+    /// The script used to edit the element threw an exception, for example
+    /// when JavaScript is disabled in the view.
+    #[error("IME script failed: {0}")]
+    ScriptException(String),
+    /// The result of the script couldn't be parsed.
+    #[error("Invalid IME script result `{0}`")]
+    InvalidResult(String),
+    /// Failed to create the script string or to read its result.
+    #[error("{0}")]
+    CreationError(#[from] CreationError),
+}
+
+/// Replaces the current composition of the focused element (or its
+/// selection if there is none) with `text`, and keeps `text` as the
+/// composition unless `commit` is `true`.
+///
+/// Returns `false` if no editable element is focused.
+const COMPOSE_FN: &str = r#"function(text, commit) {
+  var el = document.activeElement;
+  var state = window.__ulNextImeComposition;
+  if (!state || state.element !== el) {
+    state = null;
+  }
+  window.__ulNextImeComposition = null;
+
+  var isField = el && typeof el.setSelectionRange === 'function' && typeof el.setRangeText === 'function';
+  if (!el || (!isField && !el.isContentEditable)) {
+    return false;
+  }
+
+  function fire(type) {
+    var event;
+    try {
+      event = new CompositionEvent(type, { bubbles: true, cancelable: true, data: text });
+    } catch (e) {
+      event = new Event(type, { bubbles: true, cancelable: true });
+      event.data = text;
+    }
+    el.dispatchEvent(event);
+  }
+
+  if (!state) {
+    fire('compositionstart');
+  }
+  fire('compositionupdate');
+
+  var next = null;
+  if (isField) {
+    var start = state ? state.start : el.selectionStart;
+    var end = state ? state.start + state.length : el.selectionEnd;
+    el.setSelectionRange(start, end);
+    if (!document.execCommand('insertText', false, text)) {
+      el.setRangeText(text, start, end, 'end');
+      el.dispatchEvent(new Event('input', { bubbles: true }));
+    }
+    next = { element: el, start: start, length: text.length };
+  } else {
+    var selection = window.getSelection();
+    if (state) {
+      selection.removeAllRanges();
+      selection.addRange(state.range);
+    }
+    if (!selection.rangeCount) {
+      return false;
+    }
+    var begin = selection.getRangeAt(0).cloneRange();
+    begin.collapse(true);
+    document.execCommand('insertText', false, text);
+    var range = document.createRange();
+    range.setStart(begin.startContainer, begin.startOffset);
+    var caret = selection.getRangeAt(0);
+    range.setEnd(caret.endContainer, caret.endOffset);
+    next = { element: el, range: range };
+  }
+
+  if (commit) {
+    fire('compositionend');
+  } else {
+    window.__ulNextImeComposition = next;
+  }
+  return true;
+}"#;
+
+impl View {
+    /// Insert `text` in the focused element, as if it was typed.
+    ///
+    /// This fires a single [`KeyEventType::Char`] event carrying the whole
+    /// text, which is how the text committed by an input method should be
+    /// delivered. Does nothing if `text` is empty.
+    pub fn insert_text(&self, text: &str) -> Result<(), CreationError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let event = KeyEvent::new(
+            self.lib.clone(),
+            KeyEventCreationInfo {
+                ty: KeyEventType::Char,
+                modifiers: KeyEventModifiers {
+                    alt: false,
+                    ctrl: false,
+                    meta: false,
+                    shift: false,
+                },
+                virtual_key_code: VirtualKeyCode::Unknown,
+                native_key_code: 0,
+                text,
+                unmodified_text: text,
+                is_keypad: false,
+                is_auto_repeat: false,
+                is_system_key: false,
+            },
+        )?;
+        self.fire_key_event(event);
+        Ok(())
+    }
+
+    /// Show `text` as the current IME composition (the preedit text) in the
+    /// focused element.
+    ///
+    /// The first call replaces the selection of the element, the next ones
+    /// replace the text of the previous call, until [`View::ime_commit`]
+    /// is called or the focus moves to another element. `compositionstart`
+    /// and `compositionupdate` events are dispatched to the element.
+    ///
+    /// Returns `false` if no editable element (`<input>`, `<textarea>` or
+    /// `contenteditable`) is focused.
+    ///
+    /// # Limitations
+    /// Ultralight has no API for compositions, so this edits the element
+    /// with JavaScript: the composition is inserted as normal text (without
+    /// the underline of native input methods), `input` events are fired for
+    /// each update, and the position of the candidate window of the input
+    /// method is not reported. JavaScript must be enabled in the view
+    /// (see [`ViewConfigBuilder::enable_javascript`](super::ViewConfigBuilder::enable_javascript)).
+    pub fn ime_set_composition(&self, text: &str) -> Result<bool, ImeError> {
+        self.run_compose_script(text, false)
+    }
+
+    /// Replace the current IME composition of the focused element with
+    /// `text`, and end the composition (dispatching `compositionend`).
+    ///
+    /// If there is no composition, `text` is inserted at the selection.
+    /// Committing an empty text removes the composition, which can be used
+    /// to cancel it.
+    ///
+    /// Returns `false` if no editable element is focused, see
+    /// [`View::ime_set_composition`] for the limitations.
+    pub fn ime_commit(&self, text: &str) -> Result<bool, ImeError> {
+        self.run_compose_script(text, true)
+    }
+
+    fn run_compose_script(&self, text: &str, commit: bool) -> Result<bool, ImeError> {
+        let script = format!("({})({}, {})", COMPOSE_FN, js_string_literal(text), commit);
+
+        let result = self
+            .evaluate_script(&script)?
+            .map_err(ImeError::ScriptException)?;
+        match result.trim() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(ImeError::InvalidResult(result)),
+        }
+    }
+}
@@ -0,0 +1,421 @@
+//! Converting the mouse input of a host window to the events of a view,
+//! see [`InputForwarder`], and reducing the number of events fired, see
+//! [`InputCoalescer`].
+use std::cell::RefCell;
+
+use crate::{
+    error::CreationError,
+    event::{MouseButton, MouseEvent, MouseEventType, ScrollEvent, ScrollEventType},
+};
+
+use super::View;
+
+/// Convert window coordinates to view pixel coordinates.
+fn window_to_view(x: f64, y: f64, window_scale: f64, view_origin: (i32, i32)) -> (i32, i32) {
+    let x = (x * window_scale).floor() as i32;
+    let y = (y * window_scale).floor() as i32;
+    (
+        x.saturating_sub(view_origin.0),
+        y.saturating_sub(view_origin.1),
+    )
+}
+
+impl View {
+    /// Convert a position in a host window to the coordinates expected by
+    /// [`View::fire_mouse_event`].
+    ///
+    /// # Arguments
+    /// * `x`, `y` - The position in the window, in logical (scaled)
+    ///   coordinates, as reported by most windowing libraries (eg.
+    ///   `winit::dpi::LogicalPosition` or SDL mouse events).
+    /// * `window_scale` - The scale of the window, the ratio between physical
+    ///   pixels and logical coordinates, use `1.0` if `x` and `y` are already
+    ///   in physical pixels.
+    /// * `view_origin` - The position of the top-left corner of the view in
+    ///   the window, in physical pixels.
+    ///
+    /// The result may be outside of the view, see [`InputForwarder`] for a
+    /// helper handling that.
+    pub fn window_to_view_coords(
+        &self,
+        x: f64,
+        y: f64,
+        window_scale: f64,
+        view_origin: (i32, i32),
+    ) -> (i32, i32) {
+        window_to_view(x, y, window_scale, view_origin)
+    }
+}
+
+/// The receiver of the events of an [`InputForwarder`].
+///
+/// This is implemented by [`View`], it can be implemented by other types
+/// to check the events of an [`InputForwarder`] without Ultralight, for
+/// example in tests.
+pub trait InputTarget {
+    /// Fire a mouse event, see [`View::fire_mouse_event`].
+    fn mouse_event(
+        &self,
+        ty: MouseEventType,
+        x: i32,
+        y: i32,
+        button: MouseButton,
+    ) -> Result<(), CreationError>;
+
+    /// Fire a scroll event, see [`View::fire_scroll_event`].
+    fn scroll_event(
+        &self,
+        ty: ScrollEventType,
+        delta_x: i32,
+        delta_y: i32,
+    ) -> Result<(), CreationError>;
+}
+
+impl InputTarget for View {
+    fn mouse_event(
+        &self,
+        ty: MouseEventType,
+        x: i32,
+        y: i32,
+        button: MouseButton,
+    ) -> Result<(), CreationError> {
+        self.fire_mouse_event(MouseEvent::new(self.lib.clone(), ty, x, y, button)?);
+        Ok(())
+    }
+
+    fn scroll_event(
+        &self,
+        ty: ScrollEventType,
+        delta_x: i32,
+        delta_y: i32,
+    ) -> Result<(), CreationError> {
+        self.fire_scroll_event(ScrollEvent::new(self.lib.clone(), ty, delta_x, delta_y)?);
+        Ok(())
+    }
+}
+
+/// Forwards the mouse input of a host window to a view placed in it.
+///
+/// The positions given to the forwarder are in logical window coordinates,
+/// and are converted with the scale of the window and the position of the
+/// view (see [`View::window_to_view_coords`]).
+///
+/// Events outside of the view are not forwarded, except for releasing the
+/// buttons: if the pointer leaves the view while buttons are pressed, a
+/// [`MouseEventType::MouseUp`] is fired for them at the closest position in
+/// the view, so that the page doesn't keep a pressed button (eg. while
+/// dragging a slider).
+///
+/// # Example
+/// ```no_run
+/// # use ul_next::{event::MouseButton, view::{InputForwarder, View}};
+/// # use winit::{event::WindowEvent, window::Window};
+/// # fn example(view: &View, window: &Window, event: WindowEvent) -> Result<(), ul_next::error::CreationError> {
+/// let mut forwarder = InputForwarder::new((0, 0), view.width(), view.height(), window.scale_factor());
+///
+/// // in the event loop
+/// # match event {
+/// WindowEvent::CursorMoved { position, .. } => {
+///     let position = position.to_logical::<f64>(window.scale_factor());
+///     forwarder.mouse_moved(view, position.x, position.y)?;
+/// }
+/// WindowEvent::MouseInput { state, button: winit::event::MouseButton::Left, .. } => {
+///     forwarder.button(view, MouseButton::Left, state.is_pressed())?;
+/// }
+/// # _ => {}
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InputForwarder {
+    origin: (i32, i32),
+    width: u32,
+    height: u32,
+    scale: f64,
+    /// The last position of the pointer in view coordinates, not clamped.
+    position: Option<(i32, i32)>,
+    pressed: Vec<MouseButton>,
+}
+
+impl InputForwarder {
+    /// Create a new forwarder.
+    ///
+    /// # Arguments
+    /// * `origin` - The position of the top-left corner of the view in the
+    ///   window, in physical pixels.
+    /// * `width`, `height` - The size of the view, in pixels.
+    /// * `scale` - The scale of the window, see [`View::window_to_view_coords`].
+    pub fn new(origin: (i32, i32), width: u32, height: u32, scale: f64) -> Self {
+        Self {
+            origin,
+            width,
+            height,
+            scale,
+            position: None,
+            pressed: Vec::new(),
+        }
+    }
+
+    /// Set the position of the view in the window, in physical pixels.
+    pub fn set_origin(&mut self, origin: (i32, i32)) {
+        self.origin = origin;
+    }
+
+    /// Set the size of the view, in pixels.
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Set the scale of the window.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = scale;
+    }
+
+    /// Convert a position in the window to view coordinates, the result
+    /// may be outside of the view.
+    pub fn to_view_coords(&self, x: f64, y: f64) -> (i32, i32) {
+        window_to_view(x, y, self.scale, self.origin)
+    }
+
+    /// Whether `position` (in view coordinates) is inside the view.
+    pub fn contains(&self, (x, y): (i32, i32)) -> bool {
+        x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height
+    }
+
+    /// The buttons currently pressed in the view.
+    pub fn pressed_buttons(&self) -> &[MouseButton] {
+        &self.pressed
+    }
+
+    /// The pointer moved to (`x`, `y`) in the window.
+    ///
+    /// Fires a [`MouseEventType::MouseMoved`] if the position is in the
+    /// view, otherwise releases the pressed buttons.
+    pub fn mouse_moved<T: InputTarget + ?Sized>(
+        &mut self,
+        target: &T,
+        x: f64,
+        y: f64,
+    ) -> Result<(), CreationError> {
+        let position = self.to_view_coords(x, y);
+        self.position = Some(position);
+
+        if self.contains(position) {
+            let button = self.pressed.first().copied().unwrap_or(MouseButton::None);
+            target.mouse_event(MouseEventType::MouseMoved, position.0, position.1, button)
+        } else {
+            self.release_all(target)
+        }
+    }
+
+    /// A button was pressed or released, at the last position given to
+    /// [`InputForwarder::mouse_moved`].
+    ///
+    /// Presses outside of the view are ignored, and so are releases of
+    /// buttons that were not pressed in the view.
+    pub fn button<T: InputTarget + ?Sized>(
+        &mut self,
+        target: &T,
+        button: MouseButton,
+        pressed: bool,
+    ) -> Result<(), CreationError> {
+        let Some(position) = self.position else {
+            return Ok(());
+        };
+
+        if pressed {
+            if !self.contains(position) || self.pressed.contains(&button) {
+                return Ok(());
+            }
+            self.pressed.push(button);
+            target.mouse_event(MouseEventType::MouseDown, position.0, position.1, button)
+        } else {
+            let Some(index) = self.pressed.iter().position(|&b| b == button) else {
+                return Ok(());
+            };
+            self.pressed.remove(index);
+            let (x, y) = self.clamp(position);
+            target.mouse_event(MouseEventType::MouseUp, x, y, button)
+        }
+    }
+
+    /// The wheel was scrolled by (`delta_x`, `delta_y`) logical pixels,
+    /// only forwarded if the pointer is in the view.
+    pub fn scroll<T: InputTarget + ?Sized>(
+        &mut self,
+        target: &T,
+        delta_x: f64,
+        delta_y: f64,
+    ) -> Result<(), CreationError> {
+        match self.position {
+            Some(position) if self.contains(position) => target.scroll_event(
+                ScrollEventType::ScrollByPixel,
+                (delta_x * self.scale).round() as i32,
+                (delta_y * self.scale).round() as i32,
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Release all the pressed buttons, at the closest position in the view.
+    fn release_all<T: InputTarget + ?Sized>(&mut self, target: &T) -> Result<(), CreationError> {
+        let (x, y) = self.clamp(self.position.unwrap_or((0, 0)));
+        for button in std::mem::take(&mut self.pressed) {
+            target.mouse_event(MouseEventType::MouseUp, x, y, button)?;
+        }
+        Ok(())
+    }
+
+    /// Clamp `position` to the bounds of the view.
+    fn clamp(&self, (x, y): (i32, i32)) -> (i32, i32) {
+        let max_x = self.width.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let max_y = self.height.saturating_sub(1).min(i32::MAX as u32) as i32;
+        (x.clamp(0, max_x), y.clamp(0, max_y))
+    }
+}
+
+/// An event held back by an [`InputCoalescer`].
+#[derive(Debug, Clone, Copy)]
+enum PendingEvent {
+    Move {
+        x: i32,
+        y: i32,
+        button: MouseButton,
+    },
+    Scroll {
+        ty: ScrollEventType,
+        dx: i32,
+        dy: i32,
+    },
+}
+
+/// Merges the mouse moves and scrolls sent to a target between two calls to
+/// [`InputCoalescer::flush`].
+///
+/// Mice with high polling rates report hundreds of moves per second, while
+/// the page only needs the last position before each frame. The coalescer
+/// implements [`InputTarget`], so it can be given to an [`InputForwarder`]
+/// (or used directly), and:
+/// - Keeps only the last of consecutive mouse moves.
+/// - Adds up the deltas of consecutive scrolls of the same type.
+/// - Fires other events (button presses and releases) immediately, after
+///   firing the pending move or scroll, so the order of the events is kept.
+///
+/// Call [`InputCoalescer::flush`] once per frame, before
+/// [`Renderer::update`](crate::renderer::Renderer::update), to fire the
+/// pending event. Dropping the coalescer also fires it.
+///
+/// # Example
+/// ```no_run
+/// # use ul_next::{event::MouseButton, renderer::Renderer, view::{InputCoalescer, InputForwarder, View}};
+/// # use winit::event::WindowEvent;
+/// # fn example(
+/// #     renderer: &Renderer,
+/// #     view: &View,
+/// #     forwarder: &mut InputForwarder,
+/// #     event: WindowEvent,
+/// # ) -> Result<(), ul_next::error::Error> {
+/// let coalescer = InputCoalescer::new(view);
+///
+/// // in the event loop
+/// # match event {
+/// WindowEvent::CursorMoved { position, .. } => {
+///     forwarder.mouse_moved(&coalescer, position.x, position.y)?;
+/// }
+/// WindowEvent::RedrawRequested => {
+///     coalescer.flush()?;
+///     renderer.update()?;
+///     renderer.render()?;
+/// }
+/// # _ => {}
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub struct InputCoalescer<'a, T: InputTarget + ?Sized = View> {
+    target: &'a T,
+    pending: RefCell<Option<PendingEvent>>,
+}
+
+impl<'a, T: InputTarget + ?Sized> InputCoalescer<'a, T> {
+    /// Create a coalescer firing the events to `target`.
+    pub fn new(target: &'a T) -> Self {
+        Self {
+            target,
+            pending: RefCell::new(None),
+        }
+    }
+
+    /// Whether an event is waiting for [`InputCoalescer::flush`].
+    pub fn has_pending(&self) -> bool {
+        self.pending.borrow().is_some()
+    }
+
+    /// Fire the pending mouse move or scroll, if any.
+    pub fn flush(&self) -> Result<(), CreationError> {
+        // taken out first, so that the target can send events back
+        let pending = self.pending.borrow_mut().take();
+        match pending {
+            Some(PendingEvent::Move { x, y, button }) => {
+                self.target
+                    .mouse_event(MouseEventType::MouseMoved, x, y, button)
+            }
+            Some(PendingEvent::Scroll { ty, dx, dy }) => self.target.scroll_event(ty, dx, dy),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: InputTarget + ?Sized> InputTarget for InputCoalescer<'_, T> {
+    fn mouse_event(
+        &self,
+        ty: MouseEventType,
+        x: i32,
+        y: i32,
+        button: MouseButton,
+    ) -> Result<(), CreationError> {
+        if ty != MouseEventType::MouseMoved {
+            self.flush()?;
+            return self.target.mouse_event(ty, x, y, button);
+        }
+
+        let pending = *self.pending.borrow();
+        if let Some(PendingEvent::Scroll { .. }) = pending {
+            self.flush()?;
+        }
+        *self.pending.borrow_mut() = Some(PendingEvent::Move { x, y, button });
+        Ok(())
+    }
+
+    fn scroll_event(
+        &self,
+        ty: ScrollEventType,
+        delta_x: i32,
+        delta_y: i32,
+    ) -> Result<(), CreationError> {
+        let pending = *self.pending.borrow();
+        let (dx, dy) = match pending {
+            Some(PendingEvent::Scroll {
+                ty: pending_ty,
+                dx,
+                dy,
+            }) if pending_ty == ty => (dx.saturating_add(delta_x), dy.saturating_add(delta_y)),
+            Some(_) => {
+                self.flush()?;
+                (delta_x, delta_y)
+            }
+            None => (delta_x, delta_y),
+        };
+        *self.pending.borrow_mut() = Some(PendingEvent::Scroll { ty, dx, dy });
+        Ok(())
+    }
+}
+
+impl<T: InputTarget + ?Sized> Drop for InputCoalescer<'_, T> {
+    fn drop(&mut self) {
+        // nothing we can do about errors here
+        let _ = self.flush();
+    }
+}
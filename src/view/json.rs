@@ -0,0 +1,37 @@
+//! Evaluating scripts and deserializing their result, see
+//! [`View::evaluate_script_json`].
+use serde::de::DeserializeOwned;
+
+use crate::javascript::{from_value, JsSerdeError};
+
+use super::View;
+
+impl View {
+    /// Evaluate `script` in the page, and deserialize its result into `T`
+    /// (see [`javascript::from_value`](crate::javascript::from_value)).
+    ///
+    /// Exceptions thrown by the script are returned as
+    /// [`JsSerdeError::Exception`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(view: &ul_next::view::View) -> Result<(), ul_next::javascript::JsSerdeError> {
+    /// #[derive(serde::Deserialize)]
+    /// struct Row {
+    ///     name: String,
+    ///     value: Option<f64>,
+    /// }
+    ///
+    /// let rows = view.evaluate_script_json::<Vec<Row>>("getTableData()")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn evaluate_script_json<T: DeserializeOwned>(
+        &self,
+        script: &str,
+    ) -> Result<T, JsSerdeError> {
+        let context = self.lock_js_context();
+        let value = context.evaluate_script(script, None, None, 1)?;
+        from_value(&value)
+    }
+}
@@ -0,0 +1,44 @@
+//! Opacity of the page content, implemented using the CSS `opacity` property.
+use crate::error::CreationError;
+
+use super::View;
+
+impl View {
+    /// Set the opacity of the page body, used by
+    /// [`Overlay::set_opacity`](crate::overlay::Overlay::set_opacity).
+    ///
+    /// Like [`View::set_page_zoom`], this is re-applied when the DOM is ready.
+    #[cfg(any(feature = "appcore_linked", feature = "loaded"))]
+    pub(crate) fn set_content_opacity(&self, opacity: f32) -> Result<(), CreationError> {
+        self.state.content_opacity.set(Some(opacity));
+
+        self.ensure_dom_ready_hook();
+        self.apply_content_opacity()
+    }
+
+    /// Get the opacity set by [`View::set_content_opacity`].
+    #[cfg(any(feature = "appcore_linked", feature = "loaded"))]
+    pub(crate) fn content_opacity(&self) -> f32 {
+        self.state.content_opacity.get().unwrap_or(1.0)
+    }
+
+    /// Apply the opacity to the current page.
+    pub(super) fn apply_content_opacity(&self) -> Result<(), CreationError> {
+        // don't touch the page if the opacity was never set
+        let Some(opacity) = self.state.content_opacity.get() else {
+            return Ok(());
+        };
+        let script = if opacity == 1.0 {
+            "document.body && document.body.style.removeProperty('opacity');".to_string()
+        } else {
+            format!(
+                "document.body && document.body.style.setProperty('opacity', '{}');",
+                opacity
+            )
+        };
+
+        // the script can't throw
+        let _ = self.evaluate_script(&script)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! Navigation with HTTP `POST` requests.
+use std::fmt::Write;
+
+use crate::error::CreationError;
+
+use super::View;
+
+/// The form encodings supported by browsers, which can be used in
+/// [`View::navigate_with_post`].
+const FORM_ENCTYPES: &[&str] = &[
+    "application/x-www-form-urlencoded",
+    "multipart/form-data",
+    "text/plain",
+];
+
+impl View {
+    /// Navigate to `url` using an HTTP `POST` request with the given `body`.
+    ///
+    /// Ultralight doesn't provide an API for `POST` navigation, so this loads
+    /// a small page containing a `<form method="POST">` which is submitted
+    /// immediately, this means that the intermediate page will be part
+    /// of the navigation history.
+    ///
+    /// Because of this, `body` must be the fields of the form encoded as
+    /// `application/x-www-form-urlencoded` (`key=value&key2=value2`),
+    /// and `content_type` controls how the browser will encode the fields
+    /// in the request. It can be one of `application/x-www-form-urlencoded`,
+    /// `multipart/form-data` or `text/plain`, any other type will return
+    /// [`CreationError::UnsupportedContentType`].
+    ///
+    /// Note that JavaScript must be enabled in the view
+    /// (see [`ViewConfigBuilder::enable_javascript`](super::ViewConfigBuilder::enable_javascript)).
+    pub fn navigate_with_post(
+        &self,
+        url: &str,
+        body: &[u8],
+        content_type: &str,
+    ) -> Result<(), CreationError> {
+        let enctype = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if !FORM_ENCTYPES.contains(&enctype.as_str()) {
+            return Err(CreationError::UnsupportedContentType(
+                content_type.to_string(),
+            ));
+        }
+
+        let mut html = format!(
+            "<html><body><form method=\"POST\" action=\"{}\" enctype=\"{}\">",
+            escape_html(url),
+            enctype
+        );
+        for (name, value) in parse_form_urlencoded(body) {
+            write!(
+                html,
+                "<input type=\"hidden\" name=\"{}\" value=\"{}\">",
+                escape_html(&name),
+                escape_html(&value)
+            )
+            .unwrap();
+        }
+        html.push_str("</form><script>document.forms[0].submit();</script></body></html>");
+
+        self.load_html(&html)
+    }
+}
+
+/// Escape a string to be used inside HTML attributes.
+pub(super) fn escape_html(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Parse `application/x-www-form-urlencoded` data into name/value pairs.
+fn parse_form_urlencoded(body: &[u8]) -> Vec<(String, String)> {
+    body.split(|&b| b == b'&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut parts = pair.splitn(2, |&b| b == b'=');
+            let name = percent_decode(parts.next().unwrap_or_default());
+            let value = percent_decode(parts.next().unwrap_or_default());
+            (name, value)
+        })
+        .collect()
+}
+
+fn percent_decode(input: &[u8]) -> String {
+    fn hex_value(b: u8) -> Option<u8> {
+        match b {
+            b'0'..=b'9' => Some(b - b'0'),
+            b'a'..=b'f' => Some(b - b'a' + 10),
+            b'A'..=b'F' => Some(b - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let mut result = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        match input[i] {
+            b'+' => result.push(b' '),
+            b'%' => {
+                let hex = input
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| Some((hex_value(hex[0])? << 4) | hex_value(hex[1])?));
+                match hex {
+                    Some(b) => {
+                        result.push(b);
+                        i += 2;
+                    }
+                    // keep invalid escapes as is
+                    None => result.push(b'%'),
+                }
+            }
+            b => result.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&result).into_owned()
+}
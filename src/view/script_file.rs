@@ -0,0 +1,44 @@
+//! Evaluating JavaScript files, see [`View::inject_javascript_file`].
+use std::path::{Path, PathBuf};
+
+use crate::{error::CreationError, platform};
+
+use super::View;
+
+impl View {
+    /// Read the JavaScript file at `path` and evaluate it in the page, like
+    /// [`View::evaluate_script`].
+    ///
+    /// Relative paths are resolved against the base directory of
+    /// [`platform::enable_platform_filesystem`] if it was called (so the
+    /// same paths as `file:///` URLs can be used), or against the current
+    /// working directory otherwise. The file is read with [`std::fs`], a
+    /// custom [`FileSystem`](platform::FileSystem) is not used.
+    ///
+    /// The path is added as the `sourceURL` of the script, so it appears in
+    /// the stack traces and console messages of the errors it throws.
+    ///
+    /// Returns [`CreationError::IoError`] if the file can't be read, or
+    /// [`CreationError::RustStringCreationError`] if it's not valid UTF-8.
+    pub fn inject_javascript_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Result<String, String>, CreationError> {
+        let path = resolve_script_path(path.as_ref());
+        let bytes = std::fs::read(&path)?;
+        let mut script = String::from_utf8(bytes)?;
+
+        // on its own line, in case the file ends with a line comment
+        script.push_str("\n//# sourceURL=");
+        script.push_str(&path.to_string_lossy().replace(['\n', '\r'], ""));
+
+        self.evaluate_script(&script)
+    }
+}
+
+fn resolve_script_path(path: &Path) -> PathBuf {
+    match platform::platform_filesystem_base() {
+        Some(base) => base.join(path),
+        None => path.to_path_buf(),
+    }
+}
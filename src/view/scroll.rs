@@ -0,0 +1,90 @@
+//! Programmatic scrolling of the page, implemented using the JavaScript
+//! `window.scrollTo` and `window.scrollBy` functions.
+use crate::error::CreationError;
+
+use super::View;
+
+/// Errors that can occur when scrolling a [`View`] with [`View::scroll_to`],
+/// [`View::scroll_by`] or [`View::set_scroll_position`], or reading
+/// [`View::scroll_position`].
+#[derive(Debug, thiserror::Error)]
+pub enum ScrollError {
+    /// The scroll offset is `NaN` or infinite.
+    #[error("Invalid scroll offset ({0}, {1})")]
+    InvalidOffset(f64, f64),
+    /// The script used to scroll threw an exception, for example when
+    /// JavaScript is disabled in the view.
+    #[error("Scroll script failed: {0}")]
+    ScriptException(String),
+    /// The scroll position returned by the page couldn't be parsed.
+    #[error("Invalid scroll position `{0}`")]
+    InvalidPosition(String),
+    /// Failed to create the script string or to read its result.
+    #[error("{0}")]
+    CreationError(#[from] CreationError),
+}
+
+impl View {
+    /// Scroll the page so that the point (`x`, `y`) of the document, in CSS
+    /// pixels, is at the top-left corner of the view.
+    ///
+    /// Unlike firing a [`ScrollEvent`](crate::event::ScrollEvent), this jumps
+    /// to an absolute offset. Negative offsets are clamped to `0`, and the
+    /// page clamps offsets past the end of the document.
+    ///
+    /// Returns [`ScrollError::InvalidOffset`] if `x` or `y` is not finite.
+    pub fn scroll_to(&self, x: f64, y: f64) -> Result<(), ScrollError> {
+        check_offset(x, y)?;
+        self.run_scroll_script(&format!("window.scrollTo({}, {});", x.max(0.0), y.max(0.0)))
+    }
+
+    /// Scroll the page by (`dx`, `dy`) CSS pixels relative to the current
+    /// scroll position.
+    ///
+    /// Returns [`ScrollError::InvalidOffset`] if `dx` or `dy` is not finite.
+    pub fn scroll_by(&self, dx: f64, dy: f64) -> Result<(), ScrollError> {
+        check_offset(dx, dy)?;
+        self.run_scroll_script(&format!("window.scrollBy({}, {});", dx, dy))
+    }
+
+    /// Get the current scroll position of the page, in CSS pixels, as
+    /// (`x`, `y`) (`window.scrollX` and `window.scrollY`).
+    pub fn scroll_position(&self) -> Result<(f64, f64), ScrollError> {
+        let position = self
+            .evaluate_script("window.scrollX + ',' + window.scrollY")?
+            .map_err(ScrollError::ScriptException)?;
+
+        parse_position(&position).ok_or(ScrollError::InvalidPosition(position))
+    }
+
+    /// Restore a scroll position read with [`View::scroll_position`], for
+    /// example when navigating back in the history.
+    ///
+    /// This is the same as [`View::scroll_to`].
+    pub fn set_scroll_position(&self, x: f64, y: f64) -> Result<(), ScrollError> {
+        self.scroll_to(x, y)
+    }
+
+    fn run_scroll_script(&self, script: &str) -> Result<(), ScrollError> {
+        self.evaluate_script(script)?
+            .map(|_| ())
+            .map_err(ScrollError::ScriptException)
+    }
+}
+
+fn check_offset(x: f64, y: f64) -> Result<(), ScrollError> {
+    if x.is_finite() && y.is_finite() {
+        Ok(())
+    } else {
+        Err(ScrollError::InvalidOffset(x, y))
+    }
+}
+
+/// Parse the `x,y` result of the script in [`View::scroll_position`].
+fn parse_position(position: &str) -> Option<(f64, f64)> {
+    let (x, y) = position.split_once(',')?;
+    let x = x.trim().parse::<f64>().ok()?;
+    let y = y.trim().parse::<f64>().ok()?;
+
+    (x.is_finite() && y.is_finite()).then_some((x, y))
+}
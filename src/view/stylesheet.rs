@@ -0,0 +1,189 @@
+//! Per-view user stylesheets, injected as `<style>` elements in the page,
+//! see [`View::inject_stylesheet`].
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+    sync::Arc,
+};
+
+use crate::{error::CreationError, Library};
+
+use super::{find::js_string_literal, View};
+
+/// The stylesheets injected in a view, shared with their handles.
+#[derive(Default)]
+pub(super) struct InjectedStylesheets {
+    next_id: u64,
+    sheets: Vec<(u64, String)>,
+}
+
+impl InjectedStylesheets {
+    /// Remove all the stylesheets, once the view is destroyed.
+    pub(super) fn clear(&mut self) {
+        self.sheets.clear();
+    }
+}
+
+pub(super) type SharedStylesheets = Rc<RefCell<InjectedStylesheets>>;
+
+/// The id of the `<style>` element of the stylesheet `id`.
+fn element_id(id: u64) -> String {
+    format!("__ul_next_stylesheet_{}", id)
+}
+
+/// Create or replace the `<style>` element of the stylesheet `id`.
+fn apply_script(id: u64, css: &str) -> String {
+    format!(
+        "(function(id, css) {{
+  var el = document.getElementById(id);
+  if (!el) {{
+    el = document.createElement('style');
+    el.id = id;
+    (document.head || document.documentElement).appendChild(el);
+  }}
+  el.textContent = css;
+}})({}, {});",
+        js_string_literal(&element_id(id)),
+        js_string_literal(css)
+    )
+}
+
+/// Remove the `<style>` element of the stylesheet `id`, if any.
+fn remove_script(id: u64) -> String {
+    format!(
+        "(function(el) {{ if (el) el.parentNode.removeChild(el); }})(document.getElementById({}));",
+        js_string_literal(&element_id(id))
+    )
+}
+
+/// A stylesheet injected with [`View::inject_stylesheet`].
+///
+/// Dropping the handle keeps the stylesheet, use
+/// [`StylesheetHandle::remove`] to remove it. The handle doesn't keep the
+/// view alive, its methods do nothing once the view is dropped.
+pub struct StylesheetHandle {
+    lib: Arc<Library>,
+    view: ul_sys::ULView,
+    stylesheets: Weak<RefCell<InjectedStylesheets>>,
+    id: u64,
+}
+
+impl StylesheetHandle {
+    /// Replace the CSS of the stylesheet, in the current page and the next
+    /// ones.
+    pub fn update(&self, css: &str) -> Result<(), CreationError> {
+        let Some(stylesheets) = self.stylesheets.upgrade() else {
+            return Ok(());
+        };
+        {
+            let mut stylesheets = stylesheets.borrow_mut();
+            let Some(sheet) = stylesheets.sheets.iter_mut().find(|(id, _)| *id == self.id) else {
+                // already removed
+                return Ok(());
+            };
+            sheet.1 = css.to_string();
+        }
+        self.run(&apply_script(self.id, css))
+    }
+
+    /// Remove the stylesheet from the current page, it's not injected in the
+    /// next ones.
+    pub fn remove(self) -> Result<(), CreationError> {
+        let Some(stylesheets) = self.stylesheets.upgrade() else {
+            return Ok(());
+        };
+        stylesheets
+            .borrow_mut()
+            .sheets
+            .retain(|(id, _)| *id != self.id);
+        self.run(&remove_script(self.id))
+    }
+
+    /// Whether the stylesheet is still injected, `false` once the view is
+    /// dropped.
+    pub fn is_active(&self) -> bool {
+        self.stylesheets.upgrade().is_some_and(|stylesheets| {
+            stylesheets
+                .borrow()
+                .sheets
+                .iter()
+                .any(|(id, _)| *id == self.id)
+        })
+    }
+
+    fn run(&self, script: &str) -> Result<(), CreationError> {
+        // the view is alive, its stylesheets are cleared when it's destroyed
+        let view = unsafe { View::from_raw(self.lib.clone(), self.view) }
+            .ok_or(CreationError::NullReference)?;
+        // the scripts can't throw
+        let _ = view.evaluate_script(script)?;
+        Ok(())
+    }
+}
+
+impl View {
+    /// Inject `css` as a user stylesheet in the page, and in all the pages
+    /// loaded later in this view.
+    ///
+    /// The stylesheet is added as a `<style>` element at the end of the
+    /// `<head>` of the page now, and again when the DOM of each new page is
+    /// ready, before the callback of [`View::set_dom_ready_callback`] (which
+    /// is kept as is). Several stylesheets can be injected, they are applied
+    /// in order.
+    ///
+    /// Unlike [`ConfigBuilder::user_stylesheet`](crate::config::ConfigBuilder::user_stylesheet),
+    /// which applies to all the views, this only affects this view. As the
+    /// rules are part of the page, they use the author origin: use
+    /// `!important` to override the rules of the page.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn example(view: &ul_next::view::View) -> Result<(), ul_next::error::CreationError> {
+    /// let dark_mode = view.inject_stylesheet("body { background: #111; color: #eee; }");
+    /// view.load_url("https://example.com")?;
+    ///
+    /// // later
+    /// dark_mode.remove()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn inject_stylesheet(&self, css: &str) -> StylesheetHandle {
+        let id = {
+            let mut stylesheets = self.state.stylesheets.borrow_mut();
+            let id = stylesheets.next_id;
+            stylesheets.next_id += 1;
+            stylesheets.sheets.push((id, css.to_string()));
+            id
+        };
+
+        self.ensure_dom_ready_hook();
+        // the page may not be loaded yet, the DOM ready hook applies it then
+        let _ = self.evaluate_script(&apply_script(id, css));
+
+        StylesheetHandle {
+            lib: self.lib.clone(),
+            view: self.internal,
+            stylesheets: Rc::downgrade(&self.state.stylesheets),
+            id,
+        }
+    }
+
+    /// Inject the stylesheets of [`View::inject_stylesheet`] in the current
+    /// page.
+    pub(super) fn apply_stylesheets(&self) -> Result<(), CreationError> {
+        let script = self
+            .state
+            .stylesheets
+            .borrow()
+            .sheets
+            .iter()
+            .map(|(id, css)| apply_script(*id, css))
+            .collect::<String>();
+        if script.is_empty() {
+            return Ok(());
+        }
+
+        let _ = self.evaluate_script(&script)?;
+        Ok(())
+    }
+}
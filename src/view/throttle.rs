@@ -0,0 +1,234 @@
+//! Background throttling of timers and animation frames for hidden views.
+use std::time::Duration;
+
+use crate::error::CreationError;
+
+use super::View;
+
+/// Policy used by [`View::set_background_throttling`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottlePolicy {
+    /// The minimum delay used by `setTimeout` and `setInterval` timers while
+    /// the view is hidden, timers with lower delays are clamped to this value.
+    pub min_timer_interval: Duration,
+    /// Whether or not to hold `requestAnimationFrame` callbacks while the
+    /// view is hidden, they will be called once the view is shown again.
+    pub pause_raf: bool,
+}
+
+impl Default for ThrottlePolicy {
+    fn default() -> Self {
+        Self {
+            min_timer_interval: Duration::from_secs(1),
+            pause_raf: true,
+        }
+    }
+}
+
+/// Statistics about the background throttling of a view.
+///
+/// See [`View::throttling_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ThrottlingStats {
+    /// The number of timer runs that got their delay clamped.
+    pub clamped_timers: u64,
+    /// The number of `requestAnimationFrame` callbacks currently held.
+    pub parked_animation_frames: u64,
+}
+
+// Installed once per page, calling it again only updates the policy and
+// the hidden state.
+//
+// `setInterval` is re-implemented on top of `setTimeout` so that the
+// clamping takes effect on intervals that were created while visible.
+const INSTALL_SCRIPT: &str = r#"
+(function (minTimerInterval, pauseRaf, hidden) {
+    var t = window.__ulNextThrottle;
+    if (t) {
+        t.minTimerInterval = minTimerInterval;
+        t.pauseRaf = pauseRaf;
+        t.setHidden(hidden);
+        return;
+    }
+    var origSetTimeout = window.setTimeout;
+    var origClearTimeout = window.clearTimeout;
+    var origClearInterval = window.clearInterval;
+    var origRaf = window.requestAnimationFrame;
+    var origCancelRaf = window.cancelAnimationFrame;
+    var slice = Array.prototype.slice;
+
+    t = {
+        minTimerInterval: minTimerInterval,
+        pauseRaf: pauseRaf,
+        hidden: hidden,
+        clamped: 0,
+        nextId: 1000000000,
+        intervals: {},
+        parked: {},
+        parkedCount: 0,
+        clamp: function (delay) {
+            delay = Number(delay) || 0;
+            if (t.hidden && delay < t.minTimerInterval) {
+                t.clamped++;
+                return t.minTimerInterval;
+            }
+            return delay;
+        },
+        setHidden: function (hidden) {
+            t.hidden = hidden;
+            if (!hidden) {
+                var parked = t.parked;
+                t.parked = {};
+                t.parkedCount = 0;
+                Object.keys(parked).forEach(function (id) {
+                    origRaf.call(window, parked[id]);
+                });
+            }
+        }
+    };
+
+    window.setTimeout = function (fn, delay) {
+        var args = [fn, t.clamp(delay)].concat(slice.call(arguments, 2));
+        return origSetTimeout.apply(window, args);
+    };
+    window.setInterval = function (fn, delay) {
+        var args = slice.call(arguments, 2);
+        var id = t.nextId++;
+        var tick = function () {
+            t.intervals[id] = origSetTimeout.call(window, tick, t.clamp(delay));
+            if (typeof fn === "function") {
+                fn.apply(window, args);
+            } else {
+                (0, eval)(String(fn));
+            }
+        };
+        t.intervals[id] = origSetTimeout.call(window, tick, t.clamp(delay));
+        return id;
+    };
+    var clearTimer = function (id) {
+        if (id in t.intervals) {
+            origClearTimeout.call(window, t.intervals[id]);
+            delete t.intervals[id];
+        } else {
+            origClearTimeout.call(window, id);
+            origClearInterval.call(window, id);
+        }
+    };
+    window.clearTimeout = clearTimer;
+    window.clearInterval = clearTimer;
+
+    if (origRaf) {
+        window.requestAnimationFrame = function (callback) {
+            if (t.hidden && t.pauseRaf) {
+                var id = t.nextId++;
+                t.parked[id] = callback;
+                t.parkedCount++;
+                return id;
+            }
+            return origRaf.call(window, callback);
+        };
+        window.cancelAnimationFrame = function (id) {
+            if (id in t.parked) {
+                delete t.parked[id];
+                t.parkedCount--;
+            } else {
+                origCancelRaf.call(window, id);
+            }
+        };
+    }
+
+    window.__ulNextThrottle = t;
+})"#;
+
+impl View {
+    /// Throttle timers and animation frames of the page while the view is
+    /// hidden (see [`View::set_background_hidden`]).
+    ///
+    /// This works by wrapping `setTimeout`, `setInterval` and
+    /// `requestAnimationFrame` in the page's JavaScript context. Calling it
+    /// multiple times will only update the policy.
+    ///
+    /// The policy is installed again whenever the window object of a new
+    /// page is ready in the main frame (before any of the page scripts run),
+    /// so it persists across navigation and reloads. This is done by the
+    /// callback of [`View::set_window_object_ready_callback`], which is kept
+    /// as is.
+    ///
+    /// If an exception occurs while installing it in the current page, it
+    /// will be returned in the inner [`Err`].
+    pub fn set_background_throttling(
+        &self,
+        policy: ThrottlePolicy,
+    ) -> Result<Result<(), String>, CreationError> {
+        self.state.throttle_policy.set(Some(policy));
+
+        self.ensure_window_object_ready_hook();
+        self.apply_background_throttling()
+    }
+
+    /// Get the policy set by [`View::set_background_throttling`].
+    pub fn background_throttling(&self) -> Option<ThrottlePolicy> {
+        self.state.throttle_policy.get()
+    }
+
+    /// Set whether or not the view is hidden, which controls the throttling
+    /// installed by [`View::set_background_throttling`].
+    ///
+    /// When shown again, all held `requestAnimationFrame` callbacks will be
+    /// scheduled, and timers go back to their requested delays.
+    ///
+    /// The hidden state is kept across navigation, and is used once
+    /// throttling is set.
+    pub fn set_background_hidden(&self, hidden: bool) -> Result<(), CreationError> {
+        self.state.background_hidden.set(hidden);
+
+        let script = format!(
+            "window.__ulNextThrottle && window.__ulNextThrottle.setHidden({});",
+            hidden
+        );
+        // the script can't throw
+        let _ = self.evaluate_script(&script)?;
+        Ok(())
+    }
+
+    /// Whether or not the view is hidden, see [`View::set_background_hidden`].
+    pub fn is_background_hidden(&self) -> bool {
+        self.state.background_hidden.get()
+    }
+
+    /// Install the throttling in the current page, if a policy is set.
+    pub(super) fn apply_background_throttling(&self) -> Result<Result<(), String>, CreationError> {
+        let Some(policy) = self.state.throttle_policy.get() else {
+            return Ok(Ok(()));
+        };
+        let script = format!(
+            "{}({}, {}, {});",
+            INSTALL_SCRIPT,
+            policy.min_timer_interval.as_millis(),
+            policy.pause_raf,
+            self.state.background_hidden.get()
+        );
+
+        Ok(self.evaluate_script(&script)?.map(|_| ()))
+    }
+
+    /// Get the statistics of the throttling installed by
+    /// [`View::set_background_throttling`].
+    ///
+    /// Returns [`None`] if throttling is not installed in the current page.
+    pub fn throttling_stats(&self) -> Option<ThrottlingStats> {
+        let result = self
+            .evaluate_script(
+                "window.__ulNextThrottle ? \
+                 window.__ulNextThrottle.clamped + ',' + window.__ulNextThrottle.parkedCount : ''",
+            )
+            .ok()?
+            .ok()?;
+
+        let (clamped, parked) = result.split_once(',')?;
+        Some(ThrottlingStats {
+            clamped_timers: clamped.parse().ok()?,
+            parked_animation_frames: parked.parse().ok()?,
+        })
+    }
+}
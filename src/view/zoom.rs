@@ -0,0 +1,96 @@
+//! Page zoom and scale, implemented using the CSS `zoom` and `transform`
+//! properties.
+use crate::error::CreationError;
+
+use super::View;
+
+impl View {
+    /// Set the zoom factor of the page content, `1.0` is the normal size.
+    ///
+    /// Ultralight doesn't have a page zoom API, so this sets the CSS `zoom`
+    /// property on the root element of the page. Unlike
+    /// [`View::set_device_scale`], this changes the layout of the page (like
+    /// the zoom of web browsers), and mouse events should still be given in
+    /// view pixel coordinates, the page will map them to the zoomed content.
+    ///
+    /// The zoom is applied again whenever a new page is loaded in the main
+    /// frame (when the DOM is ready), so it persists across navigation and
+    /// reloads. This is done by the callback of
+    /// [`View::set_dom_ready_callback`], which is kept as is, and the zoom is
+    /// shared by all the [`View`]s of the same view (including the one given
+    /// as an argument to callbacks).
+    pub fn set_page_zoom(&self, factor: f64) -> Result<(), CreationError> {
+        self.state.page_zoom.set(factor);
+
+        self.ensure_dom_ready_hook();
+        self.apply_page_zoom()
+    }
+
+    /// Get the zoom factor of the page content set by [`View::set_page_zoom`].
+    pub fn page_zoom(&self) -> f64 {
+        self.state.page_zoom.get()
+    }
+
+    /// Apply the zoom to the current page.
+    pub(super) fn apply_page_zoom(&self) -> Result<(), CreationError> {
+        let zoom = self.state.page_zoom.get();
+        let script = if zoom == 1.0 {
+            "document.documentElement && document.documentElement.style.removeProperty('zoom');"
+                .to_string()
+        } else {
+            format!(
+                "document.documentElement && document.documentElement.style.setProperty('zoom', '{}');",
+                zoom
+            )
+        };
+
+        // the script can't throw
+        let _ = self.evaluate_script(&script)?;
+        Ok(())
+    }
+
+    /// Set the scale factor of the page, `1.0` is the normal scale.
+    ///
+    /// This is similar to the page scale of browsers (pinch zoom): the page
+    /// is magnified by `scale` without changing its layout, so the content
+    /// may overflow the view. Unlike [`View::set_device_scale`] (which sets
+    /// `window.devicePixelRatio` and the rendering resolution) and
+    /// [`View::set_page_zoom`] (which lays out the page again like the CSS
+    /// `zoom`), the layout and the rendering resolution stay the same.
+    ///
+    /// Ultralight doesn't expose the WebKit page scale, so this is done with
+    /// a CSS `transform` on the root element (with the origin at the top-left
+    /// corner), and is re-applied after navigation like [`View::set_page_zoom`],
+    /// with the same limitations.
+    pub fn set_page_scale_factor(&self, scale: f64) -> Result<(), CreationError> {
+        self.state.page_scale_factor.set(scale);
+
+        self.ensure_dom_ready_hook();
+        self.apply_page_scale_factor()
+    }
+
+    /// Get the scale factor of the page set by [`View::set_page_scale_factor`].
+    pub fn page_scale_factor(&self) -> f64 {
+        self.state.page_scale_factor.get()
+    }
+
+    /// Apply the scale to the current page.
+    pub(super) fn apply_page_scale_factor(&self) -> Result<(), CreationError> {
+        let scale = self.state.page_scale_factor.get();
+        let script = if scale == 1.0 {
+            "document.documentElement && (document.documentElement.style.removeProperty('transform'), \
+                document.documentElement.style.removeProperty('transform-origin'));"
+                .to_string()
+        } else {
+            format!(
+                "document.documentElement && (document.documentElement.style.setProperty('transform', 'scale({})'), \
+                    document.documentElement.style.setProperty('transform-origin', '0 0'));",
+                scale
+            )
+        };
+
+        // the script can't throw
+        let _ = self.evaluate_script(&script)?;
+        Ok(())
+    }
+}
@@ -5,9 +5,80 @@
 //!
 //! [`App`]: crate::app::App
 
-use std::{self, ffi::CString, sync::Arc};
+use std::{
+    self,
+    cell::{Cell, RefCell},
+    ffi::c_void,
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::{
+    bitmap::Bitmap,
+    error::{CreationError, Error},
+    overlay::{Overlay, OverlayRegistry},
+    view::Cursor,
+    view::View,
+    Library,
+};
+
+#[cfg(target_os = "windows")]
+mod win32;
+
+/// Errors that can occur in [`Window`] operations.
+#[derive(Debug, thiserror::Error)]
+pub enum WindowError {
+    /// The operation is not supported on the current platform.
+    #[error("The operation is not supported on this platform")]
+    NotSupported,
+    /// The bitmap is not in [`BitmapFormat::Bgra8UnormSrgb`](crate::bitmap::BitmapFormat::Bgra8UnormSrgb) format.
+    #[error("Unsupported bitmap format, expected `Bgra8UnormSrgb`")]
+    UnsupportedBitmapFormat,
+    /// The bitmap has no pixels.
+    #[error("The bitmap is empty")]
+    EmptyBitmap,
+    /// The platform failed to perform the operation.
+    #[error("The platform failed to perform the operation")]
+    PlatformError,
+}
+
+/// The platform window handle of a [`Window`], returned by
+/// [`Window::native_handle`].
+///
+/// The handle is owned by the [`Window`], and is only valid while it's alive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NativeWindowHandle {
+    /// The `HWND` of the window.
+    #[cfg(target_os = "windows")]
+    Hwnd(*mut c_void),
+    /// The `NSWindow*` of the window.
+    #[cfg(target_os = "macos")]
+    NsWindow(*mut c_void),
+    /// The `GLFWwindow*` of the window, AppCore uses GLFW on Linux, so the
+    /// X11 or Wayland handles can be obtained from it with the GLFW native
+    /// access functions.
+    #[cfg(target_os = "linux")]
+    GlfwWindow(*mut c_void),
+    /// The handle on other platforms, as returned by AppCore.
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    Other(*mut c_void),
+}
 
-use crate::{overlay::Overlay, view::Cursor, view::View, Library};
+impl NativeWindowHandle {
+    /// The raw pointer of the handle.
+    pub fn as_ptr(&self) -> *mut c_void {
+        match *self {
+            #[cfg(target_os = "windows")]
+            NativeWindowHandle::Hwnd(handle) => handle,
+            #[cfg(target_os = "macos")]
+            NativeWindowHandle::NsWindow(handle) => handle,
+            #[cfg(target_os = "linux")]
+            NativeWindowHandle::GlfwWindow(handle) => handle,
+            #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+            NativeWindowHandle::Other(handle) => handle,
+        }
+    }
+}
 
 /// Window creation flags
 pub struct WindowFlags {
@@ -48,16 +119,65 @@ impl WindowFlags {
     }
 }
 
+type ResizeCallback = Box<dyn FnMut(&Window, u32, u32)>;
+type DpiChangedCallback = Box<dyn FnMut(&Window, f64)>;
+
+/// The state of a [`Window`] that is shared with its resize callback.
+#[derive(Default)]
+struct WindowState {
+    overlays: OverlayRegistry,
+    /// The scale of the window when last checked, to detect DPI changes.
+    scale: Cell<f64>,
+    auto_device_scale: Cell<bool>,
+    /// Whether the AppCore resize callback is set, it is shared by
+    /// [`Window::set_resize_callback`], [`Window::set_dpi_changed_callback`]
+    /// and [`Window::enable_auto_device_scale`].
+    resize_hooked: Cell<bool>,
+    resize_callback: RefCell<Option<ResizeCallback>>,
+    dpi_changed_callback: RefCell<Option<DpiChangedCallback>>,
+}
+
+/// The underlying `ULWindow`, shared by all the handles of a [`Window`]
+/// (including the ones held by its overlays), and destroyed when the last
+/// one is dropped.
+struct WindowHandle {
+    lib: Arc<Library>,
+    internal: ul_sys::ULWindow,
+    need_to_destroy: bool,
+    /// The icon set by [`Window::set_icon`], destroyed when replaced or
+    /// with the window.
+    #[cfg(target_os = "windows")]
+    icon: Cell<*mut std::ffi::c_void>,
+}
+
+impl Drop for WindowHandle {
+    fn drop(&mut self) {
+        if self.need_to_destroy {
+            unsafe { self.lib.appcore().ulDestroyWindow(self.internal) }
+            #[cfg(target_os = "windows")]
+            unsafe {
+                win32::destroy_icon(self.icon.get())
+            }
+        }
+    }
+}
+
 /// Window struct, represents a platform window.
+///
+/// A window can be shared with [`Window::clone_handle`], and every
+/// [`Overlay`] keeps a handle to the window it was created in (see
+/// [`Overlay::window`]), so the window is destroyed when the last of these
+/// handles is dropped.
 pub struct Window {
     lib: Arc<Library>,
     internal: ul_sys::ULWindow,
-    need_to_destroy: bool,
+    handle: Rc<WindowHandle>,
+    state: Rc<WindowState>,
 }
 
 impl Window {
     /// Internal function helper to create a view.
-    /// (See [`App::create_window`](crate::app::App::create_window))
+    /// (See [`App::try_create_window`](crate::app::App::try_create_window))
     ///
     /// Returns [`None`] if failed to create the window.
     pub(crate) unsafe fn create(
@@ -79,10 +199,19 @@ impl Window {
         if internal.is_null() {
             None
         } else {
+            let state = WindowState::default();
+            state.scale.set(lib.appcore().ulWindowGetScale(internal));
             Some(Self {
-                lib,
+                lib: lib.clone(),
                 internal,
-                need_to_destroy: true,
+                handle: Rc::new(WindowHandle {
+                    lib,
+                    internal,
+                    need_to_destroy: true,
+                    #[cfg(target_os = "windows")]
+                    icon: Cell::new(std::ptr::null_mut()),
+                }),
+                state: Rc::new(state),
             })
         }
     }
@@ -94,13 +223,45 @@ impl Window {
             None
         } else {
             Some(Self {
-                lib,
+                lib: lib.clone(),
                 internal: raw,
-                need_to_destroy: false,
+                handle: Rc::new(WindowHandle {
+                    lib,
+                    internal: raw,
+                    need_to_destroy: false,
+                    #[cfg(target_os = "windows")]
+                    icon: Cell::new(std::ptr::null_mut()),
+                }),
+                state: Rc::default(),
             })
         }
     }
 
+    /// Returns the underlying [`ul_sys::ULWindow`] struct, to be used locally for
+    /// calling the underlying C API.
+    pub(crate) unsafe fn to_ul(&self) -> ul_sys::ULWindow {
+        self.internal
+    }
+
+    /// Create another handle to the same window.
+    ///
+    /// The window is destroyed when all of its handles (including the ones
+    /// held by overlays) are dropped. Callbacks are shared by all the
+    /// handles.
+    pub fn clone_handle(&self) -> Window {
+        Window {
+            lib: self.lib.clone(),
+            internal: self.internal,
+            handle: self.handle.clone(),
+            state: self.state.clone(),
+        }
+    }
+
+    /// Whether `self` and `other` are handles to the same window.
+    pub fn same_window(&self, other: &Window) -> bool {
+        self.internal == other.internal
+    }
+
     /// Get the window width (in screen coordinates).
     pub fn screen_width(&self) -> u32 {
         unsafe { self.lib.appcore().ulWindowGetScreenWidth(self.internal) }
@@ -155,8 +316,11 @@ impl Window {
     }
 
     /// Set the window title.
+    ///
+    /// AppCore takes the title as a NUL-terminated string, so any NUL
+    /// characters in `title` are removed.
     pub fn set_title(&self, title: &str) {
-        let c_string = CString::new(title).unwrap();
+        let c_string = crate::string::to_c_string_lossy(title);
         unsafe {
             self.lib
                 .appcore()
@@ -164,6 +328,53 @@ impl Window {
         }
     }
 
+    /// Get the platform window handle, to use the window with native
+    /// windowing APIs.
+    ///
+    /// See [`NativeWindowHandle`] for the handle type on each platform.
+    pub fn native_handle(&self) -> NativeWindowHandle {
+        let handle = unsafe { self.lib.appcore().ulWindowGetNativeHandle(self.internal) };
+
+        #[cfg(target_os = "windows")]
+        return NativeWindowHandle::Hwnd(handle);
+        #[cfg(target_os = "macos")]
+        return NativeWindowHandle::NsWindow(handle);
+        #[cfg(target_os = "linux")]
+        return NativeWindowHandle::GlfwWindow(handle);
+        #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+        return NativeWindowHandle::Other(handle);
+    }
+
+    /// Set the icon of the window from a [`Bitmap`] in
+    /// [`BitmapFormat::Bgra8UnormSrgb`](crate::bitmap::BitmapFormat::Bgra8UnormSrgb) format.
+    ///
+    /// This is only supported on Windows, where the icon is set with `WM_SETICON`.
+    /// The icon set by the previous call on this window (or its handles) is
+    /// destroyed, and the last one is destroyed with the window.
+    ///
+    /// AppCore doesn't expose a way to do this on Linux, and on macOS the
+    /// application icon comes from the app bundle, so [`WindowError::NotSupported`]
+    /// is always returned on other platforms, without changing the window.
+    pub fn set_icon(&self, bitmap: &Bitmap) -> Result<(), WindowError> {
+        #[cfg(target_os = "windows")]
+        unsafe {
+            let icon = win32::set_icon(
+                &self.lib,
+                self.native_handle().as_ptr(),
+                bitmap,
+                self.handle.icon.get(),
+            )?;
+            self.handle.icon.set(icon);
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = bitmap;
+            Err(WindowError::NotSupported)
+        }
+    }
+
     /// Set the cursor.
     pub fn set_cursor(&self, cursor: Cursor) {
         unsafe {
@@ -211,28 +422,141 @@ impl Window {
         }
     }
 
+    /// Called when the Window is closed.
+    ///
+    /// # Callback Arguments
+    /// * `window: &Window` - The window that fired the event (eg. self)
+    pub fn set_close_callback<F>(&self, mut callback: F)
+    where
+        F: FnMut(&Window) + 'static,
+    {
+        // captured by value, `self` may be moved after this call
+        let lib = self.lib.clone();
+        self.set_raw_close_callback(move |ul_window| {
+            let _appcore = crate::callback_panic::enter_appcore_callback();
+            let window = unsafe { &Window::from_raw(lib.clone(), ul_window).unwrap() };
+            callback(window);
+        });
+    }
+
     set_callback! {
-        /// Called when the Window is closed.
-        ///
-        /// # Callback Arguments
-        /// * `window: &Window` - The window that fired the event (eg. self)
-        pub fn set_close_callback(&self, callback: FnMut(window: &Window)) :
-            [Window::lib.appcore()][s] ulWindowSetCloseCallback(ul_window: ul_sys::ULWindow) {
-               let window = &Window::from_raw(s.lib.clone(), ul_window).unwrap();
-        }
+        /// Set the AppCore close callback, only used by [`Window::set_close_callback`].
+        fn set_raw_close_callback(&self, callback: FnMut(ul_window: ul_sys::ULWindow)) :
+            [Window::lib.appcore()] ulWindowSetCloseCallback(ul_window: ul_sys::ULWindow) {}
+    }
+
+    /// Set a callback to be called when the Window is resized.
+    ///
+    /// # Callback Arguments
+    /// * `window: &Window` - The window that fired the event (eg. self)
+    /// * `width: u32` - The new width of the window (in pixels)
+    /// * `height: u32` - The new height of the window (in pixels)
+    pub fn set_resize_callback<F>(&self, callback: F)
+    where
+        F: FnMut(&Window, u32, u32) + 'static,
+    {
+        *self.state.resize_callback.borrow_mut() = Some(Box::new(callback));
+        self.hook_resize();
+    }
+
+    /// Set a callback to be called when the DPI scale of the Window
+    /// changes, for example when it is moved to a monitor with a different
+    /// scale.
+    ///
+    /// AppCore doesn't report DPI changes directly, but the size of the
+    /// window in pixels changes with the scale, so this is detected when
+    /// the Window is resized, right before the callback of
+    /// [`Window::set_resize_callback`] is called.
+    ///
+    /// # Callback Arguments
+    /// * `window: &Window` - The window that fired the event (eg. self)
+    /// * `scale: f64` - The new scale of the window (see [`Window::scale`])
+    pub fn set_dpi_changed_callback<F>(&self, callback: F)
+    where
+        F: FnMut(&Window, f64) + 'static,
+    {
+        *self.state.dpi_changed_callback.borrow_mut() = Some(Box::new(callback));
+        self.hook_resize();
+    }
+
+    /// Keep the device scale of the views of the overlays of this window
+    /// in sync with the scale of the window, see
+    /// [`Window::sync_overlay_scales`].
+    ///
+    /// The overlays are synced every time the window is resized, which
+    /// includes DPI changes, before the callbacks of
+    /// [`Window::set_dpi_changed_callback`] and
+    /// [`Window::set_resize_callback`] are called.
+    pub fn enable_auto_device_scale(&self) {
+        self.state.auto_device_scale.set(true);
+        self.hook_resize();
+        self.sync_overlay_scales();
+    }
+
+    /// Set the device scale of the views of all the overlays created from
+    /// this window to the scale of the window (see [`View::set_device_scale`]
+    /// and [`Window::scale`]).
+    ///
+    /// If the scale of the window changed since it was last checked, the
+    /// overlays are also resized and moved by the ratio of the two scales,
+    /// so that they cover the same area of the window.
+    ///
+    /// Note that this replaces any device scale set manually on the views,
+    /// use [`View::set_page_zoom`] to zoom the content of an overlay
+    /// instead.
+    ///
+    /// This is done automatically after [`Window::enable_auto_device_scale`],
+    /// it is only needed when managing the scales manually.
+    pub fn sync_overlay_scales(&self) {
+        unsafe { sync_overlay_scales(&self.lib, self.internal, &self.state) };
     }
 
     set_callback! {
-        /// Called when the Window is resized.
-        ///
-        /// # Callback Arguments
-        /// * `window: &Window` - The window that fired the event (eg. self)
-        /// * `width: u32` - The new width of the window (in pixels)
-        /// * `height: u32` - The new height of the window (in pixels)
-        pub fn set_resize_callback(&self, callback: FnMut(window: &Window, width: u32, height: u32)) :
-            [Window::lib.appcore()][s] ulWindowSetResizeCallback(ul_window: ul_sys::ULWindow, width: u32, height: u32) {
-               let window = &Window::from_raw(s.lib.clone(), ul_window).unwrap();
+        /// Set the AppCore resize callback, only used by [`Window::hook_resize`].
+        fn set_raw_resize_callback(&self, callback: FnMut(ul_window: ul_sys::ULWindow, width: u32, height: u32)) :
+            [Window::lib.appcore()] ulWindowSetResizeCallback(ul_window: ul_sys::ULWindow, width: u32, height: u32) {}
+    }
+
+    /// Set the AppCore resize callback to dispatch the events to the
+    /// callbacks in the state of the window, if it isn't already set.
+    fn hook_resize(&self) {
+        if self.state.resize_hooked.replace(true) {
+            return;
         }
+
+        let lib = self.lib.clone();
+        let internal = self.internal;
+        let state = self.state.clone();
+        self.set_raw_resize_callback(move |ul_window, width, height| {
+            let _appcore = crate::callback_panic::enter_appcore_callback();
+            let window = unsafe { &Window::from_raw(lib.clone(), ul_window).unwrap() };
+
+            let new_scale = if state.auto_device_scale.get() {
+                unsafe { sync_overlay_scales(&lib, internal, &state) }
+            } else {
+                let scale = window.scale();
+                (state.scale.replace(scale) != scale).then_some(scale)
+            };
+
+            // the callbacks are taken out while called, so that they can
+            // replace themselves
+            if let Some(scale) = new_scale {
+                let callback = state.dpi_changed_callback.borrow_mut().take();
+                if let Some(mut callback) = callback {
+                    callback(window, scale);
+                    state
+                        .dpi_changed_callback
+                        .borrow_mut()
+                        .get_or_insert(callback);
+                }
+            }
+
+            let callback = state.resize_callback.borrow_mut().take();
+            if let Some(mut callback) = callback {
+                callback(window, width, height);
+                state.resize_callback.borrow_mut().get_or_insert(callback);
+            }
+        });
     }
 
     //pub fn is_accelerated(&self) -> bool {
@@ -254,9 +578,40 @@ impl Window {
     /// * `x` - The x-position (offset from the left of this Window), in pixels.
     /// * `y` - The y-position (offset from the top of this Window), in pixels.
     ///
-    /// Returns [`None`] if failed to create [`Overlay`].
+    /// Returns [`None`] on failure, use [`Window::try_create_overlay`] to get
+    /// the error.
+    #[deprecated(note = "use `try_create_overlay` instead, it returns the error")]
     pub fn create_overlay(&self, width: u32, height: u32, x: i32, y: i32) -> Option<Overlay> {
-        unsafe { Overlay::create(self.lib.clone(), self.internal, width, height, x, y) }
+        self.try_create_overlay(width, height, x, y).ok()
+    }
+
+    /// Create a new Overlay.
+    ///
+    /// # Arguments
+    /// * `width` - The width in pixels.
+    /// * `height` - The height in pixels.
+    /// * `x` - The x-position (offset from the left of this Window), in pixels.
+    /// * `y` - The y-position (offset from the top of this Window), in pixels.
+    ///
+    pub fn try_create_overlay(
+        &self,
+        width: u32,
+        height: u32,
+        x: i32,
+        y: i32,
+    ) -> Result<Overlay, Error> {
+        unsafe {
+            Overlay::create(
+                self.lib.clone(),
+                self.clone_handle(),
+                &self.state.overlays,
+                width,
+                height,
+                x,
+                y,
+            )
+        }
+        .ok_or_else(|| CreationError::NullReference.into())
     }
 
     /// Create a new Overlay, wrapping an existing view.
@@ -266,16 +621,84 @@ impl Window {
     /// * `x` - The x-position (offset from the left of this Window), in pixels.
     /// * `y` - The y-position (offset from the top of this Window), in pixels.
     ///
-    /// Returns [`None`] if failed to create [`Overlay`].
+    /// Returns [`None`] on failure, use [`Window::try_create_overlay_with_view`]
+    /// to get the error.
+    #[deprecated(note = "use `try_create_overlay_with_view` instead, it returns the error")]
     pub fn create_overlay_with_view(&self, view: View, x: i32, y: i32) -> Option<Overlay> {
-        unsafe { Overlay::create_with_view(self.lib.clone(), self.internal, view, x, y) }
+        self.try_create_overlay_with_view(view, x, y).ok()
+    }
+
+    /// Create a new Overlay, wrapping an existing view.
+    ///
+    /// # Arguments
+    /// * `view` - The view to wrap (will use its width and height).
+    /// * `x` - The x-position (offset from the left of this Window), in pixels.
+    /// * `y` - The y-position (offset from the top of this Window), in pixels.
+    ///
+    pub fn try_create_overlay_with_view(
+        &self,
+        view: View,
+        x: i32,
+        y: i32,
+    ) -> Result<Overlay, Error> {
+        unsafe {
+            Overlay::create_with_view(
+                self.lib.clone(),
+                self.clone_handle(),
+                &self.state.overlays,
+                view,
+                x,
+                y,
+            )
+        }
+        .ok_or_else(|| CreationError::NullReference.into())
     }
 }
 
-impl Drop for Window {
-    fn drop(&mut self) {
-        if self.need_to_destroy {
-            unsafe { self.lib.appcore().ulDestroyWindow(self.internal) }
+/// Set the device scale of the overlays in `state` to the scale of
+/// `window`, resizing them if the scale changed.
+///
+/// Returns the new scale if it changed since the last check.
+unsafe fn sync_overlay_scales(
+    lib: &Library,
+    window: ul_sys::ULWindow,
+    state: &WindowState,
+) -> Option<f64> {
+    let scale = lib.appcore().ulWindowGetScale(window);
+    let old_scale = state.scale.replace(scale);
+    let ratio = if old_scale > 0.0 && old_scale != scale {
+        scale / old_scale
+    } else {
+        1.0
+    };
+    let resize = |value: f64| (value * ratio).round();
+
+    // copy the list, so that the registry is not borrowed while calling
+    // into AppCore
+    let overlays = state.overlays.borrow().clone();
+    for overlay in overlays {
+        let view = lib.appcore().ulOverlayGetView(overlay);
+        if lib.ultralight().ulViewGetDeviceScale(view) != scale {
+            lib.ultralight().ulViewSetDeviceScale(view, scale);
+        }
+
+        if ratio != 1.0 {
+            let width = lib.appcore().ulOverlayGetWidth(overlay);
+            let height = lib.appcore().ulOverlayGetHeight(overlay);
+            let x = lib.appcore().ulOverlayGetX(overlay);
+            let y = lib.appcore().ulOverlayGetY(overlay);
+            lib.appcore().ulOverlayResize(
+                overlay,
+                resize(width as f64).max(1.0) as u32,
+                resize(height as f64).max(1.0) as u32,
+            );
+            lib.appcore().ulOverlayMoveTo(
+                overlay,
+                resize(x as f64) as i32,
+                resize(y as f64) as i32,
+            );
         }
     }
+
+    (old_scale != scale).then_some(scale)
 }
@@ -0,0 +1,150 @@
+//! Win32 implementation of window operations not provided by AppCore.
+use std::ffi::c_void;
+
+use crate::{
+    bitmap::{Bitmap, BitmapFormat},
+    Library,
+};
+
+use super::WindowError;
+
+const WM_SETICON: u32 = 0x0080;
+const ICON_SMALL: usize = 0;
+const ICON_BIG: usize = 1;
+
+#[repr(C)]
+struct IconInfo {
+    f_icon: i32,
+    x_hotspot: u32,
+    y_hotspot: u32,
+    hbm_mask: *mut c_void,
+    hbm_color: *mut c_void,
+}
+
+#[link(name = "user32")]
+extern "system" {
+    fn CreateIconIndirect(piconinfo: *const IconInfo) -> *mut c_void;
+    fn DestroyIcon(hicon: *mut c_void) -> i32;
+    fn SendMessageW(hwnd: *mut c_void, msg: u32, wparam: usize, lparam: isize) -> isize;
+}
+
+#[link(name = "gdi32")]
+extern "system" {
+    fn CreateBitmap(
+        width: i32,
+        height: i32,
+        planes: u32,
+        bit_count: u32,
+        bits: *const c_void,
+    ) -> *mut c_void;
+    fn DeleteObject(ho: *mut c_void) -> i32;
+}
+
+/// Copy the pixels of `bitmap` into a tightly packed BGRA buffer with
+/// straight (not premultiplied) alpha, as expected by `CreateIconIndirect`.
+fn icon_pixels(lib: &Library, bitmap: &Bitmap) -> Result<Vec<u8>, WindowError> {
+    if !matches!(bitmap.format(), BitmapFormat::Bgra8UnormSrgb) {
+        return Err(WindowError::UnsupportedBitmapFormat);
+    }
+    if bitmap.is_empty() {
+        return Err(WindowError::EmptyBitmap);
+    }
+
+    let width = bitmap.width() as usize;
+    let height = bitmap.height() as usize;
+    let row_bytes = bitmap.row_bytes() as usize;
+
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    unsafe {
+        let raw = bitmap.to_ul();
+        lib.ultralight().ulBitmapLockPixels(raw);
+        let data = lib.ultralight().ulBitmapRawPixels(raw) as *const u8;
+        if !data.is_null() {
+            let data = std::slice::from_raw_parts(data, lib.ultralight().ulBitmapGetSize(raw));
+            for row in data.chunks(row_bytes).take(height) {
+                pixels.extend_from_slice(&row[..width * 4]);
+            }
+        }
+        lib.ultralight().ulBitmapUnlockPixels(raw);
+    }
+    if pixels.is_empty() {
+        return Err(WindowError::EmptyBitmap);
+    }
+
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u32;
+        if alpha != 0 && alpha != 255 {
+            for c in &mut pixel[..3] {
+                *c = ((*c as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+            }
+        }
+    }
+
+    Ok(pixels)
+}
+
+/// Set both the small and big icons of the window `hwnd`, returns the new
+/// icon, which must be given as `previous` to the next call, or destroyed
+/// with [`destroy_icon`] once the window is destroyed.
+///
+/// `previous` (if not null) is destroyed once replaced.
+pub(super) unsafe fn set_icon(
+    lib: &Library,
+    hwnd: *mut c_void,
+    bitmap: &Bitmap,
+    previous: *mut c_void,
+) -> Result<*mut c_void, WindowError> {
+    if hwnd.is_null() {
+        return Err(WindowError::PlatformError);
+    }
+
+    let pixels = icon_pixels(lib, bitmap)?;
+    let width = bitmap.width() as i32;
+    let height = bitmap.height() as i32;
+
+    // the mask is ignored for 32-bit icons, but it must be provided.
+    // monochrome bitmap rows are aligned to 16 bits.
+    let mask = vec![0u8; ((width as usize + 15) / 16) * 2 * height as usize];
+
+    let hbm_color = CreateBitmap(width, height, 1, 32, pixels.as_ptr() as _);
+    let hbm_mask = CreateBitmap(width, height, 1, 1, mask.as_ptr() as _);
+
+    let icon = if hbm_color.is_null() || hbm_mask.is_null() {
+        std::ptr::null_mut()
+    } else {
+        CreateIconIndirect(&IconInfo {
+            f_icon: 1,
+            x_hotspot: 0,
+            y_hotspot: 0,
+            hbm_mask,
+            hbm_color,
+        })
+    };
+
+    // the icon keeps its own copy of the bitmaps
+    if !hbm_color.is_null() {
+        DeleteObject(hbm_color);
+    }
+    if !hbm_mask.is_null() {
+        DeleteObject(hbm_mask);
+    }
+
+    if icon.is_null() {
+        return Err(WindowError::PlatformError);
+    }
+
+    SendMessageW(hwnd, WM_SETICON, ICON_SMALL, icon as isize);
+    SendMessageW(hwnd, WM_SETICON, ICON_BIG, icon as isize);
+
+    // both icons of the window were replaced, so it's no longer used
+    destroy_icon(previous);
+
+    Ok(icon)
+}
+
+/// Destroy an icon created by [`set_icon`], null is ignored.
+pub(super) unsafe fn destroy_icon(icon: *mut c_void) {
+    if !icon.is_null() {
+        DestroyIcon(icon);
+    }
+}
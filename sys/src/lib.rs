@@ -22,6 +22,16 @@ pub mod linked {
 
 pub use defines::*;
 
+/// The major version of the Ultralight SDK these bindings were generated
+/// from.
+pub const ULTRALIGHT_VERSION_MAJOR: u32 = 1;
+/// The minor version of the Ultralight SDK these bindings were generated
+/// from.
+pub const ULTRALIGHT_VERSION_MINOR: u32 = 4;
+/// The patch version of the Ultralight SDK these bindings were generated
+/// from.
+pub const ULTRALIGHT_VERSION_PATCH: u32 = 0;
+
 #[allow(private_bounds)]
 pub mod library {
     #[allow(clippy::missing_safety_doc)]
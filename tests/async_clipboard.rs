@@ -0,0 +1,77 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use ul_next::platform::{AsyncClipboard, Clipboard};
+
+/// A future completed from another thread, like a reply from a clipboard
+/// server.
+#[derive(Default)]
+struct Reply {
+    text: Option<String>,
+    waker: Option<Waker>,
+}
+
+struct ReplyFuture(Arc<Mutex<Reply>>);
+
+impl Future for ReplyFuture {
+    type Output = Option<String>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut reply = self.0.lock().unwrap();
+        match reply.text.take() {
+            Some(text) => Poll::Ready(Some(text)),
+            None => {
+                reply.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ThreadedClipboard {
+    text: Arc<Mutex<String>>,
+}
+
+impl AsyncClipboard for ThreadedClipboard {
+    fn clear(&mut self) {
+        self.text.lock().unwrap().clear();
+    }
+
+    fn read_plain_text_async(&mut self) -> Pin<Box<dyn Future<Output = Option<String>>>> {
+        let reply = Arc::new(Mutex::new(Reply::default()));
+        let text = self.text.clone();
+        {
+            let reply = reply.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(20));
+                let mut reply = reply.lock().unwrap();
+                reply.text = Some(text.lock().unwrap().clone());
+                if let Some(waker) = reply.waker.take() {
+                    waker.wake();
+                }
+            });
+        }
+        Box::pin(ReplyFuture(reply))
+    }
+
+    fn write_plain_text(&mut self, text: &str) {
+        *self.text.lock().unwrap() = text.to_string();
+    }
+}
+
+#[test]
+fn read_blocks_on_future() {
+    let mut clipboard = ThreadedClipboard::default();
+
+    Clipboard::write_plain_text(&mut clipboard, "hello");
+    assert_eq!(clipboard.read_plain_text().as_deref(), Some("hello"));
+
+    Clipboard::clear(&mut clipboard);
+    assert_eq!(clipboard.read_plain_text().as_deref(), Some(""));
+}
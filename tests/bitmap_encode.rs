@@ -0,0 +1,110 @@
+#![cfg(all(feature = "png", feature = "jpeg"))]
+
+use ul_next::{
+    bitmap::{Bitmap, BitmapFormat, OwnedBitmap},
+    Library,
+};
+
+const WIDTH: u32 = 16;
+const HEIGHT: u32 = 8;
+
+/// BGRA pixels, the left half is opaque red, the right half is half
+/// transparent (premultiplied) blue.
+fn bgra_pixels() -> Vec<u8> {
+    let mut pixels = Vec::new();
+    for _ in 0..HEIGHT {
+        for x in 0..WIDTH {
+            if x < WIDTH / 2 {
+                pixels.extend_from_slice(&[0, 0, 255, 255]);
+            } else {
+                pixels.extend_from_slice(&[128, 0, 0, 128]);
+            }
+        }
+    }
+    pixels
+}
+
+fn decode_png(data: &[u8]) -> (png::OutputInfo, Vec<u8>) {
+    let decoder = png::Decoder::new(data);
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+    buf.truncate(info.buffer_size());
+    (info, buf)
+}
+
+fn assert_close(actual: &[u8], expected: &[u8], tolerance: u8) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!(
+            a.abs_diff(*e) <= tolerance,
+            "{actual:?} is not close to {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn png_round_trip() {
+    let lib = unsafe { Library::load().unwrap() };
+    let mut bitmap = Bitmap::create_from_pixels(
+        lib,
+        WIDTH,
+        HEIGHT,
+        BitmapFormat::Bgra8UnormSrgb,
+        &bgra_pixels(),
+    )
+    .unwrap();
+
+    let owned = OwnedBitmap::from_bitmap(&mut bitmap).unwrap();
+    for encoded in [bitmap.encode_png().unwrap(), owned.encode_png().unwrap()] {
+        let (info, pixels) = decode_png(&encoded);
+        assert_eq!((info.width, info.height), (WIDTH, HEIGHT));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+
+        // RGBA with straight alpha
+        assert_eq!(&pixels[..4], &[255, 0, 0, 255]);
+        let last = pixels.len() - 4;
+        assert_eq!(&pixels[last..], &[0, 0, 255, 128]);
+    }
+}
+
+#[test]
+fn png_grayscale() {
+    let lib = unsafe { Library::load().unwrap() };
+    let pixels = (0..WIDTH * HEIGHT).map(|i| i as u8).collect::<Vec<_>>();
+    let bitmap =
+        Bitmap::create_from_pixels(lib, WIDTH, HEIGHT, BitmapFormat::A8Unorm, &pixels).unwrap();
+
+    let (info, decoded) = decode_png(&bitmap.encode_png().unwrap());
+    assert_eq!(info.color_type, png::ColorType::Grayscale);
+    assert_eq!(decoded, pixels);
+}
+
+#[test]
+fn jpeg_round_trip() {
+    let lib = unsafe { Library::load().unwrap() };
+    let mut bitmap = Bitmap::create_from_pixels(
+        lib,
+        WIDTH,
+        HEIGHT,
+        BitmapFormat::Bgra8UnormSrgb,
+        &bgra_pixels(),
+    )
+    .unwrap();
+
+    let owned = OwnedBitmap::from_bitmap(&mut bitmap).unwrap();
+    for encoded in [
+        bitmap.encode_jpeg(100).unwrap(),
+        owned.encode_jpeg(100).unwrap(),
+    ] {
+        let mut decoder = jpeg_decoder::Decoder::new(encoded.as_slice());
+        let pixels = decoder.decode().unwrap();
+        let info = decoder.info().unwrap();
+        assert_eq!((info.width as u32, info.height as u32), (WIDTH, HEIGHT));
+        assert_eq!(info.pixel_format, jpeg_decoder::PixelFormat::RGB24);
+
+        // lossy, and the alpha is dropped
+        assert_close(&pixels[..3], &[255, 0, 0], 24);
+        let last = pixels.len() - 3;
+        assert_close(&pixels[last..], &[0, 0, 128], 24);
+    }
+}
@@ -0,0 +1,88 @@
+use ul_next::{
+    bitmap::{Bitmap, BitmapFormat, OwnedBitmap},
+    Library,
+};
+
+/// The pixel at (`x`, `y`), encoding its position.
+fn pixel(x: u32, y: u32) -> [u8; 4] {
+    [x as u8, y as u8, 0, 255]
+}
+
+/// Read the pixel at (`x`, `y`) of `pixels` with `row_bytes` stride.
+fn read(pixels: &[u8], row_bytes: u32, x: u32, y: u32) -> [u8; 4] {
+    let start = (y * row_bytes + x * 4) as usize;
+    pixels[start..start + 4].try_into().unwrap()
+}
+
+fn owned_bitmap(width: u32, height: u32) -> OwnedBitmap {
+    let pixels = (0..height)
+        .flat_map(|y| (0..width).flat_map(move |x| pixel(x, y)))
+        .collect();
+    OwnedBitmap::from_pixels(width, height, BitmapFormat::Bgra8UnormSrgb, pixels).unwrap()
+}
+
+#[test]
+fn flip_owned_bitmap() {
+    let (width, height) = (3, 5);
+
+    let mut bitmap = owned_bitmap(width, height);
+    bitmap.flip_vertical().unwrap();
+    let pixels = bitmap.pixels().unwrap();
+    for y in 0..height {
+        for x in 0..width {
+            assert_eq!(read(pixels, width * 4, x, y), pixel(x, height - 1 - y));
+        }
+    }
+
+    let mut bitmap = owned_bitmap(width, height);
+    bitmap.flip_horizontal().unwrap();
+    let pixels = bitmap.pixels().unwrap();
+    for y in 0..height {
+        for x in 0..width {
+            assert_eq!(read(pixels, width * 4, x, y), pixel(width - 1 - x, y));
+        }
+    }
+
+    let mut empty = OwnedBitmap::empty(4, 4, BitmapFormat::Bgra8UnormSrgb);
+    assert!(empty.flip_vertical().is_err());
+    assert!(empty.flip_horizontal().is_err());
+}
+
+#[test]
+fn flip_bitmap_with_stride() {
+    let lib = unsafe { Library::load().unwrap() };
+    // an odd width, so that the rows are likely padded
+    let (width, height) = (5, 4);
+
+    let mut bitmap = Bitmap::create(
+        lib,
+        width as usize,
+        height as usize,
+        BitmapFormat::Bgra8UnormSrgb,
+    )
+    .unwrap();
+    let row_bytes = bitmap.row_bytes();
+    assert!(row_bytes >= width * 4);
+    {
+        let mut pixels = bitmap.lock_pixels().unwrap();
+        for y in 0..height {
+            for x in 0..width {
+                let start = (y * row_bytes + x * 4) as usize;
+                pixels[start..start + 4].copy_from_slice(&pixel(x, y));
+            }
+        }
+    }
+
+    bitmap.flip_vertical().unwrap();
+    bitmap.flip_horizontal().unwrap();
+
+    let pixels = bitmap.lock_pixels().unwrap();
+    for y in 0..height {
+        for x in 0..width {
+            assert_eq!(
+                read(&pixels, row_bytes, x, y),
+                pixel(width - 1 - x, height - 1 - y)
+            );
+        }
+    }
+}
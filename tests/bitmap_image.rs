@@ -0,0 +1,43 @@
+#![cfg(feature = "image-compat")]
+
+use ul_next::{
+    bitmap::{Bitmap, BitmapFormat},
+    Library,
+};
+
+#[test]
+fn bgra_bitmap_to_image_and_back() {
+    let lib = unsafe { Library::load().unwrap() };
+
+    // opaque red, then half transparent (premultiplied) blue
+    let pixels = [0, 0, 255, 255, 128, 0, 0, 128];
+    let bitmap =
+        Bitmap::create_from_pixels(lib.clone(), 2, 1, BitmapFormat::Bgra8UnormSrgb, &pixels)
+            .unwrap();
+
+    let image = bitmap.to_image_buffer().unwrap();
+    let rgba = image.as_rgba8().unwrap();
+    assert_eq!(rgba.dimensions(), (2, 1));
+    assert_eq!(rgba.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    assert_eq!(rgba.get_pixel(1, 0).0, [0, 0, 255, 128]);
+
+    let mut bitmap = Bitmap::from_image_buffer(lib, &image).unwrap();
+    assert!(matches!(bitmap.format(), BitmapFormat::Bgra8UnormSrgb));
+    assert_eq!(&bitmap.lock_pixels().unwrap()[..8], &pixels);
+}
+
+#[test]
+fn a8_bitmap_to_gray_image_and_back() {
+    let lib = unsafe { Library::load().unwrap() };
+
+    let pixels = [0, 64, 128, 255];
+    let bitmap =
+        Bitmap::create_from_pixels(lib.clone(), 4, 1, BitmapFormat::A8Unorm, &pixels).unwrap();
+
+    let image = bitmap.to_image_buffer().unwrap();
+    assert_eq!(image.as_luma8().unwrap().as_raw(), &pixels);
+
+    let mut bitmap = Bitmap::from_image_buffer(lib, &image).unwrap();
+    assert!(matches!(bitmap.format(), BitmapFormat::A8Unorm));
+    assert_eq!(&bitmap.lock_pixels().unwrap()[..4], &pixels);
+}
@@ -0,0 +1,53 @@
+use ul_next::{
+    bitmap::{Bitmap, BitmapFormat},
+    Library,
+};
+
+#[test]
+fn iterate_pixels_with_stride() {
+    let lib = unsafe { Library::load().unwrap() };
+    // an odd width, so that the rows are likely padded
+    let (width, height) = (5, 3);
+
+    let mut bitmap = Bitmap::create(
+        lib,
+        width as usize,
+        height as usize,
+        BitmapFormat::Bgra8UnormSrgb,
+    )
+    .unwrap();
+    let row_bytes = bitmap.row_bytes();
+
+    let mut visited = Vec::new();
+    bitmap
+        .iterate_pixels(|x, y, pixel| {
+            assert_eq!(pixel.len(), 4);
+            visited.push((x, y));
+            pixel.copy_from_slice(&[x as u8, y as u8, 0, 255]);
+        })
+        .unwrap();
+    let expected = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .collect::<Vec<_>>();
+    assert_eq!(visited, expected);
+
+    // the pixels were written at their place in the strided buffer
+    {
+        let pixels = bitmap.lock_pixels().unwrap();
+        for y in 0..height {
+            for x in 0..width {
+                let start = (y * row_bytes + x * 4) as usize;
+                assert_eq!(pixels[start..start + 4], [x as u8, y as u8, 0, 255]);
+            }
+        }
+    }
+
+    let mut read = Vec::new();
+    bitmap
+        .for_each_pixel(|x, y, pixel| {
+            assert_eq!(pixel, [x as u8, y as u8, 0, 255]);
+            read.push((x, y));
+        })
+        .unwrap();
+    assert_eq!(read, expected);
+}
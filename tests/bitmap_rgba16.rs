@@ -0,0 +1,81 @@
+mod common;
+
+use ul_next::bitmap::{BitmapFormat, OwnedBitmap};
+
+#[test]
+fn to_rgba16_swizzles_and_expands() {
+    let pixels = vec![
+        0, 1, 2, 255, // BGRA
+        255, 128, 64, 0, //
+        10, 20, 30, 40, //
+        0, 0, 0, 0,
+    ];
+    let bitmap = OwnedBitmap::from_pixels(2, 2, BitmapFormat::Bgra8UnormSrgb, pixels).unwrap();
+
+    assert_eq!(
+        bitmap.to_rgba16().unwrap(),
+        [
+            2 * 257,
+            257,
+            0,
+            u16::MAX,
+            64 * 257,
+            128 * 257,
+            u16::MAX,
+            0,
+            30 * 257,
+            20 * 257,
+            10 * 257,
+            40 * 257,
+            0,
+            0,
+            0,
+            0,
+        ]
+    );
+
+    let alpha = OwnedBitmap::from_pixels(2, 2, BitmapFormat::A8Unorm, vec![0; 4]).unwrap();
+    assert!(alpha.to_rgba16().is_err());
+    let empty = OwnedBitmap::empty(2, 2, BitmapFormat::Bgra8UnormSrgb);
+    assert!(empty.to_rgba16().is_err());
+}
+
+#[test]
+fn to_rgba16_keeps_gradient_steps() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+    // an odd width, so that the rows of the surface are likely padded
+    let (width, height) = (255, 4);
+    let view = common::create_view(&lib, &renderer, width, height);
+
+    common::load_and_wait(&renderer, &view, |view| {
+        view.load_html(
+            "<html><body style=\"margin: 0; height: 100vh; \
+                background: linear-gradient(to right, black, white)\"></body></html>",
+        )
+        .unwrap()
+    });
+    renderer.render().unwrap();
+
+    let mut bitmap = view.surface().unwrap().bitmap().unwrap();
+    let bitmap = OwnedBitmap::from_bitmap(&mut bitmap).unwrap();
+    let pixels = bitmap.to_rgba16().unwrap();
+    assert_eq!(pixels.len(), (width * height * 4) as usize);
+
+    // the red channel of the first row goes up smoothly, without steps
+    // larger than a few 8-bit levels
+    let row = &pixels[..(width * 4) as usize];
+    let reds = row
+        .chunks_exact(4)
+        .map(|pixel| pixel[0])
+        .collect::<Vec<_>>();
+    let max_step = reds
+        .windows(2)
+        .map(|step| step[1].saturating_sub(step[0]))
+        .max()
+        .unwrap();
+    assert!(reds.windows(2).all(|step| step[1] >= step[0]));
+    assert!(reds[0] < 4 * 257 && reds[reds.len() - 1] > 251 * 257);
+    assert!(max_step <= 3 * 257, "max step {max_step}");
+    assert!(row.chunks_exact(4).all(|pixel| pixel[3] == u16::MAX));
+}
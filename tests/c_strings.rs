@@ -0,0 +1,59 @@
+use ul_next::{
+    bitmap::{Bitmap, BitmapError, BitmapFormat},
+    config::Config,
+    javascript::{JSContext, JSString, JSValue},
+    Library,
+};
+
+const MULTI_BYTE: &str = "héllo, 世界 🌍";
+
+#[test]
+fn js_string_round_trip() {
+    let lib = unsafe { Library::load().unwrap() };
+
+    let string = JSString::new(lib.clone(), MULTI_BYTE);
+    // length in UTF-16 code units
+    assert_eq!(string.len(), MULTI_BYTE.encode_utf16().count());
+    assert_eq!(string.to_string(), MULTI_BYTE);
+
+    let with_nul = "before\0after";
+    let string = JSString::new(lib, with_nul);
+    assert_eq!(string.len(), with_nul.len());
+    assert_eq!(string.to_string(), with_nul);
+}
+
+#[test]
+fn js_value_string_with_nul() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let value = JSValue::new_string(&ctx, "a\0b");
+    ctx.global_object()
+        .set_property("value", &value, Default::default())
+        .unwrap();
+    let length = ctx.evaluate_script("value.length", None, None, 1).unwrap();
+    assert_eq!(length.as_number().unwrap(), 3.0);
+}
+
+#[test]
+fn config_strings_with_nul() {
+    let lib = unsafe { Library::load().unwrap() };
+
+    // strings are passed with their length, this must not truncate or panic
+    let config = Config::start()
+        .resource_path_prefix("resources\0/")
+        .user_stylesheet(MULTI_BYTE)
+        .try_build(lib);
+    assert!(config.is_ok());
+}
+
+#[test]
+fn png_path_with_nul() {
+    let lib = unsafe { Library::load().unwrap() };
+    let bitmap = Bitmap::create(lib, 2, 2, BitmapFormat::Bgra8UnormSrgb).unwrap();
+
+    assert!(matches!(
+        bitmap.write_to_png("image\0.png"),
+        Err(BitmapError::FailedPngWrite)
+    ));
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use ul_next::{
+    config::Config,
+    javascript::{JSObject, JSPropertyAttributes},
+    platform,
+    renderer::Renderer,
+    view::{View, ViewConfig},
+    Library,
+};
+
+fn create_renderer() -> (Arc<Library>, Renderer) {
+    let lib = unsafe { Library::load_with_appcore().unwrap() };
+
+    platform::enable_platform_fontloader(lib.clone());
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+    let renderer = Renderer::create(config).unwrap();
+
+    (lib, renderer)
+}
+
+fn create_view(lib: &Arc<Library>, renderer: &Renderer) -> View {
+    let view_config = ViewConfig::start()
+        .is_accelerated(false)
+        .try_build(lib.clone())
+        .unwrap();
+    renderer.create_view(100, 100, &view_config, None).unwrap()
+}
+
+#[test]
+fn panic_in_callbacks() {
+    let (lib, renderer) = create_renderer();
+
+    // view callback
+    let view = create_view(&lib, &renderer);
+    view.set_add_console_message_callback(
+        |_view, _source, _level, _message, _line, _column, _source_id| {
+            panic!("console boom");
+        },
+    );
+
+    let _ = view.evaluate_script("console.log('hello')").unwrap();
+    renderer.update().unwrap();
+
+    let payload = lib.take_last_callback_panic().unwrap();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"console boom"));
+
+    // javascript function
+    let view = create_view(&lib, &renderer);
+
+    {
+        let ctx = view.lock_js_context();
+        let func = JSObject::new_function_with_callback(&ctx, |_ctx, _this, _args| {
+            panic!("boom");
+        });
+        ctx.global_object()
+            .set_property("panicInRust", &func, JSPropertyAttributes::default())
+            .unwrap();
+    }
+
+    assert!(lib.take_last_callback_panic().is_none());
+
+    // the panic doesn't unwind through Ultralight
+    let _ = view.evaluate_script("panicInRust()").unwrap();
+
+    // the panic is stored for the thread the callback ran on
+    let other_lib = lib.clone();
+    let on_other_thread =
+        std::thread::spawn(move || other_lib.take_last_callback_panic().is_some())
+            .join()
+            .unwrap();
+    assert!(!on_other_thread);
+
+    let payload = lib.take_last_callback_panic().unwrap();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"));
+    assert!(lib.take_last_callback_panic().is_none());
+}
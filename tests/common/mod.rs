@@ -0,0 +1,104 @@
+//! Setup shared by the tests rendering pages with Ultralight.
+// each test uses only some of the helpers
+#![allow(dead_code)]
+
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use ul_next::{
+    config::Config,
+    platform::{self, filesystems::MemoryFileSystem},
+    renderer::Renderer,
+    view::{View, ViewConfig},
+    Library,
+};
+
+/// How long to wait for a page before failing the test.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Load the library with `AppCore`, and enable the platform font loader.
+pub fn load_library() -> Arc<Library> {
+    let lib = unsafe { Library::load_with_appcore().unwrap() };
+    platform::enable_platform_fontloader(lib.clone());
+    lib
+}
+
+/// A file system with the ICU data and the certificates that Ultralight
+/// loads from `resources/`, to add the files of the test to before passing
+/// it to [`platform::set_filesystem`].
+pub fn memory_filesystem() -> MemoryFileSystem {
+    let mut fs = MemoryFileSystem::new();
+    for resource in ["icudt67l.dat", "cacert.pem"] {
+        let data = std::fs::read(format!("examples/resources/{resource}")).unwrap();
+        fs.insert(&format!("resources/{resource}"), data);
+    }
+    fs
+}
+
+/// Create a renderer with the default config.
+pub fn create_renderer(lib: &Arc<Library>) -> Renderer {
+    let config = Config::start().try_build(lib.clone()).unwrap();
+    Renderer::create(config).unwrap()
+}
+
+/// The config of CPU rendered views.
+pub fn view_config(lib: &Arc<Library>) -> ViewConfig {
+    ViewConfig::start()
+        .is_accelerated(false)
+        .try_build(lib.clone())
+        .unwrap()
+}
+
+/// Create a CPU rendered view in the default session.
+pub fn create_view(lib: &Arc<Library>, renderer: &Renderer, width: u32, height: u32) -> View {
+    let view_config = view_config(lib);
+    renderer
+        .create_view(width, height, &view_config, None)
+        .unwrap()
+}
+
+/// Update `renderer` until `done` returns `true`, failing the test if it
+/// takes too long.
+pub fn update_until(renderer: &Renderer, waiting_for: &str, mut done: impl FnMut() -> bool) {
+    let start = Instant::now();
+    while !done() {
+        assert!(
+            start.elapsed() < TIMEOUT,
+            "timed out waiting for {waiting_for}"
+        );
+        renderer.update().unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Update `renderer` for `duration`.
+pub fn update_for(renderer: &Renderer, duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        renderer.update().unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Start loading a page in `view` with `load`, and update `renderer` until
+/// its main frame finished loading.
+///
+/// This replaces the finish loading callback of the view.
+pub fn load_and_wait(renderer: &Renderer, view: &View, load: impl FnOnce(&View)) {
+    let finished = Rc::new(Cell::new(false));
+    {
+        let finished = finished.clone();
+        view.set_finish_loading_callback(move |_view, _frame_id, is_main_frame, _url| {
+            if is_main_frame {
+                finished.set(true);
+            }
+        });
+    }
+
+    load(view);
+    update_until(renderer, "the page to load", || finished.get());
+}
@@ -0,0 +1,92 @@
+//! The snippets from the READMEs of the old `ul` and `ultralight` crates,
+//! ported only by changing the imports and passing the library.
+#![cfg(feature = "compat")]
+#![allow(deprecated)]
+
+mod common;
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use ul_next::{
+    compat::{Ultralight, UltralightConfig},
+    platform,
+};
+
+#[test]
+fn readme_screenshot() {
+    let lib = common::load_library();
+    platform::set_filesystem(lib.clone(), common::memory_filesystem());
+
+    let mut config = UltralightConfig::new();
+    config
+        .resource_path("./resources/")
+        .device_scale_hint(1.0)
+        .font_family_standard("Arial");
+
+    let mut ul = Ultralight::new(lib, Some(config)).unwrap();
+
+    ul.view(256, 256, false).unwrap();
+    ul.load_html("<html><body style=\"background: red\"><h1>Hello</h1></body></html>")
+        .unwrap();
+    ul.update_until_loaded().unwrap();
+    ul.render().unwrap();
+
+    let path = std::env::temp_dir().join(format!("ul-next-compat-{}.png", std::process::id()));
+    ul.write_png_to_file(&path).unwrap();
+    assert!(std::fs::metadata(&path).unwrap().len() > 0);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn readme_callbacks() {
+    let lib = common::load_library();
+    platform::set_filesystem(lib.clone(), common::memory_filesystem());
+
+    let mut ul = Ultralight::new(lib, None).unwrap();
+    ul.view(200, 100, false).unwrap();
+
+    let dom_ready = Rc::new(Cell::new(false));
+    let finished = Rc::new(Cell::new(false));
+    let title = Rc::new(RefCell::new(String::new()));
+    {
+        let dom_ready = dom_ready.clone();
+        ul.set_dom_ready(move || dom_ready.set(true)).unwrap();
+    }
+    {
+        let finished = finished.clone();
+        ul.set_finish_loading(move || finished.set(true)).unwrap();
+    }
+    {
+        let title = title.clone();
+        ul.set_change_title(move |new_title| *title.borrow_mut() = new_title)
+            .unwrap();
+    }
+
+    ul.load_html("<html><head><title>Old API</title></head><body></body></html>")
+        .unwrap();
+    common::update_until(ul.renderer(), "the page to load", || finished.get());
+
+    assert!(dom_ready.get());
+    assert_eq!(*title.borrow(), "Old API");
+    assert!(!ul.is_loading());
+
+    assert_eq!(
+        ul.evaluate_script("document.title.length * 2").unwrap(),
+        Ok("14".to_string())
+    );
+}
+
+#[test]
+fn no_view() {
+    let lib = common::load_library();
+    platform::set_filesystem(lib.clone(), common::memory_filesystem());
+
+    let mut ul = Ultralight::new(lib, None).unwrap();
+    assert!(ul.get_view().is_none());
+    assert!(ul.load_url("https://example.com").is_err());
+    assert!(ul.update_until_loaded().is_err());
+    assert!(!ul.is_loading());
+}
@@ -0,0 +1,43 @@
+use ul_next::{config::Config, error::CreationError, Library};
+
+#[test]
+fn create_nested_cache_dir() {
+    let lib = unsafe { Library::load().unwrap() };
+
+    let root = std::env::temp_dir().join(format!("ul-next-config-paths-{}", std::process::id()));
+    let cache_dir = root.join("nested").join("cache");
+    assert!(!cache_dir.exists());
+
+    // not created by default
+    Config::start()
+        .cache_path(&cache_dir)
+        .try_build(lib.clone())
+        .unwrap();
+    assert!(!cache_dir.exists());
+
+    Config::start()
+        .cache_path(&cache_dir)
+        .create_cache_dir(true)
+        .try_build(lib)
+        .unwrap();
+    assert!(cache_dir.is_dir());
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_cache_path() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let lib = unsafe { Library::load().unwrap() };
+
+    let path = std::env::temp_dir().join(OsStr::from_bytes(b"ul-next-\xff-cache"));
+    let result = Config::start().cache_path(&path).try_build(lib.clone());
+    assert!(matches!(result, Err(CreationError::InvalidPath(p)) if p == path));
+
+    // the deprecated `build` only reports the failure
+    #[allow(deprecated)]
+    let config = Config::start().cache_path(&path).build(lib);
+    assert!(config.is_none());
+}
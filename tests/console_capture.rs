@@ -0,0 +1,141 @@
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use ul_next::{
+    renderer::Renderer,
+    view::{ConsoleMessageLevel, View},
+};
+
+/// The messages are kept in a ring buffer, filtered by level.
+fn capture_console_messages(renderer: &Renderer, view: &View) {
+    let console = view.enable_console_capture(3);
+    assert_eq!(console.capacity(), 3);
+    assert!(console.is_empty());
+
+    let _ = view
+        .evaluate_script("console.log('one'); console.warn('two'); console.error('three');")
+        .unwrap();
+    renderer.update().unwrap();
+
+    let captured = console
+        .iter()
+        .map(|message| (message.level, message.message))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        captured,
+        vec![
+            (ConsoleMessageLevel::Log, "one".to_string()),
+            (ConsoleMessageLevel::Warning, "two".to_string()),
+            (ConsoleMessageLevel::Error, "three".to_string()),
+        ]
+    );
+    // `iter` doesn't remove the messages
+    assert_eq!(console.len(), 3);
+
+    // the oldest message is dropped when full
+    let _ = view.evaluate_script("console.log('four');").unwrap();
+    renderer.update().unwrap();
+    let drained = console.drain();
+    assert_eq!(
+        drained
+            .iter()
+            .map(|message| message.message.as_str())
+            .collect::<Vec<_>>(),
+        ["two", "three", "four"]
+    );
+    assert!(drained
+        .windows(2)
+        .all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    assert!(console.is_empty());
+
+    console.set_level_filter(Some(ConsoleMessageLevel::Warning));
+    let _ = view
+        .evaluate_script("console.log('five'); console.error('six');")
+        .unwrap();
+    renderer.update().unwrap();
+    let drained = console.drain();
+    assert_eq!(drained.len(), 1);
+    assert_eq!(drained[0].message, "six");
+    assert_eq!(drained[0].level, ConsoleMessageLevel::Error);
+
+    console.clear();
+    assert!(console.is_empty());
+}
+
+/// The capture and the console callback work together.
+fn capture_with_callback(renderer: &Renderer, view: &View) {
+    let console = view.enable_console_capture(10);
+
+    // setting the callback after the capture doesn't replace it
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    {
+        let messages = messages.clone();
+        view.set_add_console_message_callback(
+            move |_view, _source, _level, message, _line, _column, _source_id| {
+                messages.borrow_mut().push(message);
+            },
+        );
+    }
+    // the view filter only applies to the callback
+    view.set_console_message_filter(ConsoleMessageLevel::Error);
+
+    let _ = view
+        .evaluate_script("console.log('log'); console.error('error');")
+        .unwrap();
+    renderer.update().unwrap();
+
+    assert_eq!(*messages.borrow(), ["error"]);
+    assert_eq!(
+        console
+            .drain()
+            .into_iter()
+            .map(|message| message.message)
+            .collect::<Vec<_>>(),
+        ["log", "error"]
+    );
+}
+
+/// The capture and the filter keep working after the view is moved.
+fn capture_after_move(renderer: &Renderer, view: View) {
+    let console = view.enable_console_capture(10);
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    {
+        let messages = messages.clone();
+        view.set_add_console_message_callback(
+            move |view, _source, _level, message, _line, _column, _source_id| {
+                // the view given to the callback shares the filter
+                assert_eq!(
+                    view.console_message_filter(),
+                    Some(ConsoleMessageLevel::Error)
+                );
+                messages.borrow_mut().push(message);
+            },
+        );
+    }
+    view.set_console_message_filter(ConsoleMessageLevel::Error);
+
+    // the capture and the filter keep working after the view is moved
+    let views = [view];
+    let _ = views[0]
+        .evaluate_script("console.log('log'); console.error('error');")
+        .unwrap();
+    renderer.update().unwrap();
+
+    assert_eq!(*messages.borrow(), ["error"]);
+    assert_eq!(console.len(), 2);
+}
+
+// all the cases share a single renderer, only one can be created at a time
+#[test]
+fn console_capture() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+
+    let view = common::create_view(&lib, &renderer, 100, 100);
+    capture_console_messages(&renderer, &view);
+    let view = common::create_view(&lib, &renderer, 100, 100);
+    capture_with_callback(&renderer, &view);
+    let view = common::create_view(&lib, &renderer, 100, 100);
+    capture_after_move(&renderer, view);
+}
@@ -0,0 +1,53 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ul_next::{
+    config::Config,
+    platform,
+    renderer::Renderer,
+    view::{ConsoleMessageLevel, ViewConfig},
+    Library,
+};
+
+#[test]
+fn filter_console_messages() {
+    let lib = unsafe { Library::load_with_appcore().unwrap() };
+    platform::enable_platform_fontloader(lib.clone());
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+    let renderer = Renderer::create(config).unwrap();
+
+    let view_config = ViewConfig::start()
+        .is_accelerated(false)
+        .try_build(lib.clone())
+        .unwrap();
+    let view = renderer.create_view(100, 100, &view_config, None).unwrap();
+
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    {
+        let messages = messages.clone();
+        view.set_add_console_message_callback(
+            move |_view, _source, level, message, _line, _column, _source_id| {
+                messages.borrow_mut().push((level, message));
+            },
+        );
+    }
+    assert_eq!(view.console_message_filter(), None);
+
+    let log_all = "console.debug('d'); console.log('l'); console.warn('w'); console.error('e');";
+
+    let _ = view.evaluate_script(log_all).unwrap();
+    renderer.update().unwrap();
+    assert_eq!(messages.borrow().len(), 4);
+
+    messages.borrow_mut().clear();
+    view.set_console_message_filter(ConsoleMessageLevel::Warning);
+    let _ = view.evaluate_script(log_all).unwrap();
+    renderer.update().unwrap();
+    assert_eq!(
+        *messages.borrow(),
+        vec![
+            (ConsoleMessageLevel::Warning, "w".to_string()),
+            (ConsoleMessageLevel::Error, "e".to_string()),
+        ]
+    );
+}
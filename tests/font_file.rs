@@ -0,0 +1,28 @@
+mod common;
+
+use ul_next::platform::FontFile;
+
+const FONT: &[u8] = include_bytes!("resources/DejaVuSansMono.ttf");
+
+#[test]
+fn font_file_from_buffer() {
+    let lib = common::load_library();
+
+    let font = FontFile::from_buffer(lib.clone(), FONT);
+    assert!(font.is_some());
+    drop(font);
+
+    let font = FontFile::from_buffer(lib, FONT.to_vec());
+    assert!(font.is_some());
+}
+
+#[test]
+fn font_file_from_path() {
+    let lib = common::load_library();
+
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/resources/DejaVuSansMono.ttf"
+    );
+    assert!(FontFile::from_path(lib, path).is_some());
+}
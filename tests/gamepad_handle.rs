@@ -0,0 +1,168 @@
+//! Checks the events sent by `Gamepad` with a fake backend, so this
+//! doesn't need Ultralight.
+
+use std::cell::RefCell;
+
+use ul_next::{
+    error::CreationError,
+    renderer::gamepad::{Gamepad, GamepadBackend, GamepadError, GamepadSlots},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Call {
+    Describe(u32, String, u32, u32),
+    Connection(u32, bool),
+    Axis(u32, u32, f64),
+    Button(u32, u32, f64),
+}
+
+#[derive(Default)]
+struct FakeBackend {
+    slots: GamepadSlots,
+    calls: RefCell<Vec<Call>>,
+}
+
+impl FakeBackend {
+    fn take_calls(&self) -> Vec<Call> {
+        std::mem::take(&mut *self.calls.borrow_mut())
+    }
+}
+
+impl GamepadBackend for FakeBackend {
+    fn gamepad_slots(&self) -> &GamepadSlots {
+        &self.slots
+    }
+
+    fn describe_gamepad(
+        &self,
+        index: u32,
+        id: &str,
+        axis_count: u32,
+        button_count: u32,
+    ) -> Result<(), CreationError> {
+        self.calls.borrow_mut().push(Call::Describe(
+            index,
+            id.to_string(),
+            axis_count,
+            button_count,
+        ));
+        Ok(())
+    }
+
+    fn gamepad_connection_changed(&self, index: u32, connected: bool) -> Result<(), CreationError> {
+        self.calls
+            .borrow_mut()
+            .push(Call::Connection(index, connected));
+        Ok(())
+    }
+
+    fn gamepad_axis_changed(
+        &self,
+        index: u32,
+        axis_index: u32,
+        value: f64,
+    ) -> Result<(), CreationError> {
+        self.calls
+            .borrow_mut()
+            .push(Call::Axis(index, axis_index, value));
+        Ok(())
+    }
+
+    fn gamepad_button_changed(
+        &self,
+        index: u32,
+        button_index: u32,
+        value: f64,
+    ) -> Result<(), CreationError> {
+        self.calls
+            .borrow_mut()
+            .push(Call::Button(index, button_index, value));
+        Ok(())
+    }
+}
+
+#[test]
+fn describes_before_connecting() {
+    let backend = FakeBackend::default();
+    let gamepad = Gamepad::connect(&backend, "pad", 4, 17).unwrap();
+
+    assert_eq!(gamepad.index(), 0);
+    assert_eq!(gamepad.id(), "pad");
+    assert_eq!(
+        backend.take_calls(),
+        vec![
+            Call::Describe(0, "pad".to_string(), 4, 17),
+            Call::Connection(0, true)
+        ]
+    );
+
+    drop(gamepad);
+    assert_eq!(backend.take_calls(), vec![Call::Connection(0, false)]);
+    assert!(!backend.slots.is_used(0));
+}
+
+#[test]
+fn values_are_validated_and_deduplicated() {
+    let backend = FakeBackend::default();
+    let mut gamepad = Gamepad::connect(&backend, "pad", 2, 2).unwrap();
+    backend.take_calls();
+
+    // values start at 0
+    assert!(!gamepad.set_axis(0, 0.0).unwrap());
+    assert!(!gamepad.set_button(1, 0.0).unwrap());
+
+    assert!(gamepad.set_axis(1, 0.5).unwrap());
+    assert!(!gamepad.set_axis(1, 0.5).unwrap());
+    assert!(gamepad.set_axis(1, -3.0).unwrap());
+    // already clamped to -1
+    assert!(!gamepad.set_axis(1, -1.0).unwrap());
+    assert!(gamepad.set_button(0, 2.0).unwrap());
+    assert!(gamepad.set_button(0, -1.0).unwrap());
+
+    assert_eq!(gamepad.axis(1), Some(-1.0));
+    assert_eq!(gamepad.button(0), Some(0.0));
+
+    assert!(matches!(
+        gamepad.set_axis(2, 0.0),
+        Err(GamepadError::AxisOutOfRange { index: 2, count: 2 })
+    ));
+    assert!(matches!(
+        gamepad.set_button(5, 1.0),
+        Err(GamepadError::ButtonOutOfRange { index: 5, count: 2 })
+    ));
+    assert!(matches!(
+        gamepad.set_axis(0, f64::NAN),
+        Err(GamepadError::InvalidValue(_))
+    ));
+
+    assert_eq!(
+        backend.take_calls(),
+        vec![
+            Call::Axis(0, 1, 0.5),
+            Call::Axis(0, 1, -1.0),
+            Call::Button(0, 0, 1.0),
+            Call::Button(0, 0, 0.0),
+        ]
+    );
+
+    gamepad.disconnect().unwrap();
+    // no second disconnection when dropped
+    assert_eq!(backend.take_calls(), vec![Call::Connection(0, false)]);
+}
+
+#[test]
+fn indices_are_reused_after_disconnection() {
+    let backend = FakeBackend::default();
+
+    let first = Gamepad::connect(&backend, "first", 1, 1).unwrap();
+    let second = Gamepad::connect(&backend, "second", 1, 1).unwrap();
+    assert_eq!(first.index(), 0);
+    assert_eq!(second.index(), 1);
+
+    drop(first);
+    let third = Gamepad::connect(&backend, "third", 1, 1).unwrap();
+    assert_eq!(third.index(), 0);
+
+    let fourth = Gamepad::connect(&backend, "fourth", 1, 1).unwrap();
+    assert_eq!(fourth.index(), 2);
+}
@@ -0,0 +1,130 @@
+//! Banding of `GliumGpuDriverReceiver::draw_texture` when converting an
+//! 8-bit sRGB gradient to linear 8-bit output, with and without dithering.
+//!
+//! `winit` only allows creating the event loop on the main thread (on some
+//! platforms), so this test runs without the test harness.
+
+use glium::{
+    backend::{glutin::SimpleWindowBuilder, Facade},
+    texture::{MipmapsOption, Texture2d, UncompressedFloatFormat},
+};
+use ul_next::{
+    bitmap::{BitmapFormat, OwnedBitmap},
+    gpu_driver::{
+        glium::{create_gpu_driver, GliumGpuDriverReceiver, OutputTransform, TransferFn},
+        GpuDriver,
+    },
+};
+use winit::{event_loop::EventLoop, window::Window};
+
+// The bright end, where a single sRGB step is 2 to 3 linear 8-bit steps.
+const FIRST_LEVEL: u32 = 192;
+const LEVELS: u32 = 64;
+// one 4x4 block of the dither matrix per level
+const BLOCK: u32 = 4;
+const WIDTH: u32 = LEVELS * BLOCK;
+const HEIGHT: u32 = BLOCK;
+
+/// A horizontal gray gradient, each level covering a `BLOCK` wide band.
+fn gradient() -> OwnedBitmap {
+    let pixels = (0..HEIGHT)
+        .flat_map(|_| {
+            (0..WIDTH).flat_map(|x| {
+                let level = (FIRST_LEVEL + x / BLOCK) as u8;
+                [level, level, level, 255]
+            })
+        })
+        .collect();
+    OwnedBitmap::from_pixels(WIDTH, HEIGHT, BitmapFormat::Bgra8UnormSrgb, pixels).unwrap()
+}
+
+/// The exact linear value of the sRGB `level`, in 8-bit steps.
+fn golden(level: u32) -> f64 {
+    let v = level as f64 / 255.0;
+    let linear = if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    };
+    linear * 255.0
+}
+
+/// Draw the texture `id` into an 8-bit target, and return the mean red
+/// value of the band of each level.
+fn draw_levels(display: &impl Facade, receiver: &GliumGpuDriverReceiver, id: u32) -> Vec<f64> {
+    let target = Texture2d::empty_with_format(
+        display,
+        UncompressedFloatFormat::U8U8U8U8,
+        MipmapsOption::NoMipmap,
+        WIDTH,
+        HEIGHT,
+    )
+    .unwrap();
+    receiver
+        .draw_texture(&id, &mut target.as_surface())
+        .unwrap();
+
+    let rows: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    (0..LEVELS)
+        .map(|level| {
+            let sum: u32 = rows
+                .iter()
+                .flat_map(|row| &row[(level * BLOCK) as usize..((level + 1) * BLOCK) as usize])
+                .map(|&(r, _, _, _)| r as u32)
+                .sum();
+            sum as f64 / (BLOCK * BLOCK) as f64
+        })
+        .collect()
+}
+
+/// The largest step between adjacent levels, and the mean distance to the
+/// golden values.
+fn banding(levels: &[f64]) -> (f64, f64) {
+    let max_step = levels
+        .windows(2)
+        .map(|step| step[1] - step[0])
+        .fold(0.0, f64::max);
+    let error = levels
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (value - golden(FIRST_LEVEL + i as u32)).abs())
+        .sum::<f64>()
+        / levels.len() as f64;
+    (max_step, error)
+}
+
+fn main() {
+    let event_loop = EventLoop::new().unwrap();
+    let (_window, display) = SimpleWindowBuilder::new()
+        .set_window_builder(Window::default_attributes().with_visible(false))
+        .build(&event_loop);
+
+    let (mut sender, mut receiver) = create_gpu_driver(&display).unwrap();
+    let id = sender.next_texture_id();
+    sender.create_texture(id, gradient());
+    receiver.render().unwrap();
+
+    receiver.set_output_transform(OutputTransform {
+        dithering: false,
+        target_gamma: TransferFn::Linear,
+    });
+    let (plain_step, plain_error) = banding(&draw_levels(&display, &receiver, id));
+
+    receiver.set_output_transform(OutputTransform {
+        dithering: true,
+        target_gamma: TransferFn::Linear,
+    });
+    let (dithered_step, dithered_error) = banding(&draw_levels(&display, &receiver, id));
+
+    // without dithering, the quantization makes steps of 2 and 3 levels,
+    // dithering spreads them to follow the golden curve
+    assert!(plain_step >= 3.0, "max step without dithering {plain_step}");
+    assert!(
+        dithered_step < plain_step - 0.25,
+        "max step with dithering {dithered_step}, without {plain_step}"
+    );
+    assert!(
+        dithered_error < plain_error * 0.75,
+        "mean error with dithering {dithered_error}, without {plain_error}"
+    );
+}
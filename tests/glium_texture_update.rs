@@ -0,0 +1,75 @@
+//! `winit` only allows creating the event loop on the main thread (on some
+//! platforms), so this test runs without the test harness.
+
+use glium::{backend::glutin::SimpleWindowBuilder, GlObject};
+use ul_next::{
+    bitmap::{BitmapFormat, OwnedBitmap},
+    gpu_driver::{
+        glium::{create_gpu_driver, EitherTexture},
+        GpuDriver,
+    },
+};
+use winit::{event_loop::EventLoop, window::Window};
+
+fn texture_id(texture: &EitherTexture) -> u32 {
+    match texture {
+        EitherTexture::Regular2d(t) => t.get_id(),
+        EitherTexture::Srgb2d(t) => t.get_id(),
+    }
+}
+
+fn a8_bitmap(width: u32, height: u32, value: u8) -> OwnedBitmap {
+    let pixels = vec![value; (width * height) as usize];
+    OwnedBitmap::from_pixels(width, height, BitmapFormat::A8Unorm, pixels).unwrap()
+}
+
+fn bgra_bitmap(width: u32, height: u32) -> OwnedBitmap {
+    let pixels = vec![0xFF; (width * height * 4) as usize];
+    OwnedBitmap::from_pixels(width, height, BitmapFormat::Bgra8UnormSrgb, pixels).unwrap()
+}
+
+fn main() {
+    let event_loop = EventLoop::new().unwrap();
+    let (_window, display) = SimpleWindowBuilder::new()
+        .set_window_builder(Window::default_attributes().with_visible(false))
+        .build(&event_loop);
+
+    let (mut sender, mut receiver) = create_gpu_driver(&display).unwrap();
+
+    let id = sender.next_texture_id();
+    sender.create_texture(id, a8_bitmap(8, 8, 0));
+    receiver.render().unwrap();
+    let texture = texture_id(receiver.get_texture(&id).unwrap());
+
+    // same size and format, the content is uploaded to the same texture
+    sender.update_texture(id, a8_bitmap(8, 8, 200));
+    receiver.render().unwrap();
+    let updated = receiver.get_texture(&id).unwrap();
+    assert_eq!(texture_id(updated), texture);
+    let EitherTexture::Regular2d(updated) = updated else {
+        panic!("A8 textures are not sRGB");
+    };
+    let pixels: Vec<Vec<(u8, u8, u8, u8)>> = updated.read();
+    assert!(pixels.iter().flatten().all(|&(r, _, _, _)| r == 200));
+
+    // the size changed, a new texture is created
+    sender.update_texture(id, a8_bitmap(16, 8, 0));
+    receiver.render().unwrap();
+    let resized = receiver.get_texture(&id).unwrap();
+    assert_ne!(texture_id(resized), texture);
+    assert_eq!((resized.width(), resized.height()), (16, 8));
+
+    // the format changed, a new texture is created
+    let texture = texture_id(resized);
+    sender.update_texture(id, bgra_bitmap(16, 8));
+    receiver.render().unwrap();
+    let reformatted = receiver.get_texture(&id).unwrap();
+    assert_ne!(texture_id(reformatted), texture);
+    assert!(matches!(reformatted, EitherTexture::Srgb2d(_)));
+
+    // and it is reused by the next update of the same format
+    let texture = texture_id(reformatted);
+    sender.update_texture(id, bgra_bitmap(16, 8));
+    receiver.render().unwrap();
+    assert_eq!(texture_id(receiver.get_texture(&id).unwrap()), texture);
+}
@@ -0,0 +1,72 @@
+use ul_next::{
+    config::Config,
+    gpu_driver::recording::{RecordedCall, RecordingDriver},
+    platform,
+    renderer::Renderer,
+    view::ViewConfig,
+    Library,
+};
+
+/// Keep only the synchronization and frame calls.
+fn frame_calls(calls: &[RecordedCall]) -> Vec<String> {
+    calls
+        .iter()
+        .filter_map(|call| match call {
+            RecordedCall::BeginSynchronize => Some("begin_synchronize".to_string()),
+            RecordedCall::EndSynchronize => Some("end_synchronize".to_string()),
+            RecordedCall::BeginFrame(id) => Some(format!("begin_frame {}", id)),
+            RecordedCall::EndFrame(id) => Some(format!("end_frame {}", id)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn frames_of_refreshed_displays() {
+    let lib = unsafe { Library::load_with_appcore().unwrap() };
+    platform::enable_platform_fontloader(lib.clone());
+
+    let driver = RecordingDriver::new(None);
+    let recording = driver.recording();
+    platform::set_gpu_driver(lib.clone(), driver);
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+    let renderer = Renderer::create(config).unwrap();
+
+    let view_config = ViewConfig::start()
+        .is_accelerated(true)
+        .try_build(lib.clone())
+        .unwrap();
+    let view = renderer.create_view(100, 100, &view_config, None).unwrap();
+    view.load_html("<html><body>frames</body></html>").unwrap();
+
+    renderer.update().unwrap();
+    renderer.refresh_display(0);
+    renderer.refresh_display(2);
+    // refreshing twice doesn't duplicate the frame
+    renderer.refresh_display(0);
+    renderer.render().unwrap();
+
+    let calls = recording.lock().unwrap().calls().to_vec();
+    assert_eq!(
+        frame_calls(&calls),
+        vec![
+            "begin_synchronize",
+            "begin_frame 0",
+            "begin_frame 2",
+            "end_frame 2",
+            "end_frame 0",
+            "end_synchronize",
+        ]
+    );
+
+    // no display refreshed, so no frame
+    recording.lock().unwrap().clear();
+    renderer.update().unwrap();
+    renderer.render().unwrap();
+
+    let calls = recording.lock().unwrap().calls().to_vec();
+    assert!(frame_calls(&calls)
+        .iter()
+        .all(|call| !call.contains("frame")));
+}
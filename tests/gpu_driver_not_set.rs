@@ -0,0 +1,26 @@
+mod common;
+
+use ul_next::{config::Config, error::CreationError, renderer::Renderer, view::ViewConfig};
+
+// a single test, so that the renderer is only created once the accelerated
+// one was refused, only one can be created at a time
+#[test]
+fn accelerated_without_gpu_driver() {
+    let lib = common::load_library();
+
+    let config = Config::start()
+        .is_accelerated(true)
+        .try_build(lib.clone())
+        .unwrap();
+    let result = Renderer::create(config);
+    assert!(matches!(result, Err(CreationError::GpuDriverNotSet)));
+
+    // CPU renderers can still be created, but not accelerated views
+    let renderer = common::create_renderer(&lib);
+    let view_config = ViewConfig::start()
+        .is_accelerated(true)
+        .try_build(lib.clone())
+        .unwrap();
+    let result = renderer.create_view(100, 100, &view_config, None);
+    assert!(matches!(result, Err(CreationError::GpuDriverNotSet)));
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use ul_next::{
+    config::Config, gpu_driver::recording::RecordingDriver, platform, renderer::Renderer,
+    view::ViewConfig, Library,
+};
+
+/// Create a renderer with an accelerated view, render it, and drop them.
+fn render_once(lib: &Arc<Library>) {
+    let config = Config::start().try_build(lib.clone()).unwrap();
+    let renderer = Renderer::create(config).unwrap();
+
+    let view_config = ViewConfig::start()
+        .is_accelerated(true)
+        .try_build(lib.clone())
+        .unwrap();
+    let view = renderer.create_view(64, 64, &view_config, None).unwrap();
+    view.load_html("<html><body>hello</body></html>").unwrap();
+
+    renderer.update().unwrap();
+    renderer.render().unwrap();
+}
+
+#[test]
+fn renderers_with_different_drivers() {
+    let lib = unsafe { Library::load_with_appcore().unwrap() };
+    platform::enable_platform_fontloader(lib.clone());
+
+    let first = RecordingDriver::new(None);
+    let first_recording = first.recording();
+    platform::set_gpu_driver(lib.clone(), first);
+    render_once(&lib);
+    assert!(!first_recording.lock().unwrap().is_empty());
+
+    // the driver is dropped, only our handle to the recording is left
+    // the renderer was dropped by `render_once`
+    unsafe { platform::clear_gpu_driver(lib.clone()) };
+    assert_eq!(Arc::strong_count(&first_recording), 1);
+    let first_calls = first_recording.lock().unwrap().len();
+
+    let second = RecordingDriver::new(None);
+    let second_recording = second.recording();
+    platform::set_gpu_driver(lib.clone(), second);
+    render_once(&lib);
+
+    assert!(!second_recording.lock().unwrap().is_empty());
+    assert_eq!(first_recording.lock().unwrap().len(), first_calls);
+
+    // setting a driver again drops the previous one
+    platform::set_gpu_driver(lib.clone(), RecordingDriver::new(None));
+    assert_eq!(Arc::strong_count(&second_recording), 1);
+
+    // the renderer was dropped by `render_once`
+    unsafe { platform::clear_gpu_driver(lib) };
+}
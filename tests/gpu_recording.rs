@@ -0,0 +1,368 @@
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "serde")]
+use ul_next::gpu_driver::recording::{replay, Recording};
+use ul_next::{
+    bitmap::{BitmapFormat, OwnedBitmap},
+    gpu_driver::{
+        recording::{RecordedCall, RecordingDriver},
+        GpuCommand, GpuDriver, GpuState, IndexBuffer, RenderBuffer, ShaderType, VertexBuffer,
+        VertexBufferFormat,
+    },
+    rect::Rect,
+};
+
+/// A driver that logs the calls it receives, allocating ids from `base`.
+struct LogDriver {
+    base: u32,
+    next_id: u32,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl LogDriver {
+    fn new(base: u32) -> (Self, Arc<Mutex<Vec<String>>>) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        (
+            Self {
+                base,
+                next_id: 0,
+                log: log.clone(),
+            },
+            log,
+        )
+    }
+
+    fn push(&self, entry: String) {
+        self.log.lock().unwrap().push(entry);
+    }
+
+    fn next(&mut self) -> u32 {
+        self.next_id += 1;
+        self.base + self.next_id
+    }
+}
+
+impl GpuDriver for LogDriver {
+    fn begin_synchronize(&mut self) {
+        self.push("begin".into());
+    }
+
+    fn end_synchronize(&mut self) {
+        self.push("end".into());
+    }
+
+    fn next_texture_id(&mut self) -> u32 {
+        self.next()
+    }
+
+    fn create_texture(&mut self, texture_id: u32, bitmap: OwnedBitmap) {
+        self.push(format!(
+            "create_texture {} {}x{} empty={}",
+            texture_id,
+            bitmap.width(),
+            bitmap.height(),
+            bitmap.is_empty()
+        ));
+    }
+
+    fn update_texture(&mut self, texture_id: u32, bitmap: OwnedBitmap) {
+        self.push(format!(
+            "update_texture {} {:?}",
+            texture_id,
+            bitmap.pixels().unwrap()
+        ));
+    }
+
+    fn destroy_texture(&mut self, texture_id: u32) {
+        self.push(format!("destroy_texture {}", texture_id));
+    }
+
+    fn next_render_buffer_id(&mut self) -> u32 {
+        self.next()
+    }
+
+    fn create_render_buffer(&mut self, render_buffer_id: u32, render_buffer: RenderBuffer) {
+        self.push(format!(
+            "create_render_buffer {} texture={}",
+            render_buffer_id, render_buffer.texture_id
+        ));
+    }
+
+    fn destroy_render_buffer(&mut self, render_buffer_id: u32) {
+        self.push(format!("destroy_render_buffer {}", render_buffer_id));
+    }
+
+    fn next_geometry_id(&mut self) -> u32 {
+        self.next()
+    }
+
+    fn create_geometry(
+        &mut self,
+        geometry_id: u32,
+        vertex_buffer: VertexBuffer,
+        index_buffer: IndexBuffer,
+    ) {
+        self.push(format!(
+            "create_geometry {} {} {:?}",
+            geometry_id,
+            vertex_buffer.buffer.len(),
+            index_buffer.buffer
+        ));
+    }
+
+    fn update_geometry(
+        &mut self,
+        geometry_id: u32,
+        vertex_buffer: VertexBuffer,
+        index_buffer: IndexBuffer,
+    ) {
+        self.push(format!(
+            "update_geometry {} {} {:?}",
+            geometry_id,
+            vertex_buffer.buffer.len(),
+            index_buffer.buffer
+        ));
+    }
+
+    fn destroy_geometry(&mut self, geometry_id: u32) {
+        self.push(format!("destroy_geometry {}", geometry_id));
+    }
+
+    fn update_command_list(&mut self, command_list: Vec<GpuCommand>) {
+        for command in command_list {
+            match command {
+                GpuCommand::ClearRenderBuffer { render_buffer_id } => {
+                    self.push(format!("clear {}", render_buffer_id))
+                }
+                GpuCommand::DrawGeometry {
+                    gpu_state,
+                    geometry_id,
+                    indices_offset,
+                    indices_count,
+                } => self.push(format!(
+                    "draw {} rb={} tex={:?} {}..{}",
+                    geometry_id,
+                    gpu_state.render_buffer_id,
+                    gpu_state.texture_1_id,
+                    indices_offset,
+                    indices_offset + indices_count
+                )),
+            }
+        }
+    }
+}
+
+fn gpu_state(render_buffer_id: u32, texture_id: u32) -> GpuState {
+    GpuState {
+        viewport_width: 4,
+        viewport_height: 4,
+        transform: [0.0; 16],
+        enable_texturing: true,
+        enable_blend: true,
+        shader_type: ShaderType::Fill,
+        render_buffer_id,
+        texture_1_id: Some(texture_id),
+        texture_2_id: None,
+        texture_3_id: None,
+        uniform_scalar: [0.0; 8],
+        uniform_vector: [[0.0; 4]; 8],
+        clip_size: 0,
+        clip: [[[0.0; 4]; 4]; 8],
+        enable_scissor: false,
+        scissor_rect: Rect {
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        },
+    }
+}
+
+/// Issue the calls of a small frame: a render buffer, a texture, a geometry
+/// and a draw, then destroy everything.
+fn draw_frame(driver: &mut impl GpuDriver) {
+    driver.begin_synchronize();
+
+    let rt_texture = driver.next_texture_id();
+    driver.create_texture(
+        rt_texture,
+        OwnedBitmap::empty(4, 4, BitmapFormat::Bgra8UnormSrgb),
+    );
+    let render_buffer = driver.next_render_buffer_id();
+    driver.create_render_buffer(
+        render_buffer,
+        RenderBuffer {
+            texture_id: rt_texture,
+            width: 4,
+            height: 4,
+            has_stencil_buffer: false,
+            has_depth_buffer: false,
+        },
+    );
+
+    let texture = driver.next_texture_id();
+    driver.create_texture(
+        texture,
+        OwnedBitmap::from_pixels(2, 1, BitmapFormat::A8Unorm, vec![1, 2]).unwrap(),
+    );
+    driver.update_texture(
+        texture,
+        OwnedBitmap::from_pixels(2, 1, BitmapFormat::A8Unorm, vec![3, 4]).unwrap(),
+    );
+
+    let geometry = driver.next_geometry_id();
+    driver.create_geometry(
+        geometry,
+        VertexBuffer {
+            format: VertexBufferFormat::Format_2f_4ub_2f,
+            buffer: vec![0; 20 * 4],
+        },
+        IndexBuffer {
+            buffer: vec![0, 1, 2, 2, 3, 0],
+        },
+    );
+
+    driver.end_synchronize();
+
+    driver.update_command_list(vec![
+        GpuCommand::ClearRenderBuffer {
+            render_buffer_id: render_buffer,
+        },
+        GpuCommand::DrawGeometry {
+            gpu_state: Box::new(gpu_state(render_buffer, texture)),
+            geometry_id: geometry,
+            indices_offset: 0,
+            indices_count: 6,
+        },
+    ]);
+
+    driver.destroy_geometry(geometry);
+    driver.destroy_texture(texture);
+    driver.destroy_render_buffer(render_buffer);
+    driver.destroy_texture(rt_texture);
+}
+
+fn expected_log(rt_texture: u32, render_buffer: u32, texture: u32, geometry: u32) -> Vec<String> {
+    vec![
+        "begin".to_string(),
+        format!("create_texture {} 4x4 empty=true", rt_texture),
+        format!(
+            "create_render_buffer {} texture={}",
+            render_buffer, rt_texture
+        ),
+        format!("create_texture {} 2x1 empty=false", texture),
+        format!("update_texture {} [3, 4]", texture),
+        format!("create_geometry {} 80 [0, 1, 2, 2, 3, 0]", geometry),
+        "end".to_string(),
+        format!("clear {}", render_buffer),
+        format!(
+            "draw {} rb={} tex=Some({}) 0..6",
+            geometry, render_buffer, texture
+        ),
+        format!("destroy_geometry {}", geometry),
+        format!("destroy_texture {}", texture),
+        format!("destroy_render_buffer {}", render_buffer),
+        format!("destroy_texture {}", rt_texture),
+    ]
+}
+
+#[test]
+fn record_without_inner_driver() {
+    let mut driver = RecordingDriver::new(None);
+    let recording = driver.recording();
+    draw_frame(&mut driver);
+
+    let recording = recording.lock().unwrap();
+    assert_eq!(recording.len(), 16);
+    // ids are allocated sequentially per kind
+    assert!(matches!(
+        recording.calls()[1],
+        RecordedCall::NextTextureId(1)
+    ));
+    assert!(matches!(
+        recording.calls()[3],
+        RecordedCall::NextRenderBufferId(1)
+    ));
+    assert!(matches!(
+        recording.calls()[5],
+        RecordedCall::NextTextureId(2)
+    ));
+    assert!(matches!(
+        recording.calls()[8],
+        RecordedCall::NextGeometryId(1)
+    ));
+}
+
+#[test]
+fn record_forwards_to_inner_driver() {
+    let (inner, log) = LogDriver::new(10);
+    let mut driver = RecordingDriver::new(Some(Box::new(inner)));
+    let recording = driver.recording();
+    draw_frame(&mut driver);
+
+    // ids come from the inner driver
+    assert_eq!(*log.lock().unwrap(), expected_log(11, 12, 13, 14));
+    assert_eq!(recording.lock().unwrap().len(), 16);
+}
+
+#[test]
+fn replay_remaps_ids() {
+    let (inner, _) = LogDriver::new(10);
+    let mut driver = RecordingDriver::new(Some(Box::new(inner)));
+    let recording = driver.recording();
+    draw_frame(&mut driver);
+    let recording = std::mem::take(&mut *recording.lock().unwrap());
+
+    let (mut replay_driver, log) = LogDriver::new(100);
+    recording.replay(&mut replay_driver);
+
+    assert_eq!(*log.lock().unwrap(), expected_log(101, 102, 103, 104));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn recording_serde_roundtrip() {
+    let mut driver = RecordingDriver::new(None);
+    let recording = driver.recording();
+    draw_frame(&mut driver);
+    let recording = std::mem::take(&mut *recording.lock().unwrap());
+
+    let json = serde_json::to_string(&recording).unwrap();
+    let loaded: Recording = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.len(), recording.len());
+
+    let (mut replay_driver, log) = LogDriver::new(0);
+    loaded.replay(&mut replay_driver);
+
+    assert_eq!(*log.lock().unwrap(), expected_log(1, 2, 3, 4));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn recording_json_file_roundtrip() {
+    let mut driver = RecordingDriver::new(None);
+    let recording = driver.recording();
+    draw_frame(&mut driver);
+    let recording = std::mem::take(&mut *recording.lock().unwrap());
+
+    let path = std::env::temp_dir().join(format!("ul-next-recording-{}.json", std::process::id()));
+    recording.save_json(&path).unwrap();
+    let loaded = Recording::load_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.len(), recording.len());
+
+    let (mut replay_driver, log) = LogDriver::new(20);
+    replay(&loaded, &mut replay_driver);
+
+    assert_eq!(*log.lock().unwrap(), expected_log(21, 22, 23, 24));
+}
+
+#[test]
+fn owned_bitmap_from_pixels_checks_size() {
+    assert!(OwnedBitmap::from_pixels(2, 2, BitmapFormat::Bgra8UnormSrgb, vec![0; 15]).is_err());
+
+    let bitmap = OwnedBitmap::from_pixels(2, 2, BitmapFormat::Bgra8UnormSrgb, vec![0; 16]).unwrap();
+    assert_eq!(bitmap.row_bytes(), 8);
+    assert_eq!(bitmap.bytes_size(), 16);
+    assert!(!bitmap.is_empty());
+}
@@ -0,0 +1,33 @@
+use ul_next::{
+    headless::{self, HeadlessError, HeadlessOptions},
+    Library,
+};
+
+#[test]
+fn render_html() {
+    let lib = unsafe { Library::load_with_appcore().unwrap() };
+
+    let bitmap = headless::render_html_to_bitmap(
+        lib.clone(),
+        "<html><body style=\"background: red\"></body></html>",
+        64,
+        32,
+        &HeadlessOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(bitmap.width(), 64);
+    assert_eq!(bitmap.height(), 32);
+
+    // BGRA
+    let pixels = bitmap.pixels().unwrap();
+    assert_eq!(&pixels[..4], &[0, 0, 255, 255]);
+
+    // the renderer stays in the thread that created it
+    let result = std::thread::spawn(move || {
+        headless::render_html_to_bitmap(lib, "", 8, 8, &HeadlessOptions::default())
+    })
+    .join()
+    .unwrap();
+    assert!(matches!(result, Err(HeadlessError::WrongThread)));
+}
@@ -0,0 +1,151 @@
+//! Checks the events fired by `InputCoalescer` with a fake target, so this
+//! doesn't need Ultralight.
+
+use std::cell::RefCell;
+
+use ul_next::{
+    error::CreationError,
+    event::{MouseButton, MouseEventType, ScrollEventType},
+    view::{InputCoalescer, InputTarget},
+};
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Mouse(MouseEventType, i32, i32, MouseButton),
+    Scroll(ScrollEventType, i32, i32),
+}
+
+#[derive(Default)]
+struct FakeView {
+    events: RefCell<Vec<Event>>,
+}
+
+impl FakeView {
+    fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut *self.events.borrow_mut())
+    }
+}
+
+impl InputTarget for FakeView {
+    fn mouse_event(
+        &self,
+        ty: MouseEventType,
+        x: i32,
+        y: i32,
+        button: MouseButton,
+    ) -> Result<(), CreationError> {
+        self.events
+            .borrow_mut()
+            .push(Event::Mouse(ty, x, y, button));
+        Ok(())
+    }
+
+    fn scroll_event(
+        &self,
+        ty: ScrollEventType,
+        delta_x: i32,
+        delta_y: i32,
+    ) -> Result<(), CreationError> {
+        self.events
+            .borrow_mut()
+            .push(Event::Scroll(ty, delta_x, delta_y));
+        Ok(())
+    }
+}
+
+fn mouse(ty: MouseEventType, x: i32, y: i32) -> Event {
+    let button = match ty {
+        MouseEventType::MouseMoved => MouseButton::None,
+        _ => MouseButton::Left,
+    };
+    Event::Mouse(ty, x, y, button)
+}
+
+#[test]
+fn move_storm_with_clicks() {
+    let view = FakeView::default();
+    let coalescer = InputCoalescer::new(&view);
+
+    // 1000 moves, with a click after the 300th and the 700th
+    for i in 0..1000 {
+        coalescer
+            .mouse_event(MouseEventType::MouseMoved, i, i, MouseButton::None)
+            .unwrap();
+        if i == 299 || i == 699 {
+            coalescer
+                .mouse_event(MouseEventType::MouseDown, i, i, MouseButton::Left)
+                .unwrap();
+            coalescer
+                .mouse_event(MouseEventType::MouseUp, i, i, MouseButton::Left)
+                .unwrap();
+        }
+    }
+    assert!(coalescer.has_pending());
+    coalescer.flush().unwrap();
+    assert!(!coalescer.has_pending());
+
+    assert_eq!(
+        view.take(),
+        [
+            mouse(MouseEventType::MouseMoved, 299, 299),
+            mouse(MouseEventType::MouseDown, 299, 299),
+            mouse(MouseEventType::MouseUp, 299, 299),
+            mouse(MouseEventType::MouseMoved, 699, 699),
+            mouse(MouseEventType::MouseDown, 699, 699),
+            mouse(MouseEventType::MouseUp, 699, 699),
+            mouse(MouseEventType::MouseMoved, 999, 999),
+        ]
+    );
+
+    // nothing left to flush
+    coalescer.flush().unwrap();
+    assert!(view.take().is_empty());
+}
+
+#[test]
+fn merge_scrolls_of_same_type() {
+    let view = FakeView::default();
+    let coalescer = InputCoalescer::new(&view);
+
+    for _ in 0..10 {
+        coalescer
+            .scroll_event(ScrollEventType::ScrollByPixel, 0, -3)
+            .unwrap();
+    }
+    coalescer
+        .scroll_event(ScrollEventType::ScrollByPage, 0, 1)
+        .unwrap();
+    coalescer
+        .scroll_event(ScrollEventType::ScrollByPage, 1, 1)
+        .unwrap();
+    coalescer
+        .mouse_event(MouseEventType::MouseMoved, 5, 5, MouseButton::None)
+        .unwrap();
+    coalescer
+        .scroll_event(ScrollEventType::ScrollByPixel, 2, 0)
+        .unwrap();
+    coalescer.flush().unwrap();
+
+    assert_eq!(
+        view.take(),
+        [
+            Event::Scroll(ScrollEventType::ScrollByPixel, 0, -30),
+            Event::Scroll(ScrollEventType::ScrollByPage, 1, 2),
+            mouse(MouseEventType::MouseMoved, 5, 5),
+            Event::Scroll(ScrollEventType::ScrollByPixel, 2, 0),
+        ]
+    );
+}
+
+#[test]
+fn drop_flushes_pending_event() {
+    let view = FakeView::default();
+    {
+        let coalescer = InputCoalescer::new(&view);
+        coalescer
+            .mouse_event(MouseEventType::MouseMoved, 1, 2, MouseButton::None)
+            .unwrap();
+        assert!(view.take().is_empty());
+    }
+    assert_eq!(view.take(), [mouse(MouseEventType::MouseMoved, 1, 2)]);
+}
@@ -0,0 +1,129 @@
+//! Checks the events of `InputForwarder` with a fake target, so this
+//! doesn't need Ultralight.
+
+use std::cell::RefCell;
+
+use ul_next::{
+    error::CreationError,
+    event::{MouseButton, MouseEventType, ScrollEventType},
+    view::{InputForwarder, InputTarget},
+};
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Mouse(MouseEventType, i32, i32, MouseButton),
+    Scroll(i32, i32),
+}
+
+#[derive(Default)]
+struct FakeView {
+    events: RefCell<Vec<Event>>,
+}
+
+impl FakeView {
+    fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut *self.events.borrow_mut())
+    }
+}
+
+impl InputTarget for FakeView {
+    fn mouse_event(
+        &self,
+        ty: MouseEventType,
+        x: i32,
+        y: i32,
+        button: MouseButton,
+    ) -> Result<(), CreationError> {
+        self.events
+            .borrow_mut()
+            .push(Event::Mouse(ty, x, y, button));
+        Ok(())
+    }
+
+    fn scroll_event(
+        &self,
+        ty: ScrollEventType,
+        delta_x: i32,
+        delta_y: i32,
+    ) -> Result<(), CreationError> {
+        assert_eq!(ty, ScrollEventType::ScrollByPixel);
+        self.events
+            .borrow_mut()
+            .push(Event::Scroll(delta_x, delta_y));
+        Ok(())
+    }
+}
+
+#[test]
+fn coordinates_with_scales() {
+    let forwarder = InputForwarder::new((10, 20), 100, 100, 1.0);
+    assert_eq!(forwarder.to_view_coords(10.0, 20.0), (0, 0));
+    assert_eq!(forwarder.to_view_coords(50.5, 70.9), (40, 50));
+
+    let forwarder = InputForwarder::new((10, 20), 100, 100, 1.5);
+    assert_eq!(forwarder.to_view_coords(10.0, 20.0), (5, 10));
+    assert_eq!(forwarder.to_view_coords(21.0, 31.0), (21, 26));
+
+    let forwarder = InputForwarder::new((10, 20), 100, 100, 2.0);
+    assert_eq!(forwarder.to_view_coords(5.0, 10.0), (0, 0));
+    assert_eq!(forwarder.to_view_coords(30.25, 40.0), (50, 60));
+
+    // outside of the window and the view
+    assert_eq!(forwarder.to_view_coords(-3.0, 0.0), (-16, -20));
+    assert!(!forwarder.contains((-16, -20)));
+    assert!(!forwarder.contains((100, 0)));
+    assert!(forwarder.contains((99, 99)));
+}
+
+#[test]
+fn events_inside_the_view() {
+    let view = FakeView::default();
+    let mut forwarder = InputForwarder::new((0, 0), 100, 50, 2.0);
+
+    forwarder.mouse_moved(&view, 10.0, 10.0).unwrap();
+    forwarder.button(&view, MouseButton::Left, true).unwrap();
+    forwarder.mouse_moved(&view, 11.0, 10.0).unwrap();
+    forwarder.scroll(&view, 0.0, -1.5).unwrap();
+    forwarder.button(&view, MouseButton::Left, false).unwrap();
+
+    assert_eq!(
+        view.take(),
+        vec![
+            Event::Mouse(MouseEventType::MouseMoved, 20, 20, MouseButton::None),
+            Event::Mouse(MouseEventType::MouseDown, 20, 20, MouseButton::Left),
+            Event::Mouse(MouseEventType::MouseMoved, 22, 20, MouseButton::Left),
+            Event::Scroll(0, -3),
+            Event::Mouse(MouseEventType::MouseUp, 22, 20, MouseButton::Left),
+        ]
+    );
+    assert!(forwarder.pressed_buttons().is_empty());
+}
+
+#[test]
+fn leaving_the_view_releases_buttons() {
+    let view = FakeView::default();
+    let mut forwarder = InputForwarder::new((100, 100), 50, 50, 1.0);
+
+    forwarder.mouse_moved(&view, 120.0, 130.0).unwrap();
+    forwarder.button(&view, MouseButton::Right, true).unwrap();
+    view.take();
+
+    // leaves at the bottom right, the button is released at the edge
+    forwarder.mouse_moved(&view, 200.0, 140.0).unwrap();
+    assert_eq!(
+        view.take(),
+        vec![Event::Mouse(
+            MouseEventType::MouseUp,
+            49,
+            40,
+            MouseButton::Right
+        )]
+    );
+
+    // nothing is forwarded outside of the view
+    forwarder.mouse_moved(&view, 20.0, -5.0).unwrap();
+    forwarder.button(&view, MouseButton::Left, true).unwrap();
+    forwarder.scroll(&view, 0.0, 10.0).unwrap();
+    forwarder.button(&view, MouseButton::Right, false).unwrap();
+    assert_eq!(view.take(), vec![]);
+}
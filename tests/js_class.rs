@@ -0,0 +1,125 @@
+use std::{cell::Cell, rc::Rc};
+
+use ul_next::{
+    javascript::{JSClassBuilder, JSContext, JSObject, JSPropertyAttributes, JSValue},
+    Library,
+};
+
+struct Counter {
+    count: u32,
+    dropped: Rc<Cell<bool>>,
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        self.dropped.set(true);
+    }
+}
+
+#[test]
+fn class_with_state() {
+    let lib = unsafe { Library::load().unwrap() };
+
+    let finalized_count = Rc::new(Cell::new(None));
+    let dropped = Rc::new(Cell::new(false));
+
+    let class = {
+        let finalized_count = finalized_count.clone();
+        JSClassBuilder::new("Counter")
+            .method("increment", |ctx, this, args| {
+                let by = match args.first() {
+                    Some(arg) => arg.as_number()? as u32,
+                    None => 1,
+                };
+                this.private_data::<Counter>().unwrap().count += by;
+                Ok(JSValue::new_undefined(ctx))
+            })
+            .property_with_setter(
+                "count",
+                |ctx, this| {
+                    let count = this.private_data::<Counter>().unwrap().count;
+                    Ok(JSValue::new_number(ctx, count as f64))
+                },
+                |_ctx, this, value| {
+                    this.private_data::<Counter>().unwrap().count = value.as_number()? as u32;
+                    Ok(())
+                },
+            )
+            .finalize(move |data| {
+                let counter = data.downcast::<Counter>().unwrap();
+                finalized_count.set(Some(counter.count));
+            })
+            .build(lib.clone())
+    };
+
+    let ctx = JSContext::new(lib.clone());
+    {
+        let counter = JSObject::new_with_class(
+            &ctx,
+            &class,
+            Box::new(Counter {
+                count: 0,
+                dropped: dropped.clone(),
+            }),
+        );
+        ctx.global_object()
+            .set_property("counter", &counter, JSPropertyAttributes::default())
+            .unwrap();
+
+        let result = ctx
+            .evaluate_script(
+                "counter.increment(); counter.increment(5); counter.count",
+                None,
+                None,
+                1,
+            )
+            .unwrap();
+        assert_eq!(result.as_number().unwrap(), 6.0);
+
+        ctx.evaluate_script("counter.count = 40; counter.increment(2)", None, None, 1)
+            .unwrap();
+        assert_eq!(counter.private_data::<Counter>().unwrap().count, 42);
+
+        let keys = ctx
+            .evaluate_script("Object.keys(counter).join(',')", None, None, 1)
+            .unwrap();
+        assert_eq!(keys.as_string().unwrap().to_string(), "count");
+
+        ctx.evaluate_script("delete counter;", None, None, 1)
+            .unwrap();
+    }
+
+    ctx.garbage_collect();
+    // the collection may happen later, but all the objects are finalized
+    // when the context is destroyed
+    drop(ctx);
+
+    assert_eq!(finalized_count.get(), Some(42));
+    assert!(dropped.get());
+}
+
+#[test]
+fn functions_of_two_libraries() {
+    // each library looks up the private data of its own objects
+    let libs = (0..2)
+        .map(|_| unsafe { Library::load().unwrap() })
+        .collect::<Vec<_>>();
+    let contexts = libs
+        .iter()
+        .map(|lib| JSContext::new(lib.clone()))
+        .collect::<Vec<_>>();
+
+    for (i, ctx) in contexts.iter().enumerate() {
+        let function = JSObject::new_function_with_callback(ctx, move |ctx, _this, _args| {
+            Ok(JSValue::new_number(ctx, i as f64))
+        });
+        ctx.global_object()
+            .set_property("library", &function, JSPropertyAttributes::default())
+            .unwrap();
+    }
+
+    for (i, ctx) in contexts.iter().enumerate() {
+        let result = ctx.evaluate_script("library()", None, None, 1).unwrap();
+        assert_eq!(result.as_number().unwrap(), i as f64);
+    }
+}
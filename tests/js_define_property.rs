@@ -0,0 +1,119 @@
+use std::{cell::Cell, rc::Rc};
+
+use ul_next::{
+    javascript::{JSContext, JSObject, JSPropertyAttributes, JSValue},
+    Library,
+};
+
+#[test]
+fn accessor_property() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let value = Rc::new(Cell::new(1.0));
+    let obj = JSObject::new(&ctx);
+    {
+        let getter_value = value.clone();
+        let setter_value = value.clone();
+        obj.define_property(
+            "value",
+            Some(Box::new(move |ctx, _this| {
+                Ok(JSValue::new_number(ctx, getter_value.get()))
+            })),
+            Some(Box::new(move |ctx, _this, value| {
+                let value = value.as_number()?;
+                if value < 0.0 {
+                    return Err(JSValue::new_string(ctx, "negative"));
+                }
+                setter_value.set(value);
+                Ok(())
+            })),
+            JSPropertyAttributes::new().dont_enum(true),
+        )
+        .unwrap();
+    }
+    ctx.global_object()
+        .set_property("obj", &obj, JSPropertyAttributes::new())
+        .unwrap();
+
+    let result = ctx
+        .evaluate_script("obj.value = obj.value + 41; obj.value", None, None, 1)
+        .unwrap();
+    assert_eq!(result.as_number().unwrap(), 42.0);
+    assert_eq!(value.get(), 42.0);
+
+    let error = ctx
+        .evaluate_script("obj.value = -1", None, None, 1)
+        .unwrap_err();
+    assert_eq!(error.as_string().unwrap().to_string(), "negative");
+    assert_eq!(value.get(), 42.0);
+
+    let keys = ctx
+        .evaluate_script("Object.keys(obj).length", None, None, 1)
+        .unwrap();
+    assert_eq!(keys.as_number().unwrap(), 0.0);
+}
+
+#[test]
+fn getter_only_property() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let obj = JSObject::new(&ctx);
+    obj.define_property(
+        "answer",
+        Some(Box::new(|ctx, _this| Ok(JSValue::new_number(ctx, 42.0)))),
+        None,
+        JSPropertyAttributes::new(),
+    )
+    .unwrap();
+    ctx.global_object()
+        .set_property("obj", &obj, JSPropertyAttributes::new())
+        .unwrap();
+
+    let result = ctx
+        .evaluate_script("obj.answer = 1; obj.answer", None, None, 1)
+        .unwrap();
+    assert_eq!(result.as_number().unwrap(), 42.0);
+
+    let enumerable = ctx
+        .evaluate_script(
+            "Object.getOwnPropertyDescriptor(obj, 'answer').enumerable",
+            None,
+            None,
+            1,
+        )
+        .unwrap();
+    assert!(enumerable.as_boolean());
+}
+
+#[test]
+fn setter_only_property() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let value = Rc::new(Cell::new(0.0));
+    let obj = JSObject::new(&ctx);
+    {
+        let value = value.clone();
+        obj.define_property(
+            "value",
+            None,
+            Some(Box::new(move |_ctx, _this, new_value| {
+                value.set(new_value.as_number()?);
+                Ok(())
+            })),
+            JSPropertyAttributes::new(),
+        )
+        .unwrap();
+    }
+    ctx.global_object()
+        .set_property("obj", &obj, JSPropertyAttributes::new())
+        .unwrap();
+
+    let result = ctx
+        .evaluate_script("obj.value = 7; typeof obj.value", None, None, 1)
+        .unwrap();
+    assert_eq!(result.as_string().unwrap().to_string(), "undefined");
+    assert_eq!(value.get(), 7.0);
+}
@@ -0,0 +1,49 @@
+use ul_next::{javascript::JSContext, Library};
+
+#[test]
+fn enumerate_properties() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let obj = ctx
+        .evaluate_script(
+            "var o = { a: 1, b: 2, c: 3 };
+             Object.defineProperty(o, 'hidden', { value: 4, enumerable: false });
+             o",
+            None,
+            None,
+            1,
+        )
+        .unwrap()
+        .as_object()
+        .unwrap();
+
+    let mut names = obj.enumerate_properties();
+    assert_eq!(names.len(), 3);
+    assert_eq!(names.next().unwrap().to_string(), "a");
+    assert_eq!(names.len(), 2);
+
+    let rest = names.map(|name| name.to_string()).collect::<Vec<_>>();
+    assert_eq!(rest, ["b", "c"]);
+
+    // same names as the array
+    let names = obj
+        .enumerate_properties()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, obj.get_property_names().into_vec());
+}
+
+#[test]
+fn enumerate_properties_of_empty_object() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let obj = ctx
+        .evaluate_script("({})", None, None, 1)
+        .unwrap()
+        .as_object()
+        .unwrap();
+
+    assert_eq!(obj.enumerate_properties().count(), 0);
+}
@@ -0,0 +1,100 @@
+use ul_next::{
+    javascript::{JSContext, JSObject, JSPropertyAttributes, JSValue},
+    Library,
+};
+
+fn object_with_value(ctx: &JSContext) -> JSObject {
+    let obj = JSObject::new(ctx);
+    obj.set_property(
+        "value",
+        &JSValue::new_number(ctx, 1.0),
+        JSPropertyAttributes::new(),
+    )
+    .unwrap();
+    ctx.global_object()
+        .set_property("obj", &obj, JSPropertyAttributes::new())
+        .unwrap();
+    obj
+}
+
+#[test]
+fn freeze_object() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let obj = object_with_value(&ctx);
+    assert!(!obj.is_frozen().unwrap());
+
+    obj.freeze().unwrap();
+    assert!(obj.is_frozen().unwrap());
+    assert!(obj.is_sealed().unwrap());
+
+    // strict mode throws on writes to frozen objects
+    let error = ctx
+        .evaluate_script("'use strict'; obj.value = 2", None, None, 1)
+        .unwrap_err();
+    assert!(error.is_object());
+    ctx.evaluate_script("'use strict'; obj.other = 2", None, None, 1)
+        .unwrap_err();
+    ctx.evaluate_script("'use strict'; delete obj.value", None, None, 1)
+        .unwrap_err();
+
+    let value = obj.get_property("value").unwrap();
+    assert_eq!(value.as_number().unwrap(), 1.0);
+    assert!(!obj.has_property("other"));
+}
+
+#[test]
+fn seal_object() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let obj = object_with_value(&ctx);
+    assert!(!obj.is_sealed().unwrap());
+
+    obj.seal().unwrap();
+    assert!(obj.is_sealed().unwrap());
+    assert!(!obj.is_frozen().unwrap());
+
+    // existing properties can still be changed
+    ctx.evaluate_script("'use strict'; obj.value = 2", None, None, 1)
+        .unwrap();
+    ctx.evaluate_script("'use strict'; obj.other = 2", None, None, 1)
+        .unwrap_err();
+    ctx.evaluate_script("'use strict'; delete obj.value", None, None, 1)
+        .unwrap_err();
+
+    let value = obj.get_property("value").unwrap();
+    assert_eq!(value.as_number().unwrap(), 2.0);
+    assert!(!obj.has_property("other"));
+}
+
+#[test]
+fn freeze_propagates_exception() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    // a proxy that refuses to be frozen
+    let proxy = ctx
+        .evaluate_script(
+            "new Proxy({}, { preventExtensions() { throw new Error('refused'); } })",
+            None,
+            None,
+            1,
+        )
+        .unwrap()
+        .as_object()
+        .unwrap();
+
+    let error = proxy.freeze().unwrap_err();
+    let message = error
+        .as_object()
+        .unwrap()
+        .get_property("message")
+        .unwrap()
+        .as_string()
+        .unwrap()
+        .to_string();
+    assert_eq!(message, "refused");
+    assert!(proxy.seal().is_err());
+}
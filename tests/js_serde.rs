@@ -0,0 +1,119 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use ul_next::{
+    javascript::{from_value, to_value, JSContext, JSValue, JsSerdeError},
+    Library,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Point,
+    Circle { radius: f64 },
+    Polygon(Vec<(i32, i32)>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Row {
+    name: String,
+    value: Option<f64>,
+    tags: Vec<String>,
+    shapes: Vec<Shape>,
+    child: Option<Box<Row>>,
+}
+
+fn sample() -> Row {
+    Row {
+        name: "root \"quoted\"".to_string(),
+        value: Some(1.5),
+        tags: vec!["a".to_string(), "\u{e9}".to_string()],
+        shapes: vec![
+            Shape::Point,
+            Shape::Circle { radius: 2.0 },
+            Shape::Polygon(vec![(0, 0), (1, -1)]),
+        ],
+        child: Some(Box::new(Row {
+            name: "child".to_string(),
+            value: None,
+            tags: vec![],
+            shapes: vec![],
+            child: None,
+        })),
+    }
+}
+
+#[test]
+fn serde_roundtrip() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let row = sample();
+    let value = to_value(&ctx, &row).unwrap();
+    assert!(value.is_object());
+
+    // the value is a real object, usable from scripts
+    ctx.global_object()
+        .set_property("row", &value, Default::default())
+        .unwrap();
+    let radius = ctx
+        .evaluate_script("row.shapes[1].Circle.radius", None, None, 1)
+        .unwrap();
+    assert_eq!(radius.as_number().unwrap(), 2.0);
+    let child_value = ctx
+        .evaluate_script("row.child.value", None, None, 1)
+        .unwrap();
+    assert!(child_value.is_null());
+
+    let back: Row = from_value(&value).unwrap();
+    assert_eq!(back, row);
+}
+
+#[test]
+fn from_script_values() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let value = ctx
+        .evaluate_script("[[1, 2], [], [3]]", None, None, 1)
+        .unwrap();
+    let nested: Vec<Vec<u8>> = from_value(&value).unwrap();
+    assert_eq!(nested, vec![vec![1, 2], vec![], vec![3]]);
+
+    // `undefined` and `null` are both `None`
+    let undefined: Option<u32> = from_value(&JSValue::new_undefined(&ctx)).unwrap();
+    assert_eq!(undefined, None);
+    let null: Option<u32> = from_value(&JSValue::new_null(&ctx)).unwrap();
+    assert_eq!(null, None);
+
+    // type mismatch
+    let string = JSValue::new_string(&ctx, "text");
+    assert!(matches!(
+        from_value::<u32>(&string),
+        Err(JsSerdeError::Json(_))
+    ));
+
+    // circular references throw in `JSON.stringify`
+    let circular = ctx
+        .evaluate_script("var a = {}; a.self = a; a", None, None, 1)
+        .unwrap();
+    assert!(matches!(
+        from_value::<serde_json::Value>(&circular),
+        Err(JsSerdeError::Exception(_))
+    ));
+}
+
+#[test]
+fn json_string_indent() {
+    let lib = unsafe { Library::load().unwrap() };
+    let ctx = JSContext::new(lib);
+
+    let value = ctx.evaluate_script("({a: [1]})", None, None, 1).unwrap();
+    assert_eq!(
+        String::from(&value.to_json_string().unwrap()),
+        r#"{"a":[1]}"#
+    );
+    assert_eq!(
+        String::from(&value.to_json_string_indented(2).unwrap()),
+        "{\n  \"a\": [\n    1\n  ]\n}"
+    );
+}
@@ -0,0 +1,33 @@
+use ul_next::{Library, Version};
+
+#[test]
+fn version_compatibility() {
+    let expected = Library::expected_version();
+    assert_eq!(expected.to_string(), "1.4.0");
+
+    let patch = Version {
+        patch: expected.patch + 1,
+        ..expected
+    };
+    assert!(patch.is_compatible_with(&expected));
+
+    let minor = Version {
+        minor: expected.minor + 1,
+        ..expected
+    };
+    assert!(!minor.is_compatible_with(&expected));
+
+    let major = Version {
+        major: expected.major + 1,
+        ..expected
+    };
+    assert!(!major.is_compatible_with(&expected));
+}
+
+#[test]
+fn loaded_library_matches_bindings() {
+    let lib = unsafe { Library::load().unwrap() };
+    assert!(lib
+        .version()
+        .is_compatible_with(&Library::expected_version()));
+}
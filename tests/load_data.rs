@@ -0,0 +1,76 @@
+mod common;
+
+use ul_next::{error::CreationError, platform};
+
+const STYLE_CSS: &str = "body { background-color: rgb(0, 255, 0); }";
+
+#[test]
+fn load_data_of_different_types() {
+    let lib = common::load_library();
+
+    let mut fs = common::memory_filesystem();
+    fs.insert("site/style.css", STYLE_CSS.as_bytes());
+    platform::set_filesystem(lib.clone(), fs);
+
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 64, 64);
+
+    // HTML with relative URLs resolved against the base URL
+    common::load_and_wait(&renderer, &view, |view| {
+        view.load_data(
+            br#"<html><head><link rel="stylesheet" href="./style.css"></head><body></body></html>"#,
+            "text/html",
+            "utf-8",
+            "file:///site/",
+        )
+        .unwrap()
+    });
+    let background = view
+        .evaluate_script("getComputedStyle(document.body).backgroundColor")
+        .unwrap()
+        .unwrap();
+    assert_eq!(background, "rgb(0, 255, 0)");
+
+    // non UTF-8 text, loaded from a `data:` URL
+    common::load_and_wait(&renderer, &view, |view| {
+        view.load_data(b"caf\xe9", "text/plain", "iso-8859-1", "")
+            .unwrap()
+    });
+    let text = view
+        .evaluate_script("document.body.innerText")
+        .unwrap()
+        .unwrap();
+    assert_eq!(text.trim(), "café");
+    assert!(view
+        .url()
+        .unwrap()
+        .starts_with("data:text/plain;charset=iso-8859-1;base64,"));
+
+    // binary data of every length modulo 3 survives the encoding
+    let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10"/></svg>"#;
+    for len in [svg.len(), svg.len() + 1, svg.len() + 2] {
+        let mut data = svg.to_vec();
+        data.resize(len, b' ');
+        common::load_and_wait(&renderer, &view, |view| {
+            view.load_data(&data, "image/svg+xml", "", "").unwrap()
+        });
+        let width = view
+            .evaluate_script("document.documentElement.getAttribute('width')")
+            .unwrap()
+            .unwrap();
+        assert_eq!(width, "10");
+    }
+
+    for (mime_type, charset) in [
+        ("text/html; charset=utf-8", ""),
+        ("text", ""),
+        ("text/", ""),
+        ("text/plain", "utf 8"),
+        ("text/plain,", ""),
+    ] {
+        assert!(matches!(
+            view.load_data(b"", mime_type, charset, ""),
+            Err(CreationError::UnsupportedContentType(_))
+        ));
+    }
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use ul_next::platform;
+
+const STYLE_CSS: &str = "body { background-color: rgb(0, 0, 255); }";
+
+#[test]
+fn relative_urls_use_base_url() {
+    let lib = common::load_library();
+
+    let mut fs = common::memory_filesystem();
+    fs.insert("assets/style.css", STYLE_CSS.as_bytes());
+    platform::set_filesystem(lib.clone(), fs);
+
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 64, 64);
+
+    common::load_and_wait(&renderer, &view, |view| {
+        view.load_html_with_base_url(
+            r#"<!DOCTYPE html><html><head><link rel="stylesheet" href="style.css"></head><body></body></html>"#,
+            "file:///assets/",
+        )
+        .unwrap()
+    });
+
+    let background = view
+        .evaluate_script("getComputedStyle(document.body).backgroundColor")
+        .unwrap()
+        .unwrap();
+    assert_eq!(background, "rgb(0, 0, 255)");
+
+    let base = view.evaluate_script("document.baseURI").unwrap().unwrap();
+    assert_eq!(base, "file:///assets/");
+    let compat_mode = view
+        .evaluate_script("document.compatMode")
+        .unwrap()
+        .unwrap();
+    assert_eq!(compat_mode, "CSS1Compat");
+}
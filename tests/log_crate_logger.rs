@@ -0,0 +1,61 @@
+#![cfg(feature = "log")]
+
+mod common;
+
+use std::{cell::Cell, rc::Rc, sync::Mutex};
+
+use ul_next::platform;
+
+/// The messages logged with the `ultralight` target.
+static MESSAGES: Mutex<Vec<(log::Level, String)>> = Mutex::new(Vec::new());
+
+struct CapturingLogger;
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.target() == "ultralight" {
+            MESSAGES
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn messages_are_forwarded_to_log() {
+    log::set_logger(&CapturingLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let lib = common::load_library();
+    platform::enable_log_crate_logger(lib.clone());
+
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 100, 100);
+
+    let failed = Rc::new(Cell::new(false));
+    {
+        let failed = failed.clone();
+        view.set_fail_loading_callback(move |_view, _frame_id, is_main_frame, _, _, _, _| {
+            if is_main_frame {
+                failed.set(true);
+            }
+        });
+    }
+    view.load_url("https://ul-next.invalid/").unwrap();
+    common::update_until(&renderer, "the page to fail loading", || failed.get());
+
+    let messages = MESSAGES.lock().unwrap();
+    assert!(!messages.is_empty());
+    // Ultralight only has these levels
+    assert!(messages.iter().all(|(level, _)| matches!(
+        level,
+        log::Level::Info | log::Level::Warn | log::Level::Error
+    )));
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use ul_next::{
+    event::{KeyEvent, KeyEventCreationInfo, KeyEventModifiers, KeyEventType},
+    key_code::VirtualKeyCode,
+    platform::{self, clipboards::MemoryClipboard},
+    view::View,
+    Library,
+};
+
+const HTML: &str = r#"<html>
+<body>
+  <input id="input" value="copied text">
+</body>
+</html>"#;
+
+fn fire_ctrl_shortcut(lib: &std::sync::Arc<Library>, view: &View, key: VirtualKeyCode, text: &str) {
+    // the shortcuts are handled as accelerator commands on the raw key down
+    let event = KeyEvent::new(
+        lib.clone(),
+        KeyEventCreationInfo {
+            ty: KeyEventType::RawKeyDown,
+            modifiers: KeyEventModifiers {
+                alt: false,
+                ctrl: true,
+                meta: false,
+                shift: false,
+            },
+            virtual_key_code: key,
+            native_key_code: 0,
+            text: "",
+            unmodified_text: text,
+            is_keypad: false,
+            is_auto_repeat: false,
+            is_system_key: false,
+        },
+    )
+    .unwrap();
+    view.fire_key_event(event);
+}
+
+#[test]
+fn copy_and_paste_with_memory_clipboard() {
+    let lib = common::load_library();
+
+    let clipboard = MemoryClipboard::new();
+    platform::set_clipboard(lib.clone(), clipboard.clone());
+
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 200, 100);
+
+    common::load_and_wait(&renderer, &view, |view| view.load_html(HTML).unwrap());
+
+    view.focus();
+    view.evaluate_script("input.focus(); input.select();")
+        .unwrap()
+        .unwrap();
+    fire_ctrl_shortcut(&lib, &view, VirtualKeyCode::C, "c");
+    assert_eq!(clipboard.text(), "copied text");
+
+    clipboard.set_text("pasted text");
+    view.evaluate_script("input.value = ''; input.focus();")
+        .unwrap()
+        .unwrap();
+    fire_ctrl_shortcut(&lib, &view, VirtualKeyCode::V, "v");
+    assert_eq!(
+        view.evaluate_script("input.value").unwrap().unwrap(),
+        "pasted text"
+    );
+
+    drop(view);
+    drop(renderer);
+    // the renderer was dropped, so the clipboard is not used anymore
+    unsafe { platform::clear_clipboard(lib) };
+}
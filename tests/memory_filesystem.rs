@@ -0,0 +1,49 @@
+mod common;
+
+use ul_next::platform;
+
+/// A 1x1 green PNG image.
+const IMAGE_PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+    0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x60, 0xf8, 0xcf, 0xf0,
+    0x1f, 0x00, 0x04, 0x01, 0x01, 0xff, 0x71, 0xeb, 0x47, 0xe5, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45,
+    0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+
+const INDEX_HTML: &str = r#"<html>
+<head><link rel="stylesheet" href="style.css"></head>
+<body><img id="image" src="/images/../image.png"></body>
+</html>"#;
+
+const STYLE_CSS: &str = "body { background-color: rgb(255, 0, 0); }";
+
+#[test]
+fn load_page_from_memory() {
+    let lib = common::load_library();
+
+    let mut fs = common::memory_filesystem();
+    fs.insert("index.html", INDEX_HTML.as_bytes());
+    fs.insert("./style.css", STYLE_CSS.as_bytes());
+    fs.insert("/image.png", IMAGE_PNG);
+    platform::set_filesystem(lib.clone(), fs);
+
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 64, 64);
+
+    common::load_and_wait(&renderer, &view, |view| {
+        view.load_url("file:///index.html").unwrap()
+    });
+
+    let background = view
+        .evaluate_script("getComputedStyle(document.body).backgroundColor")
+        .unwrap()
+        .unwrap();
+    assert_eq!(background, "rgb(255, 0, 0)");
+
+    let image_width = view
+        .evaluate_script("document.getElementById('image').naturalWidth")
+        .unwrap()
+        .unwrap();
+    assert_eq!(image_width, "1");
+}
@@ -0,0 +1,70 @@
+//! Parses a captured memory usage log, so this doesn't need Ultralight.
+
+use ul_next::renderer::{MemoryReport, MemoryStats, MemoryStatsEntry};
+
+const SAMPLE_LOG: &str = "\
+--------------------------------------------------------------------------------
+Memory Usage:
+--------------------------------------------------------------------------------
+JavaScript Heap:
+    Size: 2.50 MB
+    Capacity: 4 MB
+Page Cache:
+    Size: 0 B
+Image Decoder Cache:
+    Size: 1,536 KB
+Font Cache:
+    Glyphs: 123 KB (412 glyphs)
+Total: 12.25 MB
+Uptime: 10 seconds
+";
+
+#[test]
+fn parse_memory_usage_log() {
+    let stats = MemoryStats::from_log([SAMPLE_LOG]);
+
+    assert_eq!(stats.javascript_heap_bytes, Some(2_621_440));
+    assert_eq!(stats.page_cache_bytes, Some(0));
+    assert_eq!(stats.image_cache_bytes, Some(1536 * 1024));
+    assert_eq!(stats.font_cache_bytes, Some(123 * 1024));
+    assert_eq!(stats.total_bytes, Some(12_845_056));
+
+    assert_eq!(stats.entries.len(), 6);
+    assert_eq!(
+        stats.entries[1],
+        MemoryStatsEntry {
+            section: Some("JavaScript Heap".to_string()),
+            label: "Capacity".to_string(),
+            bytes: 4 << 20,
+        }
+    );
+    // all the non empty lines are kept, including the ones without a size
+    assert_eq!(stats.lines.len(), 14);
+    assert_eq!(stats.lines.last().unwrap(), "Uptime: 10 seconds");
+}
+
+#[test]
+fn unknown_lines_leave_fields_empty() {
+    let stats = MemoryStats::from_log(["Something: 3 MB", "Not a size: abc", "No colon"]);
+
+    assert_eq!(stats.total_bytes, None);
+    assert_eq!(stats.javascript_heap_bytes, None);
+    assert_eq!(stats.entries.len(), 1);
+    assert_eq!(stats.entries[0].bytes, 3 << 20);
+    assert_eq!(stats.lines.len(), 3);
+}
+
+#[test]
+fn report_freed_bytes() {
+    let report = MemoryReport {
+        before: Some(MemoryStats::from_log(["Total: 10 MB"])),
+        after: Some(MemoryStats::from_log(["Total: 4 MB"])),
+    };
+    assert_eq!(report.freed_bytes(), Some(6 << 20));
+
+    let report = MemoryReport {
+        before: None,
+        ..report
+    };
+    assert_eq!(report.freed_bytes(), None);
+}
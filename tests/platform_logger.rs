@@ -0,0 +1,41 @@
+mod common;
+
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use ul_next::platform::{self, LogLevel};
+
+#[test]
+fn logger_fn_receives_messages() {
+    let lib = common::load_library();
+
+    let messages: Arc<Mutex<Vec<(LogLevel, String)>>> = Arc::default();
+    {
+        let messages = messages.clone();
+        platform::set_logger_fn(lib.clone(), move |level, message| {
+            messages.lock().unwrap().push((level, message));
+        });
+    }
+
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 100, 100);
+
+    let failed = Rc::new(Cell::new(false));
+    {
+        let failed = failed.clone();
+        view.set_fail_loading_callback(move |_view, _frame_id, is_main_frame, _, _, _, _| {
+            if is_main_frame {
+                failed.set(true);
+            }
+        });
+    }
+    view.load_url("https://ul-next.invalid/").unwrap();
+    common::update_until(&renderer, "the page to fail loading", || failed.get());
+
+    let messages = messages.lock().unwrap();
+    assert!(!messages.is_empty());
+    assert!(messages.iter().all(|(_, message)| !message.is_empty()));
+}
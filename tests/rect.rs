@@ -0,0 +1,129 @@
+use ul_next::rect::Rect;
+
+fn rect<T>(left: T, top: T, right: T, bottom: T) -> Rect<T> {
+    Rect {
+        left,
+        top,
+        right,
+        bottom,
+    }
+}
+
+#[test]
+fn size() {
+    let r = rect(10, 20, 40, 30);
+    assert_eq!(r.width(), 30);
+    assert_eq!(r.height(), 10);
+
+    // inverted rects have negative size
+    let inverted = rect(40, 30, 10, 20);
+    assert_eq!(inverted.width(), -30);
+    assert_eq!(inverted.height(), -10);
+
+    let r = rect(0.5f32, 1.0, 2.0, 4.5);
+    assert_eq!(r.width(), 1.5);
+    assert_eq!(r.height(), 3.5);
+}
+
+#[test]
+fn is_empty() {
+    assert!(rect(0, 0, 0, 0).is_empty());
+    assert!(rect(5, 5, 5, 10).is_empty());
+    assert!(rect(5, 5, 10, 5).is_empty());
+    assert!(rect(10, 10, 5, 20).is_empty());
+    assert!(rect(10, 10, 20, 5).is_empty());
+    assert!(rect(i32::MAX, i32::MAX, i32::MIN, i32::MIN).is_empty());
+    assert!(!rect(0, 0, 1, 1).is_empty());
+    assert!(!rect(-10, -10, -5, -5).is_empty());
+
+    assert!(rect(0u32, 0, 0, 0).is_empty());
+    assert!(rect(0.0, 0.0, f32::NAN, 1.0).is_empty());
+    assert!(!rect(0.0f32, 0.0, 0.5, 0.5).is_empty());
+}
+
+#[test]
+fn contains_point() {
+    let r = rect(0, 0, 10, 5);
+    assert!(r.contains_point(0, 0));
+    assert!(r.contains_point(9, 4));
+    // right and bottom edges are exclusive
+    assert!(!r.contains_point(10, 0));
+    assert!(!r.contains_point(0, 5));
+    assert!(!r.contains_point(-1, 2));
+
+    assert!(!rect(0, 0, 0, 0).contains_point(0, 0));
+    assert!(!rect(10, 10, 0, 0).contains_point(5, 5));
+
+    let r = rect(0.0, 0.0, 1.0, 1.0);
+    assert!(r.contains_point(0.5, 0.999));
+    assert!(!r.contains_point(1.0, 0.5));
+}
+
+#[test]
+fn intersect() {
+    let a = rect(0, 0, 10, 10);
+    let b = rect(5, -5, 15, 5);
+    assert_eq!(a.intersect(&b), Some(rect(5, 0, 10, 5)));
+    assert_eq!(b.intersect(&a), Some(rect(5, 0, 10, 5)));
+
+    // contained
+    let inner = rect(2, 3, 4, 5);
+    assert_eq!(a.intersect(&inner), Some(inner));
+    assert_eq!(a.intersect(&a), Some(a));
+
+    // touching edges don't overlap
+    assert_eq!(a.intersect(&rect(10, 0, 20, 10)), None);
+    assert_eq!(a.intersect(&rect(0, 10, 10, 20)), None);
+    // disjoint
+    assert_eq!(a.intersect(&rect(20, 20, 30, 30)), None);
+    // empty and inverted
+    assert_eq!(a.intersect(&rect(0, 0, 0, 0)), None);
+    assert_eq!(a.intersect(&rect(5, 5, 5, 5)), None);
+    assert_eq!(a.intersect(&rect(8, 8, 2, 2)), None);
+
+    let a = rect(0.0, 0.0, 1.0, 1.0);
+    let b = rect(0.5, 0.25, 2.0, 0.75);
+    assert_eq!(a.intersect(&b), Some(rect(0.5, 0.25, 1.0, 0.75)));
+}
+
+#[test]
+fn union() {
+    let a = rect(0, 0, 10, 10);
+    let b = rect(5, -5, 15, 5);
+    assert_eq!(a.union(&b), rect(0, -5, 15, 10));
+    assert_eq!(b.union(&a), rect(0, -5, 15, 10));
+    assert_eq!(a.union(&rect(20, 20, 30, 30)), rect(0, 0, 30, 30));
+    assert_eq!(a.union(&a), a);
+
+    // empty rects are ignored
+    let empty = rect(0, 0, 0, 0);
+    let inverted = rect(100, 100, -100, -100);
+    assert_eq!(a.union(&empty), a);
+    assert_eq!(empty.union(&a), a);
+    assert_eq!(a.union(&inverted), a);
+    assert_eq!(inverted.union(&a), a);
+    assert!(empty.union(&inverted).is_empty());
+}
+
+#[test]
+fn translate_and_scale() {
+    let r = rect(1, 2, 3, 4);
+    assert_eq!(r.translate(10, -20), rect(11, -18, 13, -16));
+    assert_eq!(r.translate(0, 0), r);
+    assert_eq!(r.scale(2), rect(2, 4, 6, 8));
+    assert_eq!(r.scale(0), rect(0, 0, 0, 0));
+    assert!(r.scale(-1).is_empty());
+
+    let r = rect(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(r.scale(1.5), rect(1.5, 3.0, 4.5, 6.0));
+    assert_eq!(r.translate(-1.0, 0.5), rect(0.0, 2.5, 2.0, 4.5));
+}
+
+#[test]
+fn conversions() {
+    let r: Rect<f32> = rect(-1, 2, 300, 400).into();
+    assert_eq!(r, rect(-1.0, 2.0, 300.0, 400.0));
+
+    let uniform: [f32; 4] = rect(0.0, 0.25, 0.5, 1.0).into();
+    assert_eq!(uniform, [0.0, 0.25, 0.5, 1.0]);
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use ul_next::platform;
+
+#[test]
+fn memory_usage_keeps_a_logger() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 100, 100);
+
+    // without a logger, the one installed for the capture stays, and
+    // Ultralight can keep logging
+    assert!(renderer.memory_usage().is_some());
+    common::load_and_wait(&renderer, &view, |view| {
+        view.load_html("<p>logged</p>").unwrap()
+    });
+    assert!(renderer.memory_usage().is_some());
+
+    // a logger set with `set_logger` is restored after the capture
+    let messages: Arc<Mutex<Vec<String>>> = Arc::default();
+    {
+        let messages = messages.clone();
+        platform::set_logger_fn(lib.clone(), move |_level, message| {
+            messages.lock().unwrap().push(message);
+        });
+    }
+    let stats = renderer.memory_usage().unwrap();
+    assert!(stats.total_bytes.is_some());
+    assert!(messages.lock().unwrap().is_empty());
+
+    renderer.log_memory_usage();
+    assert!(!messages.lock().unwrap().is_empty());
+}
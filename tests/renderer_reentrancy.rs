@@ -0,0 +1,80 @@
+mod common;
+
+use std::{cell::Cell, rc::Rc, sync::Arc};
+
+use ul_next::{error::Error, renderer::Renderer, Library};
+
+/// Calls re-entering Ultralight from callbacks are refused, and deferred
+/// closures run on the next update.
+fn calls_from_callbacks_are_deferred(lib: &Arc<Library>, renderer: &Renderer) {
+    let view = common::create_view(lib, renderer, 100, 100);
+
+    let child_requested = Rc::new(Cell::new(false));
+    let resize_reentrant = Rc::new(Cell::new(false));
+    let deferred_ran = Rc::new(Cell::new(false));
+    {
+        let queue = renderer.defer_queue();
+        let child_requested = child_requested.clone();
+        let resize_reentrant = resize_reentrant.clone();
+        let deferred_ran = deferred_ran.clone();
+        view.set_create_child_view_callback(move |view, _opener, _target, _is_popup, _rect| {
+            child_requested.set(true);
+
+            // re-enters Ultralight, so it's refused
+            resize_reentrant.set(matches!(
+                view.resize(50, 50),
+                Err(Error::ReentrantCall("View::resize"))
+            ));
+
+            let deferred_ran = deferred_ran.clone();
+            queue.defer(move |renderer| {
+                renderer.render().unwrap();
+                deferred_ran.set(true);
+            });
+            None
+        });
+    }
+
+    let _ = view
+        .evaluate_script("window.open('about:blank', '_blank')")
+        .unwrap();
+    assert!(child_requested.get());
+    assert!(resize_reentrant.get());
+    assert_eq!((view.width(), view.height()), (100, 100));
+    assert!(!deferred_ran.get());
+    assert_eq!(renderer.defer_queue().len(), 1);
+
+    renderer.update().unwrap();
+    assert!(deferred_ran.get());
+    assert!(renderer.defer_queue().is_empty());
+
+    // outside of callbacks, the calls go through
+    view.resize(50, 50).unwrap();
+    assert_eq!((view.width(), view.height()), (50, 50));
+}
+
+/// Deferred closures can defer more closures, run in the same update.
+fn deferred_closures_can_defer_more(renderer: &Renderer) {
+    let count = Rc::new(Cell::new(0));
+    {
+        let count = count.clone();
+        renderer.defer(move |renderer| {
+            count.set(count.get() + 1);
+            let count = count.clone();
+            renderer.defer(move |_| count.set(count.get() + 1));
+        });
+    }
+
+    renderer.update().unwrap();
+    assert_eq!(count.get(), 2);
+}
+
+// all the cases share a single renderer, only one can be created at a time
+#[test]
+fn defer_calls_from_callbacks() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+
+    calls_from_callbacks_are_deferred(&lib, &renderer);
+    deferred_closures_can_defer_more(&renderer);
+}
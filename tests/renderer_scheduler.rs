@@ -0,0 +1,176 @@
+//! Checks the views painted by `Scheduler` with fake views and renderer, so
+//! this doesn't need Ultralight.
+
+use std::cell::Cell;
+
+use ul_next::{
+    error::Error,
+    renderer::{FrameDriver, PaintTarget, Scheduler, SchedulerStats},
+};
+
+#[derive(Default)]
+struct FakeView {
+    needs_paint: Cell<bool>,
+    paints: Cell<u32>,
+}
+
+impl FakeView {
+    fn invalidate(&self) {
+        self.needs_paint.set(true);
+    }
+
+    fn take_paints(&self) -> u32 {
+        self.paints.replace(0)
+    }
+}
+
+impl PaintTarget for FakeView {
+    fn needs_paint(&self) -> bool {
+        self.needs_paint.get()
+    }
+
+    fn set_needs_paint(&self, needs_paint: bool) {
+        self.needs_paint.set(needs_paint);
+    }
+}
+
+/// Paints the views that need painting, like `Renderer::render`.
+struct FakeRenderer<'a> {
+    views: &'a [FakeView],
+    updates: Cell<u32>,
+}
+
+impl FrameDriver for FakeRenderer<'_> {
+    fn update(&self) -> Result<(), Error> {
+        self.updates.set(self.updates.get() + 1);
+        Ok(())
+    }
+
+    fn render(&self) -> Result<(), Error> {
+        for view in self.views {
+            if view.needs_paint.replace(false) {
+                view.paints.set(view.paints.get() + 1);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn hidden_views_are_painted_once_visible() {
+    let views: [FakeView; 2] = Default::default();
+    let renderer = FakeRenderer {
+        views: &views,
+        updates: Cell::new(0),
+    };
+    let mut scheduler = Scheduler::new();
+    let _shown = scheduler.register(&views[0], true, 0);
+    let hidden = scheduler.register(&views[1], false, 0);
+
+    views[0].invalidate();
+    views[1].invalidate();
+    scheduler.tick(&renderer).unwrap();
+    assert_eq!(renderer.updates.get(), 1);
+    assert_eq!(views[0].take_paints(), 1);
+    assert_eq!(views[1].take_paints(), 0);
+    assert_eq!(scheduler.is_pending(hidden), Some(true));
+    assert_eq!(
+        scheduler.stats(),
+        SchedulerStats {
+            painted_last_frame: 1,
+            deferred: 0,
+            hidden_pending: 1,
+        }
+    );
+
+    // the paint is kept while hidden
+    scheduler.tick(&renderer).unwrap();
+    assert_eq!(views[1].take_paints(), 0);
+
+    assert!(scheduler.set_visible(hidden, true));
+    scheduler.tick(&renderer).unwrap();
+    assert_eq!(views[0].take_paints(), 0);
+    assert_eq!(views[1].take_paints(), 1);
+    assert_eq!(scheduler.is_pending(hidden), Some(false));
+    assert_eq!(scheduler.stats().hidden_pending, 0);
+}
+
+#[test]
+fn paints_per_frame_are_limited_fairly() {
+    let views: [FakeView; 3] = Default::default();
+    let renderer = FakeRenderer {
+        views: &views,
+        updates: Cell::new(0),
+    };
+    let mut scheduler = Scheduler::new();
+    scheduler.set_max_paints_per_frame(Some(1));
+    for view in &views {
+        scheduler.register(view, true, 0);
+    }
+
+    // all the views invalidate every frame, they must still take turns
+    let mut paints = [0; 3];
+    for _ in 0..6 {
+        for view in &views {
+            view.invalidate();
+        }
+        scheduler.tick(&renderer).unwrap();
+        assert_eq!(scheduler.stats().painted_last_frame, 1);
+        assert_eq!(scheduler.stats().deferred, 2);
+        for (paints, view) in paints.iter_mut().zip(&views) {
+            *paints += view.take_paints();
+        }
+    }
+    assert_eq!(paints, [2, 2, 2]);
+}
+
+#[test]
+fn higher_priority_is_painted_first() {
+    let views: [FakeView; 2] = Default::default();
+    let renderer = FakeRenderer {
+        views: &views,
+        updates: Cell::new(0),
+    };
+    let mut scheduler = Scheduler::new();
+    scheduler.set_max_paints_per_frame(Some(1));
+    scheduler.register(&views[0], true, 0);
+    let high = scheduler.register(&views[1], true, 5);
+    assert_eq!(scheduler.priority(high), Some(5));
+
+    views[0].invalidate();
+    views[1].invalidate();
+    scheduler.tick(&renderer).unwrap();
+    assert_eq!(views[0].take_paints(), 0);
+    assert_eq!(views[1].take_paints(), 1);
+
+    // the deferred view is painted when nothing else needs painting
+    scheduler.tick(&renderer).unwrap();
+    assert_eq!(views[0].take_paints(), 1);
+    assert_eq!(scheduler.stats().deferred, 0);
+}
+
+#[test]
+fn pending_paint_is_restored_on_unregister_and_drop() {
+    let views: [FakeView; 2] = Default::default();
+    let renderer = FakeRenderer {
+        views: &views,
+        updates: Cell::new(0),
+    };
+    let mut scheduler = Scheduler::new();
+    let first = scheduler.register(&views[0], false, 0);
+    scheduler.register(&views[1], false, 0);
+
+    views[0].invalidate();
+    views[1].invalidate();
+    scheduler.tick(&renderer).unwrap();
+    assert!(!views[0].needs_paint.get());
+    assert!(!views[1].needs_paint.get());
+
+    assert!(scheduler.unregister(first));
+    assert!(!scheduler.unregister(first));
+    assert!(views[0].needs_paint.get());
+    assert_eq!(scheduler.len(), 1);
+
+    drop(scheduler);
+    assert!(views[1].needs_paint.get());
+}
@@ -0,0 +1,42 @@
+mod common;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+#[test]
+fn snapshot_all_views() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+
+    let view_red = common::create_view(&lib, &renderer, 64, 32);
+    let view_blue = common::create_view(&lib, &renderer, 32, 64);
+
+    for (view, color) in [(&view_red, "red"), (&view_blue, "blue")] {
+        common::load_and_wait(&renderer, view, |view| {
+            view.load_html(&format!(
+                "<html><body style=\"background: {}\"></body></html>",
+                color
+            ))
+            .unwrap()
+        });
+    }
+
+    let output_dir = std::env::temp_dir().join(format!("ul-next-snapshot-{}", std::process::id()));
+
+    let files = renderer.take_snapshot_png(&output_dir).unwrap();
+    assert_eq!(
+        files,
+        vec![output_dir.join("view_0.png"), output_dir.join("view_1.png")]
+    );
+    for file in &files {
+        let data = std::fs::read(file).unwrap();
+        assert!(data.starts_with(PNG_SIGNATURE));
+    }
+
+    // dropped views are not captured anymore
+    drop(view_red);
+    std::fs::remove_dir_all(&output_dir).unwrap();
+    let files = renderer.take_snapshot_png(&output_dir).unwrap();
+    assert_eq!(files, vec![output_dir.join("view_0.png")]);
+
+    std::fs::remove_dir_all(&output_dir).unwrap();
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use ul_next::platform::{
+    filesystems::{MemoryFileSystem, ResourceFileSystem},
+    FileSystem,
+};
+
+fn resources() -> HashMap<String, Vec<u8>> {
+    let mut resources = HashMap::new();
+    resources.insert("cacert.pem".to_string(), b"certificates".to_vec());
+    resources.insert("/icudt67l.dat".to_string(), b"icu".to_vec());
+    resources
+}
+
+#[test]
+fn serve_resources_under_prefix() {
+    let mut fs = ResourceFileSystem::new("resources/", resources());
+
+    assert!(fs.file_exists("file:///resources/cacert.pem"));
+    assert!(fs.file_exists("resources/icudt67l.dat"));
+    assert_eq!(
+        fs.open_file("/resources/cacert.pem").unwrap(),
+        b"certificates"
+    );
+    assert_eq!(
+        fs.get_file_mime_type("resources/cacert.pem"),
+        "application/octet-stream"
+    );
+
+    // not under the prefix, and no fallback
+    assert!(!fs.file_exists("cacert.pem"));
+    assert!(fs.open_file("index.html").is_none());
+    assert!(!fs.file_exists("resources-other/cacert.pem"));
+}
+
+#[test]
+fn fallback_outside_of_prefix() {
+    let mut app = MemoryFileSystem::new();
+    app.insert("index.html", &b"<html></html>"[..]);
+    app.insert("resources/secret.txt", &b"hidden"[..]);
+
+    let mut fs = ResourceFileSystem::new("assets/ul", resources()).with_fallback(app);
+
+    assert!(fs.file_exists("assets/ul/cacert.pem"));
+    assert_eq!(fs.open_file("index.html").unwrap(), b"<html></html>");
+    assert_eq!(fs.get_file_mime_type("index.html"), "text/html");
+    // the default prefix is a normal directory here
+    assert!(fs.file_exists("resources/secret.txt"));
+}
+
+#[test]
+fn prefix_is_restricted_to_resources() {
+    let mut app = MemoryFileSystem::new();
+    app.insert("resources/page.html", &b"<html></html>"[..]);
+
+    let mut fs = ResourceFileSystem::new("resources", resources()).with_fallback(app);
+
+    // files of the fallback under the prefix are not served
+    assert!(!fs.file_exists("resources/page.html"));
+    assert!(fs.open_file("resources/page.html").is_none());
+    // and resource names can't escape the prefix
+    assert!(!fs.file_exists("resources/../cacert.pem"));
+}
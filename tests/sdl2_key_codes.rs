@@ -0,0 +1,121 @@
+#![cfg(feature = "sdl2")]
+
+use sdl2::keyboard::{Keycode, Scancode};
+use ul_next::key_code::VirtualKeyCode;
+
+/// (key code, scan code of the same key in the US layout, expected virtual key code)
+const KEYS: &[(Keycode, Scancode, i32)] = &[
+    (Keycode::A, Scancode::A, 0x41),
+    (Keycode::B, Scancode::B, 0x42),
+    (Keycode::C, Scancode::C, 0x43),
+    (Keycode::M, Scancode::M, 0x4D),
+    (Keycode::Q, Scancode::Q, 0x51),
+    (Keycode::Z, Scancode::Z, 0x5A),
+    (Keycode::Num0, Scancode::Num0, 0x30),
+    (Keycode::Num1, Scancode::Num1, 0x31),
+    (Keycode::Num5, Scancode::Num5, 0x35),
+    (Keycode::Num9, Scancode::Num9, 0x39),
+    (Keycode::Return, Scancode::Return, 0x0D),
+    (Keycode::Escape, Scancode::Escape, 0x1B),
+    (Keycode::Backspace, Scancode::Backspace, 0x08),
+    (Keycode::Tab, Scancode::Tab, 0x09),
+    (Keycode::Space, Scancode::Space, 0x20),
+    (Keycode::CapsLock, Scancode::CapsLock, 0x14),
+    (Keycode::Minus, Scancode::Minus, 0xBD),
+    (Keycode::Equals, Scancode::Equals, 0xBB),
+    (Keycode::LeftBracket, Scancode::LeftBracket, 0xDB),
+    (Keycode::RightBracket, Scancode::RightBracket, 0xDD),
+    (Keycode::Backslash, Scancode::Backslash, 0xDC),
+    (Keycode::Semicolon, Scancode::Semicolon, 0xBA),
+    (Keycode::Quote, Scancode::Apostrophe, 0xDE),
+    (Keycode::Backquote, Scancode::Grave, 0xC0),
+    (Keycode::Comma, Scancode::Comma, 0xBC),
+    (Keycode::Period, Scancode::Period, 0xBE),
+    (Keycode::Slash, Scancode::Slash, 0xBF),
+    (Keycode::F1, Scancode::F1, 0x70),
+    (Keycode::F2, Scancode::F2, 0x71),
+    (Keycode::F5, Scancode::F5, 0x74),
+    (Keycode::F10, Scancode::F10, 0x79),
+    (Keycode::F11, Scancode::F11, 0x7A),
+    (Keycode::F12, Scancode::F12, 0x7B),
+    (Keycode::F13, Scancode::F13, 0x7C),
+    (Keycode::F24, Scancode::F24, 0x87),
+    (Keycode::PrintScreen, Scancode::PrintScreen, 0x2C),
+    (Keycode::ScrollLock, Scancode::ScrollLock, 0x91),
+    (Keycode::Pause, Scancode::Pause, 0x13),
+    (Keycode::Insert, Scancode::Insert, 0x2D),
+    (Keycode::Home, Scancode::Home, 0x24),
+    (Keycode::PageUp, Scancode::PageUp, 0x21),
+    (Keycode::Delete, Scancode::Delete, 0x2E),
+    (Keycode::End, Scancode::End, 0x23),
+    (Keycode::PageDown, Scancode::PageDown, 0x22),
+    (Keycode::Left, Scancode::Left, 0x25),
+    (Keycode::Up, Scancode::Up, 0x26),
+    (Keycode::Right, Scancode::Right, 0x27),
+    (Keycode::Down, Scancode::Down, 0x28),
+    (Keycode::NumLockClear, Scancode::NumLockClear, 0x90),
+    (Keycode::KpDivide, Scancode::KpDivide, 0x6F),
+    (Keycode::KpMultiply, Scancode::KpMultiply, 0x6A),
+    (Keycode::KpMinus, Scancode::KpMinus, 0x6D),
+    (Keycode::KpPlus, Scancode::KpPlus, 0x6B),
+    (Keycode::KpEnter, Scancode::KpEnter, 0x0D),
+    (Keycode::KpPeriod, Scancode::KpPeriod, 0x6E),
+    (Keycode::Kp0, Scancode::Kp0, 0x60),
+    (Keycode::Kp1, Scancode::Kp1, 0x61),
+    (Keycode::Kp2, Scancode::Kp2, 0x62),
+    (Keycode::Kp3, Scancode::Kp3, 0x63),
+    (Keycode::Kp4, Scancode::Kp4, 0x64),
+    (Keycode::Kp5, Scancode::Kp5, 0x65),
+    (Keycode::Kp6, Scancode::Kp6, 0x66),
+    (Keycode::Kp7, Scancode::Kp7, 0x67),
+    (Keycode::Kp8, Scancode::Kp8, 0x68),
+    (Keycode::Kp9, Scancode::Kp9, 0x69),
+    (Keycode::LShift, Scancode::LShift, 0x10),
+    (Keycode::RShift, Scancode::RShift, 0x10),
+    (Keycode::LCtrl, Scancode::LCtrl, 0x11),
+    (Keycode::RCtrl, Scancode::RCtrl, 0x11),
+    (Keycode::LAlt, Scancode::LAlt, 0x12),
+    (Keycode::RAlt, Scancode::RAlt, 0x12),
+    (Keycode::LGui, Scancode::LGui, 0x5B),
+    (Keycode::RGui, Scancode::RGui, 0x5C),
+    (Keycode::Application, Scancode::Application, 0x5D),
+    (Keycode::AudioMute, Scancode::AudioMute, 0xAD),
+    (Keycode::VolumeDown, Scancode::VolumeDown, 0xAE),
+    (Keycode::VolumeUp, Scancode::VolumeUp, 0xAF),
+    (Keycode::AudioNext, Scancode::AudioNext, 0xB0),
+    (Keycode::AudioPrev, Scancode::AudioPrev, 0xB1),
+    (Keycode::AudioStop, Scancode::AudioStop, 0xB2),
+    (Keycode::AudioPlay, Scancode::AudioPlay, 0xB3),
+    (Keycode::Mail, Scancode::Mail, 0xB4),
+    (Keycode::MediaSelect, Scancode::MediaSelect, 0xB5),
+    (Keycode::AcBack, Scancode::AcBack, 0xA6),
+    (Keycode::AcForward, Scancode::AcForward, 0xA7),
+    (Keycode::AcRefresh, Scancode::AcRefresh, 0xA8),
+    (Keycode::AcSearch, Scancode::AcSearch, 0xAA),
+    (Keycode::AcHome, Scancode::AcHome, 0xAC),
+    (Keycode::Sleep, Scancode::Sleep, 0x5F),
+];
+
+#[test]
+fn keycodes_map_to_virtual_key_codes() {
+    for (keycode, _, expected) in KEYS {
+        let virtual_key_code = VirtualKeyCode::from_sdl2_keycode(*keycode)
+            .unwrap_or_else(|| panic!("{keycode:?} is not mapped"));
+        assert_eq!(i32::from(virtual_key_code), *expected, "{keycode:?}");
+    }
+}
+
+#[test]
+fn scancodes_map_to_virtual_key_codes() {
+    for (_, scancode, expected) in KEYS {
+        let virtual_key_code = VirtualKeyCode::from_sdl2_scancode(*scancode)
+            .unwrap_or_else(|| panic!("{scancode:?} is not mapped"));
+        assert_eq!(i32::from(virtual_key_code), *expected, "{scancode:?}");
+    }
+}
+
+#[test]
+fn unmapped_keys() {
+    assert!(VirtualKeyCode::from_sdl2_keycode(Keycode::Exclaim).is_none());
+    assert!(VirtualKeyCode::from_sdl2_scancode(Scancode::Power).is_none());
+}
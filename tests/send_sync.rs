@@ -0,0 +1,45 @@
+//! The types that can cross threads, the ones that can't are checked by
+//! the `compile_fail` examples in the crate documentation.
+
+use ul_next::{
+    bitmap::OwnedBitmap,
+    gpu_driver::{GpuCommand, IndexBuffer, RenderBuffer, VertexBuffer},
+    platform::{loggers::StderrLogger, LogLevel},
+    Library,
+};
+
+fn assert_send<T: Send>() {}
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn library_is_send_sync() {
+    assert_send_sync::<Library>();
+}
+
+#[test]
+fn data_types_are_send() {
+    assert_send::<OwnedBitmap>();
+    assert_send::<GpuCommand>();
+    assert_send::<RenderBuffer>();
+    assert_send::<VertexBuffer>();
+    assert_send::<IndexBuffer>();
+    assert_send::<LogLevel>();
+    assert_send::<StderrLogger>();
+}
+
+#[cfg(feature = "glium")]
+#[test]
+fn glium_gpu_driver_sender_is_send() {
+    assert_send::<ul_next::gpu_driver::glium::GliumGpuDriverSender>();
+}
+
+#[cfg(feature = "miniquad")]
+#[test]
+fn miniquad_gpu_driver_sender_is_send() {
+    assert_send::<ul_next::gpu_driver::miniquad::MiniquadGpuDriverSender>();
+}
+
+#[test]
+fn recording_gpu_driver_is_send() {
+    assert_send::<ul_next::gpu_driver::recording::RecordingDriver>();
+}
@@ -0,0 +1,36 @@
+use ul_next::{config::Config, platform, renderer::Renderer, Library};
+
+#[test]
+fn create_view_with_builder() {
+    let lib = unsafe { Library::load_with_appcore().unwrap() };
+    platform::enable_platform_fontloader(lib.clone());
+
+    let config = Config::start().try_build(lib.clone()).unwrap();
+    let renderer = Renderer::create(config).unwrap();
+
+    let view = renderer
+        .create_view_builder()
+        .size(320, 240)
+        .is_accelerated(false)
+        .is_transparent(true)
+        .initial_device_scale(2.0)
+        .build()
+        .unwrap();
+
+    assert_eq!(view.width(), 320);
+    assert_eq!(view.height(), 240);
+    assert!(!view.is_accelerated());
+    assert!(view.is_transparent());
+    assert_eq!(view.device_scale(), 2.0);
+
+    let session = renderer.create_session(false, "view_builder").unwrap();
+    let view = renderer
+        .create_view_builder()
+        .session(&session)
+        .is_accelerated(false)
+        .build()
+        .unwrap();
+
+    assert_eq!(view.width(), 800);
+    assert_eq!(view.height(), 600);
+}
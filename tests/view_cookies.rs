@@ -0,0 +1,70 @@
+mod common;
+
+use std::time::Duration;
+
+use ul_next::view::{Cookie, CookieError, CookieSameSite};
+
+#[test]
+fn cookie_formatting_and_parsing() {
+    let cookie = Cookie {
+        path: Some("/".to_string()),
+        max_age: Some(Duration::from_secs(3600)),
+        secure: true,
+        same_site: Some(CookieSameSite::Lax),
+        ..Cookie::new("id", "42")
+    };
+    assert_eq!(
+        cookie.to_string(),
+        "id=42; Path=/; Max-Age=3600; Secure; SameSite=Lax"
+    );
+
+    assert_eq!(
+        Cookie::parse_list("id=42; theme=dark=blue;  ;flag"),
+        vec![
+            Cookie::new("id", "42"),
+            Cookie::new("theme", "dark=blue"),
+            Cookie::new("", "flag"),
+        ]
+    );
+    assert!(Cookie::parse_list("").is_empty());
+}
+
+#[test]
+fn read_and_write_cookies() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+
+    let view_config = common::view_config(&lib);
+    let session = renderer.create_session(false, "view_cookies_test").unwrap();
+    let view = renderer
+        .create_view(200, 100, &view_config, Some(&session))
+        .unwrap();
+
+    common::load_and_wait(&renderer, &view, |view| {
+        view.load_html_with_base_url(
+            "<script>document.cookie = 'theme=dark';</script>",
+            "https://example.com/",
+        )
+        .unwrap()
+    });
+
+    assert_eq!(view.cookies().unwrap(), vec![Cookie::new("theme", "dark")]);
+
+    view.set_cookie(Cookie::new("user", "alice")).unwrap();
+    let cookies = view.cookies().unwrap();
+    assert!(cookies.contains(&Cookie::new("user", "alice")));
+    assert!(cookies.contains(&Cookie::new("theme", "dark")));
+
+    // removing a cookie
+    view.set_cookie(Cookie {
+        max_age: Some(Duration::ZERO),
+        ..Cookie::new("theme", "")
+    })
+    .unwrap();
+    assert_eq!(view.cookies().unwrap(), vec![Cookie::new("user", "alice")]);
+
+    assert!(matches!(
+        view.set_cookie(Cookie::new("a;b", "c")),
+        Err(CookieError::InvalidCookie(_))
+    ));
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use std::{
+    sync::{mpsc::TryRecvError, Arc},
+    time::Duration,
+};
+
+use ul_next::{renderer::Renderer, Library};
+
+/// The scripts run once, on the next page.
+fn execute_js_on_dom_ready_runs_once(lib: &Arc<Library>, renderer: &Renderer) {
+    let view = common::create_view(lib, renderer, 200, 200);
+
+    let title = view.execute_js_on_dom_ready("document.title".to_string());
+    let exception = view.execute_js_on_dom_ready("undefinedFunction()".to_string());
+    view.load_html("<html><head><title>first</title></head></html>")
+        .unwrap();
+
+    let mut title_result = None;
+    common::update_until(renderer, "the DOM to be ready", || {
+        match title.try_recv() {
+            Ok(result) => title_result = Some(result),
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => panic!("the script was dropped"),
+        }
+        title_result.is_some()
+    });
+    assert_eq!(title_result, Some(Ok("first".to_string())));
+    assert!(exception.try_recv().unwrap().is_err());
+
+    // the scripts don't run again on the next page
+    let counter = view.execute_js_on_dom_ready("window.ranOnce = true; 'done'".to_string());
+    view.load_html("<html><head><title>second</title></head></html>")
+        .unwrap();
+    common::update_until(renderer, "the DOM to be ready", || {
+        counter.try_recv().is_ok()
+    });
+
+    view.load_html("<html><head><title>third</title></head></html>")
+        .unwrap();
+    common::update_for(renderer, Duration::from_millis(500));
+    assert_eq!(
+        view.evaluate_script("String(window.ranOnce)").unwrap(),
+        Ok("undefined".to_string())
+    );
+}
+
+/// The pending scripts are kept when the view is moved.
+fn execute_js_on_dom_ready_after_move(lib: &Arc<Library>, renderer: &Renderer) {
+    let view = common::create_view(lib, renderer, 200, 200);
+
+    let title = view.execute_js_on_dom_ready("document.title".to_string());
+
+    // the pending scripts are run after the view is moved
+    let view = Box::new(view);
+    view.load_html("<html><head><title>moved</title></head></html>")
+        .unwrap();
+
+    let mut title_result = None;
+    common::update_until(renderer, "the DOM to be ready", || {
+        match title.try_recv() {
+            Ok(result) => title_result = Some(result),
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => panic!("the script was dropped"),
+        }
+        title_result.is_some()
+    });
+    assert_eq!(title_result, Some(Ok("moved".to_string())));
+}
+
+// all the cases share a single renderer, only one can be created at a time
+#[test]
+fn execute_js_on_dom_ready() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+
+    execute_js_on_dom_ready_runs_once(&lib, &renderer);
+    execute_js_on_dom_ready_after_move(&lib, &renderer);
+}
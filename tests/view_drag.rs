@@ -0,0 +1,51 @@
+mod common;
+
+use ul_next::event::{DragEvent, DragEventType};
+
+const HTML: &str = r#"<html>
+<body style="margin: 0">
+  <div id="zone" style="width: 200px; height: 200px"></div>
+  <script>
+    window.events = [];
+    var zone = document.getElementById('zone');
+    ['dragenter', 'dragover', 'dragleave', 'drop'].forEach(function(type) {
+      zone.addEventListener(type, function(e) {
+        var data = e.dataTransfer ? e.dataTransfer.getData('text/plain') : '';
+        window.events.push(type + ':' + data);
+        if (type === 'dragover' || type === 'drop') {
+          e.preventDefault();
+        }
+      });
+    });
+  </script>
+</body>
+</html>"#;
+
+#[test]
+fn fire_drag_events() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 400, 400);
+
+    common::load_and_wait(&renderer, &view, |view| view.load_html(HTML).unwrap());
+
+    let enter = DragEvent::new(DragEventType::DragEnter, 50, 50).with_data("text/plain", "hello");
+    assert!(view.fire_drag_event(enter).unwrap());
+
+    // the page cancels `dragover` to accept the drop
+    let over = DragEvent::new(DragEventType::DragOver, 60, 60).with_data("text/plain", "hello");
+    assert!(!view.fire_drag_event(over).unwrap());
+
+    let drop = DragEvent::new(DragEventType::Drop, 60, 60).with_data("text/plain", "\"quoted\"");
+    assert!(!view.fire_drag_event(drop).unwrap());
+
+    // outside of the zone, the listeners are not called
+    let outside = DragEvent::new(DragEventType::DragOver, 300, 300);
+    assert!(view.fire_drag_event(outside).unwrap());
+
+    let events = view
+        .evaluate_script("window.events.join('|')")
+        .unwrap()
+        .unwrap();
+    assert_eq!(events, "dragenter:hello|dragover:hello|drop:\"quoted\"");
+}
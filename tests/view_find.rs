@@ -0,0 +1,73 @@
+mod common;
+
+use ul_next::view::{FindError, FindOptions};
+
+const HTML: &str = r#"<html>
+<body>
+  <p>The quick brown fox jumps over the lazy dog.</p>
+  <p>A Fox said "hello (world)" to the other fox, foxes everywhere.</p>
+  <p>Price: $5.00 [sale] a+b</p>
+  <script>var fox = "not visible text";</script>
+</body>
+</html>"#;
+
+#[test]
+fn find_text_in_view() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 400, 400);
+
+    common::load_and_wait(&renderer, &view, |view| view.load_html(HTML).unwrap());
+
+    let text = view.inner_text().unwrap();
+    assert!(text.contains("The quick brown fox jumps over the lazy dog."));
+    assert!(!text.contains("not visible text"));
+
+    let case_insensitive = FindOptions::default();
+    let case_sensitive = FindOptions {
+        case_sensitive: true,
+        ..Default::default()
+    };
+    let whole_word = FindOptions {
+        whole_word: true,
+        ..Default::default()
+    };
+
+    // `fox`, `Fox`, `fox` and `foxes`
+    assert_eq!(view.find_in_page("fox", case_insensitive).unwrap(), 4);
+    assert_eq!(view.find_in_page("fox", case_sensitive).unwrap(), 3);
+    assert_eq!(view.find_in_page("fox", whole_word).unwrap(), 3);
+
+    // quotes and regex metacharacters are matched literally
+    assert_eq!(
+        view.find_in_page("\"hello (world)\"", case_insensitive)
+            .unwrap(),
+        1
+    );
+    assert_eq!(view.find_in_page("$5.00", case_insensitive).unwrap(), 1);
+    assert_eq!(view.find_in_page("[sale]", case_insensitive).unwrap(), 1);
+    assert_eq!(view.find_in_page("a+b", case_insensitive).unwrap(), 1);
+    assert_eq!(view.find_in_page(".*", case_insensitive).unwrap(), 0);
+    assert_eq!(view.find_in_page("", case_insensitive).unwrap(), 0);
+
+    // highlighting doesn't change the text
+    assert_eq!(view.find_in_page("o", case_insensitive).unwrap(), 11);
+    assert_eq!(view.inner_text().unwrap(), text);
+
+    view.scroll_to_match(0).unwrap();
+    view.scroll_to_match(10).unwrap();
+    assert!(matches!(
+        view.scroll_to_match(11),
+        Err(FindError::MatchOutOfRange {
+            index: 11,
+            count: 11
+        })
+    ));
+
+    view.clear_find().unwrap();
+    assert_eq!(view.inner_text().unwrap(), text);
+    assert!(matches!(
+        view.scroll_to_match(0),
+        Err(FindError::MatchOutOfRange { index: 0, count: 0 })
+    ));
+}
@@ -0,0 +1,55 @@
+mod common;
+
+const HTML: &str = r#"<html>
+<body>
+  <input id="input">
+  <script>
+    var events = [];
+    ['compositionstart', 'compositionupdate', 'compositionend'].forEach(function(type) {
+      input.addEventListener(type, function(e) { events.push(type + ':' + e.data); });
+    });
+  </script>
+</body>
+</html>"#;
+
+#[test]
+fn insert_text_and_compose() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 200, 100);
+
+    common::load_and_wait(&renderer, &view, |view| view.load_html(HTML).unwrap());
+
+    let value = || view.evaluate_script("input.value").unwrap().unwrap();
+
+    // nothing is focused yet
+    assert!(!view.ime_set_composition("x").unwrap());
+
+    view.focus();
+    view.evaluate_script("input.focus()").unwrap().unwrap();
+
+    view.insert_text("h\u{e9}llo ").unwrap();
+    assert_eq!(value(), "h\u{e9}llo ");
+
+    // typing "世界" with a pinyin input method
+    assert!(view.ime_set_composition("s").unwrap());
+    assert!(view.ime_set_composition("shi").unwrap());
+    assert_eq!(value(), "h\u{e9}llo shi");
+    assert!(view.ime_set_composition("shijie").unwrap());
+    assert!(view.ime_commit("\u{4e16}\u{754c}").unwrap());
+    assert_eq!(value(), "h\u{e9}llo \u{4e16}\u{754c}");
+
+    assert_eq!(
+        view.evaluate_script("events.join(',')").unwrap().unwrap(),
+        "compositionstart:s,compositionupdate:s,compositionupdate:shi,\
+         compositionupdate:shijie,compositionupdate:\u{4e16}\u{754c},\
+         compositionend:\u{4e16}\u{754c}"
+    );
+
+    // a commit without composition inserts at the caret, an empty one
+    // cancels the composition
+    assert!(view.ime_commit("!").unwrap());
+    assert!(view.ime_set_composition("abc").unwrap());
+    assert!(view.ime_commit("").unwrap());
+    assert_eq!(value(), "h\u{e9}llo \u{4e16}\u{754c}!");
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use ul_next::javascript::{JSPropertyAttributes, JSValue};
+
+const HTML: &str = r#"<html>
+<head>
+  <script>document.title = "from rust: " + window.fromRust;</script>
+</head>
+<body></body>
+</html>"#;
+
+#[test]
+fn ready_callbacks_receive_js_context() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 100, 100);
+
+    view.set_window_object_ready_callback(|_view, _frame_id, is_main_frame, _url, ctx| {
+        if is_main_frame {
+            ctx.global_object()
+                .set_property(
+                    "fromRust",
+                    &JSValue::new_number(ctx, 42.0),
+                    JSPropertyAttributes::new(),
+                )
+                .unwrap();
+        }
+    });
+
+    let title = Rc::new(RefCell::new(None));
+    {
+        let title = title.clone();
+        view.set_dom_ready_callback(move |_view, _frame_id, is_main_frame, _url, ctx| {
+            if is_main_frame {
+                let value = ctx
+                    .evaluate_script("document.title", None, None, 1)
+                    .unwrap();
+                *title.borrow_mut() = Some(value.as_string().unwrap().to_string());
+            }
+        });
+    }
+
+    common::load_and_wait(&renderer, &view, |view| view.load_html(HTML).unwrap());
+
+    assert_eq!(title.borrow().as_deref(), Some("from rust: 42"));
+}
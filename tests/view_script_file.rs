@@ -0,0 +1,71 @@
+mod common;
+
+use std::sync::Arc;
+
+use ul_next::{error::CreationError, platform, renderer::Renderer, Library};
+
+/// Absolute paths, exceptions and missing files.
+fn inject_javascript_file(lib: &Arc<Library>, renderer: &Renderer) {
+    let view = common::create_view(lib, renderer, 200, 100);
+
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("ul-next-script-{}.js", std::process::id()));
+    std::fs::write(
+        &path,
+        "var injectedLib = { add: function(a, b) { return a + b; } };\n\
+         injectedLib.add(40, 2);\n\
+         // trailing comment",
+    )
+    .unwrap();
+
+    // absolute paths are used as is
+    let result = view.inject_javascript_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(result, Ok("42".to_string()));
+    assert_eq!(
+        view.evaluate_script("injectedLib.add(1, 2)").unwrap(),
+        Ok("3".to_string())
+    );
+
+    let error_path = dir.join(format!("ul-next-script-error-{}.js", std::process::id()));
+    std::fs::write(&error_path, "throw new Error('boom');").unwrap();
+    let result = view.inject_javascript_file(&error_path).unwrap();
+    std::fs::remove_file(&error_path).unwrap();
+    assert!(result.unwrap_err().contains("boom"));
+
+    assert!(matches!(
+        view.inject_javascript_file(dir.join("ul-next-missing-script.js")),
+        Err(CreationError::IoError(_))
+    ));
+}
+
+/// Relative paths are resolved against the platform file system.
+fn relative_path_follows_the_filesystem(lib: &Arc<Library>, renderer: &Renderer) {
+    let view = common::create_view(lib, renderer, 200, 100);
+
+    let name = format!("ul-next-relative-script-{}.js", std::process::id());
+    let path = std::env::temp_dir().join(&name);
+    std::fs::write(&path, "'relative'").unwrap();
+
+    // resolved against the base of the platform file system
+    platform::enable_platform_filesystem(lib.clone(), std::env::temp_dir()).unwrap();
+    let result = view.inject_javascript_file(&name);
+
+    // and against the working directory once it is replaced
+    platform::set_filesystem(lib.clone(), common::memory_filesystem());
+    let replaced_result = view.inject_javascript_file(&name);
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(result.unwrap(), Ok("relative".to_string()));
+    assert!(matches!(replaced_result, Err(CreationError::IoError(_))));
+}
+
+// all the cases share a single renderer, only one can be created at a time
+#[test]
+fn inject_javascript_files() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+
+    inject_javascript_file(&lib, &renderer);
+    relative_path_follows_the_filesystem(&lib, &renderer);
+}
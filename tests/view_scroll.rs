@@ -0,0 +1,37 @@
+mod common;
+
+use ul_next::view::ScrollError;
+
+const TALL_HTML: &str = r#"<html>
+<body style="margin: 0"><div style="width: 100px; height: 5000px"></div></body>
+</html>"#;
+
+#[test]
+fn scroll_view() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 100, 100);
+
+    common::load_and_wait(&renderer, &view, |view| view.load_html(TALL_HTML).unwrap());
+
+    assert_eq!(view.scroll_position().unwrap(), (0.0, 0.0));
+
+    view.scroll_to(0.0, 500.0).unwrap();
+    assert_eq!(view.scroll_position().unwrap(), (0.0, 500.0));
+
+    view.scroll_by(0.0, -200.0).unwrap();
+    assert_eq!(view.scroll_position().unwrap(), (0.0, 300.0));
+
+    let (x, y) = view.scroll_position().unwrap();
+    view.scroll_to(-10.0, -10.0).unwrap();
+    assert_eq!(view.scroll_position().unwrap(), (0.0, 0.0));
+
+    view.set_scroll_position(x, y).unwrap();
+    assert_eq!(view.scroll_position().unwrap(), (0.0, 300.0));
+    view.set_scroll_position(0.0, 0.0).unwrap();
+
+    assert!(matches!(
+        view.scroll_to(f64::NAN, 0.0),
+        Err(ScrollError::InvalidOffset(..))
+    ));
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use ul_next::{platform, view::View};
+
+// HTML strings are loaded as `about:blank`, which has no local storage, so
+// the page is loaded from a file to have an origin
+const PAGE_URL: &str = "file:///index.html";
+
+fn local_storage_item(view: &View) -> String {
+    view.evaluate_script("String(localStorage.getItem('key'))")
+        .unwrap()
+        .unwrap()
+}
+
+#[test]
+fn views_in_separate_sessions_are_isolated() {
+    let lib = common::load_library();
+
+    let mut fs = common::memory_filesystem();
+    fs.insert("index.html", "<html><body></body></html>".as_bytes());
+    platform::set_filesystem(lib.clone(), fs);
+
+    let renderer = common::create_renderer(&lib);
+    let view_config = common::view_config(&lib);
+
+    let session_a = renderer.create_session(false, "session_a").unwrap();
+    let session_b = renderer.create_session(false, "session_b").unwrap();
+    assert!(!session_a.same_session(&session_b));
+
+    let view_a = renderer
+        .create_view(100, 100, &view_config, Some(&session_a))
+        .unwrap();
+    let view_b = renderer
+        .create_view(100, 100, &view_config, Some(&session_b))
+        .unwrap();
+    let shared_session_a = session_a.clone_handle();
+    let view_a2 = renderer
+        .create_view(100, 100, &view_config, Some(&shared_session_a))
+        .unwrap();
+    let view_default = renderer.create_view(100, 100, &view_config, None).unwrap();
+
+    // the views keep their sessions alive
+    drop(session_a);
+    drop(session_b);
+    drop(shared_session_a);
+
+    assert_eq!(view_a.session().unwrap().name(), "session_a");
+    assert_eq!(view_b.session().unwrap().name(), "session_b");
+    assert!(view_a
+        .session()
+        .unwrap()
+        .same_session(view_a2.session().unwrap()));
+    assert!(view_default.session().is_none());
+
+    for view in [&view_a, &view_b, &view_a2] {
+        common::load_and_wait(&renderer, view, |view| view.load_url(PAGE_URL).unwrap());
+    }
+
+    view_a
+        .evaluate_script("localStorage.setItem('key', 'a')")
+        .unwrap()
+        .unwrap();
+    view_b
+        .evaluate_script("localStorage.setItem('key', 'b')")
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(local_storage_item(&view_a), "a");
+    assert_eq!(local_storage_item(&view_b), "b");
+    // same session, same origin
+    assert_eq!(local_storage_item(&view_a2), "a");
+}
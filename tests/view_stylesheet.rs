@@ -0,0 +1,98 @@
+mod common;
+
+use std::{cell::Cell, rc::Rc, sync::Arc};
+
+use ul_next::{renderer::Renderer, Library};
+
+const HTML: &str = r#"<html>
+<head><style>p { color: rgb(0, 0, 0); }</style></head>
+<body><p id="text">Hello</p></body>
+</html>"#;
+
+/// The stylesheet is applied again after a reload, until removed.
+fn stylesheet_survives_reload(lib: &Arc<Library>, renderer: &Renderer) {
+    let view = common::create_view(lib, renderer, 200, 100);
+
+    // the user callback is kept when stylesheets are injected
+    let dom_ready_calls = Rc::new(Cell::new(0));
+    {
+        let dom_ready_calls = dom_ready_calls.clone();
+        view.set_dom_ready_callback(move |_view, _frame_id, is_main_frame, _url, _ctx| {
+            if is_main_frame {
+                dom_ready_calls.set(dom_ready_calls.get() + 1);
+            }
+        });
+    }
+
+    let color = || {
+        view.evaluate_script("getComputedStyle(document.getElementById('text')).color")
+            .unwrap()
+            .unwrap()
+    };
+
+    common::load_and_wait(renderer, &view, |view| view.load_html(HTML).unwrap());
+    assert_eq!(color(), "rgb(0, 0, 0)");
+
+    let red = view.inject_stylesheet("p { color: rgb(255, 0, 0) !important; }");
+    assert!(red.is_active());
+    assert_eq!(color(), "rgb(255, 0, 0)");
+
+    common::load_and_wait(renderer, &view, |view| view.reload());
+    assert_eq!(color(), "rgb(255, 0, 0)");
+    assert_eq!(dom_ready_calls.get(), 2);
+
+    red.update("p { color: rgb(0, 0, 255) !important; }")
+        .unwrap();
+    assert_eq!(color(), "rgb(0, 0, 255)");
+
+    red.remove().unwrap();
+    assert_eq!(color(), "rgb(0, 0, 0)");
+
+    common::load_and_wait(renderer, &view, |view| view.reload());
+    assert_eq!(color(), "rgb(0, 0, 0)");
+}
+
+/// Stylesheets injected from the callback view are kept after a move.
+fn stylesheet_from_callback_view_after_move(lib: &Arc<Library>, renderer: &Renderer) {
+    let view = common::create_view(lib, renderer, 200, 100);
+
+    // the view given to the callback shares the stylesheets of `view`, and
+    // injecting from it doesn't replace this callback
+    let dom_ready_calls = Rc::new(Cell::new(0));
+    {
+        let dom_ready_calls = dom_ready_calls.clone();
+        view.set_dom_ready_callback(move |view, _frame_id, is_main_frame, _url, _ctx| {
+            if is_main_frame {
+                if dom_ready_calls.get() == 0 {
+                    view.inject_stylesheet("p { color: rgb(255, 0, 0) !important; }");
+                }
+                dom_ready_calls.set(dom_ready_calls.get() + 1);
+            }
+        });
+    }
+
+    // the callbacks keep working after the view is moved
+    let view = Box::new(view);
+    let color = || {
+        view.evaluate_script("getComputedStyle(document.getElementById('text')).color")
+            .unwrap()
+            .unwrap()
+    };
+
+    common::load_and_wait(renderer, &view, |view| view.load_html(HTML).unwrap());
+    assert_eq!(color(), "rgb(255, 0, 0)");
+
+    common::load_and_wait(renderer, &view, |view| view.reload());
+    assert_eq!(color(), "rgb(255, 0, 0)");
+    assert_eq!(dom_ready_calls.get(), 2);
+}
+
+// all the cases share a single renderer, only one can be created at a time
+#[test]
+fn inject_stylesheet() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+
+    stylesheet_survives_reload(&lib, &renderer);
+    stylesheet_from_callback_view_after_move(&lib, &renderer);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use std::time::Duration;
+
+use ul_next::view::{ThrottlePolicy, View};
+
+const HTML: &str = r#"<html><body><script>
+window.counter = 0;
+setInterval(function () { window.counter++; }, 10);
+</script></body></html>"#;
+
+fn counter(view: &View) -> u32 {
+    view.evaluate_script("window.counter")
+        .unwrap()
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+/// The number of increments of the counter while updating the renderer
+/// for `duration`.
+fn increments(renderer: &ul_next::renderer::Renderer, view: &View, duration: Duration) -> u32 {
+    let start = counter(view);
+    common::update_for(renderer, duration);
+    counter(view) - start
+}
+
+#[test]
+fn hidden_view_timers_are_throttled() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+    let view = common::create_view(&lib, &renderer, 100, 100);
+
+    // installed again before the page scripts when the page is loaded
+    view.set_background_throttling(ThrottlePolicy {
+        min_timer_interval: Duration::from_millis(250),
+        pause_raf: true,
+    })
+    .unwrap()
+    .unwrap();
+    common::load_and_wait(&renderer, &view, |view| view.load_html(HTML).unwrap());
+
+    let period = Duration::from_millis(500);
+    let visible = increments(&renderer, &view, period);
+    assert_eq!(view.throttling_stats().unwrap().clamped_timers, 0);
+
+    view.set_background_hidden(true).unwrap();
+    let hidden = increments(&renderer, &view, period);
+    assert!(view.throttling_stats().unwrap().clamped_timers > 0);
+
+    view.set_background_hidden(false).unwrap();
+    // the timer may still wait for its last clamped delay
+    common::update_for(&renderer, Duration::from_millis(300));
+    let shown = increments(&renderer, &view, period);
+
+    assert!(hidden <= 4, "{hidden} increments while hidden");
+    assert!(
+        visible >= 10 * hidden.max(1),
+        "{visible} increments while visible"
+    );
+    assert!(
+        shown >= 10 * hidden.max(1),
+        "{shown} increments when shown again"
+    );
+
+    // the policy and the hidden state are kept across navigation
+    view.set_background_hidden(true).unwrap();
+    common::load_and_wait(&renderer, &view, |view| view.load_html(HTML).unwrap());
+    assert!(view.is_background_hidden());
+    let reloaded = increments(&renderer, &view, period);
+    assert!(view.throttling_stats().unwrap().clamped_timers > 0);
+    assert!(
+        reloaded <= 4,
+        "{reloaded} increments while hidden after reload"
+    );
+}
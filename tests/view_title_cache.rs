@@ -0,0 +1,53 @@
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use ul_next::{renderer::Renderer, view::View};
+
+/// The titles given to the title callback when the title changes by a single
+/// character a few times.
+fn title_changes(renderer: &Renderer, view: &View) -> Vec<String> {
+    let titles: Rc<RefCell<Vec<String>>> = Rc::default();
+    {
+        let titles = titles.clone();
+        view.set_change_title_callback(move |_view, title| titles.borrow_mut().push(title));
+    }
+
+    common::load_and_wait(renderer, view, |view| {
+        view.load_html("<html><head><title>page-a</title></head></html>")
+            .unwrap()
+    });
+
+    for title in ["page-b", "page-a", "page-b"] {
+        let expected_len = titles.borrow().len() + 1;
+        view.evaluate_script(&format!("document.title = '{title}'"))
+            .unwrap()
+            .unwrap();
+        common::update_until(renderer, "the title to change", || {
+            titles.borrow().len() == expected_len
+        });
+    }
+
+    let titles = titles.borrow();
+    titles[titles.len() - 4..].to_vec()
+}
+
+#[test]
+fn title_callback_cache_is_transparent() {
+    let lib = common::load_library();
+    let renderer = common::create_renderer(&lib);
+
+    // titles differing by a single character are not mixed up by the cache,
+    // and disabling it doesn't change what the callback receives
+    for capacity in [2, 1, 0] {
+        let view = common::create_view(&lib, &renderer, 200, 100);
+        view.set_string_cache_capacity(capacity);
+        assert_eq!(view.string_cache_capacity(), capacity);
+
+        assert_eq!(
+            title_changes(&renderer, &view),
+            ["page-a", "page-b", "page-a", "page-b"],
+            "with a capacity of {capacity}"
+        );
+    }
+}
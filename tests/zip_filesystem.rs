@@ -0,0 +1,65 @@
+//! Reads an archive built in memory, so this doesn't need Ultralight.
+#![cfg(feature = "zip")]
+
+use std::io::{Cursor, Write};
+
+use ul_next::platform::{filesystems::ZipFileSystem, FileSystem};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+fn archive() -> Cursor<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    writer.start_file("index.html", deflated).unwrap();
+    writer
+        .write_all(b"<html><body>hello</body></html>")
+        .unwrap();
+    writer.add_directory("assets/", stored).unwrap();
+    writer.start_file("assets/style.css", stored).unwrap();
+    writer.write_all(b"body { color: red; }").unwrap();
+    writer.start_file("./assets/data.bin", deflated).unwrap();
+    writer.write_all(&[0, 1, 2, 3]).unwrap();
+
+    let mut archive = writer.finish().unwrap();
+    archive.set_position(0);
+    archive
+}
+
+#[test]
+fn serve_files_from_zip() {
+    let mut fs = ZipFileSystem::new(archive()).unwrap();
+
+    let mut paths = fs.paths().collect::<Vec<_>>();
+    paths.sort_unstable();
+    assert_eq!(paths, ["assets/data.bin", "assets/style.css", "index.html"]);
+
+    assert!(fs.file_exists("file:///index.html"));
+    assert!(fs.file_exists("/assets/../assets/style.css"));
+    assert!(!fs.file_exists("assets"));
+    assert!(!fs.file_exists("missing.html"));
+    assert!(!fs.file_exists("../index.html"));
+
+    assert_eq!(fs.get_file_mime_type("index.html"), "text/html");
+    assert_eq!(fs.get_file_mime_type("assets/style.css"), "text/css");
+    assert_eq!(
+        fs.get_file_mime_type("assets/data.bin"),
+        "application/octet-stream"
+    );
+
+    assert_eq!(
+        fs.open_file("index.html").unwrap(),
+        b"<html><body>hello</body></html>"
+    );
+    assert_eq!(
+        fs.open_file("assets/style.css").unwrap(),
+        b"body { color: red; }"
+    );
+    assert_eq!(fs.open_file("assets/data.bin").unwrap(), [0, 1, 2, 3]);
+    assert_eq!(fs.open_file("missing.html"), None);
+}
+
+#[test]
+fn invalid_archive() {
+    assert!(ZipFileSystem::new(Cursor::new(b"not a zip".to_vec())).is_err());
+}